@@ -0,0 +1,10 @@
+ALTER TABLE jobs ADD COLUMN album_id TEXT;
+
+CREATE TABLE IF NOT EXISTS album_covers (
+    user_id TEXT NOT NULL,
+    album_id TEXT NOT NULL,
+    path TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, album_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
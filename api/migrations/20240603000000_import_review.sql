@@ -0,0 +1,4 @@
+ALTER TABLE jobs ADD COLUMN review_before_import INTEGER NOT NULL DEFAULT 0;
+
+-- Where a finished file sits on our disk, waiting to be imported.
+ALTER TABLE job_files ADD COLUMN local_path TEXT;
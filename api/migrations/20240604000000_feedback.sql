@@ -0,0 +1,7 @@
+-- User verdict on a finished job: 1 = right album/quality, -1 = wrong.
+ALTER TABLE jobs ADD COLUMN feedback INTEGER;
+
+-- Ranking inputs captured at dispatch time, so feedback can be correlated
+-- with the scores that led to picking these files.
+ALTER TABLE job_files ADD COLUMN match_score REAL;
+ALTER TABLE job_files ADD COLUMN quality_score REAL;
@@ -0,0 +1,3 @@
+-- Final slskd state of each file and the failure reason slskd reported, verbatim.
+ALTER TABLE job_files ADD COLUMN state TEXT;
+ALTER TABLE job_files ADD COLUMN error TEXT;
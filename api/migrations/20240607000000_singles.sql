@@ -0,0 +1 @@
+ALTER TABLE jobs ADD COLUMN recording_id TEXT;
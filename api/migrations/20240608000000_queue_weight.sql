@@ -0,0 +1,3 @@
+ALTER TABLE users ADD COLUMN queue_weight INTEGER NOT NULL DEFAULT 1;
+
+UPDATE users SET queue_weight = 2 WHERE is_admin = 1;
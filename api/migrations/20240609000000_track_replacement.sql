@@ -0,0 +1,5 @@
+ALTER TABLE job_files ADD COLUMN title TEXT;
+ALTER TABLE job_files ADD COLUMN artist TEXT;
+ALTER TABLE job_files ADD COLUMN replaced_by TEXT;
+
+ALTER TABLE jobs ADD COLUMN replaces_file_id TEXT;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS genre_mappings (
+    user_id TEXT NOT NULL,
+    source TEXT NOT NULL,
+    target TEXT NOT NULL,
+    PRIMARY KEY (user_id, source),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN share_library INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS bitrate_cutoffs (
+    user_id TEXT NOT NULL,
+    format TEXT NOT NULL,
+    min_bitrate INTEGER NOT NULL,
+    PRIMARY KEY (user_id, format),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
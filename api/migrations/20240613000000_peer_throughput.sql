@@ -0,0 +1 @@
+ALTER TABLE job_files ADD COLUMN average_speed REAL;
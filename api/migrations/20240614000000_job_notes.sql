@@ -0,0 +1,8 @@
+ALTER TABLE jobs ADD COLUMN note TEXT;
+
+CREATE TABLE IF NOT EXISTS job_labels (
+    job_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    PRIMARY KEY (job_id, label),
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
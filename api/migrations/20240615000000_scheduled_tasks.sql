@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS scheduled_tasks (
+    name TEXT PRIMARY KEY NOT NULL,
+    cron TEXT NOT NULL,
+    jitter_seconds INTEGER NOT NULL DEFAULT 0,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    next_run_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS task_runs (
+    id TEXT PRIMARY KEY NOT NULL,
+    task TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    finished_at TEXT,
+    success INTEGER,
+    message TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_runs_started_at ON task_runs(started_at);
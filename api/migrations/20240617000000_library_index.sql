@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS library_items (
+    path TEXT PRIMARY KEY NOT NULL,
+    folder TEXT NOT NULL,
+    artist TEXT NOT NULL,
+    album TEXT NOT NULL,
+    title TEXT NOT NULL,
+    track INTEGER,
+    format TEXT NOT NULL,
+    bitrate INTEGER,
+    mb_albumid TEXT,
+    mb_trackid TEXT,
+    mtime TEXT NOT NULL,
+    indexed_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_library_items_folder ON library_items(folder);
+CREATE INDEX IF NOT EXISTS idx_library_items_album ON library_items(mb_albumid);
+CREATE INDEX IF NOT EXISTS idx_library_items_track ON library_items(mb_trackid);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS quality_profiles (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    scoring TEXT NOT NULL,
+    -- Comma-separated lowercase extensions, empty for any format
+    formats TEXT NOT NULL DEFAULT '',
+    min_completeness REAL NOT NULL DEFAULT 1.0,
+    auto_pick INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_quality_profiles_user_id ON quality_profiles(user_id);
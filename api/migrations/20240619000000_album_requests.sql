@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS album_requests (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    album_id TEXT NOT NULL,
+    artist TEXT NOT NULL,
+    title TEXT NOT NULL,
+    target_folder TEXT NOT NULL,
+    profile_id TEXT,
+    state TEXT NOT NULL DEFAULT 'pending',
+    job_id TEXT,
+    error TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_album_requests_state ON album_requests(state, created_at);
+CREATE INDEX IF NOT EXISTS idx_album_requests_user_id ON album_requests(user_id);
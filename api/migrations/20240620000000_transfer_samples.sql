@@ -0,0 +1,12 @@
+-- Transfer speed of each downloading file, sampled by the download monitor.
+CREATE TABLE IF NOT EXISTS transfer_samples (
+    job_id TEXT NOT NULL,
+    filename TEXT NOT NULL,
+    -- Bytes/s since the previous sample.
+    speed REAL NOT NULL,
+    bytes_transferred INTEGER NOT NULL,
+    sampled_at TEXT NOT NULL,
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transfer_samples_job_id ON transfer_samples(job_id, sampled_at);
@@ -0,0 +1,8 @@
+-- Instance-wide look, set by admins. A single row, created on first save.
+CREATE TABLE IF NOT EXISTS branding (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    instance_name TEXT,
+    accent_color TEXT,
+    logo_url TEXT,
+    updated_at TEXT NOT NULL
+);
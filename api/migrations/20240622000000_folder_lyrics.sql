@@ -0,0 +1,3 @@
+-- How lyrics are saved for tracks imported into the folder: off, sidecar
+-- (.lrc file next to the track) or tags (embedded by beets).
+ALTER TABLE folders ADD COLUMN lyrics TEXT NOT NULL DEFAULT 'off';
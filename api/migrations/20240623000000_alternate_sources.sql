@@ -0,0 +1,22 @@
+-- Next-best sources of a job's tracks from the search it came from, tried in
+-- rank order when a file fails to download.
+CREATE TABLE IF NOT EXISTS job_alternates (
+    id TEXT PRIMARY KEY NOT NULL,
+    job_id TEXT NOT NULL,
+    -- Track title the source was matched to, shared with the job file.
+    title TEXT NOT NULL,
+    rank INTEGER NOT NULL,
+    username TEXT NOT NULL,
+    filename TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    match_score REAL NOT NULL,
+    quality_score REAL,
+    artist TEXT NOT NULL,
+    used BOOLEAN NOT NULL DEFAULT 0,
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_alternates_job_id ON job_alternates(job_id, title, rank);
+
+-- Failed job file a retry downloads another copy of.
+ALTER TABLE job_files ADD COLUMN retry_of TEXT;
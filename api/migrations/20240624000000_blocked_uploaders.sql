@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS blocked_uploaders (
+    user_id TEXT NOT NULL,
+    username TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (user_id, username),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Files whose transfer the user paused. slskd cannot hold a transfer, so it is
+-- cancelled and requested again on resume.
+ALTER TABLE job_files ADD COLUMN paused BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,24 @@
+-- Where a user's job notifications are sent.
+CREATE TABLE IF NOT EXISTS notification_sinks (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    -- 'webhook' (JSON POST) or 'ntfy'.
+    kind TEXT NOT NULL,
+    url TEXT NOT NULL,
+    -- Seconds job events are gathered for before one digest is sent, 0 to send each right away.
+    digest_window INTEGER NOT NULL DEFAULT 60,
+    notify_imported BOOLEAN NOT NULL DEFAULT 1,
+    notify_failed BOOLEAN NOT NULL DEFAULT 1,
+    notify_review BOOLEAN NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_sinks_user_id ON notification_sinks(user_id);
+
+-- Jobs whose failure was already notified, so a job failing again stays quiet.
+CREATE TABLE IF NOT EXISTS notified_failures (
+    job_id TEXT PRIMARY KEY NOT NULL,
+    notified_at TEXT NOT NULL,
+    FOREIGN KEY (job_id) REFERENCES jobs(id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+-- Permissions and ownership applied to files imported into the folder, after
+-- beets moved them. Modes are octal strings, e.g. '644'. NULL leaves the
+-- files as the server process created them.
+ALTER TABLE folders ADD COLUMN file_mode TEXT;
+ALTER TABLE folders ADD COLUMN dir_mode TEXT;
+ALTER TABLE folders ADD COLUMN owner_uid INTEGER;
+ALTER TABLE folders ADD COLUMN owner_gid INTEGER;
@@ -0,0 +1,3 @@
+-- Place of a remotely queued transfer in the uploader's queue, refreshed while
+-- it waits. NULL once it starts, or when the uploader never said.
+ALTER TABLE job_files ADD COLUMN queue_position INTEGER;
@@ -0,0 +1,8 @@
+-- Search rate limit set by admins, overriding the built-in one. A single row,
+-- created on first save.
+CREATE TABLE IF NOT EXISTS search_rate_limit (
+    id INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+    max_searches INTEGER NOT NULL,
+    window_seconds INTEGER NOT NULL,
+    updated_at TEXT NOT NULL
+);
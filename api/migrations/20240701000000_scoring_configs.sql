@@ -0,0 +1,13 @@
+-- Users' adjustments to album scoring. NULL columns keep the strategy's own
+-- setting. Bitrate bonuses are stored as comma-separated `kbps:bonus` pairs.
+CREATE TABLE IF NOT EXISTS scoring_configs (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    match_weight REAL,
+    completeness_weight REAL,
+    format_weight REAL,
+    availability_weight REAL,
+    min_match_score REAL,
+    bitrate_bonuses TEXT NOT NULL DEFAULT '',
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,13 @@
+-- Transfers slskd completed that no job asked for, e.g. downloads made from
+-- the slskd web interface or while Soulbeet was down, found at startup.
+CREATE TABLE IF NOT EXISTS unmanaged_downloads (
+    id TEXT PRIMARY KEY NOT NULL,
+    username TEXT NOT NULL,
+    filename TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    transfer_id TEXT NOT NULL,
+    found_at TEXT NOT NULL,
+    -- Left alone by an admin, and not offered again.
+    dismissed BOOLEAN NOT NULL DEFAULT 0,
+    UNIQUE (username, filename)
+);
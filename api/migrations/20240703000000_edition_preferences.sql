@@ -0,0 +1,8 @@
+-- Which edition of an album each user opens by default. A NULL country means
+-- the album's original release.
+CREATE TABLE IF NOT EXISTS edition_preferences (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    country TEXT,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Phrases marking version suffixes of track titles ignored when matching
+-- files, on top of the built-in ones, comma-separated.
+ALTER TABLE scoring_configs ADD COLUMN title_suffixes TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,7 @@
+-- Last release whose "what's new" announcement each user dismissed.
+CREATE TABLE IF NOT EXISTS seen_announcements (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    version TEXT NOT NULL,
+    seen_at TEXT NOT NULL,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Share of the match score lost by files whose title has a version suffix
+-- the searched one lacks, or the other way round. NULL keeps the default.
+ALTER TABLE scoring_configs ADD COLUMN suffix_penalty REAL;
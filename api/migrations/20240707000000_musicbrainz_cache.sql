@@ -0,0 +1,8 @@
+-- MusicBrainz responses, as JSON, kept to answer repeated lookups without
+-- spending the one request per second MusicBrainz allows.
+CREATE TABLE IF NOT EXISTS musicbrainz_cache (
+    key TEXT PRIMARY KEY NOT NULL,
+    response TEXT NOT NULL,
+    fetched_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_musicbrainz_cache_fetched_at ON musicbrainz_cache(fetched_at);
@@ -0,0 +1,37 @@
+-- Artists whose new albums a user wants to hear about. With auto_download,
+-- each new album is queued as an album request into target_folder once it is
+-- released.
+CREATE TABLE IF NOT EXISTS followed_artists (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    artist_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    auto_download BOOLEAN NOT NULL DEFAULT 0,
+    target_folder TEXT,
+    profile_id TEXT,
+    created_at TEXT NOT NULL,
+    last_checked_at TEXT,
+    UNIQUE (user_id, artist_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Albums of followed artists released after they were followed.
+CREATE TABLE IF NOT EXISTS wanted_releases (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    followed_artist_id TEXT NOT NULL,
+    album_id TEXT NOT NULL,
+    artist TEXT NOT NULL,
+    title TEXT NOT NULL,
+    release_date TEXT,
+    -- Album request created for it, once queued.
+    request_id TEXT,
+    -- Removed from the list by the user, kept so it is not added back.
+    dismissed BOOLEAN NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    UNIQUE (user_id, album_id),
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    FOREIGN KEY (followed_artist_id) REFERENCES followed_artists(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_wanted_releases_user_id ON wanted_releases(user_id);
@@ -0,0 +1,3 @@
+-- Cover scans downloaded with a job's tracks. They are embedded after the
+-- import rather than imported, and are not counted as tracks.
+ALTER TABLE job_files ADD COLUMN artwork BOOLEAN NOT NULL DEFAULT 0;
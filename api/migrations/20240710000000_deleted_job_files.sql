@@ -0,0 +1,9 @@
+-- Files of jobs deleted along with their user may still be listed by slskd.
+-- They were asked for, so they are recorded as dismissed unmanaged downloads
+-- rather than offered for import at the next startup.
+CREATE TRIGGER IF NOT EXISTS job_files_deleted AFTER DELETE ON job_files
+BEGIN
+    INSERT OR IGNORE INTO unmanaged_downloads (id, username, filename, size, transfer_id, found_at, dismissed)
+    VALUES (lower(hex(randomblob(16))), OLD.username, OLD.filename, OLD.size,
+            COALESCE(OLD.transfer_id, ''), strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), 1);
+END;
@@ -0,0 +1,247 @@
+//! Albums queued in batches, e.g. a whole discography.
+//!
+//! Instead of running many Soulseek searches at once, which would saturate the
+//! slskd search limiter, queued albums are searched one after the other by
+//! [`run_queue`]. Each one is downloaded from the best source found, as if the
+//! user had picked the top result, and its progress is kept in the database.
+
+use dioxus::prelude::*;
+use shared::musicbrainz::Album;
+
+use crate::db::AlbumRequest;
+
+#[cfg(feature = "server")]
+use crate::{
+    auth, browse,
+    db::{AlbumRequestState, Job},
+    demo, dispatch, musicbrainz_cache, server_error, slskd_search, DownloadOptions,
+};
+#[cfg(feature = "server")]
+use shared::slskd::AlbumResult;
+#[cfg(feature = "server")]
+use soulbeet::musicbrainz;
+#[cfg(feature = "server")]
+use std::{sync::LazyLock, time::Duration};
+#[cfg(feature = "server")]
+use tokio::sync::Notify;
+
+/// How many albums can be queued at once.
+#[cfg(feature = "server")]
+const MAX_BATCH_SIZE: usize = 50;
+
+/// Sources kept per track to retry with when a download fails.
+#[cfg(feature = "server")]
+const MAX_ALTERNATES: usize = 3;
+
+/// The queue is checked this often even when nothing wakes it up.
+#[cfg(feature = "server")]
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+#[cfg(feature = "server")]
+static WAKE: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+/// Searches for one queued album and dispatches the best result.
+#[cfg(feature = "server")]
+async fn process(request: &AlbumRequest) -> Result<Job, String> {
+    let release = musicbrainz::find_album(&request.album_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let results = slskd_search(
+        &request.user_id,
+        release.album.artist,
+        release.album.title,
+        None,
+        release.tracks,
+        None,
+        request.profile_id.clone(),
+        &Default::default(),
+        None,
+    )
+    .await
+    .map_err(|e| e.to_string())?
+    .albums;
+
+    // Results come sorted by score.
+    let best = results
+        .iter()
+        .find(|album| !album.tracks.is_empty())
+        .cloned()
+        .ok_or_else(|| "No source found on Soulseek".to_string())?;
+
+    tokio::fs::create_dir_all(&request.target_folder)
+        .await
+        .map_err(|e| format!("Failed to create target directory: {e}"))?;
+    let options = DownloadOptions {
+        album_id: Some(request.album_id.clone()),
+        alternates: AlbumResult::alternate_sources(&best.tracks, &results, MAX_ALTERNATES),
+        gap_fills: best.gap_sources(&results, MAX_ALTERNATES),
+        ..Default::default()
+    };
+    dispatch::dispatch(
+        &request.user_id,
+        best.tracks,
+        &request.target_folder,
+        &options,
+    )
+    .await
+}
+
+/// Works through queued albums, oldest first, until the server stops.
+#[cfg(feature = "server")]
+pub async fn run_queue() {
+    match AlbumRequest::requeue_interrupted().await {
+        Ok(0) => {}
+        Ok(n) => tracing::info!("Requeued {} interrupted album searches", n),
+        Err(e) => tracing::warn!("Failed to requeue interrupted album searches: {}", e),
+    }
+
+    loop {
+        let request = match AlbumRequest::next_pending().await {
+            Ok(Some(request)) => request,
+            Ok(None) => {
+                let _ = tokio::time::timeout(POLL_INTERVAL, WAKE.notified()).await;
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read the album queue: {}", e);
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+        };
+
+        if let Err(e) =
+            AlbumRequest::set_state(&request.id, AlbumRequestState::Searching, None, None).await
+        {
+            tracing::warn!("Failed to update album request {}: {}", request.id, e);
+            tokio::time::sleep(POLL_INTERVAL).await;
+            continue;
+        }
+        let (state, job_id, error) = match process(&request).await {
+            Ok(job) => (AlbumRequestState::Done, Some(job.id), None),
+            Err(e) => {
+                tracing::info!(
+                    "Queued album {} - {} failed: {}",
+                    request.artist,
+                    request.title,
+                    e
+                );
+                (AlbumRequestState::Failed, None, Some(e))
+            }
+        };
+        if let Err(e) =
+            AlbumRequest::set_state(&request.id, state, job_id.as_deref(), error.as_deref()).await
+        {
+            tracing::warn!("Failed to update album request {}: {}", request.id, e);
+        }
+    }
+}
+
+/// Has the queue search the requests just created without waiting for its
+/// next poll.
+#[cfg(feature = "server")]
+pub(crate) fn wake_queue() {
+    WAKE.notify_one();
+}
+
+/// The studio albums of an artist, to pick from before queueing them.
+#[server]
+pub async fn get_artist_albums(artist: String) -> Result<Vec<Album>, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if demo::enabled() {
+        demo::check_rate_limit().map_err(server_error)?;
+    }
+
+    let artist = artist.trim();
+    musicbrainz_cache::cached(format!("artist albums {artist}"), async {
+        musicbrainz::artist_albums(artist)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(server_error)
+}
+
+/// The studio albums of the artist with MusicBrainz id `artist_id`, oldest
+/// first.
+#[server]
+pub async fn get_artist_releases(artist_id: String) -> Result<Vec<Album>, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if demo::enabled() {
+        demo::check_rate_limit().map_err(server_error)?;
+    }
+
+    let artist_id = artist_id.trim();
+    musicbrainz_cache::cached(format!("artist releases {artist_id}"), async {
+        musicbrainz::artist_releases(artist_id)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(server_error)
+}
+
+/// Queues albums to be searched and downloaded one after the other.
+#[server]
+pub async fn queue_albums(
+    token: String,
+    albums: Vec<Album>,
+    target_folder: String,
+    profile_id: Option<String>,
+) -> Result<Vec<AlbumRequest>, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if albums.is_empty() {
+        return Err(server_error("No albums selected"));
+    }
+    if albums.len() > MAX_BATCH_SIZE {
+        return Err(server_error(format!(
+            "At most {MAX_BATCH_SIZE} albums can be queued at once"
+        )));
+    }
+    if target_folder.trim().is_empty() {
+        return Err(server_error("Target folder is required"));
+    }
+
+    let mut requests = Vec::with_capacity(albums.len());
+    for album in &albums {
+        requests.push(
+            AlbumRequest::create(&claims.sub, album, &target_folder, profile_id.as_deref())
+                .await
+                .map_err(server_error)?,
+        );
+    }
+    WAKE.notify_one();
+    Ok(requests)
+}
+
+#[server]
+pub async fn get_album_requests(token: String) -> Result<Vec<AlbumRequest>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    AlbumRequest::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+/// Removes a queued album that is not being searched right now.
+#[server]
+pub async fn cancel_album_request(token: String, id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    AlbumRequest::delete(&claims.sub, &id)
+        .await
+        .map_err(server_error)
+}
@@ -0,0 +1,92 @@
+//! "What's new" announcements, from the changelog bundled with the server.
+//!
+//! Each user is shown the changelog entries of the releases made since the
+//! last announcement they dismissed. Users are not shown anything on their
+//! first visit, which only records the current release.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::db::SeenAnnouncement;
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+
+/// Changelog bundled with the server, newest release first.
+#[cfg(feature = "server")]
+const CHANGELOG: &str = include_str!("../changelog.json");
+
+/// What changed in one release.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub highlights: Vec<String>,
+}
+
+/// Orders versions like "0.10.2" by their numbers.
+#[cfg(feature = "server")]
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+/// The changelog entries of releases after `seen`, newest first.
+#[cfg(feature = "server")]
+fn entries_since(seen: &str) -> Vec<ChangelogEntry> {
+    let entries: Vec<ChangelogEntry> = match serde_json::from_str(CHANGELOG) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!("Failed to read the bundled changelog: {}", e);
+            return Vec::new();
+        }
+    };
+    let seen = version_key(seen);
+    let current = version_key(env!("CARGO_PKG_VERSION"));
+    let mut entries: Vec<ChangelogEntry> = entries
+        .into_iter()
+        .filter(|e| version_key(&e.version) > seen && version_key(&e.version) <= current)
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(version_key(&e.version)));
+    entries
+}
+
+/// What changed since the user last dismissed the announcement, empty when
+/// nothing did.
+#[server]
+pub async fn whats_new(token: String) -> Result<Vec<ChangelogEntry>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+    if claims.is_demo {
+        return Ok(Vec::new());
+    }
+
+    match SeenAnnouncement::get(&claims.sub)
+        .await
+        .map_err(server_error)?
+    {
+        Some(seen) => Ok(entries_since(&seen)),
+        None => {
+            SeenAnnouncement::save(&claims.sub, env!("CARGO_PKG_VERSION"))
+                .await
+                .map_err(server_error)?;
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Hides the announcement until the next release.
+#[server]
+pub async fn dismiss_whats_new(token: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    SeenAnnouncement::save(&claims.sub, env!("CARGO_PKG_VERSION"))
+        .await
+        .map_err(server_error)
+}
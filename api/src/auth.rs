@@ -7,13 +7,27 @@ pub struct AuthResponse {
     pub username: String,
     pub user_id: String,
     pub expires_at: i64, // Absolute timestamp (seconds)
+    #[serde(default)]
+    pub is_admin: bool,
+    /// Session of the anonymous demo user, which can browse but not change anything.
+    #[serde(default)]
+    pub is_demo: bool,
 }
 
+#[cfg(feature = "server")]
+use dioxus::fullstack::{
+    axum::{extract::Request, middleware::Next, response::IntoResponse, response::Response},
+    StatusCode,
+};
 #[cfg(feature = "server")]
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 #[cfg(feature = "server")]
 use std::env;
 
+/// How long a signed media URL stays valid, in seconds.
+#[cfg(feature = "server")]
+const MEDIA_URL_TTL: i64 = 15 * 60;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // user_id
@@ -21,10 +35,19 @@ pub struct Claims {
     pub iat: usize,
     pub exp: usize,
     pub purpose: String, // "access" or "refresh"
+    #[serde(default)]
+    pub is_admin: bool,
+    #[serde(default)]
+    pub is_demo: bool,
 }
 
 #[cfg(feature = "server")]
-pub fn create_tokens(user_id: String, username: String) -> Result<AuthResponse, String> {
+pub fn create_tokens(
+    user_id: String,
+    username: String,
+    is_admin: bool,
+    is_demo: bool,
+) -> Result<AuthResponse, String> {
     let secret = env::var("SECRET_KEY").unwrap_or_else(|_| "secret".to_string());
     let encoding_key = EncodingKey::from_secret(secret.as_bytes());
     let now = chrono::Utc::now();
@@ -42,6 +65,8 @@ pub fn create_tokens(user_id: String, username: String) -> Result<AuthResponse,
         iat,
         exp: access_exp as usize,
         purpose: "access".to_string(),
+        is_admin,
+        is_demo,
     };
 
     let token =
@@ -59,6 +84,8 @@ pub fn create_tokens(user_id: String, username: String) -> Result<AuthResponse,
         iat,
         exp: refresh_exp,
         purpose: "refresh".to_string(),
+        is_admin,
+        is_demo,
     };
 
     let refresh_token =
@@ -70,6 +97,8 @@ pub fn create_tokens(user_id: String, username: String) -> Result<AuthResponse,
         username,
         user_id,
         expires_at: access_exp,
+        is_admin,
+        is_demo,
     })
 }
 
@@ -90,3 +119,100 @@ pub fn verify_token(token: &str, expected_purpose: &str) -> Result<Claims, Strin
 
     Ok(token_data.claims)
 }
+
+/// Claims of a signed media URL, which grants access to a single path.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MediaClaims {
+    pub sub: String, // user_id
+    pub path: String,
+    pub exp: usize,
+    pub purpose: String, // "media"
+}
+
+/// Signs `path` for `user_id`, returning a URL that `<img>` and `<audio>` tags
+/// can load without an `Authorization` header. It expires after a few minutes.
+#[cfg(feature = "server")]
+pub fn sign_media_url(user_id: &str, path: &str) -> Result<String, String> {
+    let secret = env::var("SECRET_KEY").unwrap_or_else(|_| "secret".to_string());
+    let exp = chrono::Utc::now()
+        .checked_add_signed(chrono::Duration::seconds(MEDIA_URL_TTL))
+        .expect("valid timestamp")
+        .timestamp() as usize;
+
+    let claims = MediaClaims {
+        sub: user_id.to_string(),
+        path: path.to_string(),
+        exp,
+        purpose: "media".to_string(),
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(format!("{path}?token={token}"))
+}
+
+#[cfg(feature = "server")]
+pub fn verify_media_token(token: &str, path: &str) -> Result<MediaClaims, String> {
+    let secret = env::var("SECRET_KEY").unwrap_or_else(|_| "secret".to_string());
+
+    let token_data = decode::<MediaClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    if token_data.claims.purpose != "media" {
+        return Err("Invalid token purpose".to_string());
+    }
+    if token_data.claims.path != path {
+        return Err("Token was not issued for this path".to_string());
+    }
+
+    Ok(token_data.claims)
+}
+
+/// Middleware for media endpoints: rejects requests without a valid signed
+/// `token` query parameter for the requested path.
+#[cfg(feature = "server")]
+pub async fn require_signed_url(request: Request, next: Next) -> Response {
+    let token = request
+        .uri()
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="));
+
+    let Some(token) = token else {
+        return (StatusCode::UNAUTHORIZED, "Missing media token").into_response();
+    };
+    if let Err(e) = verify_media_token(token, request.uri().path()) {
+        return (StatusCode::UNAUTHORIZED, e).into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(feature = "server")]
+pub fn verify_admin(token: &str) -> Result<Claims, String> {
+    let claims = verify_token(token, "access")?;
+    if !claims.is_admin {
+        return Err("Admin privileges required".to_string());
+    }
+    Ok(claims)
+}
+
+/// Like [`verify_token`] for an access token, but rejects the read-only demo
+/// user. Every server function that changes something goes through this.
+#[cfg(feature = "server")]
+pub fn verify_writable(token: &str) -> Result<Claims, String> {
+    let claims = verify_token(token, "access")?;
+    if claims.is_demo {
+        return Err("Not available in the demo".to_string());
+    }
+    Ok(claims)
+}
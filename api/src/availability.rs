@@ -0,0 +1,311 @@
+//! Quick estimate of whether an album can be found on Soulseek, shown before
+//! the user commits to a full search, and reports of which albums of a whole
+//! discography can be found in which formats.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use shared::musicbrainz::Album;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "server")]
+use crate::{auth, browse, searches, server_error, soulseek};
+#[cfg(feature = "server")]
+use soulbeet::musicbrainz;
+#[cfg(feature = "server")]
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        LazyLock, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Availability {
+    Likely,
+    Scarce,
+    Unavailable,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AvailabilityEstimate {
+    pub availability: Availability,
+    /// Users who answered the probe with audio files.
+    pub sources: usize,
+}
+
+/// Probe searches are much shorter than real ones.
+#[cfg(feature = "server")]
+const PROBE_TIMEOUT_SECS: i64 = 8;
+
+/// Probe results are reused for this long.
+#[cfg(feature = "server")]
+const CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// From this many sources an album is considered likely available.
+#[cfg(feature = "server")]
+const LIKELY_THRESHOLD: usize = 5;
+
+#[cfg(feature = "server")]
+static CACHE: LazyLock<Mutex<HashMap<String, (Instant, AvailabilityEstimate)>>> =
+    LazyLock::new(Default::default);
+
+/// Most probe searches a discography report may run.
+#[cfg(feature = "server")]
+const MAX_REPORT_BUDGET: usize = 30;
+
+/// Users per format found by earlier reports, reused for [`CACHE_TTL`] without
+/// spending a probe.
+#[cfg(feature = "server")]
+static FORMAT_CACHE: LazyLock<Mutex<HashMap<String, (Instant, BTreeMap<String, usize>)>>> =
+    LazyLock::new(Default::default);
+
+/// The latest report of each user, by user id.
+#[cfg(feature = "server")]
+static REPORTS: LazyLock<Mutex<HashMap<String, AvailabilityReport>>> =
+    LazyLock::new(Default::default);
+
+#[cfg(feature = "server")]
+static NEXT_REPORT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// One album of a discography report.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseAvailability {
+    pub album: Album,
+    /// Users sharing audio files of the album in each format, e.g. "flac".
+    /// `None` until it is probed, or when the budget ran out before it.
+    pub formats: Option<BTreeMap<String, usize>>,
+}
+
+/// Which albums of an artist's discography can be found on Soulseek, in
+/// which formats, from short probe searches.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AvailabilityReport {
+    pub id: u64,
+    pub artist: String,
+    pub releases: Vec<ReleaseAvailability>,
+    /// Probe searches the report may run. Albums probed recently are reused
+    /// and do not count.
+    pub budget: usize,
+    pub probes_used: usize,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "server")]
+fn cache_key(artist: &str, album: &str) -> String {
+    format!("{} {}", artist, album).to_lowercase()
+}
+
+/// Runs a short search for the album, counted among the user's searches and
+/// against the search rate limit.
+#[server]
+pub async fn probe_availability(
+    token: String,
+    artist: String,
+    album: String,
+) -> Result<AvailabilityEstimate, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if crate::demo::enabled() {
+        // The demo never reaches Soulseek, its mocked searches always find albums.
+        return Ok(AvailabilityEstimate {
+            availability: Availability::Likely,
+            sources: 3,
+        });
+    }
+
+    let key = cache_key(&artist, &album);
+    if let Some((at, estimate)) = CACHE.lock().unwrap().get(&key) {
+        if at.elapsed() < CACHE_TTL {
+            return Ok(estimate.clone());
+        }
+    }
+
+    let probe = soulseek().probe_availability(
+        &artist,
+        &album,
+        chrono::Duration::seconds(PROBE_TIMEOUT_SECS),
+    );
+    let sources = searches::in_user_slot(&claims.sub, probe)
+        .await
+        .map_err(server_error)?;
+
+    let availability = match sources {
+        0 => Availability::Unavailable,
+        n if n < LIKELY_THRESHOLD => Availability::Scarce,
+        _ => Availability::Likely,
+    };
+    let estimate = AvailabilityEstimate {
+        availability,
+        sources,
+    };
+    CACHE
+        .lock()
+        .unwrap()
+        .insert(key, (Instant::now(), estimate.clone()));
+    Ok(estimate)
+}
+
+/// Applies `change` to the user's report, unless it was replaced or cancelled
+/// since. Returns whether it was still current.
+#[cfg(feature = "server")]
+fn update_report(user_id: &str, id: u64, change: impl FnOnce(&mut AvailabilityReport)) -> bool {
+    match REPORTS.lock().unwrap().get_mut(user_id) {
+        Some(report) if report.id == id => {
+            change(report);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(feature = "server")]
+fn cached_formats(key: &str) -> Option<BTreeMap<String, usize>> {
+    FORMAT_CACHE
+        .lock()
+        .unwrap()
+        .get(key)
+        .filter(|(at, _)| at.elapsed() < CACHE_TTL)
+        .map(|(_, formats)| formats.clone())
+}
+
+/// Probes the artist's albums one after the other, oldest first, until the
+/// budget is spent.
+#[cfg(feature = "server")]
+async fn run_report(user_id: String, id: u64, artist: String, budget: usize) {
+    let albums = match musicbrainz::artist_albums(&artist).await {
+        Ok(albums) => albums,
+        Err(e) => {
+            update_report(&user_id, id, |report| {
+                report.error = Some(e.to_string());
+                report.done = true;
+            });
+            return;
+        }
+    };
+    let releases = albums
+        .iter()
+        .cloned()
+        .map(|album| ReleaseAvailability {
+            album,
+            formats: None,
+        })
+        .collect();
+    if !update_report(&user_id, id, |report| report.releases = releases) {
+        return;
+    }
+
+    let mut used = 0;
+    for (index, album) in albums.iter().enumerate() {
+        let key = cache_key(&album.artist, &album.title);
+        let formats = match cached_formats(&key) {
+            Some(formats) => formats,
+            None if used < budget => {
+                used += 1;
+                let probe = soulseek().probe_formats(
+                    &album.artist,
+                    &album.title,
+                    chrono::Duration::seconds(PROBE_TIMEOUT_SECS),
+                );
+                let probed = searches::in_user_slot(&user_id, probe).await;
+                match probed {
+                    Ok(formats) => {
+                        FORMAT_CACHE
+                            .lock()
+                            .unwrap()
+                            .insert(key, (Instant::now(), formats.clone()));
+                        formats
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to probe {}: {}", album.title, e);
+                        if !update_report(&user_id, id, |report| report.probes_used = used) {
+                            return;
+                        }
+                        continue;
+                    }
+                }
+            }
+            None => continue,
+        };
+        let current = update_report(&user_id, id, |report| {
+            report.releases[index].formats = Some(formats);
+            report.probes_used = used;
+        });
+        if !current {
+            return;
+        }
+    }
+    update_report(&user_id, id, |report| report.done = true);
+}
+
+/// Starts a report of which of the artist's albums can be found in which
+/// formats, running at most `budget` probe searches. It replaces the user's
+/// previous report, follow it with [`get_availability_report`].
+#[server]
+pub async fn start_availability_report(
+    token: String,
+    artist: String,
+    budget: usize,
+) -> Result<AvailabilityReport, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let artist = artist.trim().to_string();
+    if artist.is_empty() {
+        return Err(server_error("Artist is required"));
+    }
+    if !(1..=MAX_REPORT_BUDGET).contains(&budget) {
+        return Err(server_error(format!(
+            "The budget must be between 1 and {MAX_REPORT_BUDGET} searches"
+        )));
+    }
+
+    let report = AvailabilityReport {
+        id: NEXT_REPORT_ID.fetch_add(1, Ordering::Relaxed),
+        artist: artist.clone(),
+        budget,
+        ..Default::default()
+    };
+    REPORTS
+        .lock()
+        .unwrap()
+        .insert(claims.sub.clone(), report.clone());
+    tokio::spawn(run_report(claims.sub, report.id, artist, budget));
+    Ok(report)
+}
+
+/// The user's latest availability report, as far as it got.
+#[server]
+pub async fn get_availability_report(
+    token: String,
+) -> Result<Option<AvailabilityReport>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    Ok(REPORTS.lock().unwrap().get(&claims.sub).cloned())
+}
+
+/// Stops the user's report before its next probe and forgets it.
+#[server]
+pub async fn cancel_availability_report(token: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    REPORTS.lock().unwrap().remove(&claims.sub);
+    Ok(())
+}
@@ -0,0 +1,13 @@
+//! Prints how job feedback correlates with the ranking scores.
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to start tokio runtime");
+
+    match runtime.block_on(api::feedback::analyze()) {
+        Ok(report) => print!("{report}"),
+        Err(e) => {
+            eprintln!("Feedback analysis failed: {e}");
+            std::process::exit(1);
+        }
+    }
+}
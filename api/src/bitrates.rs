@@ -0,0 +1,77 @@
+//! Per-format minimum bitrates.
+//!
+//! Unlike the quality score, which only ranks candidates, these cutoffs remove
+//! files below the user's minimum from search results altogether.
+
+use dioxus::prelude::*;
+
+use crate::db::BitrateCutoff;
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+#[cfg(feature = "server")]
+use shared::slskd::BitrateCutoffs;
+
+/// The user's cutoffs, as applied to search results.
+#[cfg(feature = "server")]
+pub async fn bitrate_cutoffs(user_id: &str) -> BitrateCutoffs {
+    let cutoffs = BitrateCutoff::get_all_by_user(user_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load bitrate cutoffs for {}: {}", user_id, e);
+            Vec::new()
+        });
+    BitrateCutoffs(
+        cutoffs
+            .into_iter()
+            .map(|c| (c.format, c.min_bitrate))
+            .collect(),
+    )
+}
+
+#[server]
+pub async fn get_bitrate_cutoffs(token: String) -> Result<Vec<BitrateCutoff>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    BitrateCutoff::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn set_bitrate_cutoff(
+    token: String,
+    format: String,
+    min_bitrate: i32,
+) -> Result<BitrateCutoff, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let format = format.trim().trim_start_matches('.');
+    if format.is_empty() {
+        return Err(server_error("Format is required"));
+    }
+    if min_bitrate <= 0 {
+        return Err(server_error("Minimum bitrate must be positive"));
+    }
+
+    BitrateCutoff::upsert(&claims.sub, format, min_bitrate)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn delete_bitrate_cutoff(token: String, format: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    BitrateCutoff::delete(&claims.sub, &format)
+        .await
+        .map_err(server_error)
+}
@@ -0,0 +1,66 @@
+//! Soulseek users a user never wants to download from, e.g. for their dead
+//! queues. Their files are left out of album searches.
+
+use dioxus::prelude::*;
+
+use crate::db::BlockedUploader;
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+
+/// Usernames of the uploaders the user blocked, as applied to search results.
+#[cfg(feature = "server")]
+pub async fn blocked_usernames(user_id: &str) -> Vec<String> {
+    BlockedUploader::get_all_by_user(user_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load blocked uploaders for {}: {}", user_id, e);
+            Vec::new()
+        })
+        .into_iter()
+        .map(|b| b.username)
+        .collect()
+}
+
+#[server]
+pub async fn get_blocked_uploaders(token: String) -> Result<Vec<BlockedUploader>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    BlockedUploader::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+/// Leaves `username`'s files out of the user's searches from now on.
+#[server]
+pub async fn block_uploader(
+    token: String,
+    username: String,
+) -> Result<BlockedUploader, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if username.is_empty() {
+        return Err(server_error("Username is required"));
+    }
+
+    BlockedUploader::create(&claims.sub, &username)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn unblock_uploader(token: String, username: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    BlockedUploader::delete(&claims.sub, &username)
+        .await
+        .map_err(server_error)
+}
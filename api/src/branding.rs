@@ -0,0 +1,76 @@
+//! Instance name, accent color and logo, so that several instances can be told
+//! apart and operators can de-brand the interface.
+
+use dioxus::prelude::*;
+
+use crate::db::Branding;
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+
+/// Longest instance name accepted, it has to fit in the navbar.
+#[cfg(feature = "server")]
+const MAX_NAME_LENGTH: usize = 40;
+
+#[cfg(feature = "server")]
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Trims every field, turning blank ones into `None`, and validates the rest.
+#[cfg(feature = "server")]
+fn normalize(branding: Branding) -> Result<Branding, String> {
+    let clean = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let branding = Branding {
+        instance_name: clean(branding.instance_name),
+        accent_color: clean(branding.accent_color).map(|c| c.to_lowercase()),
+        logo_url: clean(branding.logo_url),
+    };
+
+    if branding
+        .instance_name
+        .as_ref()
+        .is_some_and(|name| name.chars().count() > MAX_NAME_LENGTH)
+    {
+        return Err(format!(
+            "Instance name must be at most {MAX_NAME_LENGTH} characters"
+        ));
+    }
+    if branding
+        .accent_color
+        .as_deref()
+        .is_some_and(|color| !is_hex_color(color))
+    {
+        return Err("Accent color must look like #14b8a6".to_string());
+    }
+    if branding.logo_url.as_deref().is_some_and(|url| {
+        !["https://", "http://", "/", "data:image/"]
+            .iter()
+            .any(|prefix| url.starts_with(prefix))
+    }) {
+        return Err(
+            "Logo must be an http(s) URL, an absolute path or an image data URL".to_string(),
+        );
+    }
+    Ok(branding)
+}
+
+/// Public, the login page is branded too.
+#[server]
+pub async fn get_branding() -> Result<Branding, ServerFnError> {
+    Branding::get().await.map_err(server_error)
+}
+
+#[server]
+pub async fn save_branding(token: String, branding: Branding) -> Result<Branding, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let branding = normalize(branding).map_err(server_error)?;
+    branding.save().await.map_err(server_error)?;
+    Ok(branding)
+}
@@ -0,0 +1,100 @@
+//! Read-only browse mode.
+//!
+//! With `BROWSE_MODE` set, the instance only serves the library browser,
+//! streaming and the download history, so it can be exposed to the internet
+//! while searching and downloading stay on an instance kept on the LAN. The
+//! slskd client is never built and startup skips the scheduler, notifications
+//! and everything that talks to slskd. [`browse_guard`] refuses every server
+//! function not in [`ALLOWED`], and those that search or download also fail
+//! through [`check`] when called directly, without a request.
+
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::{
+    axum::{extract::Request, middleware::Next, response::IntoResponse, response::Response},
+    StatusCode,
+};
+
+/// Server functions served in browse mode: signing in, the library,
+/// streaming and the download history.
+#[cfg(feature = "server")]
+const ALLOWED: &[&str] = &[
+    // Session and app shell
+    "login",
+    "refresh_token",
+    "demo_enabled",
+    "demo_login",
+    "browse_mode",
+    "get_branding",
+    "whats_new",
+    "dismiss_whats_new",
+    // Library
+    "get_user_folders",
+    "get_library",
+    "get_owned_recordings",
+    "has_album_cover",
+    "album_cover_url",
+    "library_file_url",
+    // History
+    "get_jobs",
+    "get_job_updates",
+    "job_file_url",
+    // Public share pages
+    "get_shared_album",
+];
+
+/// Routes declared with an explicit path, under `/api/`: covers, audio
+/// streams and share downloads, which check their own signed URL, and the
+/// job events socket.
+#[cfg(feature = "server")]
+const ALLOWED_ROUTES: &[&str] = &["media/", "share/", "jobs/events"];
+
+/// Whether this instance only serves the library, streaming and history.
+#[cfg(feature = "server")]
+pub(crate) fn enabled() -> bool {
+    std::env::var("BROWSE_MODE").is_ok_and(|v| matches!(v.as_str(), "1" | "true"))
+}
+
+/// Whether a request to `path` is served in browse mode.
+#[cfg(feature = "server")]
+fn allowed(path: &str) -> bool {
+    // Pages and assets; every server function lives under `/api/`.
+    let Some(endpoint) = path.strip_prefix("/api/") else {
+        return true;
+    };
+    if ALLOWED_ROUTES
+        .iter()
+        .any(|route| endpoint.starts_with(route))
+    {
+        return true;
+    }
+    // `#[server]` functions are served at `/api/<name><hash of the module>`.
+    let name = endpoint.trim_end_matches(|c: char| c.is_ascii_digit());
+    ALLOWED.contains(&name)
+}
+
+/// Middleware refusing, in browse mode, every server function outside the
+/// library, streaming and history.
+#[cfg(feature = "server")]
+pub async fn browse_guard(request: Request, next: Next) -> Response {
+    if enabled() && !allowed(request.uri().path()) {
+        return (StatusCode::FORBIDDEN, "Not available on this instance").into_response();
+    }
+    next.run(request).await
+}
+
+/// Fails in browse mode, for server functions that search or download.
+#[cfg(feature = "server")]
+pub(crate) fn check() -> Result<(), String> {
+    if enabled() {
+        return Err("Searching and downloading are disabled on this instance".to_string());
+    }
+    Ok(())
+}
+
+/// Whether the app should only offer the library, streaming and history.
+#[server]
+pub async fn browse_mode() -> Result<bool, ServerFnError> {
+    Ok(enabled())
+}
@@ -0,0 +1,197 @@
+//! Configuration validation.
+//!
+//! Checks everything Soulbeet needs from its environment up front, so that a
+//! misconfigured deployment shows up as one report at startup (and on demand
+//! for admins) instead of panics and silent import failures later on.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::{auth, browse, dispatch, provider, server_error, soulseek};
+#[cfg(feature = "server")]
+use soulbeet::{beets, error::SoulseekError};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConfigCheck {
+    pub name: String,
+    pub ok: bool,
+    /// What was found, and how to fix it when the check failed.
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigReport {
+    pub checks: Vec<ConfigCheck>,
+}
+
+impl ConfigReport {
+    pub fn is_ok(&self) -> bool {
+        self.checks.iter().all(|c| c.ok)
+    }
+
+    #[cfg(feature = "server")]
+    fn push(&mut self, name: &str, result: Result<String, String>) {
+        let (ok, message) = match result {
+            Ok(message) => (true, message),
+            Err(message) => (false, message),
+        };
+        self.checks.push(ConfigCheck {
+            name: name.to_string(),
+            ok,
+            message,
+        });
+    }
+}
+
+#[cfg(feature = "server")]
+const REQUIRED_VARS: [&str; 3] = ["SLSKD_URL", "SLSKD_API_KEY", "SLSKD_DOWNLOAD_PATH"];
+
+/// Environment variables the slskd client needs but that are not set.
+#[cfg(feature = "server")]
+pub fn missing_env_vars() -> Vec<&'static str> {
+    REQUIRED_VARS
+        .into_iter()
+        .filter(|var| std::env::var(var).is_err())
+        .collect()
+}
+
+/// Runs every configuration check and collects the results.
+#[cfg(feature = "server")]
+pub async fn validate() -> ConfigReport {
+    let mut report = ConfigReport::default();
+
+    let browse = browse::enabled();
+    let missing = missing_env_vars();
+    let env_ok = missing.is_empty();
+    report.push(
+        "Environment",
+        if browse {
+            Ok("BROWSE_MODE is set, slskd is not used".to_string())
+        } else if env_ok {
+            Ok("SLSKD_URL, SLSKD_API_KEY and SLSKD_DOWNLOAD_PATH are set".to_string())
+        } else {
+            Err(format!(
+                "Missing environment variables: {}",
+                missing.join(", ")
+            ))
+        },
+    );
+
+    report.push("Secret key", check_secret_key());
+
+    if env_ok && !browse {
+        report.push("slskd API", check_slskd().await);
+        report.push("Download path", check_download_path().await);
+    } else if !browse {
+        let skipped = || Err("Skipped: slskd environment variables are missing".to_string());
+        report.push("slskd API", skipped());
+        report.push("Download path", skipped());
+    }
+
+    report.push(
+        "Beets config",
+        beets::check_config()
+            .await
+            .map(|()| "Configuration loaded".to_string())
+            .map_err(|e| format!("beets could not load its configuration: {e}")),
+    );
+
+    report
+}
+
+#[cfg(feature = "server")]
+fn check_secret_key() -> Result<String, String> {
+    match std::env::var("SECRET_KEY") {
+        Ok(key) if key.is_empty() || key == "secret" => {
+            Err("SECRET_KEY uses an empty or default value, set it to a random string".to_string())
+        }
+        Ok(_) => Ok("SECRET_KEY is set".to_string()),
+        Err(_) => {
+            Err("SECRET_KEY is not set, tokens are signed with an insecure default".to_string())
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+async fn check_slskd() -> Result<String, String> {
+    match soulseek().check_session().await {
+        Ok(()) => Ok("Connected and authenticated".to_string()),
+        Err(SoulseekError::Api { status: 401, .. })
+        | Err(SoulseekError::Api { status: 403, .. }) => Err(
+            "slskd rejected SLSKD_API_KEY, check it matches the key in the slskd config"
+                .to_string(),
+        ),
+        Err(e) => Err(format!("Could not reach slskd at SLSKD_URL: {e}")),
+    }
+}
+
+/// Checks the download folder is writable here, and that files slskd reports
+/// as downloaded actually show up in it, which catches volumes mounted at
+/// different places in the two containers.
+#[cfg(feature = "server")]
+async fn check_download_path() -> Result<String, String> {
+    let path = std::path::PathBuf::from(std::env::var("SLSKD_DOWNLOAD_PATH").unwrap_or_default());
+    if !path.is_dir() {
+        return Err(format!(
+            "{} does not exist or is not a directory",
+            path.display()
+        ));
+    }
+
+    let probe = path.join(".soulbeet-write-test");
+    if let Err(e) = tokio::fs::write(&probe, b"").await {
+        return Err(format!("{} is not writable: {e}", path.display()));
+    }
+    let _ = tokio::fs::remove_file(&probe).await;
+
+    let downloads = provider().get_all_downloads().await.unwrap_or_default();
+    let completed: Vec<_> = downloads.iter().filter(|d| d.is_succeeded()).collect();
+    if completed.is_empty() {
+        return Ok(format!(
+            "{} is writable (no completed slskd downloads to cross-check yet)",
+            path.display()
+        ));
+    }
+
+    let found = completed
+        .iter()
+        .any(|d| std::path::Path::new(&dispatch::local_path(&d.filename)).exists());
+    if found {
+        Ok(format!(
+            "{} is writable and shared with slskd",
+            path.display()
+        ))
+    } else {
+        Err(format!(
+            "None of slskd's completed downloads were found under {}, make sure it points to the slskd downloads volume",
+            path.display()
+        ))
+    }
+}
+
+/// Validates the configuration and logs the report, flagging every failed check.
+#[cfg(feature = "server")]
+pub async fn log_report() -> ConfigReport {
+    let report = validate().await;
+    for check in &report.checks {
+        if check.ok {
+            tracing::info!("Config check '{}': {}", check.name, check.message);
+        } else {
+            tracing::error!("Config check '{}' failed: {}", check.name, check.message);
+        }
+    }
+    if !report.is_ok() {
+        tracing::error!("Configuration is incomplete, downloads or imports will fail until fixed");
+    }
+    report
+}
+
+#[server]
+pub async fn validate_config(token: String) -> Result<ConfigReport, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    Ok(validate().await)
+}
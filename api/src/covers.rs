@@ -0,0 +1,170 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use crate::{auth, db, server_error};
+#[cfg(feature = "server")]
+use soulbeet::beets;
+#[cfg(feature = "server")]
+use std::path::PathBuf;
+
+/// Largest cover image accepted for upload.
+pub const MAX_COVER_SIZE: usize = 10 * 1024 * 1024;
+
+#[cfg(feature = "server")]
+fn covers_dir() -> PathBuf {
+    PathBuf::from(std::env::var("COVERS_PATH").unwrap_or_else(|_| "covers".to_string()))
+}
+
+/// Returns the file extension matching the image's magic bytes.
+#[cfg(feature = "server")]
+fn image_extension(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if data.starts_with(&[0x89, b'P', b'N', b'G']) {
+        Some("png")
+    } else {
+        None
+    }
+}
+
+/// Embeds the user's custom cover, if any, into the files imported by `job`.
+#[cfg(feature = "server")]
+pub async fn embed_custom_cover(job: &db::Job) -> Result<(), String> {
+    let Some(album_id) = &job.album_id else {
+        return Ok(());
+    };
+    let Some(cover) = db::AlbumCover::get(&job.user_id, album_id).await? else {
+        return Ok(());
+    };
+
+    let library = crate::library::job_library(job).await;
+    let query = crate::library::job_query(job);
+    beets::embed_art(&library, std::path::Path::new(&cover.path), &query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Embeds the cover downloaded with the tracks of `job` into the files it
+/// imported, unless the user set a custom cover for the album. Among several
+/// images, one named like a front cover is picked, else the largest.
+#[cfg(feature = "server")]
+pub async fn embed_downloaded_artwork(job: &db::Job, artwork: &[String]) -> Result<(), String> {
+    let Some(album_id) = &job.album_id else {
+        return Ok(());
+    };
+    if db::AlbumCover::get(&job.user_id, album_id).await?.is_some() {
+        return Ok(());
+    }
+    let is_front = |path: &String| {
+        let name = path.rsplit('/').next().unwrap_or_default().to_lowercase();
+        ["front", "cover", "folder"]
+            .iter()
+            .any(|word| name.contains(word))
+    };
+    let size = |path: &String| std::fs::metadata(path).map_or(0, |m| m.len());
+    let Some(cover) = artwork
+        .iter()
+        .filter(|path| size(path) > 0)
+        .max_by_key(|path| (is_front(path), size(path)))
+    else {
+        return Ok(());
+    };
+
+    let library = crate::library::job_library(job).await;
+    let query = crate::library::job_query(job);
+    beets::embed_art(&library, std::path::Path::new(cover), &query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Stores a custom cover for an album. It is embedded at import time instead
+/// of the Cover Art Archive image, and immediately into already imported files.
+#[server]
+pub async fn upload_album_cover(
+    token: String,
+    album_id: String,
+    data: Vec<u8>,
+) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    // The id names the cover file, so only a well-formed MBID is accepted.
+    let Ok(album_id) = uuid::Uuid::parse_str(&album_id).map(|id| id.to_string()) else {
+        return Err(server_error("Invalid album id"));
+    };
+    if data.len() > MAX_COVER_SIZE {
+        return Err(server_error("Cover image is too large (max 10 MB)"));
+    }
+    let Some(extension) = image_extension(&data) else {
+        return Err(server_error("Cover must be a JPEG or PNG image"));
+    };
+
+    let dir = covers_dir().join(&claims.sub);
+    if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+        return Err(server_error(format!(
+            "Failed to create covers directory: {e}"
+        )));
+    }
+
+    if let Some(previous) = db::AlbumCover::get(&claims.sub, &album_id)
+        .await
+        .map_err(server_error)?
+    {
+        let _ = tokio::fs::remove_file(&previous.path).await;
+    }
+
+    let path = dir.join(format!("{album_id}.{extension}"));
+    if let Err(e) = tokio::fs::write(&path, &data).await {
+        return Err(server_error(format!("Failed to save cover: {e}")));
+    }
+
+    db::AlbumCover::upsert(&claims.sub, &album_id, &path.to_string_lossy())
+        .await
+        .map_err(server_error)?;
+
+    let imported = db::Job::get_imported_for_album(&claims.sub, &album_id)
+        .await
+        .map_err(server_error)?;
+    for job in imported {
+        if let Err(e) = embed_custom_cover(&job).await {
+            tracing::warn!("Failed to embed cover for job {}: {}", job.id, e);
+        }
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn remove_album_cover(token: String, album_id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if let Some(cover) = db::AlbumCover::get(&claims.sub, &album_id)
+        .await
+        .map_err(server_error)?
+    {
+        let _ = tokio::fs::remove_file(&cover.path).await;
+        db::AlbumCover::delete(&claims.sub, &album_id)
+            .await
+            .map_err(server_error)?;
+    }
+
+    Ok(())
+}
+
+#[server]
+pub async fn has_album_cover(token: String, album_id: String) -> Result<bool, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    db::AlbumCover::get(&claims.sub, &album_id)
+        .await
+        .map(|cover| cover.is_some())
+        .map_err(server_error)
+}
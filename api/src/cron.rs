@@ -0,0 +1,230 @@
+//! Minimal cron expressions for scheduled tasks.
+//!
+//! Supports the standard five fields (minute, hour, day of month, month, day
+//! of week) with `*`, lists, ranges and steps, plus the `@hourly`, `@daily`,
+//! `@weekly` and `@monthly` shorthands. Times are evaluated in UTC.
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, TimeZone, Timelike, Utc};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    /// Whether the day of month and day of week fields are restricted. When
+    /// both are, a day matching either of them matches, as in cron.
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!(
+                "Expected 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7).map_err(|e| format!("weekday: {e}"))?;
+        // Both 0 and 7 are Sunday.
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+
+        Ok(CronSchedule {
+            minutes: parse_field(minute, 0, 59).map_err(|e| format!("minute: {e}"))?,
+            hours: parse_field(hour, 0, 23).map_err(|e| format!("hour: {e}"))?,
+            days: parse_field(day, 1, 31).map_err(|e| format!("day of month: {e}"))?,
+            months: parse_field(month, 1, 12).map_err(|e| format!("month: {e}"))?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+
+    fn matches_day(&self, time: &DateTime<Utc>) -> bool {
+        let day = self.days & (1 << time.day()) != 0;
+        let weekday = self.weekdays & (1 << time.weekday().num_days_from_sunday()) != 0;
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+
+    /// The first matching minute strictly after `after`, if any within five years.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.duration_trunc(Duration::minutes(1)).ok()? + Duration::minutes(1);
+        let limit = after + Duration::days(5 * 366);
+
+        while time < limit {
+            if self.months & (1 << time.month()) == 0 {
+                let (year, month) = if time.month() == 12 {
+                    (time.year() + 1, 1)
+                } else {
+                    (time.year(), time.month() + 1)
+                };
+                time = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()?;
+            } else if !self.matches_day(&time) {
+                time = time.duration_trunc(Duration::days(1)).ok()? + Duration::days(1);
+            } else if self.hours & (1 << time.hour()) == 0 {
+                time = time.duration_trunc(Duration::hours(1)).ok()? + Duration::hours(1);
+            } else if self.minutes & (1 << time.minute()) == 0 {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+}
+
+/// Parses one field into a bit set of the allowed values.
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("invalid step '{step}'"))?;
+                if step == 0 {
+                    return Err("step must be positive".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let parse_value = |value: &str| -> Result<u32, String> {
+            let value: u32 = value
+                .parse()
+                .map_err(|_| format!("invalid value '{value}'"))?;
+            if value < min || value > max {
+                return Err(format!("{value} is outside {min}-{max}"));
+            }
+            Ok(value)
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+                // A single value with a step runs to the end of the range, e.g. "5/15".
+                None if step > 1 => (parse_value(range)?, max),
+                None => {
+                    let value = parse_value(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("range {start}-{end} is reversed"));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            set |= 1 << value;
+        }
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> DateTime<Utc> {
+        CronSchedule::parse(expression)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_fields() {
+        let schedule = CronSchedule::parse("*/15 9-17 * * 1,3,5").unwrap();
+        assert_eq!(
+            schedule.minutes,
+            (1 << 0) | (1 << 15) | (1 << 30) | (1 << 45)
+        );
+        assert_eq!(schedule.hours, (9..=17).fold(0, |set, h| set | (1 << h)));
+        assert_eq!(schedule.weekdays, (1 << 1) | (1 << 3) | (1 << 5));
+        assert!(!schedule.days_restricted);
+        assert!(schedule.weekdays_restricted);
+
+        // Both 0 and 7 are Sunday.
+        assert_eq!(
+            CronSchedule::parse("0 0 * * 7").unwrap(),
+            CronSchedule::parse("0 0 * * 0").unwrap()
+        );
+        assert_eq!(
+            CronSchedule::parse("@daily").unwrap(),
+            CronSchedule::parse("0 0 * * *").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_bad_expressions() {
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 5-2 * * *").is_err());
+        assert!(CronSchedule::parse("0 0 0 * *").is_err());
+    }
+
+    #[test]
+    fn finds_the_next_run() {
+        // Strictly after, even on a matching minute.
+        assert_eq!(
+            next("0 4 * * *", "2024-03-10T04:00:00Z"),
+            at("2024-03-11T04:00:00Z")
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2024-03-10T04:07:30Z"),
+            at("2024-03-10T04:15:00Z")
+        );
+        // Crosses the end of the year.
+        assert_eq!(
+            next("@monthly", "2024-12-15T00:00:00Z"),
+            at("2025-01-01T00:00:00Z")
+        );
+        // 2024-03-10 is a Sunday.
+        assert_eq!(
+            next("0 6 * * 1", "2024-03-10T12:00:00Z"),
+            at("2024-03-11T06:00:00Z")
+        );
+    }
+
+    #[test]
+    fn matches_either_restricted_day() {
+        // The 1st of the month or any Friday, as in cron.
+        assert_eq!(
+            next("0 0 1 * 5", "2024-03-10T00:00:00Z"),
+            at("2024-03-15T00:00:00Z")
+        );
+        assert_eq!(
+            next("0 0 1 * 5", "2024-03-29T00:00:00Z"),
+            at("2024-04-01T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn gives_up_on_impossible_dates() {
+        let schedule = CronSchedule::parse("0 0 31 2 *").unwrap();
+        assert_eq!(schedule.next_after(at("2024-01-01T00:00:00Z")), None);
+    }
+}
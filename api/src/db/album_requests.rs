@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// Progress of an album queued in a batch, e.g. from a discography.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[cfg_attr(feature = "server", sqlx(rename_all = "snake_case"))]
+pub enum AlbumRequestState {
+    /// Waiting for its turn to be searched.
+    Pending,
+    Searching,
+    /// A source was picked and a download job created.
+    Done,
+    Failed,
+}
+
+/// An album waiting to be searched for and downloaded without the user
+/// picking a source by hand.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct AlbumRequest {
+    pub id: String,
+    pub user_id: String,
+    /// MusicBrainz release to download.
+    pub album_id: String,
+    pub artist: String,
+    pub title: String,
+    pub target_folder: String,
+    /// Quality profile the search runs with, the default one when unset.
+    pub profile_id: Option<String>,
+    pub state: AlbumRequestState,
+    /// Download job created once a source was found.
+    pub job_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[cfg(feature = "server")]
+impl AlbumRequest {
+    pub async fn create(
+        user_id: &str,
+        album: &shared::musicbrainz::Album,
+        target_folder: &str,
+        profile_id: Option<&str>,
+    ) -> Result<AlbumRequest, String> {
+        let pool = get_pool().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query_as::<_, AlbumRequest>(
+            "INSERT INTO album_requests (id, user_id, album_id, artist, title, target_folder, profile_id, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(&album.id)
+        .bind(&album.artist)
+        .bind(&album.title)
+        .bind(target_folder)
+        .bind(profile_id)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// The user's requests, latest first.
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<AlbumRequest>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, AlbumRequest>(
+            "SELECT * FROM album_requests WHERE user_id = ? ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// The oldest request still waiting to be searched.
+    pub async fn next_pending() -> Result<Option<AlbumRequest>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, AlbumRequest>(
+            "SELECT * FROM album_requests WHERE state = 'pending' ORDER BY created_at LIMIT 1",
+        )
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn set_state(
+        id: &str,
+        state: AlbumRequestState,
+        job_id: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "UPDATE album_requests SET state = ?, job_id = ?, error = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(state)
+        .bind(job_id)
+        .bind(error)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Puts requests interrupted mid-search by a restart back in line.
+    pub async fn requeue_interrupted() -> Result<u64, String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE album_requests SET state = 'pending' WHERE state = 'searching'")
+            .execute(pool)
+            .await
+            .map(|r| r.rows_affected())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Removes a request that has not been searched yet, or a finished one.
+    pub async fn delete(user_id: &str, id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "DELETE FROM album_requests WHERE id = ? AND user_id = ? AND state != 'searching'",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use super::get_pool;
+
+/// The last release whose announcement a user dismissed.
+pub struct SeenAnnouncement;
+
+impl SeenAnnouncement {
+    /// `None` until the user first logs in.
+    pub async fn get(user_id: &str) -> Result<Option<String>, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar::<_, String>("SELECT version FROM seen_announcements WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn save(user_id: &str, version: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO seen_announcements (user_id, version, seen_at) VALUES (?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET version = excluded.version,
+                seen_at = excluded.seen_at",
+        )
+        .bind(user_id)
+        .bind(version)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// Lowest bitrate a user accepts for a format. Search results below it are
+/// dropped instead of being ranked lower.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct BitrateCutoff {
+    pub user_id: String,
+    /// File extension, lowercased.
+    pub format: String,
+    /// Minimum bitrate in kbps.
+    pub min_bitrate: i32,
+}
+
+#[cfg(feature = "server")]
+impl BitrateCutoff {
+    pub async fn upsert(
+        user_id: &str,
+        format: &str,
+        min_bitrate: i32,
+    ) -> Result<BitrateCutoff, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, BitrateCutoff>(
+            "INSERT INTO bitrate_cutoffs (user_id, format, min_bitrate) VALUES (?, ?, ?)
+             ON CONFLICT(user_id, format) DO UPDATE SET min_bitrate = excluded.min_bitrate
+             RETURNING *",
+        )
+        .bind(user_id)
+        .bind(format.to_lowercase())
+        .bind(min_bitrate)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<BitrateCutoff>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, BitrateCutoff>(
+            "SELECT * FROM bitrate_cutoffs WHERE user_id = ? ORDER BY format",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(user_id: &str, format: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM bitrate_cutoffs WHERE user_id = ? AND format = ?")
+            .bind(user_id)
+            .bind(format)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// A Soulseek user whose files a user never wants offered again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct BlockedUploader {
+    pub user_id: String,
+    /// Soulseek username, as slskd reports it.
+    pub username: String,
+    pub created_at: String,
+}
+
+#[cfg(feature = "server")]
+impl BlockedUploader {
+    pub async fn create(user_id: &str, username: &str) -> Result<BlockedUploader, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, BlockedUploader>(
+            "INSERT INTO blocked_uploaders (user_id, username, created_at) VALUES (?, ?, ?)
+             ON CONFLICT(user_id, username) DO UPDATE SET username = excluded.username
+             RETURNING *",
+        )
+        .bind(user_id)
+        .bind(username)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<BlockedUploader>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, BlockedUploader>(
+            "SELECT * FROM blocked_uploaders WHERE user_id = ? ORDER BY username",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(user_id: &str, username: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM blocked_uploaders WHERE user_id = ? AND username = ?")
+            .bind(user_id)
+            .bind(username)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
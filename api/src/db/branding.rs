@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// How the instance presents itself. Unset fields fall back to the Soulbeet
+/// defaults.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct Branding {
+    pub instance_name: Option<String>,
+    /// `#rrggbb` color replacing the default teal accent.
+    pub accent_color: Option<String>,
+    pub logo_url: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl Branding {
+    pub async fn get() -> Result<Branding, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Branding>(
+            "SELECT instance_name, accent_color, logo_url FROM branding WHERE id = 1",
+        )
+        .fetch_optional(pool)
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn save(&self) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO branding (id, instance_name, accent_color, logo_url, updated_at) VALUES (1, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET instance_name = excluded.instance_name,
+                accent_color = excluded.accent_color, logo_url = excluded.logo_url, updated_at = excluded.updated_at",
+        )
+        .bind(&self.instance_name)
+        .bind(&self.accent_color)
+        .bind(&self.logo_url)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
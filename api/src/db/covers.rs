@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// A user-supplied cover image that replaces the Cover Art Archive artwork.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct AlbumCover {
+    pub user_id: String,
+    pub album_id: String,
+    pub path: String,
+    pub updated_at: String,
+}
+
+#[cfg(feature = "server")]
+impl AlbumCover {
+    pub async fn upsert(user_id: &str, album_id: &str, path: &str) -> Result<AlbumCover, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, AlbumCover>(
+            "INSERT INTO album_covers (user_id, album_id, path, updated_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT(user_id, album_id) DO UPDATE SET path = excluded.path, updated_at = excluded.updated_at
+             RETURNING *",
+        )
+        .bind(user_id)
+        .bind(album_id)
+        .bind(path)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get(user_id: &str, album_id: &str) -> Result<Option<AlbumCover>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, AlbumCover>(
+            "SELECT * FROM album_covers WHERE user_id = ? AND album_id = ?",
+        )
+        .bind(user_id)
+        .bind(album_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(user_id: &str, album_id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM album_covers WHERE user_id = ? AND album_id = ?")
+            .bind(user_id)
+            .bind(album_id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use shared::musicbrainz::EditionPreference;
+
+use super::get_pool;
+
+/// Which edition of an album a user opens by default.
+pub struct StoredEditionPreference;
+
+impl StoredEditionPreference {
+    /// The original release until the user saves a preference.
+    pub async fn get(user_id: &str) -> Result<EditionPreference, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar::<_, Option<String>>(
+            "SELECT country FROM edition_preferences WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map(|country| EditionPreference {
+            country: country.flatten(),
+        })
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn save(user_id: &str, preference: &EditionPreference) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO edition_preferences (user_id, country, updated_at) VALUES (?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET country = excluded.country,
+                updated_at = excluded.updated_at",
+        )
+        .bind(user_id)
+        .bind(&preference.country)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
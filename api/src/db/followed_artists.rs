@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// An artist whose new albums a user is told about, and optionally gets.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct FollowedArtist {
+    pub id: String,
+    pub user_id: String,
+    /// MusicBrainz id of the artist.
+    pub artist_id: String,
+    pub name: String,
+    /// Whether new albums are queued for download once released.
+    pub auto_download: bool,
+    /// Folder new albums are downloaded to, required for `auto_download`.
+    pub target_folder: Option<String>,
+    /// Quality profile their searches run with, the default one when unset.
+    pub profile_id: Option<String>,
+    /// Albums released before this day are not new.
+    pub created_at: String,
+    pub last_checked_at: Option<String>,
+}
+
+/// A new album of a followed artist.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct WantedRelease {
+    pub id: String,
+    pub user_id: String,
+    pub followed_artist_id: String,
+    /// MusicBrainz release to download.
+    pub album_id: String,
+    pub artist: String,
+    pub title: String,
+    pub release_date: Option<String>,
+    /// Album request searching for it, once queued.
+    pub request_id: Option<String>,
+    pub created_at: String,
+}
+
+#[cfg(feature = "server")]
+impl FollowedArtist {
+    /// Follows the artist, or updates how an artist already followed is.
+    pub async fn upsert(
+        user_id: &str,
+        artist: &shared::musicbrainz::Artist,
+        auto_download: bool,
+        target_folder: Option<&str>,
+        profile_id: Option<&str>,
+    ) -> Result<FollowedArtist, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, FollowedArtist>(
+            "INSERT INTO followed_artists (id, user_id, artist_id, name, auto_download, target_folder, profile_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(user_id, artist_id) DO UPDATE SET auto_download = excluded.auto_download,
+                target_folder = excluded.target_folder, profile_id = excluded.profile_id
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(user_id)
+        .bind(&artist.id)
+        .bind(&artist.name)
+        .bind(auto_download)
+        .bind(target_folder)
+        .bind(profile_id)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<FollowedArtist>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, FollowedArtist>(
+            "SELECT * FROM followed_artists WHERE user_id = ? ORDER BY name",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Every follow of every user, oldest checked first.
+    pub async fn get_all() -> Result<Vec<FollowedArtist>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, FollowedArtist>(
+            "SELECT * FROM followed_artists ORDER BY last_checked_at IS NOT NULL, last_checked_at",
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn set_checked(id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE followed_artists SET last_checked_at = ? WHERE id = ?")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Unfollows the artist, forgetting its wanted albums not queued yet.
+    pub async fn delete(user_id: &str, id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "DELETE FROM wanted_releases WHERE user_id = ? AND followed_artist_id = ? AND request_id IS NULL",
+        )
+        .bind(user_id)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        sqlx::query("DELETE FROM followed_artists WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+impl WantedRelease {
+    /// The album to search for.
+    pub fn album(&self) -> shared::musicbrainz::Album {
+        shared::musicbrainz::Album {
+            id: self.album_id.clone(),
+            title: self.title.clone(),
+            artist: self.artist.clone(),
+            artist_id: None,
+            release_date: self.release_date.clone(),
+            genres: Vec::new(),
+        }
+    }
+
+    /// Whether the album is out, going by its release date.
+    pub fn is_released(&self, today: &str) -> bool {
+        self.release_date
+            .as_deref()
+            .is_some_and(|date| date <= today)
+    }
+}
+
+#[cfg(feature = "server")]
+impl WantedRelease {
+    /// Adds the album to the wanted list unless it is already on it. Returns
+    /// whether it was added.
+    pub async fn add(
+        followed: &FollowedArtist,
+        album: &shared::musicbrainz::Album,
+    ) -> Result<bool, String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT OR IGNORE INTO wanted_releases (id, user_id, followed_artist_id, album_id, artist, title, release_date, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&followed.user_id)
+        .bind(&followed.id)
+        .bind(&album.id)
+        .bind(&album.artist)
+        .bind(&album.title)
+        .bind(&album.release_date)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get(user_id: &str, id: &str) -> Result<WantedRelease, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, WantedRelease>(
+            "SELECT * FROM wanted_releases WHERE user_id = ? AND id = ?",
+        )
+        .bind(user_id)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Wanted album not found".to_string())
+    }
+
+    /// The user's wanted albums, latest releases first.
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<WantedRelease>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, WantedRelease>(
+            "SELECT * FROM wanted_releases WHERE user_id = ? AND dismissed = 0 ORDER BY release_date DESC, created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// The wanted albums of a followed artist not queued yet.
+    pub async fn get_unqueued(followed_artist_id: &str) -> Result<Vec<WantedRelease>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, WantedRelease>(
+            "SELECT * FROM wanted_releases WHERE followed_artist_id = ? AND request_id IS NULL AND dismissed = 0",
+        )
+        .bind(followed_artist_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn set_request(id: &str, request_id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE wanted_releases SET request_id = ? WHERE id = ?")
+            .bind(request_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Takes the album off the wanted list for good.
+    pub async fn dismiss(user_id: &str, id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE wanted_releases SET dismissed = 1 WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// Maps a MusicBrainz or Last.fm tag to the genre a user wants in their library.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct GenreMapping {
+    pub user_id: String,
+    /// Tag as reported by the source, lowercased.
+    pub source: String,
+    pub target: String,
+}
+
+#[cfg(feature = "server")]
+impl GenreMapping {
+    pub async fn upsert(user_id: &str, source: &str, target: &str) -> Result<GenreMapping, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, GenreMapping>(
+            "INSERT INTO genre_mappings (user_id, source, target) VALUES (?, ?, ?)
+             ON CONFLICT(user_id, source) DO UPDATE SET target = excluded.target
+             RETURNING *",
+        )
+        .bind(user_id)
+        .bind(source.to_lowercase())
+        .bind(target)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<GenreMapping>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, GenreMapping>(
+            "SELECT * FROM genre_mappings WHERE user_id = ? ORDER BY source",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(user_id: &str, source: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM genre_mappings WHERE user_id = ? AND source = ?")
+            .bind(user_id)
+            .bind(source)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,771 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use shared::slskd::TrackResult;
+#[cfg(feature = "server")]
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "server")]
+use crate::{events, DownloadOptions};
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+#[cfg(feature = "server")]
+use super::{get_pool, UnmanagedDownload};
+
+/// Lifecycle of a download job.
+///
+/// Jobs start `Queued` until the scheduler gives them a download slot.
+/// `Dispatching` acts as a write-ahead marker: it is persisted before slskd is
+/// asked to download anything, so a job still in this state after a restart
+/// means we crashed between recording the intent and confirming the dispatch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[cfg_attr(feature = "server", sqlx(rename_all = "snake_case"))]
+pub enum JobState {
+    Queued,
+    Dispatching,
+    Downloading,
+    /// Files are downloaded and staged, waiting for the user to pick what to import.
+    AwaitingReview,
+    Importing,
+    Imported,
+    Failed,
+    /// beets hung on the import, and again on its retry, and was killed.
+    ImportTimedOut,
+}
+
+/// Thumbs up/down left by the user on a finished job.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[repr(i32)]
+pub enum Feedback {
+    Up = 1,
+    Down = -1,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct Job {
+    pub id: String,
+    pub user_id: String,
+    pub target_folder: String,
+    pub state: JobState,
+    pub created_at: String,
+    pub updated_at: String,
+    /// MusicBrainz release the files were downloaded for, if known.
+    pub album_id: Option<String>,
+    /// MusicBrainz recording of a single-track download, tagged on its own.
+    pub recording_id: Option<String>,
+    pub review_before_import: bool,
+    pub feedback: Option<Feedback>,
+    /// Job file of an imported album this job downloads a replacement for.
+    pub replaces_file_id: Option<String>,
+    /// Free-text note left by the user when confirming the download.
+    pub note: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct JobFile {
+    pub id: String,
+    pub job_id: String,
+    pub username: String,
+    pub filename: String,
+    pub size: i64,
+    pub transfer_id: Option<String>,
+    pub local_path: Option<String>,
+    pub match_score: Option<f64>,
+    pub quality_score: Option<f64>,
+    /// Final slskd state description, e.g. "Completed, Rejected".
+    pub state: Option<String>,
+    /// Failure reason reported by slskd, verbatim.
+    pub error: Option<String>,
+    /// Track title and artist the file was matched to.
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    /// Job that downloaded a replacement for this file.
+    pub replaced_by: Option<String>,
+    /// Transfer speed measured by slskd in bytes/s, for successful downloads.
+    pub average_speed: Option<f64>,
+    /// Failed file of the same job this one is another copy of.
+    pub retry_of: Option<String>,
+    /// Paused by the user, slskd has no transfer for it until it is resumed.
+    pub paused: bool,
+    /// Place in the uploader's queue while the transfer waits there.
+    pub queue_position: Option<i64>,
+    /// A cover scan of the album rather than one of its tracks.
+    pub artwork: bool,
+}
+
+/// Another source for one of a job's tracks, kept from the original search.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct JobAlternate {
+    pub id: String,
+    pub job_id: String,
+    pub title: String,
+    /// Lower is better, counted per title.
+    pub rank: i64,
+    pub username: String,
+    pub filename: String,
+    pub size: i64,
+    pub match_score: f64,
+    pub quality_score: Option<f64>,
+    pub artist: String,
+    pub used: bool,
+}
+
+#[cfg(feature = "server")]
+impl Job {
+    /// Queues `tracks` for download. Nothing is sent to slskd yet.
+    pub async fn create_queued(
+        user_id: &str,
+        target_folder: &str,
+        options: &DownloadOptions,
+        tracks: &[TrackResult],
+        replaces_file_id: Option<&str>,
+    ) -> Result<Job, String> {
+        let pool = get_pool().await;
+        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+        let id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let job = sqlx::query_as::<_, Job>(
+            "INSERT INTO jobs (id, user_id, target_folder, state, created_at, updated_at, album_id, recording_id, review_before_import, replaces_file_id, note) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(target_folder)
+        .bind(JobState::Queued)
+        .bind(&now)
+        .bind(&now)
+        .bind(&options.album_id)
+        .bind(&options.recording_id)
+        .bind(options.review_before_import)
+        .bind(replaces_file_id)
+        .bind(&options.note)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        for label in &options.labels {
+            sqlx::query("INSERT OR IGNORE INTO job_labels (job_id, label) VALUES (?, ?)")
+                .bind(&id)
+                .bind(label)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+
+        for track in tracks {
+            sqlx::query(
+                "INSERT INTO job_files (id, job_id, username, filename, size, match_score, quality_score, title, artist) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&id)
+            .bind(&track.base.username)
+            .bind(&track.base.filename)
+            .bind(track.base.size)
+            .bind(track.match_score)
+            .bind(track.base.quality_score())
+            .bind(&track.title)
+            .bind(&track.artist)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+
+        for artwork in &options.artwork {
+            sqlx::query(
+                "INSERT INTO job_files (id, job_id, username, filename, size, artwork) VALUES (?, ?, ?, ?, ?, 1)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&id)
+            .bind(&artwork.base.username)
+            .bind(&artwork.base.filename)
+            .bind(artwork.base.size)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+
+        let mut ranks: HashMap<String, i64> = HashMap::new();
+        for alternate in &options.alternates {
+            let Some(track) = tracks
+                .iter()
+                .find(|t| t.title.eq_ignore_ascii_case(&alternate.title))
+            else {
+                continue;
+            };
+            if tracks
+                .iter()
+                .any(|t| t.base.filename == alternate.base.filename)
+            {
+                continue;
+            }
+            let rank = ranks.entry(track.title.to_lowercase()).or_default();
+            sqlx::query(
+                "INSERT INTO job_alternates (id, job_id, title, rank, username, filename, size, match_score, quality_score, artist) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&id)
+            .bind(&track.title)
+            .bind(*rank)
+            .bind(&alternate.base.username)
+            .bind(&alternate.base.filename)
+            .bind(alternate.base.size)
+            .bind(alternate.match_score)
+            .bind(alternate.base.quality_score())
+            .bind(&alternate.artist)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+            *rank += 1;
+        }
+
+        // Gaps are alternates of a title the job has no file for.
+        let mut gap_files = HashSet::new();
+        for gap in &options.gap_fills {
+            if tracks
+                .iter()
+                .any(|t| t.title.eq_ignore_ascii_case(&gap.title))
+                || !gap_files.insert((&gap.base.username, &gap.base.filename))
+            {
+                continue;
+            }
+            let rank = ranks.entry(gap.title.to_lowercase()).or_default();
+            sqlx::query(
+                "INSERT INTO job_alternates (id, job_id, title, rank, username, filename, size, match_score, quality_score, artist) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&id)
+            .bind(&gap.title)
+            .bind(*rank)
+            .bind(&gap.base.username)
+            .bind(&gap.base.filename)
+            .bind(gap.base.size)
+            .bind(gap.match_score)
+            .bind(gap.base.quality_score())
+            .bind(&gap.artist)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+            *rank += 1;
+        }
+
+        tx.commit().await.map_err(|e| e.to_string())?;
+        events::publish(&job.user_id, &job.id, job.state.clone());
+        Ok(job)
+    }
+
+    /// Records a job importing `downloads`, which slskd already completed
+    /// outside of any job, into `target_folder`.
+    pub async fn create_adopted(
+        user_id: &str,
+        target_folder: &str,
+        downloads: &[UnmanagedDownload],
+    ) -> Result<Job, String> {
+        let pool = get_pool().await;
+        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+        let id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now().to_rfc3339();
+
+        let job = sqlx::query_as::<_, Job>(
+            "INSERT INTO jobs (id, user_id, target_folder, state, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(target_folder)
+        .bind(JobState::Importing)
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        for download in downloads {
+            sqlx::query(
+                "INSERT INTO job_files (id, job_id, username, filename, size, transfer_id) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&id)
+            .bind(&download.username)
+            .bind(&download.filename)
+            .bind(download.size)
+            .bind(&download.transfer_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        }
+
+        tx.commit().await.map_err(|e| e.to_string())?;
+        events::publish(&job.user_id, &job.id, job.state.clone());
+        Ok(job)
+    }
+
+    pub async fn get(id: &str) -> Result<Job, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Job not found".to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<Job>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE user_id = ? ORDER BY created_at DESC")
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_by_state(state: JobState) -> Result<Vec<Job>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE state = ? ORDER BY created_at")
+            .bind(state)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Jobs currently holding a download slot.
+    pub async fn get_active() -> Result<Vec<Job>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE state IN (?, ?)")
+            .bind(JobState::Dispatching)
+            .bind(JobState::Downloading)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_imported_for_album(user_id: &str, album_id: &str) -> Result<Vec<Job>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Job>(
+            "SELECT * FROM jobs WHERE user_id = ? AND album_id = ? AND state = ?",
+        )
+        .bind(user_id)
+        .bind(album_id)
+        .bind(JobState::Imported)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Updates the job state and publishes the change to the job's owner.
+    pub async fn set_state(id: &str, state: JobState) -> Result<(), String> {
+        let pool = get_pool().await;
+        let user_id: Option<String> = sqlx::query_scalar(
+            "UPDATE jobs SET state = ?, updated_at = ? WHERE id = ? RETURNING user_id",
+        )
+        .bind(&state)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        if let Some(user_id) = user_id {
+            events::publish(&user_id, id, state);
+        }
+        Ok(())
+    }
+
+    /// Moves the job from `from` to `to` in one statement. Returns false when
+    /// it was no longer in `from`, e.g. another request moved it first.
+    pub async fn transition(id: &str, from: JobState, to: JobState) -> Result<bool, String> {
+        let pool = get_pool().await;
+        let user_id: Option<String> = sqlx::query_scalar(
+            "UPDATE jobs SET state = ?, updated_at = ? WHERE id = ? AND state = ? RETURNING user_id",
+        )
+        .bind(&to)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(id)
+        .bind(&from)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        if let Some(user_id) = &user_id {
+            events::publish(user_id, id, to);
+        }
+        Ok(user_id.is_some())
+    }
+
+    pub async fn set_feedback(id: &str, feedback: Option<Feedback>) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE jobs SET feedback = ? WHERE id = ?")
+            .bind(feedback)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// All jobs that received feedback, across users.
+    pub async fn get_rated() -> Result<Vec<Job>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE feedback IS NOT NULL")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn files(&self) -> Result<Vec<JobFile>, String> {
+        JobFile::get_all_by_job(&self.id).await
+    }
+
+    pub async fn labels(&self) -> Result<Vec<String>, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar("SELECT label FROM job_labels WHERE job_id = ? ORDER BY label")
+            .bind(&self.id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Sampled transfer speeds of each of the job's files, oldest first, keyed
+    /// by job file id.
+    pub async fn speed_history(&self) -> Result<HashMap<String, Vec<f64>>, String> {
+        let pool = get_pool().await;
+        let rows: Vec<(String, f64)> = sqlx::query_as(
+            "SELECT f.id, s.speed FROM transfer_samples s
+             JOIN job_files f ON f.job_id = s.job_id AND f.filename = s.filename
+             WHERE s.job_id = ? ORDER BY s.sampled_at",
+        )
+        .bind(&self.id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut history: HashMap<String, Vec<f64>> = HashMap::new();
+        for (file_id, speed) in rows {
+            history.entry(file_id).or_default().push(speed);
+        }
+        Ok(history)
+    }
+
+    /// Bytes of each of the job's files received by the last speed sample,
+    /// keyed by job file id.
+    pub async fn transferred_bytes(&self) -> Result<HashMap<String, i64>, String> {
+        let pool = get_pool().await;
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT f.id, MAX(s.bytes_transferred) FROM transfer_samples s
+             JOIN job_files f ON f.job_id = s.job_id AND f.filename = s.filename
+             WHERE s.job_id = ? GROUP BY f.id",
+        )
+        .bind(&self.id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "server")]
+impl JobFile {
+    pub async fn get(id: &str) -> Result<JobFile, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, JobFile>("SELECT * FROM job_files WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "File not found".to_string())
+    }
+
+    /// Uploader and filename of every file a job ever asked for.
+    pub async fn requested_files() -> Result<HashSet<(String, String)>, String> {
+        let pool = get_pool().await;
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT username, filename FROM job_files")
+                .fetch_all(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+        Ok(rows.into_iter().collect())
+    }
+
+    pub async fn get_all_by_job(job_id: &str) -> Result<Vec<JobFile>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, JobFile>("SELECT * FROM job_files WHERE job_id = ?")
+            .bind(job_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Attaches the slskd transfer ID once slskd has acknowledged the file.
+    pub async fn set_transfer_id(
+        job_id: &str,
+        filename: &str,
+        transfer_id: &str,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE job_files SET transfer_id = ? WHERE job_id = ? AND filename = ?")
+            .bind(transfer_id)
+            .bind(job_id)
+            .bind(filename)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn set_paused(id: &str, paused: bool) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE job_files SET paused = ? WHERE id = ?")
+            .bind(paused)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn set_queue_position(
+        job_id: &str,
+        filename: &str,
+        position: Option<i64>,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE job_files SET queue_position = ? WHERE job_id = ? AND filename = ?")
+            .bind(position)
+            .bind(job_id)
+            .bind(filename)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Records the spelling slskd accepted for a file that was requested
+    /// under another one, so that its transfer can be matched.
+    pub async fn rename(job_id: &str, filename: &str, new_filename: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE job_files SET filename = ? WHERE job_id = ? AND filename = ?")
+            .bind(new_filename)
+            .bind(job_id)
+            .bind(filename)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn set_local_path(
+        job_id: &str,
+        filename: &str,
+        local_path: &str,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE job_files SET local_path = ? WHERE job_id = ? AND filename = ?")
+            .bind(local_path)
+            .bind(job_id)
+            .bind(filename)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn set_replaced_by(id: &str, job_id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE job_files SET replaced_by = ? WHERE id = ?")
+            .bind(job_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn set_outcome(
+        job_id: &str,
+        filename: &str,
+        state: &str,
+        error: Option<&str>,
+        average_speed: Option<f64>,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "UPDATE job_files SET state = ?, error = ?, average_speed = ? WHERE job_id = ? AND filename = ?",
+        )
+        .bind(state)
+        .bind(error)
+        .bind(average_speed)
+        .bind(job_id)
+        .bind(filename)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Adds another copy of the failed file `retry_of` to its job, from an
+    /// alternate source.
+    pub async fn create_retry(
+        alternate: &JobAlternate,
+        retry_of: &JobFile,
+    ) -> Result<JobFile, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, JobFile>(
+            "INSERT INTO job_files (id, job_id, username, filename, size, match_score, quality_score, title, artist, retry_of) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&alternate.job_id)
+        .bind(&alternate.username)
+        .bind(&alternate.filename)
+        .bind(alternate.size)
+        .bind(alternate.match_score)
+        .bind(alternate.quality_score)
+        .bind(&alternate.title)
+        .bind(&alternate.artist)
+        .bind(&retry_of.id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Adds a track the job's album lacked, from the source `alternate`.
+    pub async fn create_gap_fill(alternate: &JobAlternate) -> Result<JobFile, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, JobFile>(
+            "INSERT INTO job_files (id, job_id, username, filename, size, match_score, quality_score, title, artist) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&alternate.job_id)
+        .bind(&alternate.username)
+        .bind(&alternate.filename)
+        .bind(alternate.size)
+        .bind(alternate.match_score)
+        .bind(alternate.quality_score)
+        .bind(&alternate.title)
+        .bind(&alternate.artist)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Records another request for the same file from the same peer, spelled
+    /// differently.
+    pub async fn create_variant(file: &JobFile, filename: &str) -> Result<JobFile, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, JobFile>(
+            "INSERT INTO job_files (id, job_id, username, filename, size, match_score, quality_score, title, artist, retry_of) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&file.job_id)
+        .bind(&file.username)
+        .bind(filename)
+        .bind(file.size)
+        .bind(file.match_score)
+        .bind(file.quality_score)
+        .bind(&file.title)
+        .bind(&file.artist)
+        .bind(&file.id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Records the speed of a running transfer, in bytes/s.
+    pub async fn add_speed_sample(
+        job_id: &str,
+        filename: &str,
+        speed: f64,
+        bytes_transferred: u64,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO transfer_samples (job_id, filename, speed, bytes_transferred, sampled_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(job_id)
+        .bind(filename)
+        .bind(speed)
+        .bind(bytes_transferred as i64)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Deletes the speed samples of jobs no longer downloading, only shown
+    /// while they are. Returns how many were deleted.
+    pub async fn prune_speed_samples() -> Result<u64, String> {
+        let pool = get_pool().await;
+        let result = sqlx::query(
+            "DELETE FROM transfer_samples WHERE job_id IN
+             (SELECT id FROM jobs WHERE state NOT IN (?, ?, ?))",
+        )
+        .bind(JobState::Queued)
+        .bind(JobState::Dispatching)
+        .bind(JobState::Downloading)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(result.rows_affected())
+    }
+
+    /// Average measured transfer speed per uploader, in bytes/s, across all
+    /// past downloads.
+    pub async fn peer_throughput() -> Result<HashMap<String, f64>, String> {
+        let pool = get_pool().await;
+        let rows: Vec<(String, f64)> = sqlx::query_as(
+            "SELECT username, AVG(average_speed) FROM job_files WHERE average_speed > 0 GROUP BY username",
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Share of the finished downloads that succeeded, per uploader.
+    pub async fn peer_reliability() -> Result<HashMap<String, f64>, String> {
+        let pool = get_pool().await;
+        let rows: Vec<(String, f64)> = sqlx::query_as(
+            "SELECT username, AVG(error IS NULL AND state LIKE '%Succeeded%') FROM job_files WHERE state LIKE 'Completed%' GROUP BY username",
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(rows.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "server")]
+impl JobAlternate {
+    /// Takes the best unused alternate source of a job's track, if any is left.
+    pub async fn take_next(job_id: &str, title: &str) -> Result<Option<JobAlternate>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, JobAlternate>(
+            "UPDATE job_alternates SET used = 1 WHERE id = (
+                SELECT id FROM job_alternates WHERE job_id = ? AND title = ? COLLATE NOCASE AND used = 0
+                ORDER BY rank LIMIT 1
+             ) RETURNING *",
+        )
+        .bind(job_id)
+        .bind(title)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Titles with sources left but no file in the job yet: the gaps of a
+    /// partial album.
+    pub async fn gap_titles(job_id: &str) -> Result<Vec<String>, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar(
+            "SELECT DISTINCT a.title FROM job_alternates a WHERE a.job_id = ? AND a.used = 0
+             AND NOT EXISTS (
+                SELECT 1 FROM job_files f WHERE f.job_id = a.job_id AND f.title = a.title COLLATE NOCASE
+             )",
+        )
+        .bind(job_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+}
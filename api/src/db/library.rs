@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// One file of a library folder, as last seen by beets.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct LibraryEntry {
+    pub path: String,
+    /// Library folder the file was imported into.
+    pub folder: String,
+    pub artist: String,
+    pub album: String,
+    pub title: String,
+    pub track: Option<i64>,
+    pub format: String,
+    /// Bitrate in kbps.
+    pub bitrate: Option<i64>,
+    pub mb_albumid: Option<String>,
+    pub mb_trackid: Option<String>,
+    /// File modification time reported by beets, compared to spot changes.
+    pub mtime: String,
+    pub indexed_at: String,
+}
+
+#[cfg(feature = "server")]
+impl LibraryEntry {
+    pub async fn upsert(entry: &LibraryEntry) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO library_items (path, folder, artist, album, title, track, format, bitrate, mb_albumid, mb_trackid, mtime, indexed_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(path) DO UPDATE SET folder = excluded.folder, artist = excluded.artist, album = excluded.album,
+                title = excluded.title, track = excluded.track, format = excluded.format, bitrate = excluded.bitrate,
+                mb_albumid = excluded.mb_albumid, mb_trackid = excluded.mb_trackid, mtime = excluded.mtime,
+                indexed_at = excluded.indexed_at",
+        )
+        .bind(&entry.path)
+        .bind(&entry.folder)
+        .bind(&entry.artist)
+        .bind(&entry.album)
+        .bind(&entry.title)
+        .bind(entry.track)
+        .bind(&entry.format)
+        .bind(entry.bitrate)
+        .bind(&entry.mb_albumid)
+        .bind(&entry.mb_trackid)
+        .bind(&entry.mtime)
+        .bind(&entry.indexed_at)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn get_by_folder(folder: &str) -> Result<Vec<LibraryEntry>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, LibraryEntry>("SELECT * FROM library_items WHERE folder = ?")
+            .bind(folder)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Every file in the user's folders, by artist, album and track number.
+    pub async fn get_by_user(user_id: &str) -> Result<Vec<LibraryEntry>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, LibraryEntry>(
+            "SELECT l.* FROM library_items l JOIN folders f ON l.folder = f.path
+             WHERE f.user_id = ?
+             ORDER BY l.artist COLLATE NOCASE, l.album COLLATE NOCASE, l.track, l.title",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// The file at `path`, if it is in one of the user's folders.
+    pub async fn get_for_user(user_id: &str, path: &str) -> Result<Option<LibraryEntry>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, LibraryEntry>(
+            "SELECT l.* FROM library_items l JOIN folders f ON l.folder = f.path
+             WHERE f.user_id = ? AND l.path = ?",
+        )
+        .bind(user_id)
+        .bind(path)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(path: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM library_items WHERE path = ?")
+            .bind(path)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Whether `folder` already holds files of an album with this artist and
+    /// title, but from another release than `mb_albumid`. Files without a
+    /// release id are not counted, nothing says they are another edition.
+    pub async fn has_other_release(
+        folder: &str,
+        artist: &str,
+        album: &str,
+        mb_albumid: &str,
+    ) -> Result<bool, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM library_items WHERE folder = ? AND artist = ? COLLATE NOCASE
+                AND album = ? COLLATE NOCASE AND mb_albumid IS NOT NULL AND mb_albumid != ?)",
+        )
+        .bind(folder)
+        .bind(artist)
+        .bind(album)
+        .bind(mb_albumid)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Which of `mb_trackids` the user has in any of their folders, on any release.
+    pub async fn owned_recordings(
+        user_id: &str,
+        mb_trackids: &[String],
+    ) -> Result<Vec<String>, String> {
+        if mb_trackids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pool = get_pool().await;
+        let placeholders = vec!["?"; mb_trackids.len()].join(", ");
+        let sql = format!(
+            "SELECT DISTINCT l.mb_trackid FROM library_items l JOIN folders f ON l.folder = f.path
+             WHERE f.user_id = ? AND l.mb_trackid IN ({placeholders})"
+        );
+        let mut query = sqlx::query_scalar::<_, String>(&sql).bind(user_id);
+        for id in mb_trackids {
+            query = query.bind(id);
+        }
+        query.fetch_all(pool).await.map_err(|e| e.to_string())
+    }
+}
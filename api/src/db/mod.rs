@@ -0,0 +1,371 @@
+#[cfg(feature = "server")]
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "server")]
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Row, Sqlite};
+#[cfg(feature = "server")]
+use std::sync::OnceLock;
+#[cfg(feature = "server")]
+use tokio::sync::OnceCell;
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+mod album_requests;
+#[cfg(feature = "server")]
+mod announcements;
+mod bitrates;
+mod blocked_uploaders;
+mod branding;
+mod covers;
+#[cfg(feature = "server")]
+mod edition_preferences;
+mod followed_artists;
+mod genres;
+mod jobs;
+mod library;
+#[cfg(feature = "server")]
+mod musicbrainz_cache;
+mod notifications;
+mod passkeys;
+#[cfg(feature = "server")]
+mod profiles;
+#[cfg(feature = "server")]
+mod scoring_config;
+#[cfg(feature = "server")]
+mod search_rate_limit;
+#[cfg(feature = "server")]
+mod share_links;
+mod tasks;
+mod unmanaged_downloads;
+
+pub use album_requests::*;
+#[cfg(feature = "server")]
+pub use announcements::*;
+pub use bitrates::*;
+pub use blocked_uploaders::*;
+pub use branding::*;
+pub use covers::*;
+#[cfg(feature = "server")]
+pub use edition_preferences::*;
+pub use followed_artists::*;
+pub use genres::*;
+pub use jobs::*;
+pub use library::*;
+#[cfg(feature = "server")]
+pub use musicbrainz_cache::*;
+pub use notifications::*;
+pub use passkeys::*;
+#[cfg(feature = "server")]
+pub use profiles::*;
+#[cfg(feature = "server")]
+pub use scoring_config::*;
+#[cfg(feature = "server")]
+pub use search_rate_limit::*;
+#[cfg(feature = "server")]
+pub use share_links::*;
+pub use tasks::*;
+pub use unmanaged_downloads::*;
+
+#[cfg(feature = "server")]
+static POOL: OnceCell<Pool<Sqlite>> = OnceCell::const_new();
+
+#[cfg(feature = "server")]
+pub async fn get_pool() -> &'static Pool<Sqlite> {
+    POOL.get_or_init(|| async {
+        let database_url =
+            std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:soulbeet.db".to_string());
+
+        if database_url.starts_with("sqlite:") {
+            let path_str = database_url.trim_start_matches("sqlite:");
+            let path = std::path::Path::new(path_str);
+            if !path.exists() {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).expect("Failed to create database directory");
+                }
+                std::fs::File::create(path).expect("Failed to create database file");
+            }
+        }
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to database");
+
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("Failed to run migrations");
+
+        grant_admin_users(&pool).await;
+
+        pool
+    })
+    .await
+}
+
+/// Makes the users listed in `ADMIN_USERS`, comma-separated usernames,
+/// admins. Rights are only granted, removing a name keeps the user admin.
+#[cfg(feature = "server")]
+async fn grant_admin_users(pool: &Pool<Sqlite>) {
+    let Ok(names) = std::env::var("ADMIN_USERS") else {
+        return;
+    };
+    for name in names.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match sqlx::query("UPDATE users SET is_admin = 1 WHERE username = ?")
+            .bind(name)
+            .execute(pool)
+            .await
+        {
+            Ok(r) if r.rows_affected() == 0 => {
+                tracing::warn!("ADMIN_USERS: no user named {}", name)
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("ADMIN_USERS: failed to grant admin to {}: {}", name, e),
+        }
+    }
+}
+
+/// Writes a consistent copy of the database to `path`, which must not exist.
+#[cfg(feature = "server")]
+pub async fn backup_to(path: &std::path::Path) -> Result<(), String> {
+    let pool = get_pool().await;
+    sqlx::query("VACUUM INTO ?")
+        .bind(path.to_string_lossy())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    #[serde(skip)]
+    pub password_hash: String,
+    pub is_admin: bool,
+    /// Share of download slots relative to other users with queued jobs.
+    pub queue_weight: i64,
+    /// Whether the user's library folders are shared through slskd.
+    pub share_library: bool,
+}
+
+/// Whether and how lyrics are saved for tracks imported into a folder.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[cfg_attr(feature = "server", sqlx(rename_all = "snake_case"))]
+pub enum LyricsMode {
+    #[default]
+    Off,
+    /// An `.lrc` file next to each track, read by most players.
+    Sidecar,
+    /// Embedded in the files' lyrics tag.
+    Tags,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct Folder {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub path: String,
+    #[serde(default)]
+    pub lyrics: LyricsMode,
+    #[cfg_attr(feature = "server", sqlx(flatten))]
+    #[serde(default)]
+    pub permissions: FolderPermissions,
+}
+
+/// Permissions and ownership given to files imported into a folder. Unset
+/// fields leave the files as the server process created them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct FolderPermissions {
+    /// Octal mode of imported files, e.g. "644".
+    pub file_mode: Option<String>,
+    /// Octal mode of the album and artist folders, e.g. "755".
+    pub dir_mode: Option<String>,
+    pub owner_uid: Option<i64>,
+    pub owner_gid: Option<i64>,
+}
+
+#[cfg(feature = "server")]
+impl User {
+    pub async fn create(username: &str, password: &str) -> Result<User, String> {
+        let pool = get_pool().await;
+        let salt = SaltString::generate(&mut OsRng);
+        let argon2 = Argon2::default();
+        let password_hash = argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| e.to_string())?
+            .to_string();
+
+        let id = Uuid::new_v4().to_string();
+
+        let user = sqlx::query_as::<_, User>(
+            "INSERT INTO users (id, username, password_hash) VALUES (?, ?, ?) RETURNING *",
+        )
+        .bind(&id)
+        .bind(username)
+        .bind(password_hash)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(user)
+    }
+
+    pub async fn verify(username: &str, password: &str) -> Result<User, String> {
+        let pool = get_pool().await;
+        let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("User not found")?;
+
+        let parsed_hash = PasswordHash::new(&user.password_hash).map_err(|e| e.to_string())?;
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .map_err(|_| "Invalid password")?;
+
+        Ok(user)
+    }
+
+    pub async fn get(id: &str) -> Result<User, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "User not found".to_string())
+    }
+
+    pub async fn get_by_username(username: &str) -> Result<User, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = ?")
+            .bind(username)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "User not found".to_string())
+    }
+
+    pub async fn set_share_library(id: &str, enabled: bool) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE users SET share_library = ? WHERE id = ?")
+            .bind(enabled)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn get_all() -> Result<Vec<User>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, User>("SELECT * FROM users")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_folders(&self) -> Result<Vec<Folder>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Folder>("SELECT * FROM folders WHERE user_id = ?")
+            .bind(&self.id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(feature = "server")]
+impl Folder {
+    pub async fn create(user_id: &str, name: &str, path: &str) -> Result<Folder, String> {
+        let pool = get_pool().await;
+        let id = Uuid::new_v4().to_string();
+
+        let folder = sqlx::query_as::<_, Folder>(
+            "INSERT INTO folders (id, user_id, name, path) VALUES (?, ?, ?, ?) RETURNING *",
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(name)
+        .bind(path)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(folder)
+    }
+
+    pub async fn get_all() -> Result<Vec<Folder>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Folder>("SELECT * FROM folders")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<Folder>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Folder>("SELECT * FROM folders WHERE user_id = ?")
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_by_path(user_id: &str, path: &str) -> Result<Option<Folder>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Folder>("SELECT * FROM folders WHERE user_id = ? AND path = ?")
+            .bind(user_id)
+            .bind(path)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn set_lyrics(user_id: &str, id: &str, mode: LyricsMode) -> Result<(), String> {
+        let pool = get_pool().await;
+        let result = sqlx::query("UPDATE folders SET lyrics = ? WHERE id = ? AND user_id = ?")
+            .bind(mode)
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        if result.rows_affected() == 0 {
+            return Err("Folder not found".to_string());
+        }
+        Ok(())
+    }
+
+    pub async fn set_permissions(id: &str, permissions: &FolderPermissions) -> Result<(), String> {
+        let pool = get_pool().await;
+        let result = sqlx::query(
+            "UPDATE folders SET file_mode = ?, dir_mode = ?, owner_uid = ?, owner_gid = ?
+             WHERE id = ?",
+        )
+        .bind(&permissions.file_mode)
+        .bind(&permissions.dir_mode)
+        .bind(permissions.owner_uid)
+        .bind(permissions.owner_gid)
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        if result.rows_affected() == 0 {
+            return Err("Folder not found".to_string());
+        }
+        Ok(())
+    }
+}
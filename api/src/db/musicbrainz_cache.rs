@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+
+use super::get_pool;
+
+/// MusicBrainz responses stored as JSON, keyed by what was looked up.
+pub struct MusicBrainzCache;
+
+impl MusicBrainzCache {
+    /// The response stored under `key`, unless it was fetched before `since`.
+    pub async fn get(key: &str, since: DateTime<Utc>) -> Result<Option<String>, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar::<_, String>(
+            "SELECT response FROM musicbrainz_cache WHERE key = ? AND fetched_at >= ?",
+        )
+        .bind(key)
+        .bind(since.to_rfc3339())
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn put(key: &str, response: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO musicbrainz_cache (key, response, fetched_at) VALUES (?, ?, ?)
+             ON CONFLICT(key) DO UPDATE SET response = excluded.response,
+                fetched_at = excluded.fetched_at",
+        )
+        .bind(key)
+        .bind(response)
+        .bind(Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Removes the responses fetched before `before`.
+    pub async fn prune(before: DateTime<Utc>) -> Result<u64, String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM musicbrainz_cache WHERE fetched_at < ?")
+            .bind(before.to_rfc3339())
+            .execute(pool)
+            .await
+            .map(|result| result.rows_affected())
+            .map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+/// How a sink delivers notifications.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::Type))]
+#[cfg_attr(feature = "server", sqlx(rename_all = "snake_case"))]
+pub enum NotificationKind {
+    /// A JSON POST with the title, message and events.
+    #[default]
+    Webhook,
+    /// A plain text POST to an ntfy topic URL.
+    Ntfy,
+}
+
+/// Where one of a user's job notifications are sent, and which.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct NotificationSink {
+    pub id: String,
+    pub user_id: String,
+    pub kind: NotificationKind,
+    pub url: String,
+    /// Seconds job events are gathered for before one digest is sent, 0 to
+    /// send each right away.
+    pub digest_window: i64,
+    pub notify_imported: bool,
+    pub notify_failed: bool,
+    pub notify_review: bool,
+    pub created_at: String,
+}
+
+#[cfg(feature = "server")]
+impl NotificationSink {
+    /// Creates the sink, or updates it when its id is set.
+    pub async fn save(user_id: &str, sink: &NotificationSink) -> Result<NotificationSink, String> {
+        let pool = get_pool().await;
+        let id = if sink.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            sink.id.clone()
+        };
+
+        sqlx::query_as::<_, NotificationSink>(
+            "INSERT INTO notification_sinks (id, user_id, kind, url, digest_window, notify_imported, notify_failed, notify_review, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET kind = excluded.kind, url = excluded.url,
+                digest_window = excluded.digest_window, notify_imported = excluded.notify_imported,
+                notify_failed = excluded.notify_failed, notify_review = excluded.notify_review
+             WHERE notification_sinks.user_id = excluded.user_id
+             RETURNING *",
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(sink.kind)
+        .bind(&sink.url)
+        .bind(sink.digest_window)
+        .bind(sink.notify_imported)
+        .bind(sink.notify_failed)
+        .bind(sink.notify_review)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Notification sink not found".to_string())
+    }
+
+    pub async fn get(id: &str) -> Result<Option<NotificationSink>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, NotificationSink>("SELECT * FROM notification_sinks WHERE id = ?")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<NotificationSink>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, NotificationSink>(
+            "SELECT * FROM notification_sinks WHERE user_id = ? ORDER BY created_at",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(user_id: &str, id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM notification_sinks WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Records that the job's failure was notified. `false` when it already
+    /// was, e.g. for a job that failed again after a retry.
+    pub async fn mark_failure_notified(job_id: &str) -> Result<bool, String> {
+        let pool = get_pool().await;
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO notified_failures (job_id, notified_at) VALUES (?, ?)",
+        )
+        .bind(job_id)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// A passkey a user signs in with instead of their password.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct Passkey {
+    /// Credential ID, base64url encoded.
+    pub id: String,
+    pub user_id: String,
+    /// Label given by the user, e.g. "Laptop".
+    pub name: String,
+    /// The credential as stored by webauthn-rs, as JSON.
+    #[serde(skip)]
+    pub credential: String,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl Passkey {
+    pub async fn create(
+        id: &str,
+        user_id: &str,
+        name: &str,
+        credential: &str,
+    ) -> Result<Passkey, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Passkey>(
+            "INSERT INTO passkeys (id, user_id, name, credential, created_at) VALUES (?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(name)
+        .bind(credential)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<Passkey>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, Passkey>("SELECT * FROM passkeys WHERE user_id = ? ORDER BY created_at")
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Saves the credential after a sign-in, whose signature counter moved.
+    pub async fn mark_used(id: &str, credential: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE passkeys SET credential = ?, last_used_at = ? WHERE id = ?")
+            .bind(credential)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn delete(user_id: &str, id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM passkeys WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
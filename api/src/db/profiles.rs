@@ -0,0 +1,126 @@
+use shared::{profiles::QualityProfile, slskd::ScoringStrategyKind};
+use uuid::Uuid;
+
+use super::get_pool;
+
+/// A quality profile created by a user, usually cloned from a preset.
+#[derive(Clone, Debug, sqlx::FromRow)]
+pub struct StoredProfile {
+    pub id: String,
+    pub user_id: String,
+    pub name: String,
+    pub description: String,
+    pub scoring: String,
+    pub formats: String,
+    pub min_completeness: f64,
+    pub auto_pick: bool,
+}
+
+fn scoring_name(scoring: ScoringStrategyKind) -> &'static str {
+    match scoring {
+        ScoringStrategyKind::QualityFirst => "quality_first",
+        ScoringStrategyKind::AvailabilityFirst => "availability_first",
+        ScoringStrategyKind::SizeFirst => "size_first",
+    }
+}
+
+impl From<StoredProfile> for QualityProfile {
+    fn from(stored: StoredProfile) -> Self {
+        let scoring = match stored.scoring.as_str() {
+            "availability_first" => ScoringStrategyKind::AvailabilityFirst,
+            "size_first" => ScoringStrategyKind::SizeFirst,
+            _ => ScoringStrategyKind::QualityFirst,
+        };
+        QualityProfile {
+            id: stored.id,
+            name: stored.name,
+            description: stored.description,
+            scoring,
+            formats: stored
+                .formats
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect(),
+            min_completeness: stored.min_completeness,
+            auto_pick: stored.auto_pick,
+            built_in: false,
+        }
+    }
+}
+
+impl StoredProfile {
+    pub async fn get_all_by_user(user_id: &str) -> Result<Vec<QualityProfile>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, StoredProfile>(
+            "SELECT * FROM quality_profiles WHERE user_id = ? ORDER BY name",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map(|rows| rows.into_iter().map(QualityProfile::from).collect())
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn get(user_id: &str, id: &str) -> Result<Option<QualityProfile>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, StoredProfile>(
+            "SELECT * FROM quality_profiles WHERE user_id = ? AND id = ?",
+        )
+        .bind(user_id)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map(|row| row.map(QualityProfile::from))
+        .map_err(|e| e.to_string())
+    }
+
+    /// Creates the profile, or updates it if the user already has one with
+    /// this id. Profiles without an id get a new one.
+    pub async fn save(user_id: &str, profile: &QualityProfile) -> Result<QualityProfile, String> {
+        let pool = get_pool().await;
+        let id = if profile.id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            profile.id.clone()
+        };
+
+        let result = sqlx::query(
+            "INSERT INTO quality_profiles (id, user_id, name, description, scoring, formats, min_completeness, auto_pick)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET name = excluded.name, description = excluded.description,
+                scoring = excluded.scoring, formats = excluded.formats,
+                min_completeness = excluded.min_completeness, auto_pick = excluded.auto_pick
+             WHERE quality_profiles.user_id = excluded.user_id",
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(&profile.name)
+        .bind(&profile.description)
+        .bind(scoring_name(profile.scoring))
+        .bind(profile.formats.join(","))
+        .bind(profile.min_completeness)
+        .bind(profile.auto_pick)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        if result.rows_affected() == 0 {
+            return Err("Profile not found".to_string());
+        }
+        StoredProfile::get(user_id, &id)
+            .await?
+            .ok_or_else(|| "Profile not found".to_string())
+    }
+
+    pub async fn delete(user_id: &str, id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM quality_profiles WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
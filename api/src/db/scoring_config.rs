@@ -0,0 +1,115 @@
+use shared::slskd::{BitrateBonus, ScoringConfig, ScoringWeights};
+
+use super::get_pool;
+
+/// A user's scoring adjustments, applied to every search they run.
+#[derive(Clone, Debug, sqlx::FromRow)]
+pub struct StoredScoringConfig {
+    pub match_weight: Option<f64>,
+    pub completeness_weight: Option<f64>,
+    pub format_weight: Option<f64>,
+    pub availability_weight: Option<f64>,
+    pub min_match_score: Option<f64>,
+    pub bitrate_bonuses: String,
+    pub title_suffixes: String,
+    pub suffix_penalty: Option<f64>,
+}
+
+impl From<StoredScoringConfig> for ScoringConfig {
+    fn from(stored: StoredScoringConfig) -> Self {
+        let weights = match (
+            stored.match_weight,
+            stored.completeness_weight,
+            stored.format_weight,
+            stored.availability_weight,
+        ) {
+            (Some(match_score), Some(completeness), Some(format), Some(availability)) => {
+                Some(ScoringWeights {
+                    match_score,
+                    completeness,
+                    format,
+                    availability,
+                })
+            }
+            _ => None,
+        };
+        ScoringConfig {
+            weights,
+            min_match_score: stored.min_match_score,
+            bitrate_bonuses: stored
+                .bitrate_bonuses
+                .split(',')
+                .filter_map(|pair| {
+                    let (bitrate, bonus) = pair.split_once(':')?;
+                    Some(BitrateBonus {
+                        min_bitrate: bitrate.parse().ok()?,
+                        bonus: bonus.parse().ok()?,
+                    })
+                })
+                .collect(),
+            title_suffixes: stored
+                .title_suffixes
+                .split(',')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+            suffix_penalty: stored.suffix_penalty,
+        }
+    }
+}
+
+impl StoredScoringConfig {
+    /// The default config until the user saves one.
+    pub async fn get(user_id: &str) -> Result<ScoringConfig, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, StoredScoringConfig>(
+            "SELECT match_weight, completeness_weight, format_weight, availability_weight,
+                min_match_score, bitrate_bonuses, title_suffixes, suffix_penalty
+             FROM scoring_configs WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map(|stored| stored.map(ScoringConfig::from).unwrap_or_default())
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn save(user_id: &str, config: &ScoringConfig) -> Result<(), String> {
+        let pool = get_pool().await;
+        let weights = config.weights;
+        let bitrate_bonuses = config
+            .bitrate_bonuses
+            .iter()
+            .map(|b| format!("{}:{}", b.min_bitrate, b.bonus))
+            .collect::<Vec<_>>()
+            .join(",");
+        sqlx::query(
+            "INSERT INTO scoring_configs (user_id, match_weight, completeness_weight, format_weight,
+                availability_weight, min_match_score, bitrate_bonuses, title_suffixes,
+                suffix_penalty, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET match_weight = excluded.match_weight,
+                completeness_weight = excluded.completeness_weight,
+                format_weight = excluded.format_weight,
+                availability_weight = excluded.availability_weight,
+                min_match_score = excluded.min_match_score,
+                bitrate_bonuses = excluded.bitrate_bonuses,
+                title_suffixes = excluded.title_suffixes,
+                suffix_penalty = excluded.suffix_penalty, updated_at = excluded.updated_at",
+        )
+        .bind(user_id)
+        .bind(weights.map(|w| w.match_score))
+        .bind(weights.map(|w| w.completeness))
+        .bind(weights.map(|w| w.format))
+        .bind(weights.map(|w| w.availability))
+        .bind(config.min_match_score)
+        .bind(bitrate_bonuses)
+        .bind(config.title_suffixes.join(","))
+        .bind(config.suffix_penalty)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
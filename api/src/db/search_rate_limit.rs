@@ -0,0 +1,49 @@
+use shared::slskd::SearchRateLimit;
+
+use super::get_pool;
+
+/// The search rate limit saved by an admin, replacing the built-in one.
+#[derive(Clone, Debug, sqlx::FromRow)]
+pub struct StoredRateLimit {
+    pub max_searches: i64,
+    pub window_seconds: i64,
+}
+
+impl From<StoredRateLimit> for SearchRateLimit {
+    fn from(stored: StoredRateLimit) -> Self {
+        SearchRateLimit {
+            max_searches: stored.max_searches.max(1) as usize,
+            window_seconds: stored.window_seconds,
+        }
+    }
+}
+
+impl StoredRateLimit {
+    /// `None` until an admin saves one.
+    pub async fn get() -> Result<Option<SearchRateLimit>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, StoredRateLimit>(
+            "SELECT max_searches, window_seconds FROM search_rate_limit WHERE id = 1",
+        )
+        .fetch_optional(pool)
+        .await
+        .map(|stored| stored.map(SearchRateLimit::from))
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn save(limit: &SearchRateLimit) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT INTO search_rate_limit (id, max_searches, window_seconds, updated_at) VALUES (1, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET max_searches = excluded.max_searches,
+                window_seconds = excluded.window_seconds, updated_at = excluded.updated_at",
+        )
+        .bind(limit.max_searches as i64)
+        .bind(limit.window_seconds)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use uuid::Uuid;
+
+use super::get_pool;
+
+/// A public link to an imported album. Its id is the secret part of the URL,
+/// so it never leaves the server except to the owner.
+#[derive(Clone, Debug, sqlx::FromRow)]
+pub struct ShareLink {
+    pub id: String,
+    pub user_id: String,
+    pub job_id: String,
+    pub password_hash: Option<String>,
+    pub expires_at: String,
+    pub created_at: String,
+}
+
+impl ShareLink {
+    pub async fn create(
+        user_id: &str,
+        job_id: &str,
+        password: Option<&str>,
+        expires_at: &str,
+    ) -> Result<ShareLink, String> {
+        let pool = get_pool().await;
+        let password_hash = match password {
+            Some(password) => Some(
+                Argon2::default()
+                    .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
+                    .map_err(|e| e.to_string())?
+                    .to_string(),
+            ),
+            None => None,
+        };
+
+        sqlx::query_as::<_, ShareLink>(
+            "INSERT INTO share_links (id, user_id, job_id, password_hash, expires_at, created_at) VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+        )
+        .bind(Uuid::new_v4().simple().to_string())
+        .bind(user_id)
+        .bind(job_id)
+        .bind(password_hash)
+        .bind(expires_at)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// The link with this id, unless it has expired.
+    pub async fn get_active(id: &str) -> Result<ShareLink, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, ShareLink>("SELECT * FROM share_links WHERE id = ? AND expires_at > ?")
+            .bind(id)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Share link not found or expired".to_string())
+    }
+
+    pub async fn get_by_job(job_id: &str) -> Result<Vec<ShareLink>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, ShareLink>(
+            "SELECT * FROM share_links WHERE job_id = ? ORDER BY created_at DESC",
+        )
+        .bind(job_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn delete(id: &str, user_id: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("DELETE FROM share_links WHERE id = ? AND user_id = ?")
+            .bind(id)
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Deletes expired links, returning their ids.
+    pub async fn delete_expired() -> Result<Vec<String>, String> {
+        let pool = get_pool().await;
+        sqlx::query_scalar("DELETE FROM share_links WHERE expires_at <= ? RETURNING id")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Checks `password` against the link's password. Links without one
+    /// accept anything.
+    pub fn verify_password(&self, password: Option<&str>) -> Result<(), String> {
+        let Some(hash) = &self.password_hash else {
+            return Ok(());
+        };
+        let Some(password) = password else {
+            return Err("Password required".to_string());
+        };
+        let parsed_hash = PasswordHash::new(hash).map_err(|e| e.to_string())?;
+        Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .map_err(|_| "Invalid password".to_string())
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+#[cfg(feature = "server")]
+use super::get_pool;
+
+/// Persisted schedule of a background task.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct ScheduledTask {
+    pub name: String,
+    pub cron: String,
+    /// Random delay of up to this many seconds added to every run, so tasks
+    /// sharing a schedule do not all start at once.
+    pub jitter_seconds: i64,
+    pub enabled: bool,
+    pub next_run_at: Option<String>,
+}
+
+/// One execution of a scheduled task.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct TaskRun {
+    pub id: String,
+    pub task: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    /// `None` while the run is in progress.
+    pub success: Option<bool>,
+    /// Summary of what the run did, or why it failed.
+    pub message: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl ScheduledTask {
+    /// Creates the task with its default schedule unless it already exists,
+    /// keeping any schedule an admin has set.
+    pub async fn ensure(name: &str, cron: &str, jitter_seconds: i64) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "INSERT OR IGNORE INTO scheduled_tasks (name, cron, jitter_seconds) VALUES (?, ?, ?)",
+        )
+        .bind(name)
+        .bind(cron)
+        .bind(jitter_seconds)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn get(name: &str) -> Result<ScheduledTask, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, ScheduledTask>("SELECT * FROM scheduled_tasks WHERE name = ?")
+            .bind(name)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Task not found".to_string())
+    }
+
+    pub async fn get_all() -> Result<Vec<ScheduledTask>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, ScheduledTask>("SELECT * FROM scheduled_tasks ORDER BY name")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Updates the schedule. The next run is cleared so the scheduler
+    /// computes it again from the new expression.
+    pub async fn update(
+        name: &str,
+        cron: &str,
+        jitter_seconds: i64,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "UPDATE scheduled_tasks SET cron = ?, jitter_seconds = ?, enabled = ?, next_run_at = NULL WHERE name = ?",
+        )
+        .bind(cron)
+        .bind(jitter_seconds)
+        .bind(enabled)
+        .bind(name)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub async fn set_next_run(name: &str, next_run_at: Option<&str>) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE scheduled_tasks SET next_run_at = ? WHERE name = ?")
+            .bind(next_run_at)
+            .bind(name)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "server")]
+impl TaskRun {
+    pub async fn start(task: &str) -> Result<TaskRun, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, TaskRun>(
+            "INSERT INTO task_runs (id, task, started_at) VALUES (?, ?, ?) RETURNING *",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(task)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn finish(id: &str, success: bool, message: &str) -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query("UPDATE task_runs SET finished_at = ?, success = ?, message = ? WHERE id = ?")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(success)
+            .bind(message)
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Most recent runs across all tasks.
+    pub async fn get_recent(limit: i64) -> Result<Vec<TaskRun>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, TaskRun>("SELECT * FROM task_runs ORDER BY started_at DESC LIMIT ?")
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Runs left unfinished by a restart are marked as failed.
+    pub async fn fail_interrupted() -> Result<(), String> {
+        let pool = get_pool().await;
+        sqlx::query(
+            "UPDATE task_runs SET finished_at = ?, success = 0, message = 'Interrupted by a restart' WHERE finished_at IS NULL",
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use super::get_pool;
+#[cfg(feature = "server")]
+use uuid::Uuid;
+
+/// A transfer slskd completed that no job asked for.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct UnmanagedDownload {
+    pub id: String,
+    /// Soulseek user the file came from.
+    pub username: String,
+    /// Path of the file on the uploader's side, as slskd reports it.
+    pub filename: String,
+    pub size: i64,
+    pub transfer_id: String,
+    pub found_at: String,
+    pub dismissed: bool,
+}
+
+#[cfg(feature = "server")]
+impl UnmanagedDownload {
+    /// Records the transfer, unless it already was. Returns whether it is new.
+    pub async fn adopt(
+        username: &str,
+        filename: &str,
+        size: i64,
+        transfer_id: &str,
+    ) -> Result<bool, String> {
+        let pool = get_pool().await;
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO unmanaged_downloads (id, username, filename, size, transfer_id, found_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(username)
+        .bind(filename)
+        .bind(size)
+        .bind(transfer_id)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// The downloads not imported nor dismissed yet, by uploader and path.
+    pub async fn get_pending() -> Result<Vec<UnmanagedDownload>, String> {
+        let pool = get_pool().await;
+        sqlx::query_as::<_, UnmanagedDownload>(
+            "SELECT * FROM unmanaged_downloads WHERE dismissed = 0 ORDER BY username, filename",
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Removes the pending downloads among `ids`, to import them, and returns them.
+    pub async fn take(ids: &[String]) -> Result<Vec<UnmanagedDownload>, String> {
+        let pool = get_pool().await;
+        let mut taken = Vec::new();
+        for id in ids {
+            if let Some(download) = sqlx::query_as::<_, UnmanagedDownload>(
+                "DELETE FROM unmanaged_downloads WHERE id = ? AND dismissed = 0 RETURNING *",
+            )
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            {
+                taken.push(download);
+            }
+        }
+        Ok(taken)
+    }
+
+    pub async fn dismiss(ids: &[String]) -> Result<(), String> {
+        let pool = get_pool().await;
+        for id in ids {
+            sqlx::query("UPDATE unmanaged_downloads SET dismissed = 1 WHERE id = ?")
+                .bind(id)
+                .execute(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
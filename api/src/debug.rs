@@ -0,0 +1,57 @@
+//! Troubleshooting tools for search results.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use shared::{musicbrainz::Album, slskd::MatchResult};
+
+#[cfg(feature = "server")]
+use crate::{browse, server_error};
+#[cfg(feature = "server")]
+use shared::slskd::ScoringConfig;
+#[cfg(feature = "server")]
+use soulbeet::{musicbrainz, slskd};
+
+/// How a filename scores against a release, as computed during searches.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MatchBreakdown {
+    pub album: Album,
+    /// Track titles the filename was compared against.
+    pub tracks: Vec<String>,
+    pub result: MatchResult,
+    /// Minimum total score for a file to appear in search results.
+    pub threshold: f64,
+}
+
+/// Scores a raw Soulseek filename (full remote path) against a MusicBrainz
+/// release, to explain why a file did or did not show up in search results.
+#[server]
+pub async fn debug_match(
+    filename: String,
+    album_id: String,
+) -> Result<MatchBreakdown, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let release = musicbrainz::find_album(album_id.trim())
+        .await
+        .map_err(server_error)?;
+    let durations: Vec<Option<i32>> = release.tracks.iter().map(|t| t.duration_secs()).collect();
+    let tracks: Vec<String> = release.tracks.into_iter().map(|t| t.title).collect();
+    let titles: Vec<&str> = tracks.iter().map(String::as_str).collect();
+
+    let result = slskd::rank_match(
+        &filename,
+        None,
+        Some(&release.album.artist),
+        Some(&release.album.title),
+        &titles,
+        &durations,
+        &ScoringConfig::default().title_suffixes(),
+    );
+
+    Ok(MatchBreakdown {
+        album: release.album,
+        tracks,
+        result,
+        threshold: slskd::MIN_MATCH_SCORE,
+    })
+}
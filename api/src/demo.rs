@@ -0,0 +1,227 @@
+//! Public demo mode.
+//!
+//! With `DEMO_MODE` set, the login page offers an anonymous, read-only demo
+//! session. Its Soulseek searches are answered with generated results so the
+//! instance never touches the network, MusicBrainz lookups are cached, and
+//! every search is rate limited so a public instance stays cheap to run.
+
+use dioxus::prelude::*;
+
+use crate::auth::AuthResponse;
+
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+#[cfg(feature = "server")]
+use shared::{
+    musicbrainz::{SearchPage, Track},
+    slskd::{AlbumResult, AudioFormat, MatchResult, SearchResult, TrackResult},
+};
+#[cfg(feature = "server")]
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "server")]
+const DEMO_USER_ID: &str = "demo";
+
+/// Searches allowed per minute across all demo sessions, unless overridden
+/// with `DEMO_SEARCHES_PER_MINUTE`.
+#[cfg(feature = "server")]
+const DEFAULT_SEARCHES_PER_MINUTE: usize = 20;
+
+#[cfg(feature = "server")]
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// MusicBrainz results are reused for this long.
+#[cfg(feature = "server")]
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The cache is cleared once it holds this many searches.
+#[cfg(feature = "server")]
+const CACHE_CAPACITY: usize = 1000;
+
+#[cfg(feature = "server")]
+static SEARCHES: LazyLock<Mutex<VecDeque<Instant>>> = LazyLock::new(Default::default);
+
+#[cfg(feature = "server")]
+static CACHE: LazyLock<Mutex<HashMap<String, (Instant, SearchPage)>>> =
+    LazyLock::new(Default::default);
+
+/// Whether this instance runs as a public demo.
+#[cfg(feature = "server")]
+pub(crate) fn enabled() -> bool {
+    std::env::var("DEMO_MODE").is_ok_and(|v| matches!(v.as_str(), "1" | "true"))
+}
+
+/// Counts a search against the demo rate limit, failing once it is reached.
+#[cfg(feature = "server")]
+pub(crate) fn check_rate_limit() -> Result<(), String> {
+    let limit = std::env::var("DEMO_SEARCHES_PER_MINUTE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_SEARCHES_PER_MINUTE);
+
+    let mut searches = SEARCHES.lock().unwrap();
+    while searches
+        .front()
+        .is_some_and(|at| at.elapsed() > RATE_LIMIT_WINDOW)
+    {
+        searches.pop_front();
+    }
+    if searches.len() >= limit {
+        return Err("The demo is busy, try again in a minute".to_string());
+    }
+    searches.push_back(Instant::now());
+    Ok(())
+}
+
+/// Runs a MusicBrainz search through the demo cache and rate limit.
+#[cfg(feature = "server")]
+pub(crate) async fn cached_search<F>(key: String, search: F) -> Result<SearchPage, String>
+where
+    F: std::future::Future<Output = Result<SearchPage, String>>,
+{
+    let key = key.to_lowercase();
+    if let Some((at, results)) = CACHE.lock().unwrap().get(&key) {
+        if at.elapsed() < CACHE_TTL {
+            return Ok(results.clone());
+        }
+    }
+
+    check_rate_limit()?;
+    let results = search.await?;
+
+    let mut cache = CACHE.lock().unwrap();
+    if cache.len() >= CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(key, (Instant::now(), results.clone()));
+    Ok(results)
+}
+
+/// Made up uploaders: username, format, bitrate, free slot, speed, queue.
+#[cfg(feature = "server")]
+const MOCK_SOURCES: [(&str, &str, i32, bool, i32, i32); 3] = [
+    ("demo-archivist", "flac", 1000, true, 2_500_000, 0),
+    ("demo-commuter", "mp3", 320, true, 800_000, 2),
+    ("demo-slowpoke", "flac", 900, false, 60_000, 14),
+];
+
+/// Plausible Soulseek results for a search, built from the requested tracks
+/// instead of asking the network.
+#[cfg(feature = "server")]
+pub(crate) fn mock_results(artist: &str, album: &str, tracks: &[Track]) -> Vec<AlbumResult> {
+    MOCK_SOURCES
+        .iter()
+        .enumerate()
+        .map(
+            |(rank, &(username, format, bitrate, free_slot, speed, queue))| {
+                let album_path = format!("Music\\{artist}\\{album}");
+                let tracks: Vec<TrackResult> = tracks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, track)| {
+                        let duration = track.duration_secs().unwrap_or(240);
+                        let base = SearchResult {
+                            username: username.to_string(),
+                            filename: format!(
+                                "{album_path}\\{:02} - {}.{format}",
+                                i + 1,
+                                track.title
+                            ),
+                            size: i64::from(duration) * i64::from(bitrate) * 1000 / 8,
+                            bitrate: Some(bitrate),
+                            duration: Some(duration),
+                            sample_rate: Some(44_100),
+                            bit_depth: (format == "flac").then_some(16),
+                            has_free_upload_slot: free_slot,
+                            upload_speed: speed,
+                            queue_length: queue,
+                            alternate_sources: Vec::new(),
+                        };
+                        TrackResult::new(
+                            base,
+                            MatchResult {
+                                guessed_artist: artist.to_string(),
+                                guessed_album: album.to_string(),
+                                matched_track: track.title.clone(),
+                                artist_score: 1.0,
+                                album_score: 1.0,
+                                track_score: 1.0,
+                                total_score: 1.0,
+                                duration_score: Some(1.0),
+                                disc: None,
+                                track_number: Some(i as u32 + 1),
+                            },
+                        )
+                    })
+                    .collect();
+
+                let track_count = tracks.len();
+                let mut result = AlbumResult {
+                    username: username.to_string(),
+                    album_path,
+                    album_title: album.to_string(),
+                    artist: Some(artist.to_string()),
+                    track_count,
+                    total_size: tracks.iter().map(|t| t.base.size).sum(),
+                    tracks,
+                    dominant_quality: AudioFormat::from_extension(format, Some(bitrate)),
+                    has_free_upload_slot: free_slot,
+                    upload_speed: speed,
+                    queue_length: queue,
+                    score: 0.95 - 0.15 * rank as f64,
+                    eta_seconds: None,
+                    ambiguous_matches: false,
+                    expected_tracks: track_count,
+                    uploader_reliability: None,
+                    missing_tracks: Vec::new(),
+                    layout: Default::default(),
+                    companions: Vec::new(),
+                };
+                result.eta_seconds = Some(result.estimate_eta(None));
+                result
+            },
+        )
+        .collect()
+}
+
+/// Whether the login page should offer the demo session.
+#[server]
+pub async fn demo_enabled() -> Result<bool, ServerFnError> {
+    Ok(enabled())
+}
+
+/// Starts an anonymous, read-only demo session.
+#[server]
+pub async fn demo_login() -> Result<AuthResponse, ServerFnError> {
+    if !enabled() {
+        return Err(server_error("Demo mode is not enabled"));
+    }
+
+    auth::create_tokens(DEMO_USER_ID.to_string(), "demo".to_string(), false, true)
+        .map_err(server_error)
+}
+
+/// Plausible files for a raw search: one copy of `query` from each mock
+/// uploader.
+#[cfg(feature = "server")]
+pub(crate) fn mock_raw_results(query: &str) -> Vec<SearchResult> {
+    let track = Track {
+        id: String::new(),
+        title: query.to_string(),
+        artist: String::new(),
+        album_id: None,
+        album_title: None,
+        release_date: None,
+        duration: Some("4:00".to_string()),
+        genres: Vec::new(),
+    };
+    mock_results("Unsorted", "Live", std::slice::from_ref(&track))
+        .into_iter()
+        .flat_map(|album| album.tracks)
+        .map(|t| t.base)
+        .collect()
+}
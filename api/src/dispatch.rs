@@ -0,0 +1,999 @@
+//! Download dispatch and monitoring.
+//!
+//! Jobs are queued, then handed to slskd a few at a time by [`schedule`], which
+//! interleaves users instead of serving them in request order.
+//!
+//! Every dispatch is recorded in the database *before* slskd is contacted, so
+//! that a crash between the HTTP call and the bookkeeping can be detected and
+//! repaired by [`reconcile`] on the next startup.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use shared::slskd::{is_artwork_file, DownloadState, FileEntry, TrackResult, PENDING_TRANSFER_ID};
+use soulbeet::{beets, cue, slskd::filename_variants};
+use tokio::sync::{broadcast::error::RecvError, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::{
+    covers,
+    db::{Job, JobAlternate, JobFile, JobState, UnmanagedDownload, User},
+    editions, genres, library, lyrics, permissions, provider, shares, DownloadOptions,
+    DownloadPlan, PlannedFile, PlannedRequest,
+};
+
+/// Serializes scheduling passes so two of them never fill the same slot.
+static SCHEDULER: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// Number of jobs allowed to download at once, the rest wait in the queue.
+fn max_active_jobs() -> usize {
+    std::env::var("MAX_ACTIVE_JOBS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3)
+}
+
+/// Queues the tracks as a new job and starts it if a slot is free.
+pub async fn dispatch(
+    user_id: &str,
+    tracks: Vec<TrackResult>,
+    target_folder: &str,
+    options: &DownloadOptions,
+) -> Result<Job, String> {
+    enqueue(user_id, tracks, target_folder, options, None).await
+}
+
+/// Queues a replacement for one file of an imported job. It is imported into
+/// the same folder, where beets swaps it for the previous copy of the track.
+pub async fn dispatch_replacement(
+    original: &Job,
+    file: &JobFile,
+    track: TrackResult,
+) -> Result<Job, String> {
+    let options = DownloadOptions {
+        album_id: original.album_id.clone(),
+        recording_id: original.recording_id.clone(),
+        review_before_import: false,
+        note: None,
+        labels: original.labels().await?,
+        alternates: Vec::new(),
+        gap_fills: Vec::new(),
+        artwork: Vec::new(),
+    };
+    enqueue(
+        &original.user_id,
+        vec![track],
+        &original.target_folder,
+        &options,
+        Some(&file.id),
+    )
+    .await
+}
+
+/// The tracks as they will be requested: the fastest copy of each, picked
+/// before anything is recorded, and each file once.
+async fn prepare(tracks: Vec<TrackResult>) -> Vec<TrackResult> {
+    let tracks = provider().resolve_sources(tracks).await;
+    let mut seen = HashSet::new();
+    tracks
+        .into_iter()
+        .filter(|t| seen.insert((t.base.username.clone(), t.base.filename.clone())))
+        .collect()
+}
+
+/// What [`dispatch`] would request, without recording or sending anything.
+pub async fn plan(
+    tracks: Vec<TrackResult>,
+    target_folder: &str,
+    options: &DownloadOptions,
+) -> Result<DownloadPlan, String> {
+    let requested = tracks.len();
+    let tracks = prepare(tracks).await;
+
+    let mut requests: Vec<PlannedRequest> = Vec::new();
+    for track in &tracks {
+        let file = PlannedFile {
+            filename: track.base.filename.clone(),
+            size: track.base.size,
+            bitrate: track.base.bitrate,
+            title: track.title.clone(),
+        };
+        match requests
+            .iter_mut()
+            .find(|r| r.username == track.base.username)
+        {
+            Some(request) => request.files.push(file),
+            None => requests.push(PlannedRequest {
+                username: track.base.username.clone(),
+                files: vec![file],
+            }),
+        }
+    }
+
+    Ok(DownloadPlan {
+        target_folder: target_folder.to_string(),
+        total_size: tracks.iter().map(|t| t.base.size).sum(),
+        duplicates: requested - tracks.len(),
+        requests,
+        alternates: options.alternates.len(),
+        review_before_import: options.review_before_import,
+        starts_now: Job::get_active().await?.len() < max_active_jobs()
+            && Job::get_by_state(JobState::Queued).await?.is_empty(),
+    })
+}
+
+async fn enqueue(
+    user_id: &str,
+    tracks: Vec<TrackResult>,
+    target_folder: &str,
+    options: &DownloadOptions,
+    replaces_file_id: Option<&str>,
+) -> Result<Job, String> {
+    let tracks = prepare(tracks).await;
+    let job =
+        Job::create_queued(user_id, target_folder, options, &tracks, replaces_file_id).await?;
+
+    schedule().await;
+    Ok(job)
+}
+
+/// Queued jobs in the order they will be started.
+///
+/// Each user's jobs keep their request order, but users take turns: a job's
+/// rank is the number of slots its user would hold once it starts, divided by
+/// the user's queue weight. A user with weight 2 thus gets two slots for every
+/// one given to a user with weight 1.
+pub async fn fair_queue() -> Result<Vec<Job>, String> {
+    let queued = Job::get_by_state(JobState::Queued).await?;
+    let weights: HashMap<_, _> = User::get_all()
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.queue_weight.max(1)))
+        .collect();
+
+    let mut held: HashMap<String, i64> = HashMap::new();
+    for job in Job::get_active().await? {
+        *held.entry(job.user_id).or_default() += 1;
+    }
+
+    let mut ranked: Vec<_> = queued
+        .into_iter()
+        .map(|job| {
+            let slots = held.entry(job.user_id.clone()).or_default();
+            *slots += 1;
+            let weight = weights.get(&job.user_id).copied().unwrap_or(1);
+            (*slots as f64 / weight as f64, job)
+        })
+        .collect();
+    // Stable, so ties are broken by request time.
+    ranked.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    Ok(ranked.into_iter().map(|(_, job)| job).collect())
+}
+
+/// Starts queued jobs, fairest first, while download slots are free.
+pub async fn schedule() {
+    let _guard = SCHEDULER.lock().await;
+
+    loop {
+        let active = match Job::get_active().await {
+            Ok(active) => active.len(),
+            Err(e) => {
+                warn!("Failed to count active jobs: {}", e);
+                return;
+            }
+        };
+        if active >= max_active_jobs() {
+            return;
+        }
+
+        let next = match fair_queue().await {
+            Ok(queue) => queue.into_iter().next(),
+            Err(e) => {
+                warn!("Failed to compute the download queue: {}", e);
+                return;
+            }
+        };
+        let Some(job) = next else {
+            return;
+        };
+
+        // A job still queued would be picked again straight away.
+        if let Err(e) = Job::set_state(&job.id, JobState::Dispatching).await {
+            warn!("Failed to start job {}: {}", job.id, e);
+            return;
+        }
+        if let Err(e) = start(job).await {
+            warn!("Failed to start job: {}", e);
+        }
+    }
+}
+
+/// Sends a dispatching job's files to slskd and starts monitoring them. The
+/// job is marked as failed when they can't be sent.
+async fn start(job: Job) -> Result<(), String> {
+    let files = match job.files().await {
+        Ok(files) => files,
+        Err(e) => {
+            Job::set_state(&job.id, JobState::Failed).await?;
+            return Err(e);
+        }
+    };
+    let files = files
+        .into_iter()
+        .map(|f| (f.username, f.filename, f.size))
+        .collect();
+    let res = match provider().fetch(files).await {
+        Ok(res) => res,
+        Err(e) => {
+            Job::set_state(&job.id, JobState::Failed).await?;
+            return Err(e.to_string());
+        }
+    };
+
+    for download in &res {
+        if download.filename != download.original_filename {
+            JobFile::rename(&job.id, &download.original_filename, &download.filename).await?;
+        }
+        JobFile::set_transfer_id(&job.id, &download.filename, &download.id).await?;
+    }
+    Job::set_state(&job.id, JobState::Downloading).await?;
+
+    let filenames = res.iter().map(|d| d.filename.clone()).collect();
+    spawn_monitor(job, filenames);
+
+    Ok(())
+}
+
+/// Repairs jobs left in the `Dispatching` state by a previous crash, fails
+/// those whose import it interrupted, resumes monitoring of jobs that were
+/// downloading, and records the transfers slskd completed without a job as
+/// [`UnmanagedDownload`]s.
+///
+/// If slskd knows about any of a dispatching job's files, the dispatch went
+/// through: the transfer IDs are adopted and monitoring resumes. Otherwise the
+/// request never reached slskd and the job is marked as failed.
+pub async fn reconcile() -> Result<(), String> {
+    // No import runs yet, these were cut short by the restart.
+    for job in Job::get_by_state(JobState::Importing).await? {
+        warn!(
+            "Import of job {} was interrupted, marking as failed",
+            job.id
+        );
+        Job::set_state(&job.id, JobState::Failed).await?;
+    }
+
+    for job in Job::get_by_state(JobState::Downloading).await? {
+        let filenames = job
+            .files()
+            .await?
+            .into_iter()
+            .filter(|f| f.transfer_id.is_some())
+            .map(|f| f.filename)
+            .collect();
+        spawn_monitor(job, filenames);
+    }
+
+    let downloads = provider()
+        .get_all_downloads()
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Err(e) = adopt_unmanaged(&downloads).await {
+        warn!("Failed to record the downloads no job asked for: {}", e);
+    }
+
+    let pending = Job::get_by_state(JobState::Dispatching).await?;
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    info!("Reconciling {} interrupted dispatches", pending.len());
+
+    for job in pending {
+        let files = job.files().await?;
+        let mut adopted = Vec::new();
+
+        for file in &files {
+            if let Some(transfer) = downloads
+                .iter()
+                .find(|d| d.username == file.username && d.filename == file.filename)
+            {
+                JobFile::set_transfer_id(&job.id, &file.filename, &transfer.id).await?;
+                adopted.push(file.filename.clone());
+            }
+        }
+
+        if adopted.is_empty() {
+            warn!("Job {} never reached slskd, marking as failed", job.id);
+            Job::set_state(&job.id, JobState::Failed).await?;
+        } else {
+            info!(
+                "Job {} adopted {} transfers from slskd, resuming monitoring",
+                job.id,
+                adopted.len()
+            );
+            Job::set_state(&job.id, JobState::Downloading).await?;
+            spawn_monitor(job, adopted);
+        }
+    }
+
+    Ok(())
+}
+
+/// Records the completed `downloads` no job asked for, e.g. made from the
+/// slskd web interface or while Soulbeet was down, so they can be imported.
+/// A file asked for under another spelling of its name was asked for all the
+/// same.
+async fn adopt_unmanaged(downloads: &[FileEntry]) -> Result<(), String> {
+    let requested = JobFile::requested_files().await?;
+    let mut adopted = 0;
+    for download in downloads.iter().filter(|d| d.is_succeeded()) {
+        let was_requested = std::iter::once(download.filename.clone())
+            .chain(filename_variants(&download.filename))
+            .any(|filename| requested.contains(&(download.username.clone(), filename)));
+        if was_requested {
+            continue;
+        }
+        if UnmanagedDownload::adopt(
+            &download.username,
+            &download.filename,
+            download.size as i64,
+            &download.id,
+        )
+        .await?
+        {
+            adopted += 1;
+        }
+    }
+    if adopted > 0 {
+        info!("Found {} completed downloads no job asked for", adopted);
+    }
+    Ok(())
+}
+
+/// Imports unmanaged downloads into `target_folder`, as a job of `user_id`.
+pub async fn import_unmanaged(
+    user_id: &str,
+    target_folder: &str,
+    downloads: Vec<UnmanagedDownload>,
+) -> Result<Job, String> {
+    let job = Job::create_adopted(user_id, target_folder, &downloads).await?;
+    let mut paths = Vec::new();
+    for download in &downloads {
+        let path = local_path(&download.filename);
+        JobFile::set_local_path(&job.id, &download.filename, &path).await?;
+        paths.push(path);
+    }
+    paths.sort();
+    paths.dedup();
+
+    let importing = job.clone();
+    tokio::spawn(async move {
+        import_job(&importing, paths).await;
+    });
+    Ok(job)
+}
+
+/// Running transfers get a speed sample this often.
+const SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Uploaders are asked for the queue position of waiting transfers this often.
+const QUEUE_POSITION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// A job whose downloads have not all finished by then is marked as failed.
+const MONITOR_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20 * 60);
+
+/// Records the speed of each running transfer since its previous sample.
+async fn sample_speeds(
+    job_id: &str,
+    batch_status: &[&FileEntry],
+    last_bytes: &mut HashMap<String, (u64, std::time::Instant)>,
+) {
+    let now = std::time::Instant::now();
+    for file in batch_status {
+        if !file.is_in_progress() {
+            continue;
+        }
+        let speed = match last_bytes.get(&file.filename) {
+            Some(&(bytes, at)) => {
+                file.bytes_transferred.saturating_sub(bytes) as f64
+                    / now.duration_since(at).as_secs_f64().max(1.0)
+            }
+            // First sample of the transfer, slskd's average is all there is.
+            None => file.average_speed,
+        };
+        last_bytes.insert(file.filename.clone(), (file.bytes_transferred, now));
+        if let Err(e) =
+            JobFile::add_speed_sample(job_id, &file.filename, speed, file.bytes_transferred).await
+        {
+            warn!("Failed to record transfer speed: {}", e);
+        }
+    }
+}
+
+/// Records where the job's remotely queued transfers are in their uploaders'
+/// queues, asking the uploaders again when `ask` is set. Transfers that left
+/// the queue lose their position.
+async fn update_queue_positions(
+    job_id: &str,
+    batch_status: &[&FileEntry],
+    positions: &mut HashMap<String, Option<u32>>,
+    ask: bool,
+) {
+    for file in batch_status {
+        let known = positions.get(&file.filename).copied().flatten();
+        let position = if !file.is_queued_remotely() {
+            None
+        } else if ask {
+            match provider()
+                .get_queue_position(&file.username, &file.id)
+                .await
+            {
+                Ok(position) => position.or(file.place_in_queue),
+                Err(e) => {
+                    debug!("Failed to get queue position of {}: {}", file.filename, e);
+                    known.or(file.place_in_queue)
+                }
+            }
+        } else {
+            known.or(file.place_in_queue)
+        };
+        if positions.get(&file.filename) == Some(&position) {
+            continue;
+        }
+        positions.insert(file.filename.clone(), position);
+        if let Err(e) =
+            JobFile::set_queue_position(job_id, &file.filename, position.map(i64::from)).await
+        {
+            warn!("Failed to record queue position: {}", e);
+        }
+    }
+}
+
+/// Sends a retry to slskd. Returns the filenames of the transfers started,
+/// empty when slskd refused it.
+async fn request_retry(job_id: &str, retry: &JobFile) -> Vec<String> {
+    let error = match provider()
+        .fetch(vec![(
+            retry.username.clone(),
+            retry.filename.clone(),
+            retry.size,
+        )])
+        .await
+    {
+        Ok(res) if res.is_empty() => "slskd refused the download request".to_string(),
+        Ok(res) => {
+            let mut started = Vec::new();
+            for download in res {
+                if download.filename != download.original_filename {
+                    let _ =
+                        JobFile::rename(job_id, &download.original_filename, &download.filename)
+                            .await;
+                }
+                let _ = JobFile::set_transfer_id(job_id, &download.filename, &download.id).await;
+                started.push(download.filename);
+            }
+            return started;
+        }
+        Err(e) => e.to_string(),
+    };
+    let _ = JobFile::set_outcome(job_id, &retry.filename, "Rejected", Some(&error), None).await;
+    Vec::new()
+}
+
+/// Retries each file that just failed, keeping the job going instead of
+/// importing it without the track. A file the peer rejected is first asked
+/// for again under its other spellings, as the peer may only know it with
+/// backslashes or in another Unicode form; then the next alternate sources
+/// are tried. Returns the filenames of the retries started.
+async fn retry_failed(
+    job_id: &str,
+    batch_status: &[&FileEntry],
+    retried: &mut HashSet<String>,
+) -> Vec<String> {
+    let failed: Vec<_> = batch_status
+        .iter()
+        .filter(|f| f.failure_reason().is_some() && !retried.contains(&f.filename))
+        .collect();
+    if failed.is_empty() {
+        return Vec::new();
+    }
+    let files = match JobFile::get_all_by_job(job_id).await {
+        Ok(files) => files,
+        Err(e) => {
+            warn!("Failed to read job files for retries: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut started = Vec::new();
+    for entry in failed {
+        retried.insert(entry.filename.clone());
+        let Some(file) = files.iter().find(|f| f.filename == entry.filename) else {
+            continue;
+        };
+
+        if entry.state.contains(&DownloadState::Rejected) {
+            let tried: HashSet<_> = files
+                .iter()
+                .filter(|f| f.username == file.username)
+                .map(|f| f.filename.as_str())
+                .collect();
+            let variant = filename_variants(&file.filename)
+                .into_iter()
+                .find(|v| !tried.contains(v.as_str()));
+            if let Some(variant) = variant {
+                match JobFile::create_variant(file, &variant).await {
+                    Ok(retry) => {
+                        info!(
+                            "Transfer of {} was rejected, retrying as {}",
+                            file.filename, retry.filename
+                        );
+                        let variant_started = request_retry(job_id, &retry).await;
+                        if !variant_started.is_empty() {
+                            started.extend(variant_started);
+                            continue;
+                        }
+                    }
+                    Err(e) => warn!("Failed to record retry: {}", e),
+                }
+            }
+        }
+
+        let Some(title) = &file.title else {
+            continue;
+        };
+
+        // Sources can be gone by now, move on to the next one until a request goes through.
+        loop {
+            let alternate = match JobAlternate::take_next(job_id, title).await {
+                Ok(Some(alternate)) => alternate,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Failed to read alternate sources: {}", e);
+                    break;
+                }
+            };
+            let retry = match JobFile::create_retry(&alternate, file).await {
+                Ok(retry) => retry,
+                Err(e) => {
+                    warn!("Failed to record retry: {}", e);
+                    break;
+                }
+            };
+            info!(
+                "Transfer of {} failed, retrying with {} from {}",
+                file.filename, retry.filename, retry.username
+            );
+            let retry_started = request_retry(job_id, &retry).await;
+            if !retry_started.is_empty() {
+                started.extend(retry_started);
+                break;
+            }
+        }
+    }
+    started
+}
+
+/// Requests the tracks a partial album lacked from other uploaders, moving on
+/// to the next source of a track until a request goes through. Returns the
+/// filenames of the transfers started.
+async fn fill_gaps(job_id: &str) -> Vec<String> {
+    let titles = match JobAlternate::gap_titles(job_id).await {
+        Ok(titles) => titles,
+        Err(e) => {
+            warn!("Failed to read missing tracks: {}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut started = Vec::new();
+    for title in titles {
+        loop {
+            let alternate = match JobAlternate::take_next(job_id, &title).await {
+                Ok(Some(alternate)) => alternate,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Failed to read alternate sources: {}", e);
+                    break;
+                }
+            };
+            let file = match JobFile::create_gap_fill(&alternate).await {
+                Ok(file) => file,
+                Err(e) => {
+                    warn!("Failed to record missing track: {}", e);
+                    break;
+                }
+            };
+            info!(
+                "Album lacks {}, requesting {} from {}",
+                title, file.filename, file.username
+            );
+            let file_started = request_retry(job_id, &file).await;
+            if !file_started.is_empty() {
+                started.extend(file_started);
+                break;
+            }
+        }
+    }
+    started
+}
+
+/// Whether slskd still knows any of the transfers of `files`, looked up by
+/// ID. The transfer list sent to monitors can predate a request that just
+/// went through.
+async fn has_known_transfer(files: &[JobFile]) -> bool {
+    for file in files {
+        let Some(transfer_id) = known_transfer_id(file) else {
+            continue;
+        };
+        if let Ok(Some(_)) = provider().get_download(&file.username, transfer_id).await {
+            return true;
+        }
+    }
+    false
+}
+
+/// The ID of the transfer of `file`, unless slskd has not told it yet.
+fn known_transfer_id(file: &JobFile) -> Option<&str> {
+    file.transfer_id
+        .as_deref()
+        .filter(|id| *id != PENDING_TRANSFER_ID)
+}
+
+/// The transfer of `file` among `downloads`. Transfers are told apart by the
+/// ID slskd gave them, as the same file can be requested again under another
+/// transfer. Files slskd queued without telling their ID are found by name.
+fn find_transfer<'a>(file: &JobFile, downloads: &'a [FileEntry]) -> Option<&'a FileEntry> {
+    let mut by_user = downloads.iter().filter(|d| d.username == file.username);
+    match known_transfer_id(file) {
+        Some(id) => by_user.find(|d| d.id == id),
+        None => by_user.find(|d| d.filename == file.filename),
+    }
+}
+
+/// Watches the given files until slskd reports them finished, then imports them.
+pub fn spawn_monitor(job: Job, mut download_filenames: Vec<String>) {
+    tracing::info!("Started monitoring downloads: {:?}", download_filenames);
+
+    tokio::spawn(async move {
+        let job_id = job.id.clone();
+        let mut updates = provider().progress();
+        let mut deadline = tokio::time::Instant::now() + MONITOR_TIMEOUT;
+        let mut last_sample = std::time::Instant::now();
+        let mut last_bytes = HashMap::new();
+        let mut last_queue_check = std::time::Instant::now();
+        let mut queue_positions = HashMap::new();
+        let mut retried = HashSet::new();
+
+        loop {
+            let update = match tokio::time::timeout_at(deadline, updates.recv()).await {
+                Ok(Ok(update)) => update,
+                // Missed updates are fine, the next one has every download.
+                Ok(Err(RecvError::Lagged(_))) => continue,
+                Ok(Err(RecvError::Closed)) => break,
+                Err(_) => {
+                    info!(
+                        "Download monitoring timed out for batch {:?}",
+                        download_filenames
+                    );
+                    let _ = Job::set_state(&job_id, JobState::Failed).await;
+                    break;
+                }
+            };
+
+            let files = match JobFile::get_all_by_job(&job_id).await {
+                Ok(files) => files,
+                Err(e) => {
+                    warn!("Failed to read job files: {}", e);
+                    continue;
+                }
+            };
+            let files: Vec<_> = files
+                .into_iter()
+                .filter(|f| download_filenames.contains(&f.filename))
+                .collect();
+
+            // Paused files have no transfer, and the job waits for them as long as they are paused.
+            let paused = files.iter().any(|f| f.paused);
+            if paused {
+                deadline = tokio::time::Instant::now() + MONITOR_TIMEOUT;
+            }
+
+            let mut batch_status = Vec::new();
+            for file in files.iter().filter(|f| !f.paused) {
+                let Some(transfer) = find_transfer(file, &update.downloads) else {
+                    continue;
+                };
+                // Found by name, its ID is kept to tell it from later requests.
+                if known_transfer_id(file).is_none() {
+                    let _ = JobFile::set_transfer_id(&job_id, &file.filename, &transfer.id).await;
+                }
+                batch_status.push(transfer);
+            }
+
+            // If we can't find any of our downloads, they might have been cleared or invalid
+            if batch_status.is_empty() {
+                if paused || has_known_transfer(&files).await {
+                    continue;
+                }
+                info!("No active downloads found for batch, assuming completed or lost.");
+                let _ = Job::set_state(&job_id, JobState::Failed).await;
+                break;
+            }
+
+            for file in batch_status
+                .iter()
+                .filter(|f| update.changed.contains(&f.id))
+            {
+                info!("Transfer of {}: {}", file.filename, file.state_description);
+            }
+
+            if last_sample.elapsed() >= SAMPLE_INTERVAL {
+                sample_speeds(&job_id, &batch_status, &mut last_bytes).await;
+                last_sample = std::time::Instant::now();
+            }
+
+            let ask = last_queue_check.elapsed() >= QUEUE_POSITION_INTERVAL;
+            update_queue_positions(&job_id, &batch_status, &mut queue_positions, ask).await;
+            if ask {
+                last_queue_check = std::time::Instant::now();
+            }
+
+            let retries = retry_failed(&job_id, &batch_status, &mut retried).await;
+            if !retries.is_empty() {
+                download_filenames.extend(retries);
+                continue;
+            }
+
+            let all_finished = !paused && batch_status.iter().all(|d| d.is_finished());
+
+            if all_finished {
+                // The tracks a partial album lacked come once its own files are in.
+                let gap_fills = fill_gaps(&job_id).await;
+                if !gap_fills.is_empty() {
+                    download_filenames.extend(gap_fills);
+                    continue;
+                }
+
+                for file in &batch_status {
+                    let reason = file.failure_reason();
+                    if let Some(reason) = &reason {
+                        warn!("Transfer of {} failed: {}", file.filename, reason);
+                    }
+                    let average_speed = (reason.is_none() && file.average_speed > 0.0)
+                        .then_some(file.average_speed);
+                    let _ = JobFile::set_outcome(
+                        &job_id,
+                        &file.filename,
+                        &file.state_description,
+                        reason.as_deref(),
+                        average_speed,
+                    )
+                    .await;
+                }
+
+                let successful_downloads: Vec<_> = batch_status
+                    .iter()
+                    .filter(|d| d.is_succeeded())
+                    .map(|d| d.filename.as_str())
+                    .collect();
+
+                // Cover scans alone are nothing to import.
+                if successful_downloads.iter().all(|f| is_artwork_file(f)) {
+                    info!("Downloads finished but none succeeded. Skipping import.");
+                    let _ = Job::set_state(&job_id, JobState::Failed).await;
+                    break;
+                }
+
+                let mut paths = HashSet::new();
+                for filename in &successful_downloads {
+                    let path = local_path(filename);
+                    let _ = JobFile::set_local_path(&job_id, filename, &path).await;
+                    paths.insert(path);
+                }
+
+                if job.review_before_import {
+                    info!(
+                        "Downloads completed ({} successful). Waiting for review before import",
+                        successful_downloads.len()
+                    );
+                    let _ = Job::set_state(&job_id, JobState::AwaitingReview).await;
+                } else {
+                    info!(
+                        "Downloads completed ({} successful). Starting import to {:?}",
+                        successful_downloads.len(),
+                        job.target_folder
+                    );
+                    import_job(&job, paths.into_iter().collect()).await;
+                }
+                break;
+            }
+        }
+
+        // The job no longer holds a download slot.
+        schedule().await;
+    });
+}
+
+/// Runs the beets import of a job, as a single track when it is one.
+async fn run_beets(
+    job: &Job,
+    library: &beets::Library,
+    paths: Vec<String>,
+    fields: &[String],
+) -> std::io::Result<()> {
+    let target = Path::new(&job.target_folder);
+    match &job.recording_id {
+        Some(recording_id) => {
+            beets::import_single(library, paths, target, recording_id, fields).await
+        }
+        None => beets::import(library, paths, target, fields).await,
+    }
+}
+
+/// Whether albums downloaded as one file and a cue sheet are split into
+/// tracks before the import, from `SPLIT_CUE_IMAGES`.
+fn split_cue_images() -> bool {
+    !std::env::var("SPLIT_CUE_IMAGES").is_ok_and(|v| matches!(v.as_str(), "0" | "false"))
+}
+
+/// Replaces each image and its cue sheet among `paths`, alone in their
+/// folder, with the tracks split from it. An image that fails to split is
+/// imported as it is.
+async fn split_images(paths: Vec<String>) -> Vec<String> {
+    let extension = |path: &str| {
+        Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_lowercase)
+    };
+    let mut folders: HashMap<PathBuf, Vec<String>> = HashMap::new();
+    for path in paths {
+        let folder = Path::new(&path).parent().map(Path::to_path_buf);
+        folders
+            .entry(folder.unwrap_or_default())
+            .or_default()
+            .push(path);
+    }
+
+    let mut split = Vec::new();
+    for files in folders.into_values() {
+        let (cues, audio): (Vec<_>, Vec<_>) = files
+            .iter()
+            .partition(|path| extension(path).as_deref() == Some("cue"));
+        let ([cue_sheet], [image]) = (cues.as_slice(), audio.as_slice()) else {
+            split.extend(files);
+            continue;
+        };
+        match cue::split(Path::new(cue_sheet), Path::new(image)).await {
+            Ok(tracks) => split.extend(tracks.into_iter().map(|t| t.display().to_string())),
+            Err(e) => {
+                warn!("Failed to split {}, importing it whole: {}", image, e);
+                split.extend(files);
+            }
+        }
+    }
+    split.sort();
+    split
+}
+
+/// Imports `paths` into the job's target folder and records the outcome.
+pub async fn import_job(job: &Job, paths: Vec<String>) -> JobState {
+    let _ = Job::set_state(&job.id, JobState::Importing).await;
+    // Cover scans are embedded once the tracks are in, beets skips them.
+    let (artwork, paths): (Vec<String>, Vec<String>) =
+        paths.into_iter().partition(|path| is_artwork_file(path));
+    let paths = match split_cue_images() {
+        true => split_images(paths).await,
+        false => paths,
+    };
+    tracing::info!("Importing paths: {:?}", paths);
+
+    let mut fields = vec![format!("{}={}", library::JOB_FIELD, job.id)];
+    fields.extend(
+        genres::genre_for_job(job)
+            .await
+            .map(|genre| format!("genre={genre}")),
+    );
+    if let Some(edition) = editions::edition_for_job(job).await {
+        info!(
+            "Another edition is already in the folder, importing as {}",
+            edition
+        );
+        fields.push(format!("edition={edition}"));
+    }
+    let library = library::job_library(job).await;
+    let mut result = run_beets(job, &library, paths.clone(), &fields).await;
+    // A hung beets usually goes through on a second try, e.g. once a lock
+    // held by another process was released.
+    if matches!(&result, Err(e) if e.kind() == ErrorKind::TimedOut) {
+        // The killed run may have moved some files into the library already.
+        let mut remaining = Vec::new();
+        for path in paths {
+            if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                remaining.push(path);
+            }
+        }
+        if remaining.is_empty() {
+            warn!("Import of job {} timed out after moving every file", job.id);
+        } else {
+            warn!("Import of job {} timed out, retrying once", job.id);
+            result = run_beets(job, &library, remaining, &fields).await;
+        }
+    }
+    let state = match result {
+        Ok(()) => {
+            if let Err(e) = covers::embed_custom_cover(job).await {
+                warn!("Failed to embed custom cover: {}", e);
+            }
+            if let Err(e) = covers::embed_downloaded_artwork(job, &artwork).await {
+                warn!("Failed to embed downloaded artwork: {}", e);
+            }
+            // The scans are in the tracks now, or unwanted over a custom cover.
+            for path in &artwork {
+                if let Err(e) = tokio::fs::remove_file(path).await {
+                    warn!("Failed to remove artwork {}: {}", path, e);
+                }
+            }
+            if let Some(file_id) = &job.replaces_file_id {
+                let _ = JobFile::set_replaced_by(file_id, &job.id).await;
+            }
+            if let Err(e) = library::index_job(job).await {
+                warn!("Failed to index imported files: {}", e);
+            }
+            match lyrics::save_for_job(job).await {
+                Ok(0) => {}
+                Ok(n) => info!("Saved lyrics for {} tracks", n),
+                Err(e) => warn!("Failed to save lyrics: {}", e),
+            }
+            if let Err(e) = permissions::apply_for_job(job).await {
+                warn!("Failed to set permissions of imported files: {}", e);
+            }
+            shares::rescan_after_import(&job.user_id).await;
+            JobState::Imported
+        }
+        Err(e) if e.kind() == ErrorKind::TimedOut => {
+            warn!("Import of job {} timed out again: {}", job.id, e);
+            JobState::ImportTimedOut
+        }
+        Err(e) => {
+            info!("Beets import error: {}", e);
+            JobState::Failed
+        }
+    };
+    let _ = Job::set_state(&job.id, state.clone()).await;
+    state
+}
+
+/// Maps a remote slskd filename to where slskd stored it on our disk.
+pub fn local_path(filename: &str) -> String {
+    let download_path_base =
+        std::env::var("SLSKD_DOWNLOAD_PATH").unwrap_or_else(|_| "/downloads".to_string());
+    let download_path_buf = PathBuf::from(&download_path_base);
+
+    // Normalize path separators (win -> linux)
+    let filename_str = filename.replace('\\', "/");
+    let path = Path::new(&filename_str);
+    let components: Vec<_> = path.components().collect();
+
+    // Keep only the last directory and filename (d1/d2/d3/file -> d3/file)
+    if components.len() >= 2 {
+        let len = components.len();
+        let last_dir = components[len - 2].as_os_str();
+        let file_name = components[len - 1].as_os_str();
+
+        let relative_path = PathBuf::from(last_dir).join(file_name);
+        let full_path = download_path_buf.join(relative_path);
+
+        full_path.to_string_lossy().to_string()
+    } else {
+        // Fallback
+        let full_path = download_path_buf.join(path);
+        full_path.to_string_lossy().to_string()
+    }
+}
@@ -0,0 +1,83 @@
+//! The editions of an album (original, remasters, deluxe and regional
+//! releases) and which one a user opens by default.
+
+use dioxus::prelude::*;
+use shared::musicbrainz::{Edition, EditionPreference};
+
+#[cfg(feature = "server")]
+use crate::db::StoredEditionPreference;
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+#[cfg(feature = "server")]
+use soulbeet::musicbrainz;
+
+/// The official releases of the album `id` belongs to, oldest first.
+#[server]
+pub async fn album_editions(id: String) -> Result<Vec<Edition>, ServerFnError> {
+    musicbrainz::album_editions(&id).await.map_err(server_error)
+}
+
+/// The release of the album `id` belongs to that the user prefers to open.
+#[server]
+pub async fn preferred_edition(token: String, id: String) -> Result<String, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let preference = StoredEditionPreference::get(&claims.sub)
+        .await
+        .map_err(server_error)?;
+    // Albums are found through their original release already.
+    if preference.country.is_none() {
+        return Ok(id);
+    }
+    let editions = musicbrainz::album_editions(&id)
+        .await
+        .map_err(server_error)?;
+    Ok(preference
+        .pick(&editions)
+        .map(|edition| edition.id.clone())
+        .unwrap_or(id))
+}
+
+#[server]
+pub async fn get_edition_preference(token: String) -> Result<EditionPreference, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    StoredEditionPreference::get(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+/// Saves which edition of an album the user opens by default. The country
+/// is a two-letter code, e.g. "GB".
+#[server]
+pub async fn save_edition_preference(
+    token: String,
+    preference: EditionPreference,
+) -> Result<EditionPreference, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let country = preference
+        .country
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty());
+    if country
+        .as_deref()
+        .is_some_and(|c| c.len() != 2 || !c.chars().all(|ch| ch.is_ascii_uppercase()))
+    {
+        return Err(server_error("Country must be a two-letter code, e.g. GB"));
+    }
+    let preference = EditionPreference { country };
+    StoredEditionPreference::save(&claims.sub, &preference)
+        .await
+        .map_err(server_error)?;
+    Ok(preference)
+}
@@ -0,0 +1,85 @@
+//! Side-by-side editions of the same album.
+//!
+//! beets files an album under `$albumartist/$album`, so a second edition of an
+//! album already in the folder would land in (and be merged with, or replace)
+//! the first one. When that happens, the import sets an `edition` field derived
+//! from the MusicBrainz release, which the path templates append to the album
+//! directory, e.g. `Album (2009 Remaster)` or `Album [Deluxe Edition]`.
+
+use soulbeet::musicbrainz::{self, ReleaseVersion};
+
+use crate::db::{Job, LibraryEntry};
+
+/// Suffix telling this release apart from another edition already in the
+/// job's folder, or `None` when there is no collision.
+pub async fn edition_for_job(job: &Job) -> Option<String> {
+    // Single tracks are not filed per release, and a replacement goes where
+    // the file it replaces was.
+    if job.recording_id.is_some() || job.replaces_file_id.is_some() {
+        return None;
+    }
+    let album_id = job.album_id.as_deref()?;
+    let release = match musicbrainz::release_version(album_id).await {
+        Ok(release) => release,
+        Err(e) => {
+            tracing::warn!("Failed to fetch release {}: {}", album_id, e);
+            return None;
+        }
+    };
+
+    match LibraryEntry::has_other_release(
+        &job.target_folder,
+        &release.artist,
+        &release.title,
+        album_id,
+    )
+    .await
+    {
+        Ok(true) => Some(edition_label(album_id, &release)),
+        Ok(false) => None,
+        Err(e) => {
+            tracing::warn!("Failed to look for other editions: {}", e);
+            None
+        }
+    }
+}
+
+/// The most telling name for a release: its disambiguation comment when it has
+/// one, then its year, then its country, then its id.
+fn edition_label(album_id: &str, release: &ReleaseVersion) -> String {
+    if let Some(disambiguation) = &release.disambiguation {
+        let disambiguation = title_case(disambiguation.trim());
+        // Dated editions read like a year, other ones like a tag.
+        return if has_year(&disambiguation) {
+            format!("({disambiguation})")
+        } else {
+            format!("[{disambiguation}]")
+        };
+    }
+    if let Some(year) = release.date.as_deref().and_then(|d| d.get(..4)) {
+        return format!("({year})");
+    }
+    if let Some(country) = &release.country {
+        return format!("[{country}]");
+    }
+    format!("[{}]", album_id.get(..8).unwrap_or(album_id))
+}
+
+fn has_year(text: &str) -> bool {
+    text.split(|c: char| !c.is_ascii_digit())
+        .any(|word| word.len() == 4 && (word.starts_with("19") || word.starts_with("20")))
+}
+
+/// Capitalizes each word, MusicBrainz comments being mostly lowercase.
+fn title_case(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
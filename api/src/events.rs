@@ -0,0 +1,157 @@
+//! Job progress events.
+//!
+//! Every job state change is appended to an in-memory log and broadcast to
+//! subscribers. Clients follow it over a websocket, or by long-polling
+//! [`get_job_updates`] when a proxy in between does not let websockets through.
+//! Both deliver the same events, identified by an increasing cursor.
+
+use dioxus::fullstack::{WebSocketOptions, Websocket};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::JobState;
+#[cfg(feature = "server")]
+use crate::{auth, server_error};
+#[cfg(feature = "server")]
+use std::{
+    collections::VecDeque,
+    sync::{LazyLock, Mutex},
+};
+#[cfg(feature = "server")]
+use tokio::sync::broadcast;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JobEvent {
+    pub cursor: u64,
+    pub job_id: String,
+    pub state: JobState,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobUpdates {
+    /// Cursor to pass to the next [`get_job_updates`] call.
+    pub cursor: u64,
+    pub events: Vec<JobEvent>,
+}
+
+/// How many events are kept for clients catching up.
+#[cfg(feature = "server")]
+const LOG_SIZE: usize = 1000;
+
+/// How long a long-poll request waits for an event before returning empty.
+#[cfg(feature = "server")]
+const LONG_POLL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(25);
+
+#[cfg(feature = "server")]
+struct EventLog {
+    /// Events with the ID of the user owning the job.
+    events: Mutex<(u64, VecDeque<(String, JobEvent)>)>,
+    sender: broadcast::Sender<(String, JobEvent)>,
+}
+
+#[cfg(feature = "server")]
+static EVENTS: LazyLock<EventLog> = LazyLock::new(|| EventLog {
+    events: Mutex::new((0, VecDeque::with_capacity(LOG_SIZE))),
+    sender: broadcast::channel(64).0,
+});
+
+/// Records a job state change and notifies subscribers.
+#[cfg(feature = "server")]
+pub fn publish(user_id: &str, job_id: &str, state: JobState) {
+    let mut log = EVENTS.events.lock().unwrap();
+    log.0 += 1;
+    let event = JobEvent {
+        cursor: log.0,
+        job_id: job_id.to_string(),
+        state,
+    };
+    if log.1.len() == LOG_SIZE {
+        log.1.pop_front();
+    }
+    log.1.push_back((user_id.to_string(), event.clone()));
+    // Sending fails only when nobody is listening.
+    let _ = EVENTS.sender.send((user_id.to_string(), event));
+}
+
+/// Receives every user's job events from now on, with the user's ID.
+#[cfg(feature = "server")]
+pub(crate) fn subscribe() -> broadcast::Receiver<(String, JobEvent)> {
+    EVENTS.sender.subscribe()
+}
+
+/// The user's events after `cursor`, and the cursor to resume from.
+#[cfg(feature = "server")]
+fn events_since(user_id: &str, cursor: u64) -> JobUpdates {
+    let log = EVENTS.events.lock().unwrap();
+    // A cursor from before a server restart is ahead of the log, replay it all.
+    let cursor = if cursor > log.0 { 0 } else { cursor };
+    JobUpdates {
+        cursor: log.0,
+        events: log
+            .1
+            .iter()
+            .filter(|(user, event)| user == user_id && event.cursor > cursor)
+            .map(|(_, event)| event.clone())
+            .collect(),
+    }
+}
+
+/// Long-polling fallback for [`job_events`]: returns the events after
+/// `since_cursor`, waiting for the next one if there are none yet.
+#[server]
+pub async fn get_job_updates(
+    token: String,
+    since_cursor: u64,
+) -> Result<JobUpdates, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    // Subscribe before reading the log so no event slips in between.
+    let mut receiver = EVENTS.sender.subscribe();
+    let updates = events_since(&claims.sub, since_cursor);
+    if !updates.events.is_empty() {
+        return Ok(updates);
+    }
+
+    let wait_for_event = async {
+        loop {
+            match receiver.recv().await {
+                Ok((user, _)) if user == claims.sub => break,
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    };
+    let _ = tokio::time::timeout(LONG_POLL_TIMEOUT, wait_for_event).await;
+
+    Ok(events_since(&claims.sub, updates.cursor))
+}
+
+/// Streams the user's job events as they happen.
+#[get("/api/jobs/events?token")]
+pub async fn job_events(
+    token: String,
+    options: WebSocketOptions,
+) -> Result<Websocket<(), JobEvent>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut receiver = EVENTS.sender.subscribe();
+    Ok(options.on_upgrade(move |mut socket| async move {
+        loop {
+            match receiver.recv().await {
+                Ok((user, event)) if user == claims.sub => {
+                    if socket.send(event).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }))
+}
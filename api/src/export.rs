@@ -0,0 +1,278 @@
+//! Exports of a user's data, for spreadsheets or moving to another tool.
+//!
+//! Each dataset is a flat table: download history has a row per downloaded
+//! file, the wishlist a row per queued album, and the library index a row per
+//! imported track. The server builds the whole file and the browser saves it.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{AlbumRequest, Folder, Job, LibraryEntry},
+    server_error,
+};
+#[cfg(feature = "server")]
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportDataset {
+    History,
+    /// Albums queued for download, see [`crate::queue_albums`].
+    Wishlist,
+    Library,
+}
+
+impl ExportDataset {
+    pub const ALL: [ExportDataset; 3] = [Self::History, Self::Wishlist, Self::Library];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::History => "history",
+            Self::Wishlist => "wishlist",
+            Self::Library => "library",
+        }
+    }
+
+    /// Columns of the dataset, in export order.
+    pub fn fields(self) -> &'static [&'static str] {
+        match self {
+            Self::History => &[
+                "created_at",
+                "job_id",
+                "job_state",
+                "target_folder",
+                "note",
+                "labels",
+                "artist",
+                "title",
+                "username",
+                "filename",
+                "size",
+                "file_state",
+                "error",
+                "average_speed",
+                "local_path",
+            ],
+            Self::Wishlist => &[
+                "created_at",
+                "album_id",
+                "artist",
+                "title",
+                "target_folder",
+                "state",
+                "job_id",
+                "error",
+            ],
+            Self::Library => &[
+                "indexed_at",
+                "folder",
+                "artist",
+                "album",
+                "title",
+                "track",
+                "format",
+                "bitrate",
+                "mb_albumid",
+                "mb_trackid",
+                "path",
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportRequest {
+    pub dataset: ExportDataset,
+    pub format: ExportFormat,
+    /// Columns to keep, all of them when empty.
+    pub fields: Vec<String>,
+    /// Only rows dated on or after this day, `YYYY-MM-DD`.
+    pub from: Option<String>,
+    /// Only rows dated on or before this day, `YYYY-MM-DD`.
+    pub to: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportFile {
+    pub filename: String,
+    pub content_type: String,
+    pub content: String,
+}
+
+/// Checks a `YYYY-MM-DD` bound, dropping empty ones.
+#[cfg(feature = "server")]
+fn parse_day(day: Option<String>) -> Result<Option<String>, String> {
+    match day.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(day) => chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d")
+            .map(|_| Some(day.to_string()))
+            .map_err(|_| format!("Invalid date '{day}', expected YYYY-MM-DD")),
+    }
+}
+
+/// Whether an RFC 3339 timestamp falls within the days `from..=to`.
+#[cfg(feature = "server")]
+fn in_range(timestamp: &str, from: Option<&str>, to: Option<&str>) -> bool {
+    let day = timestamp.get(..10).unwrap_or(timestamp);
+    from.is_none_or(|from| day >= from) && to.is_none_or(|to| day <= to)
+}
+
+/// The dataset's rows for the user, each with every field, and the timestamp
+/// the date range applies to.
+#[cfg(feature = "server")]
+async fn rows(user_id: &str, dataset: ExportDataset) -> Result<Vec<(String, Value)>, String> {
+    let mut rows = Vec::new();
+    match dataset {
+        ExportDataset::History => {
+            for job in Job::get_all_by_user(user_id).await? {
+                let labels = job.labels().await?.join(";");
+                for file in job.files().await? {
+                    rows.push((
+                        job.created_at.clone(),
+                        serde_json::json!({
+                            "created_at": job.created_at,
+                            "job_id": job.id,
+                            "job_state": job.state,
+                            "target_folder": job.target_folder,
+                            "note": job.note,
+                            "labels": labels,
+                            "artist": file.artist,
+                            "title": file.title,
+                            "username": file.username,
+                            "filename": file.filename,
+                            "size": file.size,
+                            "file_state": file.state,
+                            "error": file.error,
+                            "average_speed": file.average_speed,
+                            "local_path": file.local_path,
+                        }),
+                    ));
+                }
+            }
+        }
+        ExportDataset::Wishlist => {
+            for request in AlbumRequest::get_all_by_user(user_id).await? {
+                let created_at = request.created_at.clone();
+                rows.push((
+                    created_at,
+                    serde_json::to_value(request).map_err(|e| e.to_string())?,
+                ));
+            }
+        }
+        ExportDataset::Library => {
+            for folder in Folder::get_all_by_user(user_id).await? {
+                for entry in LibraryEntry::get_by_folder(&folder.path).await? {
+                    let indexed_at = entry.indexed_at.clone();
+                    rows.push((
+                        indexed_at,
+                        serde_json::to_value(entry).map_err(|e| e.to_string())?,
+                    ));
+                }
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// Quotes a CSV cell when it holds a separator, quote or line break.
+#[cfg(feature = "server")]
+fn csv_cell(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+#[cfg(feature = "server")]
+fn to_csv(fields: &[&str], rows: &[Map<String, Value>]) -> String {
+    let mut out = fields.join(",");
+    out.push_str("\r\n");
+    for row in rows {
+        let cells: Vec<String> = fields
+            .iter()
+            .map(|f| csv_cell(row.get(*f).unwrap_or(&Value::Null)))
+            .collect();
+        out.push_str(&cells.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// The user's download history, wishlist or library index as CSV or JSON.
+#[server]
+pub async fn export_data(
+    token: String,
+    request: ExportRequest,
+) -> Result<ExportFile, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let available = request.dataset.fields();
+    let fields: Vec<&str> = if request.fields.is_empty() {
+        available.to_vec()
+    } else {
+        // Kept in the dataset's order, whatever the order asked for.
+        available
+            .iter()
+            .copied()
+            .filter(|f| request.fields.iter().any(|r| r == f))
+            .collect()
+    };
+    if let Some(unknown) = request
+        .fields
+        .iter()
+        .find(|f| !available.contains(&f.as_str()))
+    {
+        return Err(server_error(format!("Unknown field '{unknown}'")));
+    }
+    let from = parse_day(request.from).map_err(server_error)?;
+    let to = parse_day(request.to).map_err(server_error)?;
+
+    let rows: Vec<Map<String, Value>> = rows(&claims.sub, request.dataset)
+        .await
+        .map_err(server_error)?
+        .into_iter()
+        .filter(|(at, _)| in_range(at, from.as_deref(), to.as_deref()))
+        .map(|(_, row)| {
+            let mut row = match row {
+                Value::Object(map) => map,
+                _ => Map::new(),
+            };
+            fields
+                .iter()
+                .map(|f| (f.to_string(), row.remove(*f).unwrap_or(Value::Null)))
+                .collect()
+        })
+        .collect();
+
+    let day = chrono::Utc::now().format("%Y-%m-%d");
+    let name = request.dataset.name();
+    Ok(match request.format {
+        ExportFormat::Csv => ExportFile {
+            filename: format!("soulbeet-{name}-{day}.csv"),
+            content_type: "text/csv".to_string(),
+            content: to_csv(&fields, &rows),
+        },
+        ExportFormat::Json => ExportFile {
+            filename: format!("soulbeet-{name}-{day}.json"),
+            content_type: "application/json".to_string(),
+            content: serde_json::to_string_pretty(&rows).map_err(server_error)?,
+        },
+    })
+}
@@ -0,0 +1,130 @@
+//! Offline analysis of job feedback against the ranking scores.
+//!
+//! Run with `cargo run -p api --features server --bin feedback_report`.
+
+use std::fmt;
+
+use crate::db::{Feedback, Job};
+
+/// Per-job ranking signals, averaged over the job's files.
+struct Sample {
+    rating: f64,
+    match_score: f64,
+    min_match_score: f64,
+    quality_score: f64,
+}
+
+pub struct Signal {
+    pub name: &'static str,
+    pub mean_up: f64,
+    pub mean_down: f64,
+    /// Pearson correlation between the signal and the rating (-1 / 1).
+    pub correlation: f64,
+}
+
+pub struct FeedbackReport {
+    pub up: usize,
+    pub down: usize,
+    pub signals: Vec<Signal>,
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(s, c), v| (s + v, c + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+fn correlation(xs: &[f64], ys: &[f64]) -> f64 {
+    let mx = mean(xs.iter().copied());
+    let my = mean(ys.iter().copied());
+    let cov: f64 = xs.iter().zip(ys).map(|(x, y)| (x - mx) * (y - my)).sum();
+    let vx: f64 = xs.iter().map(|x| (x - mx).powi(2)).sum();
+    let vy: f64 = ys.iter().map(|y| (y - my).powi(2)).sum();
+    if vx == 0.0 || vy == 0.0 {
+        0.0
+    } else {
+        cov / (vx.sqrt() * vy.sqrt())
+    }
+}
+
+pub async fn analyze() -> Result<FeedbackReport, String> {
+    let mut samples = Vec::new();
+
+    for job in Job::get_rated().await? {
+        let files = job.files().await?;
+        let match_scores: Vec<f64> = files.iter().filter_map(|f| f.match_score).collect();
+        let quality_scores: Vec<f64> = files.iter().filter_map(|f| f.quality_score).collect();
+        if match_scores.is_empty() {
+            continue;
+        }
+
+        samples.push(Sample {
+            rating: match job.feedback {
+                Some(Feedback::Up) => 1.0,
+                _ => -1.0,
+            },
+            match_score: mean(match_scores.iter().copied()),
+            min_match_score: match_scores.iter().copied().fold(f64::INFINITY, f64::min),
+            quality_score: mean(quality_scores.into_iter()),
+        });
+    }
+
+    let ratings: Vec<f64> = samples.iter().map(|s| s.rating).collect();
+    let signal = |name: &'static str, get: fn(&Sample) -> f64| {
+        let values: Vec<f64> = samples.iter().map(get).collect();
+        Signal {
+            name,
+            mean_up: mean(samples.iter().filter(|s| s.rating > 0.0).map(get)),
+            mean_down: mean(samples.iter().filter(|s| s.rating < 0.0).map(get)),
+            correlation: correlation(&values, &ratings),
+        }
+    };
+
+    Ok(FeedbackReport {
+        up: samples.iter().filter(|s| s.rating > 0.0).count(),
+        down: samples.iter().filter(|s| s.rating < 0.0).count(),
+        signals: vec![
+            signal("match score (avg)", |s| s.match_score),
+            signal("match score (min)", |s| s.min_match_score),
+            signal("quality score (avg)", |s| s.quality_score),
+        ],
+    })
+}
+
+impl fmt::Display for FeedbackReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Rated jobs: {} up, {} down", self.up, self.down)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<22} {:>8} {:>8} {:>8}",
+            "signal", "up", "down", "corr"
+        )?;
+        for signal in &self.signals {
+            writeln!(
+                f,
+                "{:<22} {:>8.3} {:>8.3} {:>8.3}",
+                signal.name, signal.mean_up, signal.mean_down, signal.correlation
+            )?;
+        }
+
+        // Suggest weights proportional to how well each signal predicts a good pick.
+        let positive: f64 = self.signals.iter().map(|s| s.correlation.max(0.0)).sum();
+        if positive > 0.0 {
+            writeln!(f)?;
+            writeln!(f, "Suggested relative weights:")?;
+            for signal in &self.signals {
+                writeln!(
+                    f,
+                    "  {:<20} {:.2}",
+                    signal.name,
+                    signal.correlation.max(0.0) / positive
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
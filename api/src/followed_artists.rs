@@ -0,0 +1,251 @@
+//! New-release watch.
+//!
+//! Users follow artists from their discography. The `release_watch` task looks
+//! up the albums of every followed artist on MusicBrainz and adds those
+//! released since the artist was followed to the user's wanted list. For
+//! artists followed with auto-download, wanted albums are queued as album
+//! requests once their release date has come, and searched like albums queued
+//! from a discography.
+
+use dioxus::prelude::*;
+use shared::musicbrainz::Artist;
+
+use crate::db::{AlbumRequest, FollowedArtist, WantedRelease};
+#[cfg(feature = "server")]
+use crate::{album_requests, auth, browse, musicbrainz_cache, server_error};
+#[cfg(feature = "server")]
+use shared::musicbrainz::Album;
+#[cfg(feature = "server")]
+use soulbeet::musicbrainz;
+#[cfg(feature = "server")]
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Today, as MusicBrainz writes release dates.
+#[cfg(feature = "server")]
+fn today() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+/// Whether `album` came out on or after the day `followed` was followed.
+/// Release dates may only give the year or the month, they are compared at
+/// that precision, so an album of the year an artist was followed is new.
+/// Albums without a date cannot tell, they are left out.
+#[cfg(feature = "server")]
+fn is_new(album: &Album, followed: &FollowedArtist) -> bool {
+    let since = followed
+        .created_at
+        .get(..10)
+        .unwrap_or(&followed.created_at);
+    album.release_date.as_deref().is_some_and(|date| {
+        let precision = date.len().min(since.len());
+        date.get(..precision) >= since.get(..precision)
+    })
+}
+
+/// The albums of the artist with MusicBrainz id `artist_id`, shared through
+/// the cache with the discographies opened meanwhile.
+#[cfg(feature = "server")]
+async fn artist_releases(artist_id: &str) -> Result<Vec<Album>, String> {
+    musicbrainz_cache::cached(format!("artist releases {artist_id}"), async {
+        musicbrainz::artist_releases(artist_id)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await
+}
+
+/// Queues the released albums on the wanted list of `followed`, returning how
+/// many were queued.
+#[cfg(feature = "server")]
+async fn queue_released(followed: &FollowedArtist, today: &str) -> Result<usize, String> {
+    let Some(folder) = followed.target_folder.as_deref() else {
+        return Ok(0);
+    };
+    let mut queued = 0;
+    for wanted in WantedRelease::get_unqueued(&followed.id).await? {
+        if !wanted.is_released(today) {
+            continue;
+        }
+        let request = AlbumRequest::create(
+            &followed.user_id,
+            &wanted.album(),
+            folder,
+            followed.profile_id.as_deref(),
+        )
+        .await?;
+        WantedRelease::set_request(&wanted.id, &request.id).await?;
+        queued += 1;
+    }
+    Ok(queued)
+}
+
+/// Adds the new albums of followed artists to the wanted lists and queues
+/// the released ones of artists followed with auto-download. Returns a
+/// summary for the run history.
+#[cfg(feature = "server")]
+pub(crate) async fn check_new_releases() -> Result<String, String> {
+    let today = today();
+    // Artists followed by several users are looked up once.
+    let mut albums: HashMap<String, Vec<Album>> = HashMap::new();
+    let (mut added, mut queued, mut failed) = (0, 0, 0);
+
+    for followed in FollowedArtist::get_all().await? {
+        let artist_albums = match albums.entry(followed.artist_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match artist_releases(&followed.artist_id).await {
+                Ok(fetched) => entry.insert(fetched),
+                Err(e) => {
+                    tracing::warn!("Failed to look up the albums of {}: {}", followed.name, e);
+                    failed += 1;
+                    continue;
+                }
+            },
+        };
+        for album in artist_albums.iter().filter(|a| is_new(a, &followed)) {
+            if WantedRelease::add(&followed, album).await? {
+                tracing::info!("New album of {}: {}", followed.name, album.title);
+                added += 1;
+            }
+        }
+        if followed.auto_download {
+            queued += queue_released(&followed, &today).await?;
+        }
+        FollowedArtist::set_checked(&followed.id).await?;
+    }
+
+    if queued > 0 {
+        album_requests::wake_queue();
+    }
+    let mut summary = format!(
+        "Checked {} artists: {added} new albums, {queued} queued for download",
+        albums.len()
+    );
+    if failed > 0 {
+        summary.push_str(&format!(", {failed} lookups failed"));
+    }
+    Ok(summary)
+}
+
+/// Follows `artist`, or changes how an artist already followed is. With
+/// `auto_download`, their new albums are downloaded to `target_folder`.
+#[server]
+pub async fn follow_artist(
+    token: String,
+    artist: Artist,
+    auto_download: bool,
+    target_folder: Option<String>,
+    profile_id: Option<String>,
+) -> Result<FollowedArtist, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let target_folder = target_folder.filter(|folder| !folder.trim().is_empty());
+    if auto_download && target_folder.is_none() {
+        return Err(server_error(
+            "A target folder is required to download new albums",
+        ));
+    }
+
+    FollowedArtist::upsert(
+        &claims.sub,
+        &artist,
+        auto_download,
+        target_folder.as_deref(),
+        profile_id.as_deref(),
+    )
+    .await
+    .map_err(server_error)
+}
+
+#[server]
+pub async fn unfollow_artist(token: String, id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    FollowedArtist::delete(&claims.sub, &id)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn get_followed_artists(token: String) -> Result<Vec<FollowedArtist>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    FollowedArtist::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn get_wanted_releases(token: String) -> Result<Vec<WantedRelease>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    WantedRelease::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+/// Queues a wanted album for download right away, released or not.
+#[server]
+pub async fn queue_wanted_release(
+    token: String,
+    id: String,
+    target_folder: String,
+    profile_id: Option<String>,
+) -> Result<AlbumRequest, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if target_folder.trim().is_empty() {
+        return Err(server_error("Target folder is required"));
+    }
+    let wanted = WantedRelease::get(&claims.sub, &id)
+        .await
+        .map_err(server_error)?;
+    if wanted.request_id.is_some() {
+        return Err(server_error("This album is already queued"));
+    }
+
+    let request = AlbumRequest::create(
+        &claims.sub,
+        &wanted.album(),
+        &target_folder,
+        profile_id.as_deref(),
+    )
+    .await
+    .map_err(server_error)?;
+    WantedRelease::set_request(&wanted.id, &request.id)
+        .await
+        .map_err(server_error)?;
+    album_requests::wake_queue();
+    Ok(request)
+}
+
+/// Removes an album from the wanted list. It is not added back.
+#[server]
+pub async fn dismiss_wanted_release(token: String, id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    WantedRelease::dismiss(&claims.sub, &id)
+        .await
+        .map_err(server_error)
+}
@@ -0,0 +1,109 @@
+//! Genre enrichment at import time.
+//!
+//! Soulseek files rarely carry useful genre tags, so the genre is taken from
+//! the MusicBrainz release (and Last.fm when `LASTFM_API_KEY` is set), mapped
+//! through the user's own taxonomy, and written by beets during import.
+
+use dioxus::prelude::*;
+
+use crate::db::GenreMapping;
+#[cfg(feature = "server")]
+use crate::{auth, db::Job, server_error};
+#[cfg(feature = "server")]
+use soulbeet::{lastfm, musicbrainz};
+#[cfg(feature = "server")]
+use std::collections::HashMap;
+
+/// At most this many genres are written to a release.
+#[cfg(feature = "server")]
+const MAX_GENRES: usize = 3;
+
+/// Genre to write for the job's release, if any tag could be found and mapped.
+///
+/// Without mappings, the most voted tags are used as they are. Once the user
+/// has mappings, only tags with a mapping are kept.
+#[cfg(feature = "server")]
+pub async fn genre_for_job(job: &Job) -> Option<String> {
+    let album_id = job.album_id.as_deref()?;
+    let release = match musicbrainz::release_tags(album_id).await {
+        Ok(release) => release,
+        Err(e) => {
+            tracing::warn!("Failed to fetch tags for release {}: {}", album_id, e);
+            return None;
+        }
+    };
+
+    let mut tags = release.tags;
+    if let Ok(api_key) = std::env::var("LASTFM_API_KEY") {
+        match lastfm::album_tags(&api_key, &release.artist, &release.title).await {
+            Ok(lastfm_tags) => tags.extend(lastfm_tags),
+            Err(e) => tracing::warn!("Failed to fetch Last.fm tags: {}", e),
+        }
+    }
+
+    let mappings: HashMap<_, _> = GenreMapping::get_all_by_user(&job.user_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|m| (m.source, m.target))
+        .collect();
+
+    let mut genres: Vec<String> = Vec::new();
+    for tag in tags {
+        let genre = if mappings.is_empty() {
+            Some(tag)
+        } else {
+            mappings.get(&tag.to_lowercase()).cloned()
+        };
+        if let Some(genre) = genre {
+            if !genres.iter().any(|g| g.eq_ignore_ascii_case(&genre)) {
+                genres.push(genre);
+            }
+        }
+        if genres.len() == MAX_GENRES {
+            break;
+        }
+    }
+
+    (!genres.is_empty()).then(|| genres.join(", "))
+}
+
+#[server]
+pub async fn get_genre_mappings(token: String) -> Result<Vec<GenreMapping>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    GenreMapping::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn set_genre_mapping(
+    token: String,
+    source: String,
+    target: String,
+) -> Result<GenreMapping, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    GenreMapping::upsert(&claims.sub, source.trim(), target.trim())
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn delete_genre_mapping(token: String, source: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    GenreMapping::delete(&claims.sub, &source)
+        .await
+        .map_err(server_error)
+}
@@ -0,0 +1,358 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::db::{Feedback, Job, JobFile};
+#[cfg(feature = "server")]
+use crate::{
+    auth, browse,
+    db::{JobState, User},
+    dispatch, provider, server_error, slskd_search,
+};
+#[cfg(feature = "server")]
+use shared::musicbrainz::Track;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JobWithFiles {
+    pub job: Job,
+    pub files: Vec<JobFile>,
+    pub labels: Vec<String>,
+    /// Sampled transfer speeds in bytes/s, oldest first, keyed by job file id.
+    #[serde(default)]
+    pub speeds: HashMap<String, Vec<f64>>,
+    /// Percent of each running transfer received as of the last speed
+    /// sample, keyed by job file id.
+    #[serde(default)]
+    pub progress: HashMap<String, f64>,
+}
+
+/// A job waiting for a download slot, as seen by admins.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedJob {
+    pub job: Job,
+    pub username: String,
+    pub file_count: usize,
+    /// Average match score of the job's files.
+    pub match_score: Option<f64>,
+}
+
+/// What to do with staged files the user chose not to import.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum RejectedFiles {
+    #[default]
+    Discard,
+    Archive,
+}
+
+/// Where files rejected during review are archived, one folder per job.
+#[cfg(feature = "server")]
+pub(crate) fn archive_root() -> std::path::PathBuf {
+    std::path::PathBuf::from(
+        std::env::var("ARCHIVE_PATH").unwrap_or_else(|_| "archive".to_string()),
+    )
+}
+
+#[cfg(feature = "server")]
+async fn get_owned_job(token: &str, job_id: &str) -> Result<Job, ServerFnError> {
+    let claims = auth::verify_token(token, "access").map_err(server_error)?;
+    let job = Job::get(job_id).await.map_err(server_error)?;
+    if job.user_id != claims.sub {
+        return Err(server_error("Job not found"));
+    }
+    Ok(job)
+}
+
+#[server]
+pub async fn get_jobs(token: String) -> Result<Vec<JobWithFiles>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let jobs = Job::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)?;
+
+    let mut res = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        let files = job.files().await.map_err(server_error)?;
+        let labels = job.labels().await.map_err(server_error)?;
+        let speeds = job.speed_history().await.map_err(server_error)?;
+        let transferred = job.transferred_bytes().await.map_err(server_error)?;
+        let progress = files
+            .iter()
+            .filter(|file| file.size > 0 && file.state.is_none())
+            .filter_map(|file| {
+                let bytes = transferred.get(&file.id)?;
+                Some((file.id.clone(), *bytes as f64 * 100.0 / file.size as f64))
+            })
+            .collect();
+        res.push(JobWithFiles {
+            job,
+            files,
+            labels,
+            speeds,
+            progress,
+        });
+    }
+    Ok(res)
+}
+
+/// A file of one of the user's downloading jobs, with the job.
+#[cfg(feature = "server")]
+async fn get_downloading_file(token: &str, file_id: &str) -> Result<(Job, JobFile), ServerFnError> {
+    auth::verify_writable(token).map_err(server_error)?;
+    let file = JobFile::get(file_id).await.map_err(server_error)?;
+    let job = get_owned_job(token, &file.job_id).await?;
+    if job.state != JobState::Downloading {
+        return Err(server_error(
+            "Only files of running downloads can be paused",
+        ));
+    }
+    Ok((job, file))
+}
+
+/// Pauses the transfer of one file of a running download. The job waits for
+/// it until it is resumed.
+#[server]
+pub async fn pause_transfer(token: String, file_id: String) -> Result<(), ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let (_, file) = get_downloading_file(&token, &file_id).await?;
+    if file.paused || file.state.is_some() {
+        return Err(server_error("The transfer is not running"));
+    }
+    let Some(transfer_id) = &file.transfer_id else {
+        return Err(server_error("slskd has not started the transfer yet"));
+    };
+
+    // Marked first, so the monitor does not take the cancelled transfer for a failure.
+    JobFile::set_paused(&file.id, true)
+        .await
+        .map_err(server_error)?;
+    if let Err(e) = provider().pause_download(&file.username, transfer_id).await {
+        let _ = JobFile::set_paused(&file.id, false).await;
+        return Err(server_error(e));
+    }
+    Ok(())
+}
+
+/// Requests a paused transfer from its uploader again.
+#[server]
+pub async fn resume_transfer(token: String, file_id: String) -> Result<(), ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let (job, file) = get_downloading_file(&token, &file_id).await?;
+    if !file.paused {
+        return Err(server_error("The transfer is not paused"));
+    }
+
+    let download = provider()
+        .resume_download(&file.username, &file.filename, file.size)
+        .await
+        .map_err(server_error)?
+        .ok_or_else(|| server_error("slskd refused the download request"))?;
+    if download.filename != download.original_filename {
+        JobFile::rename(&job.id, &download.original_filename, &download.filename)
+            .await
+            .map_err(server_error)?;
+    }
+    JobFile::set_transfer_id(&job.id, &download.filename, &download.id)
+        .await
+        .map_err(server_error)?;
+    JobFile::set_paused(&file.id, false)
+        .await
+        .map_err(server_error)
+}
+
+/// Imports only the `keep` files of a job awaiting review; the other staged
+/// files are deleted or moved to the archive folder.
+#[server]
+pub async fn import_reviewed(
+    token: String,
+    job_id: String,
+    keep: Vec<String>,
+    rejected: RejectedFiles,
+) -> Result<(), ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let job = get_owned_job(&token, &job_id).await?;
+    // Claimed before touching any file, so a second submit of the review
+    // can't import or delete them again.
+    let claimed = Job::transition(&job.id, JobState::AwaitingReview, JobState::Importing)
+        .await
+        .map_err(server_error)?;
+    if !claimed {
+        return Err(server_error("Job is not awaiting review"));
+    }
+
+    let files = match job.files().await {
+        Ok(files) => files,
+        Err(e) => {
+            // Nothing was touched, the review can be submitted again.
+            let _ = Job::set_state(&job.id, JobState::AwaitingReview).await;
+            return Err(server_error(e));
+        }
+    };
+    let (kept, dropped): (Vec<_>, Vec<_>) = files
+        .into_iter()
+        .filter_map(|f| f.local_path)
+        .partition(|path| keep.contains(path));
+
+    let archive_dir = archive_root().join(&job.id);
+
+    for path in dropped {
+        let result = match rejected {
+            RejectedFiles::Discard => tokio::fs::remove_file(&path).await,
+            RejectedFiles::Archive => {
+                let file_name = std::path::Path::new(&path)
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_owned();
+                match tokio::fs::create_dir_all(&archive_dir).await {
+                    Ok(()) => tokio::fs::rename(&path, archive_dir.join(file_name)).await,
+                    Err(e) => Err(e),
+                }
+            }
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to handle rejected file {}: {}", path, e);
+        }
+    }
+
+    if kept.is_empty() {
+        // Nothing left to import, the job ends here.
+        Job::set_state(&job.id, JobState::Failed)
+            .await
+            .map_err(server_error)?;
+        return Ok(());
+    }
+
+    tokio::spawn(async move {
+        dispatch::import_job(&job, kept).await;
+    });
+    Ok(())
+}
+
+/// Searches for another copy of one track of an imported job, preferring the
+/// uploader of the original file, and queues it to replace the bad one.
+#[server]
+pub async fn replace_file(
+    token: String,
+    job_id: String,
+    file_id: String,
+) -> Result<Job, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let job = get_owned_job(&token, &job_id).await?;
+    if job.state != JobState::Imported {
+        return Err(server_error("Only files of imported jobs can be replaced"));
+    }
+    let file = job
+        .files()
+        .await
+        .map_err(server_error)?
+        .into_iter()
+        .find(|f| f.id == file_id)
+        .ok_or_else(|| server_error("File not found"))?;
+
+    let title = file.title.clone().unwrap_or_else(|| {
+        std::path::Path::new(&file.filename.replace('\\', "/"))
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+    let artist = file.artist.clone().unwrap_or_default();
+    let track = Track {
+        id: job.recording_id.clone().unwrap_or_default(),
+        title: title.clone(),
+        artist: artist.clone(),
+        album_id: job.album_id.clone(),
+        album_title: None,
+        release_date: None,
+        duration: None,
+        genres: Vec::new(),
+    };
+
+    let candidates: Vec<_> = slskd_search(
+        &job.user_id,
+        artist,
+        title,
+        None,
+        vec![track],
+        None,
+        None,
+        &Default::default(),
+        None,
+    )
+    .await?
+    .albums
+    .into_iter()
+    .flat_map(|album| album.tracks)
+    .filter(|t| !(t.base.username == file.username && t.base.filename == file.filename))
+    .collect();
+    let best = candidates
+        .iter()
+        .find(|t| t.base.username == file.username)
+        .or_else(|| {
+            candidates
+                .iter()
+                .max_by(|a, b| a.match_score.total_cmp(&b.match_score))
+        })
+        .cloned()
+        .ok_or_else(|| server_error("No replacement found on Soulseek"))?;
+
+    dispatch::dispatch_replacement(&job, &file, best)
+        .await
+        .map_err(server_error)
+}
+
+/// Queued jobs of all users, in the order they will be started.
+#[server]
+pub async fn get_queue(token: String) -> Result<Vec<QueuedJob>, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let usernames: std::collections::HashMap<_, _> = User::get_all()
+        .await
+        .map_err(server_error)?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    let queue = dispatch::fair_queue().await.map_err(server_error)?;
+    let mut res = Vec::with_capacity(queue.len());
+    for job in queue {
+        let files = job.files().await.map_err(server_error)?;
+        let scores: Vec<f64> = files.iter().filter_map(|f| f.match_score).collect();
+        res.push(QueuedJob {
+            username: usernames.get(&job.user_id).cloned().unwrap_or_default(),
+            file_count: files.iter().filter(|f| !f.artwork).count(),
+            match_score: (!scores.is_empty())
+                .then(|| scores.iter().sum::<f64>() / scores.len() as f64),
+            job,
+        });
+    }
+    Ok(res)
+}
+
+/// Records whether a finished job picked the right album and quality.
+#[server]
+pub async fn rate_job(
+    token: String,
+    job_id: String,
+    feedback: Option<Feedback>,
+) -> Result<(), ServerFnError> {
+    let job = get_owned_job(&token, &job_id).await?;
+    if !matches!(
+        job.state,
+        JobState::Imported | JobState::Failed | JobState::ImportTimedOut
+    ) {
+        return Err(server_error("Only finished jobs can be rated"));
+    }
+
+    Job::set_feedback(&job.id, feedback)
+        .await
+        .map_err(server_error)
+}
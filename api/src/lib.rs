@@ -1,5 +1,78 @@
+mod album_requests;
+mod announcements;
 pub mod auth;
+mod availability;
+mod bitrates;
+mod blocked_uploaders;
+mod branding;
+mod browse;
+mod config;
+mod covers;
+#[cfg(feature = "server")]
+mod cron;
 pub mod db;
+mod debug;
+mod demo;
+#[cfg(feature = "server")]
+pub mod dispatch;
+mod edition_preferences;
+#[cfg(feature = "server")]
+mod editions;
+mod events;
+mod export;
+#[cfg(feature = "server")]
+pub mod feedback;
+mod followed_artists;
+mod genres;
+mod jobs;
+mod library;
+mod lyrics;
+mod media;
+#[cfg(feature = "server")]
+mod musicbrainz_cache;
+mod notifications;
+mod passkeys;
+mod permissions;
+mod profiles;
+mod searches;
+mod share_links;
+mod shares;
+mod soulseek;
+mod tasks;
+mod unmanaged;
+mod uploads;
+
+pub use album_requests::*;
+pub use announcements::*;
+pub use availability::*;
+pub use bitrates::*;
+pub use blocked_uploaders::*;
+pub use branding::*;
+pub use browse::*;
+pub use config::*;
+pub use covers::*;
+pub use debug::*;
+pub use demo::*;
+pub use edition_preferences::*;
+pub use events::*;
+pub use export::*;
+pub use followed_artists::*;
+pub use genres::*;
+pub use jobs::*;
+pub use library::*;
+pub use lyrics::*;
+pub use media::*;
+pub use notifications::*;
+pub use passkeys::*;
+pub use permissions::*;
+pub use profiles::*;
+pub use searches::*;
+pub use share_links::*;
+pub use shares::*;
+pub use soulseek::*;
+pub use tasks::*;
+pub use unmanaged::*;
+pub use uploads::*;
 
 use std::sync::LazyLock;
 
@@ -8,19 +81,25 @@ use chrono::Duration;
 use dioxus::{logger::tracing::info, prelude::*};
 use serde::{Deserialize, Serialize};
 use shared::{
-    download::DownloadQuery,
-    musicbrainz::{AlbumWithTracks, SearchResult},
-    slskd::{AlbumResult, DownloadResponse, DownloadState, FileEntry, TrackResult},
+    download::{DownloadQuery, TrackDownloadQuery},
+    musicbrainz::{AlbumWithTracks, Artist, SearchPage},
+    slskd::{AlbumResult, FileEntry, SharedDirectory, TrackResult},
 };
 
 #[cfg(feature = "server")]
-use shared::musicbrainz::Track;
-#[cfg(feature = "server")]
-use soulbeet::beets;
+use shared::{musicbrainz::Track, slskd::SearchRefinements};
+
 #[cfg(feature = "server")]
 use soulbeet::musicbrainz;
 #[cfg(feature = "server")]
-use soulbeet::slskd::{SoulseekClient, SoulseekClientBuilder};
+use soulbeet::provider::DownloadProvider;
+#[cfg(feature = "server")]
+use soulbeet::slskd::{
+    scoring::{self, ScoringStrategy},
+    AlbumSearch, EarlyExit, SearchRequest, SoulseekBackend, SoulseekClient, SoulseekClientBuilder,
+};
+#[cfg(feature = "server")]
+use std::sync::{Arc, RwLock};
 
 #[cfg(feature = "server")]
 static SLSKD_CLIENT: LazyLock<SoulseekClient> = LazyLock::new(|| {
@@ -29,14 +108,111 @@ static SLSKD_CLIENT: LazyLock<SoulseekClient> = LazyLock::new(|| {
     let download_path =
         std::env::var("SLSKD_DOWNLOAD_PATH").expect("Missing SLSKD_DOWNLOAD_PATH env var");
 
-    SoulseekClientBuilder::new()
+    let mut builder = SoulseekClientBuilder::new()
         .api_key(&api_key)
         .base_url(&base_url)
-        .download_path(&download_path)
-        .build()
-        .expect("Failed to create Soulseek client")
+        .download_path(&download_path);
+    if let Some(early_exit) = early_exit() {
+        builder = builder.early_exit(early_exit);
+    }
+    builder.build().expect("Failed to create Soulseek client")
 });
 
+/// Where searches and downloads go: slskd, through [`SLSKD_CLIENT`], unless
+/// replaced with [`set_soulseek_backend`].
+#[cfg(feature = "server")]
+static SOULSEEK: LazyLock<RwLock<Arc<dyn SoulseekBackend>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(SLSKD_CLIENT.clone())));
+
+#[cfg(feature = "server")]
+pub(crate) fn soulseek() -> Arc<dyn SoulseekBackend> {
+    SOULSEEK.read().unwrap().clone()
+}
+
+/// Sends searches and downloads to `backend` from now on, e.g. a
+/// [`soulbeet::slskd::MockBackend`] to exercise the API without slskd.
+#[cfg(feature = "server")]
+pub fn set_soulseek_backend(backend: Arc<dyn SoulseekBackend>) {
+    *SOULSEEK.write().unwrap() = backend;
+}
+
+/// Where album searches and job downloads go, when not the Soulseek backend.
+#[cfg(feature = "server")]
+static PROVIDER: LazyLock<RwLock<Option<Arc<dyn DownloadProvider>>>> =
+    LazyLock::new(Default::default);
+
+/// The provider album searches and jobs go through: the Soulseek backend
+/// unless replaced with [`set_download_provider`].
+#[cfg(feature = "server")]
+pub(crate) fn provider() -> Arc<dyn DownloadProvider> {
+    match PROVIDER.read().unwrap().clone() {
+        Some(provider) => provider,
+        None => soulseek(),
+    }
+}
+
+/// Searches albums and fetches job files through `provider` from now on,
+/// instead of the Soulseek backend.
+#[cfg(feature = "server")]
+pub fn set_download_provider(provider: Arc<dyn DownloadProvider>) {
+    *PROVIDER.write().unwrap() = Some(provider);
+}
+
+/// When album searches stop before their timeout, `None` when
+/// `SEARCH_EARLY_EXIT_ALBUMS` is 0.
+#[cfg(feature = "server")]
+fn early_exit() -> Option<EarlyExit> {
+    let default = EarlyExit::default();
+    let min_albums = std::env::var("SEARCH_EARLY_EXIT_ALBUMS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default.min_albums);
+    if min_albums == 0 {
+        return None;
+    }
+    let min_score = std::env::var("SEARCH_EARLY_EXIT_SCORE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default.min_score);
+    let formats = match std::env::var("SEARCH_EARLY_EXIT_FORMATS") {
+        Ok(formats) => formats
+            .split(',')
+            .map(|f| f.trim().to_lowercase())
+            .filter(|f| !f.is_empty())
+            .collect(),
+        Err(_) => default.formats,
+    };
+    Some(EarlyExit {
+        min_albums,
+        min_score,
+        formats,
+    })
+}
+
+/// Server-side startup tasks, to be awaited before serving requests.
+#[cfg(feature = "server")]
+pub async fn startup() {
+    config::log_report().await;
+    // The LAN instance sharing the database runs the tasks and notifications.
+    if browse::enabled() {
+        tracing::info!("Browse mode: searching and downloading are disabled");
+        return;
+    }
+    tasks::start_scheduler().await;
+    tokio::spawn(notifications::run());
+    if !config::missing_env_vars().is_empty() {
+        tracing::error!("Skipping dispatch reconciliation, slskd is not configured");
+        return;
+    }
+
+    soulseek::load_search_rate_limit().await;
+    if let Err(e) = dispatch::reconcile().await {
+        tracing::error!("Failed to reconcile interrupted dispatches: {}", e);
+    }
+    dispatch::schedule().await;
+    tokio::spawn(album_requests::run_queue());
+}
+
 fn server_error<E: std::fmt::Display>(e: E) -> ServerFnError {
     ServerFnError::ServerError {
         message: e.to_string(),
@@ -47,6 +223,9 @@ fn server_error<E: std::fmt::Display>(e: E) -> ServerFnError {
 
 #[server]
 pub async fn register(username: String, password: String) -> Result<(), ServerFnError> {
+    if demo::enabled() {
+        return Err(server_error("Registration is disabled in demo mode"));
+    }
     db::User::create(&username, &password)
         .await
         .map_err(server_error)
@@ -60,7 +239,7 @@ pub async fn login(username: String, password: String) -> Result<AuthResponse, S
         Err(e) => return Err(server_error(e)),
     };
 
-    auth::create_tokens(user.id, user.username).map_err(server_error)
+    auth::create_tokens(user.id, user.username, user.is_admin, false).map_err(server_error)
 }
 
 #[server]
@@ -70,9 +249,15 @@ pub async fn refresh_token(token: String) -> Result<AuthResponse, ServerFnError>
         Err(e) => return Err(server_error(e)),
     };
 
-    // In a real app, you might want to check if the user still exists or if the refresh token has been revoked
+    if claims.is_demo {
+        return auth::create_tokens(claims.sub, claims.username, false, true).map_err(server_error);
+    }
+
+    // Reread the user so deleted users are signed out and admin rights
+    // granted or revoked since the last login apply.
+    let user = db::User::get(&claims.sub).await.map_err(server_error)?;
 
-    auth::create_tokens(claims.sub, claims.username).map_err(server_error)
+    auth::create_tokens(user.id, user.username, user.is_admin, false).map_err(server_error)
 }
 
 #[server]
@@ -93,7 +278,7 @@ pub async fn create_user_folder(
     name: String,
     path: String,
 ) -> Result<db::Folder, ServerFnError> {
-    let claims = match auth::verify_token(&token, "access") {
+    let claims = match auth::verify_writable(&token) {
         Ok(c) => c,
         Err(e) => return Err(server_error(e)),
     };
@@ -102,28 +287,135 @@ pub async fn create_user_folder(
         return Err(server_error(format!("Failed to create directory: {}", e)));
     }
 
-    db::Folder::create(&claims.sub, &name, &path)
+    let folder = db::Folder::create(&claims.sub, &name, &path)
         .await
-        .map_err(server_error)
+        .map_err(server_error)?;
+
+    if let Err(e) = shares::sync_user_shares(&claims.sub).await {
+        tracing::warn!("Failed to share new folder: {}", e);
+    }
+    Ok(folder)
 }
 
+/// What past downloads tell of each uploader, by username.
 #[cfg(feature = "server")]
+struct PeerHistory {
+    throughput: std::collections::HashMap<String, f64>,
+    reliability: std::collections::HashMap<String, f64>,
+}
+
+#[cfg(feature = "server")]
+impl PeerHistory {
+    async fn load() -> Self {
+        PeerHistory {
+            throughput: db::JobFile::peer_throughput().await.unwrap_or_default(),
+            reliability: db::JobFile::peer_reliability().await.unwrap_or_default(),
+        }
+    }
+
+    /// Sets how long each album would take to download from its uploader, and
+    /// how often the uploader's files arrived before.
+    fn annotate(&self, albums: &mut [AlbumResult]) {
+        for album in albums.iter_mut() {
+            let measured_speed = self.throughput.get(&album.username).copied();
+            album.eta_seconds = Some(album.estimate_eta(measured_speed));
+            album.uploader_reliability = self.reliability.get(&album.username).copied();
+        }
+    }
+}
+
+/// Searches slskd with one of the user's quality profiles, dropping files
+/// below their bitrate cutoffs and those `refinements` leave out. With a
+/// `search_id`, the albums ranked so far are reported to
+/// [`searches::report_partial`] while the search runs. `query` replaces the
+/// "artist album" text sent to Soulseek. Albums are ranked by the profile's
+/// strategy, adjusted by the user's scoring config and then by the one of
+/// `refinements`. With the `release_id` of the edition the user picked, files
+/// naming another edition rank lower, or are left out when `refinements` asks
+/// to.
+#[cfg(feature = "server")]
+#[allow(clippy::too_many_arguments)]
 async fn slskd_search(
+    user_id: &str,
     artist: String,
     album: String,
+    query: Option<String>,
     tracks: Vec<Track>,
-) -> Result<Vec<AlbumResult>, ServerFnError> {
-    let mut search = match SLSKD_CLIENT
-        .search(artist, album, tracks, Duration::seconds(45))
+    release_id: Option<&str>,
+    profile_id: Option<String>,
+    refinements: &SearchRefinements,
+    search_id: Option<String>,
+) -> Result<AlbumSearch, ServerFnError> {
+    let profile = resolve_profile(user_id, profile_id.as_deref())
         .await
-    {
+        .map_err(server_error)?;
+    let peers = PeerHistory::load().await;
+    let report_partial = |mut albums: Vec<AlbumResult>| {
+        if let Some(id) = &search_id {
+            peers.annotate(&mut albums);
+            searches::report_partial(id, albums);
+        }
+    };
+    let mut filters = profile.filters(bitrate_cutoffs(user_id).await);
+    filters.blocked_users = blocked_usernames(user_id).await;
+    refinements.apply(&mut filters);
+    let mut scoring_config = refinements.scoring.clone();
+    validate_scoring(&mut scoring_config).map_err(server_error)?;
+    let configured = scoring::Configured {
+        strategy: scoring::strategy_for(profile.scoring),
+        config: db::StoredScoringConfig::get(user_id)
+            .await
+            .unwrap_or_default()
+            .merged(&scoring_config),
+    };
+    let edition = match release_id {
+        Some(id) => match musicbrainz::release_version(id).await {
+            Ok(version) => Some(shared::musicbrainz::Edition {
+                id: id.to_string(),
+                title: version.title,
+                disambiguation: version.disambiguation,
+                date: version.date,
+                country: version.country,
+            }),
+            Err(e) => {
+                info!("Searching without an edition preference for {id}: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+    let preferred = edition
+        .as_ref()
+        .map(|edition| scoring::PreferredEdition::new(&configured, edition));
+    if let (Some(preferred), true) = (&preferred, refinements.exclude_other_editions) {
+        filters.excluded_terms = preferred.excluded_terms();
+    }
+    let strategy: &dyn ScoringStrategy = match &preferred {
+        Some(preferred) => preferred,
+        None => &configured,
+    };
+    let result = provider()
+        .search(SearchRequest {
+            artist,
+            album,
+            query,
+            tracks,
+            timeout: Duration::seconds(45),
+            strategy,
+            filters: &filters,
+            search_id: search_id.as_deref(),
+            on_partial: Some(&report_partial),
+        })
+        .await;
+    let mut search = match result {
         Ok(s) => s,
         Err(e) => return Err(server_error(e)),
     };
 
-    search.sort_by(|a, b| b.score.total_cmp(&a.score));
+    search.albums.sort_by(|a, b| b.score.total_cmp(&a.score));
+    peers.annotate(&mut search.albums);
 
-    for album in search.iter().take(10) {
+    for album in search.albums.iter().take(10) {
         info!("Album: {}", album.album_title);
         info!("Score: {}", album.score);
         info!("Quality: {}", album.dominant_quality);
@@ -133,63 +425,372 @@ async fn slskd_search(
             info!("  Title: {:?}", track.title);
             info!("  Artist: {:?}", track.artist);
             info!("  Album: {:?}", track.album);
-            info!("  Format: {:?}", track.base.quality());
+            info!("  Format: {}", track.base.format());
         }
     }
 
     Ok(search)
 }
 
-#[cfg(feature = "server")]
-async fn slskd_download(tracks: Vec<TrackResult>) -> Result<Vec<DownloadResponse>, ServerFnError> {
-    SLSKD_CLIENT.download(tracks).await.map_err(server_error)
+/// Per-dispatch choices made by the user when confirming a download.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DownloadOptions {
+    /// MusicBrainz release the tracks belong to, if known.
+    pub album_id: Option<String>,
+    /// MusicBrainz recording, when a single track is downloaded on its own.
+    pub recording_id: Option<String>,
+    /// Stage finished files for review instead of importing them right away.
+    pub review_before_import: bool,
+    /// Free-text note kept with the job, e.g. where the release came from.
+    pub note: Option<String>,
+    /// Labels to find the job by in the history, e.g. "vinyl rip".
+    pub labels: Vec<String>,
+    /// Other sources of the tracks, best first, downloaded instead of a file
+    /// that fails. See [`AlbumResult::alternate_sources`].
+    #[serde(default)]
+    pub alternates: Vec<TrackResult>,
+    /// Sources of tracks the chosen album lacks, best first, requested once
+    /// the job's own files are in. See [`AlbumResult::gap_sources`].
+    #[serde(default)]
+    pub gap_fills: Vec<TrackResult>,
+    /// The uploader's cover scans, downloaded with the tracks and embedded
+    /// once they are imported.
+    #[serde(default)]
+    pub artwork: Vec<TrackResult>,
+}
+
+/// A file of a [`DownloadPlan`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedFile {
+    pub filename: String,
+    pub size: i64,
+    pub bitrate: Option<i32>,
+    pub title: String,
 }
 
+/// The files one download request asks of an uploader.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedRequest {
+    pub username: String,
+    pub files: Vec<PlannedFile>,
+}
+
+/// What a download would request, as returned by [`plan_download`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadPlan {
+    pub target_folder: String,
+    /// One per uploader, after each track was moved to its fastest source.
+    pub requests: Vec<PlannedRequest>,
+    /// In bytes.
+    pub total_size: i64,
+    /// Tracks left out as copies of a file already requested.
+    pub duplicates: usize,
+    /// Sources kept to fall back on when a file fails.
+    pub alternates: usize,
+    pub review_before_import: bool,
+    /// Whether the job would start right away rather than wait in the queue.
+    pub starts_now: bool,
+}
+
+/// Results of a MusicBrainz search sent at once, unless the query asks for
+/// fewer.
+pub const SEARCH_PAGE_SIZE: u8 = 25;
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SearchQuery {
     pub artist: Option<String>,
     pub query: String,
+    /// Results to skip, to load the page after the ones already shown.
+    #[serde(default)]
+    pub offset: u16,
+    /// Results per page, [`SEARCH_PAGE_SIZE`] when unset. MusicBrainz sends
+    /// at most 100.
+    #[serde(default)]
+    pub limit: Option<u8>,
+}
+
+impl SearchQuery {
+    #[cfg(feature = "server")]
+    fn page_size(&self) -> u8 {
+        self.limit.unwrap_or(SEARCH_PAGE_SIZE).clamp(1, 100)
+    }
+
+    /// Key of the page in the MusicBrainz cache.
+    #[cfg(feature = "server")]
+    fn cache_key(&self, kind: &str) -> String {
+        format!(
+            "{kind} {:?} {} {}+{}",
+            self.artist,
+            self.query,
+            self.offset,
+            self.page_size()
+        )
+    }
 }
 
 #[server]
-pub async fn search_album(input: SearchQuery) -> Result<Vec<SearchResult>, ServerFnError> {
-    musicbrainz::search(
-        &input.artist,
-        &input.query,
-        musicbrainz::SearchType::Album,
-        25,
-    )
+pub async fn search_album(input: SearchQuery) -> Result<SearchPage, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let key = input.cache_key("album");
+    let search = musicbrainz_cache::cached(key.clone(), async {
+        musicbrainz::search(
+            &input.artist,
+            &input.query,
+            musicbrainz::SearchType::Album,
+            input.page_size(),
+            input.offset,
+        )
+        .await
+        .map_err(|e| e.to_string())
+    });
+    if demo::enabled() {
+        return demo::cached_search(key, search).await.map_err(server_error);
+    }
+    search.await.map_err(server_error)
+}
+
+#[server]
+pub async fn search_track(input: SearchQuery) -> Result<SearchPage, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let key = input.cache_key("track");
+    let search = musicbrainz_cache::cached(key.clone(), async {
+        musicbrainz::search(
+            &input.artist,
+            &input.query,
+            musicbrainz::SearchType::Track,
+            input.page_size(),
+            input.offset,
+        )
+        .await
+        .map_err(|e| e.to_string())
+    });
+    if demo::enabled() {
+        return demo::cached_search(key, search).await.map_err(server_error);
+    }
+    search.await.map_err(server_error)
+}
+
+/// Artists whose name matches the query, to browse their discography. The
+/// query's artist is not used.
+#[server]
+pub async fn search_artist(input: SearchQuery) -> Result<SearchPage, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let key = input.cache_key("artist");
+    let search = musicbrainz_cache::cached(key.clone(), async {
+        musicbrainz::search_artist(&input.query, input.page_size(), input.offset)
+            .await
+            .map_err(|e| e.to_string())
+    });
+    if demo::enabled() {
+        return demo::cached_search(key, search).await.map_err(server_error);
+    }
+    search.await.map_err(server_error)
+}
+
+/// Artists related to the one with MusicBrainz id `artist_id`, to suggest
+/// next to their albums.
+#[server]
+pub async fn get_similar_artists(artist_id: String) -> Result<Vec<Artist>, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if demo::enabled() {
+        demo::check_rate_limit().map_err(server_error)?;
+    }
+
+    let artist_id = artist_id.trim();
+    musicbrainz_cache::cached(format!("similar {artist_id}"), async {
+        musicbrainz::similar_artists(artist_id)
+            .await
+            .map_err(|e| e.to_string())
+    })
     .await
     .map_err(server_error)
 }
 
 #[server]
-pub async fn search_track(input: SearchQuery) -> Result<Vec<SearchResult>, ServerFnError> {
-    musicbrainz::search(
-        &input.artist,
-        &input.query,
-        musicbrainz::SearchType::Track,
-        25,
-    )
+pub async fn find_album(id: String) -> Result<AlbumWithTracks, ServerFnError> {
+    musicbrainz_cache::cached(format!("release {id}"), async {
+        musicbrainz::find_album(&id)
+            .await
+            .map_err(|e| e.to_string())
+    })
     .await
     .map_err(server_error)
 }
 
+/// The search id chosen by the client, or a new one. slskd only takes UUIDs.
+#[cfg(feature = "server")]
+fn search_id_or_new(search_id: Option<String>) -> String {
+    search_id
+        .filter(|id| uuid::Uuid::parse_str(id).is_ok())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Starts a search for an album's sources in the background.
 #[server]
-pub async fn find_album(id: String) -> Result<AlbumWithTracks, ServerFnError> {
-    musicbrainz::find_album(&id).await.map_err(server_error)
+pub async fn search_downloads(
+    token: String,
+    data: DownloadQuery,
+) -> Result<SearchTicket, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let search_id = search_id_or_new(data.search_id);
+    if claims.is_demo {
+        demo::check_rate_limit().map_err(server_error)?;
+        let results = demo::mock_results(&data.album.artist, &data.album.title, &data.tracks);
+        return Ok(SearchTicket {
+            search_id,
+            status: SearchStatus::Done(results),
+        });
+    }
+
+    let user_id = claims.sub.clone();
+    let id = search_id.clone();
+    Ok(searches::spawn(&claims.sub, search_id, async move {
+        slskd_search(
+            &user_id,
+            data.album.artist,
+            data.album.title,
+            data.query,
+            data.tracks,
+            data.edition_picked.then_some(data.album.id.as_str()),
+            data.profile_id,
+            &data.refinements,
+            Some(id),
+        )
+        .await
+        .map(SearchStatus::from)
+    }))
+}
+
+/// Starts a search for a single track, outside of any album, in the background.
+/// Soulseek is asked for "artist title", and files in a folder named after the
+/// track's album, when known, rank higher, without the others losing anything.
+#[server]
+pub async fn search_single_downloads(
+    token: String,
+    data: TrackDownloadQuery,
+) -> Result<SearchTicket, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let TrackDownloadQuery {
+        track,
+        profile_id,
+        search_id,
+        refinements,
+    } = data;
+    let search_id = search_id_or_new(search_id);
+    if claims.is_demo {
+        demo::check_rate_limit().map_err(server_error)?;
+        let results = demo::mock_results(&track.artist, &track.title, std::slice::from_ref(&track));
+        return Ok(SearchTicket {
+            search_id,
+            status: SearchStatus::Done(results),
+        });
+    }
+
+    let user_id = claims.sub.clone();
+    let id = search_id.clone();
+    Ok(searches::spawn(&claims.sub, search_id, async move {
+        let query = format!("{} {}", track.artist, track.title);
+        let album = track.album_title.clone().unwrap_or_default();
+        slskd_search(
+            &user_id,
+            track.artist.clone(),
+            album,
+            Some(query),
+            vec![track],
+            None,
+            profile_id,
+            &refinements,
+            Some(id),
+        )
+        .await
+        .map(SearchStatus::from)
+    }))
 }
 
+/// Everything an uploader shares, to look for more from a good source.
 #[server]
-pub async fn search_downloads(data: DownloadQuery) -> Result<Vec<AlbumResult>, ServerFnError> {
-    slskd_search(data.album.artist, data.album.title, data.tracks).await
+pub async fn browse_user_shares(
+    token: String,
+    username: String,
+) -> Result<Vec<SharedDirectory>, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+    if claims.is_demo {
+        return Err(server_error("Not available in the demo"));
+    }
+
+    soulseek()
+        .browse_user(&username)
+        .await
+        .map_err(server_error)
+}
+
+/// What [`download`] would request for these tracks, from which users,
+/// without queueing anything. For automation to show or log the plan first.
+#[server]
+pub async fn plan_download(
+    token: String,
+    tracks: Vec<TrackResult>,
+    target_folder: String,
+    options: DownloadOptions,
+) -> Result<DownloadPlan, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_token(&token, "access") {
+        return Err(server_error(e));
+    }
+
+    dispatch::plan(tracks, &target_folder, &options)
+        .await
+        .map_err(server_error)
 }
 
 #[server]
 pub async fn download(
+    token: String,
     tracks: Vec<TrackResult>,
     target_folder: String,
-) -> Result<Vec<DownloadResponse>, ServerFnError> {
+    options: DownloadOptions,
+) -> Result<db::Job, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut options = options;
+    options.note = options
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    options.labels = options
+        .labels
+        .iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+        .collect();
+
     let target_path_buf = std::path::Path::new(&target_folder).to_path_buf();
     if let Err(e) = tokio::fs::create_dir_all(&target_path_buf).await {
         return Err(server_error(format!(
@@ -198,122 +799,7 @@ pub async fn download(
         )));
     }
 
-    let res = slskd_download(tracks).await?;
-    let download_filenames: Vec<String> = res.iter().map(|d| d.filename.clone()).collect();
-    let target_path = target_path_buf;
-
-    tracing::info!("Started monitoring downloads: {:?}", download_filenames);
-
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
-        let mut attempts = 0;
-        const MAX_ATTEMPTS: usize = 600; // ~20 minutes timeout
-
-        loop {
-            interval.tick().await;
-            attempts += 1;
-
-            if attempts > MAX_ATTEMPTS {
-                info!(
-                    "Download monitoring timed out for batch {:?}",
-                    download_filenames
-                );
-                break;
-            }
-
-            match SLSKD_CLIENT.get_all_downloads().await {
-                Ok(downloads) => {
-                    let batch_status: Vec<_> = downloads
-                        .iter()
-                        .filter(|file| download_filenames.contains(&file.filename))
-                        .collect();
-
-                    // If we can't find any of our downloads, they might have been cleared or invalid
-                    if batch_status.is_empty() {
-                        info!("No active downloads found for batch, assuming completed or lost.");
-                        break;
-                    }
-
-                    let all_finished = batch_status.iter().all(|d| {
-                        d.state.iter().any(|s| {
-                            matches!(
-                                s,
-                                DownloadState::Succeeded
-                                    | DownloadState::Completed
-                                    | DownloadState::Aborted
-                                    | DownloadState::Cancelled
-                                    | DownloadState::Errored
-                            )
-                        })
-                    });
-
-                    if all_finished {
-                        let successful_downloads: Vec<_> = batch_status
-                            .iter()
-                            .filter(|d| {
-                                d.state.iter().any(|s| {
-                                    matches!(s, DownloadState::Succeeded | DownloadState::Completed)
-                                })
-                            })
-                            .collect();
-
-                        if !successful_downloads.is_empty() {
-                            info!(
-                                "Downloads completed ({} successful). Starting import to {:?}",
-                                successful_downloads.len(),
-                                target_path
-                            );
-
-                            let download_path_base = std::env::var("SLSKD_DOWNLOAD_PATH")
-                                .unwrap_or_else(|_| "/downloads".to_string());
-                            let download_path_buf = std::path::PathBuf::from(&download_path_base);
-
-                            let paths: Vec<String> = successful_downloads
-                                .iter()
-                                .filter_map(|d| {
-                                    // Normalize path separators (win -> linux)
-                                    let filename_str = d.filename.replace('\\', "/");
-                                    let path = std::path::Path::new(&filename_str);
-                                    let components: Vec<_> = path.components().collect();
-
-                                    // Keep only the last directory and filename (d1/d2/d3/file -> d3/file)
-                                    if components.len() >= 2 {
-                                        let len = components.len();
-                                        let last_dir = components[len - 2].as_os_str();
-                                        let file_name = components[len - 1].as_os_str();
-
-                                        let relative_path =
-                                            std::path::PathBuf::from(last_dir).join(file_name);
-                                        let full_path = download_path_buf.join(relative_path);
-
-                                        Some(full_path.to_string_lossy().to_string())
-                                    } else {
-                                        // Fallback
-                                        let full_path = download_path_buf.join(path);
-                                        Some(full_path.to_string_lossy().to_string())
-                                    }
-                                })
-                                .collect::<std::collections::HashSet<_>>()
-                                .into_iter()
-                                .collect();
-
-                            tracing::info!("Importing paths: {:?}", paths);
-
-                            if let Err(e) = beets::import(paths, &target_path).await {
-                                info!("Beets import error: {}", e);
-                            }
-                        } else {
-                            info!("Downloads finished but none succeeded. Skipping import.");
-                        }
-                        break;
-                    }
-                }
-                Err(e) => {
-                    info!("Error fetching download status: {}", e);
-                }
-            }
-        }
-    });
-
-    Ok(res)
+    dispatch::dispatch(&claims.sub, tracks, &target_folder, &options)
+        .await
+        .map_err(server_error)
 }
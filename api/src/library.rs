@@ -0,0 +1,191 @@
+//! Index of the music already in users' library folders.
+//!
+//! `library_items` mirrors what beets knows about every library folder, so
+//! checks like "is this track already owned" are a database lookup rather than
+//! a `beet ls` run. Each import indexes its files right away, and the
+//! `library_index` task reconciles the folders with beets to catch changes made
+//! outside Soulbeet.
+
+use dioxus::prelude::*;
+
+use crate::db::LibraryEntry;
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{Folder, Job},
+    server_error,
+};
+#[cfg(feature = "server")]
+use soulbeet::beets::{self, Library, LibraryItem};
+#[cfg(feature = "server")]
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+/// Where the beets libraries of users or folders are kept, under
+/// `BEETS_LIBRARY_SCOPE`.
+#[cfg(feature = "server")]
+fn libraries_dir() -> PathBuf {
+    PathBuf::from(
+        std::env::var("BEETS_LIBRARIES_PATH").unwrap_or_else(|_| "beets-libraries".to_string()),
+    )
+}
+
+/// The beets library holding a user's folder. `BEETS_LIBRARY_SCOPE` picks
+/// one library for everyone (`shared`, the default), one per user (`user`)
+/// or one per library folder (`folder`), so users do not see each other's
+/// music as duplicates.
+#[cfg(feature = "server")]
+pub(crate) fn beets_library(user_id: &str, folder: Option<&Folder>) -> Library {
+    let scope = std::env::var("BEETS_LIBRARY_SCOPE").unwrap_or_default();
+    match (scope.as_str(), folder) {
+        ("folder", Some(folder)) => Library::at(
+            libraries_dir()
+                .join("folders")
+                .join(format!("{}.db", folder.id)),
+        ),
+        ("user" | "folder", _) => {
+            Library::at(libraries_dir().join("users").join(format!("{user_id}.db")))
+        }
+        _ => Library::shared(),
+    }
+}
+
+/// The beets library a job imports into.
+#[cfg(feature = "server")]
+pub(crate) async fn job_library(job: &Job) -> Library {
+    let folder = match Folder::get_by_path(&job.user_id, &job.target_folder).await {
+        Ok(folder) => folder,
+        Err(e) => {
+            tracing::warn!("Failed to read folder {}: {}", job.target_folder, e);
+            None
+        }
+    };
+    beets_library(&job.user_id, folder.as_ref())
+}
+
+/// Flexible beets field recording which job imported an item. Imports are
+/// singletons, so `mb_albumid` is not set and cannot find a job's files.
+#[cfg(feature = "server")]
+pub(crate) const JOB_FIELD: &str = "soulbeet_job";
+
+/// Beets query for the files a job imported.
+#[cfg(feature = "server")]
+pub(crate) fn job_query(job: &Job) -> Vec<String> {
+    vec![
+        format!("{JOB_FIELD}:{}", job.id),
+        format!("path:{}", job.target_folder),
+    ]
+}
+
+#[cfg(feature = "server")]
+fn to_entry(folder: &str, item: LibraryItem, indexed_at: &str) -> LibraryEntry {
+    LibraryEntry {
+        path: item.path.to_string_lossy().to_string(),
+        folder: folder.to_string(),
+        artist: item.artist,
+        album: item.album,
+        title: item.title,
+        track: item.track.map(i64::from),
+        format: item.format,
+        bitrate: item.bitrate.map(i64::from),
+        mb_albumid: item.mb_albumid,
+        mb_trackid: item.mb_trackid,
+        mtime: item.mtime,
+        indexed_at: indexed_at.to_string(),
+    }
+}
+
+/// Adds the files of a freshly imported job to the index.
+#[cfg(feature = "server")]
+pub async fn index_job(job: &Job) -> Result<usize, String> {
+    let items = beets::list_items(&job_library(job).await, &job_query(job))
+        .await
+        .map_err(|e| e.to_string())?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let count = items.len();
+    for item in items {
+        LibraryEntry::upsert(&to_entry(&job.target_folder, item, &now)).await?;
+    }
+    Ok(count)
+}
+
+/// Brings the index of every library folder in line with beets, touching only
+/// the files that were added, changed or removed since the last scan.
+#[cfg(feature = "server")]
+pub async fn reconcile() -> Result<String, String> {
+    // A path shared by several users is scanned once, in the first one's library.
+    let mut seen = HashSet::new();
+    let folders: Vec<(String, Library)> = Folder::get_all()
+        .await?
+        .into_iter()
+        .filter(|folder| seen.insert(folder.path.clone()))
+        .map(|folder| {
+            let library = beets_library(&folder.user_id, Some(&folder));
+            (folder.path, library)
+        })
+        .collect();
+    let now = chrono::Utc::now().to_rfc3339();
+    let (mut added, mut updated, mut removed) = (0, 0, 0);
+
+    for (folder, library) in &folders {
+        let items = beets::list_items(library, &[format!("path:{folder}")])
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut indexed: HashMap<String, String> = LibraryEntry::get_by_folder(folder)
+            .await?
+            .into_iter()
+            .map(|entry| (entry.path, entry.mtime))
+            .collect();
+
+        for item in items {
+            let entry = to_entry(folder, item, &now);
+            match indexed.remove(&entry.path) {
+                Some(mtime) if mtime == entry.mtime => continue,
+                Some(_) => updated += 1,
+                None => added += 1,
+            }
+            LibraryEntry::upsert(&entry).await?;
+        }
+        // Whatever beets no longer lists was deleted or moved away.
+        for path in indexed.into_keys() {
+            LibraryEntry::delete(&path).await?;
+            removed += 1;
+        }
+    }
+
+    Ok(format!(
+        "Scanned {} folders: {added} added, {updated} updated, {removed} removed",
+        folders.len()
+    ))
+}
+
+/// Every indexed file in the user's folders, for the library browser.
+#[server]
+pub async fn get_library(token: String) -> Result<Vec<LibraryEntry>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    LibraryEntry::get_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+/// Which of the given MusicBrainz recordings are already in the user's library.
+#[server]
+pub async fn get_owned_recordings(
+    token: String,
+    recording_ids: Vec<String>,
+) -> Result<Vec<String>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    LibraryEntry::owned_recordings(&claims.sub, &recording_ids)
+        .await
+        .map_err(server_error)
+}
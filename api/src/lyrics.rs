@@ -0,0 +1,85 @@
+//! Lyrics for imported tracks, from LRCLIB.
+//!
+//! Each library folder chooses whether lyrics are saved, as `.lrc` sidecar
+//! files next to the tracks or in the files' tags. Synced lyrics are preferred,
+//! plain ones are used when that is all LRCLIB has.
+
+use dioxus::prelude::*;
+
+use crate::db::LyricsMode;
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{Folder, Job},
+    library, server_error,
+};
+#[cfg(feature = "server")]
+use soulbeet::{beets, lrclib};
+
+/// Fetches and saves lyrics for the files `job` imported, as its folder asks.
+/// Returns how many tracks got lyrics.
+#[cfg(feature = "server")]
+pub async fn save_for_job(job: &Job) -> Result<usize, String> {
+    let folder = Folder::get_by_path(&job.user_id, &job.target_folder).await?;
+    let mode = folder
+        .as_ref()
+        .map(|folder| folder.lyrics)
+        .unwrap_or_default();
+    if mode == LyricsMode::Off {
+        return Ok(0);
+    }
+
+    let library = library::beets_library(&job.user_id, folder.as_ref());
+    let items = beets::list_items(&library, &library::job_query(job))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut saved = 0;
+    for item in items {
+        let lyrics = match lrclib::find_lyrics(&item.artist, &item.title, &item.album).await {
+            Ok(Some(lyrics)) => lyrics,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!("Failed to fetch lyrics for {}: {}", item.title, e);
+                continue;
+            }
+        };
+        let Some(text) = lyrics.best() else {
+            continue;
+        };
+
+        let result = match mode {
+            LyricsMode::Off => Ok(()),
+            LyricsMode::Sidecar => tokio::fs::write(item.path.with_extension("lrc"), text)
+                .await
+                .map_err(|e| e.to_string()),
+            LyricsMode::Tags => beets::modify(
+                &library,
+                &[format!("path:{}", item.path.display())],
+                &[format!("lyrics={text}")],
+            )
+            .await
+            .map_err(|e| e.to_string()),
+        };
+        match result {
+            Ok(()) => saved += 1,
+            Err(e) => tracing::warn!("Failed to save lyrics of {}: {}", item.title, e),
+        }
+    }
+    Ok(saved)
+}
+
+#[server]
+pub async fn set_folder_lyrics(
+    token: String,
+    folder_id: String,
+    mode: LyricsMode,
+) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    Folder::set_lyrics(&claims.sub, &folder_id, mode)
+        .await
+        .map_err(server_error)
+}
@@ -0,0 +1,132 @@
+//! Media served to the browser: custom covers, staged audio files and the
+//! files of users' libraries.
+//!
+//! `<img>` and `<audio>` tags cannot send the access token, so these endpoints
+//! are reached through short-lived signed URLs handed out by the server
+//! functions below, and checked by [`auth::require_signed_url`].
+
+use dioxus::fullstack::FileStream;
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use crate::{auth, db, server_error};
+
+#[get("/api/media/covers/{user_id}/{album_id}")]
+#[middleware(dioxus::fullstack::axum::middleware::from_fn(auth::require_signed_url))]
+pub async fn cover_image(user_id: String, album_id: String) -> Result<FileStream, ServerFnError> {
+    let cover = db::AlbumCover::get(&user_id, &album_id)
+        .await
+        .map_err(server_error)?
+        .ok_or_else(|| server_error("Cover not found"))?;
+
+    FileStream::from_path(&cover.path)
+        .await
+        .map_err(server_error)
+}
+
+#[get("/api/media/files/{file_id}")]
+#[middleware(dioxus::fullstack::axum::middleware::from_fn(auth::require_signed_url))]
+pub async fn job_file_audio(file_id: String) -> Result<FileStream, ServerFnError> {
+    let file = db::JobFile::get(&file_id).await.map_err(server_error)?;
+    let path = file
+        .local_path
+        .ok_or_else(|| server_error("File was not downloaded"))?;
+
+    FileStream::from_path(&path).await.map_err(server_error)
+}
+
+/// Library paths go in URLs hex encoded, so slashes and other characters of
+/// file names do not get in the way of routing.
+#[cfg(feature = "server")]
+fn hex_encode(text: &str) -> String {
+    text.bytes().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(feature = "server")]
+fn hex_decode(hex: &str) -> Option<String> {
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[get("/api/media/library/{user_id}/{path}")]
+#[middleware(dioxus::fullstack::axum::middleware::from_fn(auth::require_signed_url))]
+pub async fn library_file_audio(
+    user_id: String,
+    path: String,
+) -> Result<FileStream, ServerFnError> {
+    let path = hex_decode(&path).ok_or_else(|| server_error("Invalid path"))?;
+    let entry = db::LibraryEntry::get_for_user(&user_id, &path)
+        .await
+        .map_err(server_error)?
+        .ok_or_else(|| server_error("File not found"))?;
+
+    FileStream::from_path(&entry.path)
+        .await
+        .map_err(server_error)
+}
+
+/// Signed URL of the user's custom cover for an album, if they uploaded one.
+#[server]
+pub async fn album_cover_url(
+    token: String,
+    album_id: String,
+) -> Result<Option<String>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if db::AlbumCover::get(&claims.sub, &album_id)
+        .await
+        .map_err(server_error)?
+        .is_none()
+    {
+        return Ok(None);
+    }
+
+    let path = format!("/api/media/covers/{}/{}", claims.sub, album_id);
+    auth::sign_media_url(&claims.sub, &path)
+        .map(Some)
+        .map_err(server_error)
+}
+
+/// Signed URL to play a downloaded file of one of the user's jobs.
+#[server]
+pub async fn job_file_url(token: String, file_id: String) -> Result<String, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let file = db::JobFile::get(&file_id).await.map_err(server_error)?;
+    let job = db::Job::get(&file.job_id).await.map_err(server_error)?;
+    if job.user_id != claims.sub {
+        return Err(server_error("File not found"));
+    }
+
+    let path = format!("/api/media/files/{}", file.id);
+    auth::sign_media_url(&claims.sub, &path).map_err(server_error)
+}
+
+/// Signed URL to play a file of the user's library.
+#[server]
+pub async fn library_file_url(token: String, path: String) -> Result<String, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if db::LibraryEntry::get_for_user(&claims.sub, &path)
+        .await
+        .map_err(server_error)?
+        .is_none()
+    {
+        return Err(server_error("File not found"));
+    }
+
+    let path = format!("/api/media/library/{}/{}", claims.sub, hex_encode(&path));
+    auth::sign_media_url(&claims.sub, &path).map_err(server_error)
+}
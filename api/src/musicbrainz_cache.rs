@@ -0,0 +1,57 @@
+//! Cache of MusicBrainz responses in the database.
+//!
+//! MusicBrainz serves one request per second, so searching the same artist
+//! again or reopening an album would otherwise wait in line behind every other
+//! lookup. Responses are kept for `MUSICBRAINZ_CACHE_HOURS` (24 by default,
+//! 0 turns the cache off).
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::db::MusicBrainzCache;
+
+const DEFAULT_CACHE_HOURS: i64 = 24;
+
+fn cache_hours() -> i64 {
+    std::env::var("MUSICBRAINZ_CACHE_HOURS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CACHE_HOURS)
+}
+
+/// The response cached under `key`, or the one `fetch` gets, which is then
+/// cached. Failing to read or write the cache only costs the lookup.
+pub(crate) async fn cached<T, F>(key: String, fetch: F) -> Result<T, String>
+where
+    T: Serialize + DeserializeOwned,
+    F: std::future::Future<Output = Result<T, String>>,
+{
+    let hours = cache_hours();
+    if hours <= 0 {
+        return fetch.await;
+    }
+    let key = key.to_lowercase();
+    let since = chrono::Utc::now() - chrono::Duration::hours(hours);
+
+    match MusicBrainzCache::get(&key, since).await {
+        Ok(Some(response)) => match serde_json::from_str(&response) {
+            Ok(response) => return Ok(response),
+            Err(e) => tracing::warn!("Ignoring unreadable MusicBrainz cache entry {key}: {e}"),
+        },
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to read the MusicBrainz cache: {e}"),
+    }
+
+    let response = fetch.await?;
+    match serde_json::to_string(&response) {
+        Ok(json) => {
+            if let Err(e) = MusicBrainzCache::put(&key, &json).await {
+                tracing::warn!("Failed to cache MusicBrainz response {key}: {e}");
+            }
+        }
+        Err(e) => tracing::warn!("Failed to serialize MusicBrainz response {key}: {e}"),
+    }
+    if let Err(e) = MusicBrainzCache::prune(since).await {
+        tracing::warn!("Failed to prune the MusicBrainz cache: {e}");
+    }
+    Ok(response)
+}
@@ -0,0 +1,392 @@
+//! Job notifications sent to the user's webhooks and ntfy topics.
+//!
+//! Finished jobs are gathered per sink for its digest window, starting with
+//! the first one, then sent as a single digest, so a 15-album batch makes one
+//! notification rather than 15. A job failing again after a retry is only
+//! notified the first time.
+
+use dioxus::prelude::*;
+
+use crate::db::NotificationSink;
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{Job, JobState, NotificationKind, User},
+    events, server_error,
+};
+#[cfg(feature = "server")]
+use serde::Serialize;
+#[cfg(feature = "server")]
+use std::{
+    collections::HashMap,
+    net::{IpAddr, ToSocketAddrs},
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+#[cfg(feature = "server")]
+use tokio::sync::broadcast::error::RecvError;
+
+/// Longest digest window allowed, in seconds.
+#[cfg(feature = "server")]
+const MAX_DIGEST_WINDOW: i64 = 24 * 60 * 60;
+
+/// One job event waiting in a digest.
+#[cfg(feature = "server")]
+#[derive(Clone, Debug, Serialize)]
+struct Notice {
+    job_id: String,
+    state: JobState,
+    /// What the job downloaded, e.g. "Artist - Album".
+    label: String,
+}
+
+/// Notices gathered for each sink, by sink id, until its window ends.
+#[cfg(feature = "server")]
+static PENDING: LazyLock<Mutex<HashMap<String, Vec<Notice>>>> = LazyLock::new(Default::default);
+
+/// How long a sink may take to answer.
+#[cfg(feature = "server")]
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Redirects are not followed, they could lead anywhere the URL was not
+/// allowed to.
+#[cfg(feature = "server")]
+static HTTP: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(SEND_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("Failed to build the notification client")
+});
+
+/// Whether `ip` is reachable from the internet, rather than this machine or
+/// its local network.
+#[cfg(feature = "server")]
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // Carrier-grade NAT, 100.64.0.0/10.
+                || (ip.octets()[0] == 100 && ip.octets()[1] & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    // Unique local, fc00::/7, and link-local, fe80::/10.
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Fails unless every address the host of `url` resolves to is public, so
+/// that sinks cannot be pointed at services only the server can reach.
+#[cfg(feature = "server")]
+async fn check_public_url(url: &str) -> Result<(), String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {e}"))?;
+    let host = url.host_str().ok_or("The URL has no host")?.to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addresses = tokio::task::spawn_blocking(move || {
+        // IPv6 literals come bracketed.
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        (host, port)
+            .to_socket_addrs()
+            .map(|addresses| addresses.collect::<Vec<_>>())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Failed to resolve the URL's host: {e}"))?;
+    if addresses.is_empty() || !addresses.iter().all(|a| is_public(a.ip())) {
+        return Err("Only admins can send notifications to local addresses".to_string());
+    }
+    Ok(())
+}
+
+/// Checks the sink's URL again before sending, its host may resolve
+/// elsewhere since it was saved. Admins' sinks may be local.
+#[cfg(feature = "server")]
+async fn check_sink_url(sink: &NotificationSink) -> Result<(), String> {
+    if User::get(&sink.user_id).await?.is_admin {
+        return Ok(());
+    }
+    check_public_url(&sink.url).await
+}
+
+#[cfg(feature = "server")]
+fn state_label(state: &JobState) -> &'static str {
+    match state {
+        JobState::Imported => "imported",
+        JobState::Failed => "failed",
+        JobState::ImportTimedOut => "import timed out",
+        JobState::AwaitingReview => "awaiting review",
+        _ => "updated",
+    }
+}
+
+#[cfg(feature = "server")]
+fn wants(sink: &NotificationSink, state: &JobState) -> bool {
+    match state {
+        JobState::Imported => sink.notify_imported,
+        JobState::Failed | JobState::ImportTimedOut => sink.notify_failed,
+        JobState::AwaitingReview => sink.notify_review,
+        _ => false,
+    }
+}
+
+/// "Artist - Album" from the job's first file, its folder standing for the album.
+#[cfg(feature = "server")]
+async fn job_label(job_id: &str) -> String {
+    let Ok(job) = Job::get(job_id).await else {
+        return "A download".to_string();
+    };
+    let files = job.files().await.unwrap_or_default();
+    let Some(file) = files.iter().find(|f| !f.artwork) else {
+        return "A download".to_string();
+    };
+    let mut parts = file.filename.rsplit(['\\', '/']);
+    let name = parts.next().unwrap_or_default();
+    let album = if job.recording_id.is_some() {
+        file.title.as_deref().unwrap_or(name)
+    } else {
+        parts.next().unwrap_or(name)
+    };
+    match &file.artist {
+        Some(artist) => format!("{artist} - {album}"),
+        None => album.to_string(),
+    }
+}
+
+/// Title and text of a notification for `notices`.
+#[cfg(feature = "server")]
+fn compose(notices: &[Notice]) -> (String, String) {
+    if let [notice] = notices {
+        return (
+            format!("Download {}", state_label(&notice.state)),
+            notice.label.clone(),
+        );
+    }
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for notice in notices {
+        let label = state_label(&notice.state);
+        match counts.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((label, 1)),
+        }
+    }
+    let summary = counts
+        .iter()
+        .map(|(label, count)| format!("{count} {label}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let lines = notices
+        .iter()
+        .map(|n| format!("{} ({})", n.label, state_label(&n.state)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (
+        format!("{} downloads finished: {summary}", notices.len()),
+        lines,
+    )
+}
+
+#[cfg(feature = "server")]
+async fn send(sink: &NotificationSink, notices: &[Notice]) -> Result<(), String> {
+    check_sink_url(sink).await?;
+    let (title, message) = compose(notices);
+    let request = match sink.kind {
+        NotificationKind::Webhook => HTTP.post(&sink.url).json(&serde_json::json!({
+            "title": title,
+            "message": message,
+            "events": notices,
+        })),
+        NotificationKind::Ntfy => HTTP
+            .post(&sink.url)
+            .header("Title", format!("Soulbeet: {title}"))
+            .body(message),
+    };
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{} answered {}", sink.url, response.status()));
+    }
+    Ok(())
+}
+
+/// Sends what gathered for the sink during its window.
+#[cfg(feature = "server")]
+async fn flush(sink_id: String) {
+    let Some(notices) = PENDING.lock().unwrap().remove(&sink_id) else {
+        return;
+    };
+    // Read again, the sink may have been edited or deleted meanwhile.
+    let sink = match NotificationSink::get(&sink_id).await {
+        Ok(Some(sink)) => sink,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!("Failed to read notification sink {}: {}", sink_id, e);
+            return;
+        }
+    };
+    if let Err(e) = send(&sink, &notices).await {
+        tracing::warn!("Failed to send notification to {}: {}", sink.url, e);
+    }
+}
+
+/// Adds a notice to the sink's digest, starting its window if it is the first.
+#[cfg(feature = "server")]
+fn enqueue(sink: &NotificationSink, notice: Notice) {
+    let first = {
+        let mut pending = PENDING.lock().unwrap();
+        let notices = pending.entry(sink.id.clone()).or_default();
+        notices.push(notice);
+        notices.len() == 1
+    };
+    if first {
+        let window = Duration::from_secs(sink.digest_window.clamp(0, MAX_DIGEST_WINDOW) as u64);
+        let sink_id = sink.id.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            flush(sink_id).await;
+        });
+    }
+}
+
+#[cfg(feature = "server")]
+async fn handle(user_id: &str, job_id: &str, state: JobState) {
+    let sinks = match NotificationSink::get_all_by_user(user_id).await {
+        Ok(sinks) => sinks,
+        Err(e) => {
+            tracing::warn!("Failed to read notification sinks: {}", e);
+            return;
+        }
+    };
+    let sinks: Vec<_> = sinks.into_iter().filter(|s| wants(s, &state)).collect();
+    if sinks.is_empty() {
+        return;
+    }
+    if matches!(state, JobState::Failed | JobState::ImportTimedOut)
+        && !NotificationSink::mark_failure_notified(job_id)
+            .await
+            .unwrap_or(true)
+    {
+        return;
+    }
+
+    let notice = Notice {
+        job_id: job_id.to_string(),
+        state,
+        label: job_label(job_id).await,
+    };
+    for sink in &sinks {
+        enqueue(sink, notice.clone());
+    }
+}
+
+/// Follows job events and notifies users, until the server stops.
+#[cfg(feature = "server")]
+pub async fn run() {
+    let mut receiver = events::subscribe();
+    loop {
+        match receiver.recv().await {
+            Ok((user_id, event)) => handle(&user_id, &event.job_id, event.state).await,
+            Err(RecvError::Lagged(missed)) => {
+                tracing::warn!("Notifications missed {} job events", missed)
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn validate(sink: &mut NotificationSink) -> Result<(), ServerFnError> {
+    sink.url = sink.url.trim().to_string();
+    if !(sink.url.starts_with("http://") || sink.url.starts_with("https://")) {
+        return Err(server_error("The URL must start with http:// or https://"));
+    }
+    if !(0..=MAX_DIGEST_WINDOW).contains(&sink.digest_window) {
+        return Err(server_error(
+            "The digest window must be between 0 seconds and a day",
+        ));
+    }
+    Ok(())
+}
+
+#[server]
+pub async fn get_notification_sinks(token: String) -> Result<Vec<NotificationSink>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    NotificationSink::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+/// Creates one of the user's notification sinks, or updates it when its id is
+/// set. Only admins may point one at a local address.
+#[server]
+pub async fn save_notification_sink(
+    token: String,
+    sink: NotificationSink,
+) -> Result<NotificationSink, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut sink = sink;
+    validate(&mut sink)?;
+    if !claims.is_admin {
+        check_public_url(&sink.url).await.map_err(server_error)?;
+    }
+    NotificationSink::save(&claims.sub, &sink)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn delete_notification_sink(token: String, id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    NotificationSink::delete(&claims.sub, &id)
+        .await
+        .map_err(server_error)
+}
+
+/// Sends a test notification to the sink right away. Admins only, and what
+/// the URL answered is only logged, so it cannot be used to probe the network.
+#[server]
+pub async fn test_notification_sink(
+    token: String,
+    sink: NotificationSink,
+) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_admin(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut sink = sink;
+    sink.user_id = claims.sub;
+    validate(&mut sink)?;
+    let notice = Notice {
+        job_id: String::new(),
+        state: JobState::Imported,
+        label: "Soulbeet test notification".to_string(),
+    };
+    send(&sink, &[notice]).await.map_err(|e| {
+        tracing::warn!("Test notification to {} failed: {}", sink.url, e);
+        server_error("The notification could not be delivered, see the server logs")
+    })
+}
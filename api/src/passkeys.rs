@@ -0,0 +1,280 @@
+//! Passkey (WebAuthn) sign-in, as an alternative to passwords.
+//!
+//! Registering or signing in takes two calls: the first returns the options to
+//! hand to the browser's WebAuthn API along with a challenge id, the second
+//! checks the browser's answer to that challenge. Challenges are kept in
+//! memory for a few minutes. Passkeys need `WEBAUTHN_ORIGIN`, the URL users
+//! open Soulbeet at, since they are bound to its domain.
+//!
+//! Options and answers cross the server functions as JSON strings, in the
+//! shape the WebAuthn browser API uses, with binary fields base64url encoded.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthResponse;
+use crate::db::Passkey;
+
+#[cfg(feature = "server")]
+use crate::{auth, db::User, demo, server_error};
+#[cfg(feature = "server")]
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+#[cfg(feature = "server")]
+use webauthn_rs::prelude::{
+    CredentialID, Passkey as Credential, PasskeyAuthentication, PasskeyRegistration,
+    PublicKeyCredential, RegisterPublicKeyCredential, Url, Uuid, Webauthn, WebauthnBuilder,
+};
+
+/// Options for the browser's WebAuthn API, and the challenge to answer.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PasskeyChallenge {
+    pub challenge_id: String,
+    /// JSON for `navigator.credentials.create()` or `.get()`.
+    pub options: String,
+}
+
+/// Challenges are forgotten after this long.
+#[cfg(feature = "server")]
+const CHALLENGE_TTL: Duration = Duration::from_secs(5 * 60);
+
+#[cfg(feature = "server")]
+enum Ceremony {
+    Registration {
+        user_id: String,
+        state: PasskeyRegistration,
+    },
+    Authentication {
+        user_id: String,
+        state: PasskeyAuthentication,
+    },
+}
+
+#[cfg(feature = "server")]
+static CHALLENGES: LazyLock<Mutex<HashMap<String, (Instant, Ceremony)>>> =
+    LazyLock::new(Default::default);
+
+#[cfg(feature = "server")]
+static WEBAUTHN: LazyLock<Result<Webauthn, String>> = LazyLock::new(|| {
+    let origin = std::env::var("WEBAUTHN_ORIGIN")
+        .map_err(|_| "Passkeys need WEBAUTHN_ORIGIN to be set".to_string())?;
+    let origin = Url::parse(&origin).map_err(|e| format!("Invalid WEBAUTHN_ORIGIN: {e}"))?;
+    let rp_id = origin
+        .domain()
+        .ok_or("WEBAUTHN_ORIGIN must have a domain name")?
+        .to_string();
+    WebauthnBuilder::new(&rp_id, &origin)
+        .map_err(|e| e.to_string())?
+        .rp_name("Soulbeet")
+        .build()
+        .map_err(|e| e.to_string())
+});
+
+#[cfg(feature = "server")]
+fn webauthn() -> Result<&'static Webauthn, ServerFnError> {
+    WEBAUTHN.as_ref().map_err(server_error)
+}
+
+/// Keeps a ceremony's state until its answer comes back.
+#[cfg(feature = "server")]
+fn remember(ceremony: Ceremony) -> String {
+    let id = Uuid::new_v4().to_string();
+    let mut challenges = CHALLENGES.lock().unwrap();
+    challenges.retain(|_, (created, _)| created.elapsed() < CHALLENGE_TTL);
+    challenges.insert(id.clone(), (Instant::now(), ceremony));
+    id
+}
+
+#[cfg(feature = "server")]
+fn take(challenge_id: &str) -> Result<Ceremony, ServerFnError> {
+    match CHALLENGES.lock().unwrap().remove(challenge_id) {
+        Some((created, ceremony)) if created.elapsed() < CHALLENGE_TTL => Ok(ceremony),
+        _ => Err(server_error("Passkey challenge expired, try again")),
+    }
+}
+
+/// The credential ID as stored in the `passkeys` table.
+#[cfg(feature = "server")]
+fn credential_key(id: &CredentialID) -> Result<String, ServerFnError> {
+    match serde_json::to_value(id).map_err(server_error)? {
+        serde_json::Value::String(key) => Ok(key),
+        other => Ok(other.to_string()),
+    }
+}
+
+#[cfg(feature = "server")]
+fn parse_credential(passkey: &Passkey) -> Result<Credential, ServerFnError> {
+    serde_json::from_str(&passkey.credential).map_err(server_error)
+}
+
+/// Starts registering a passkey for the signed-in user.
+#[server]
+pub async fn start_passkey_registration(token: String) -> Result<PasskeyChallenge, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let webauthn = webauthn()?;
+    let user_uuid = Uuid::parse_str(&claims.sub).map_err(server_error)?;
+    let existing = Passkey::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)?
+        .iter()
+        .map(|p| parse_credential(p).map(|c| c.cred_id().clone()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (options, state) = webauthn
+        .start_passkey_registration(
+            user_uuid,
+            &claims.username,
+            &claims.username,
+            Some(existing),
+        )
+        .map_err(server_error)?;
+
+    Ok(PasskeyChallenge {
+        challenge_id: remember(Ceremony::Registration {
+            user_id: claims.sub,
+            state,
+        }),
+        options: serde_json::to_string(&options).map_err(server_error)?,
+    })
+}
+
+/// Saves the passkey the browser created for a registration challenge.
+#[server]
+pub async fn finish_passkey_registration(
+    token: String,
+    challenge_id: String,
+    name: String,
+    credential: String,
+) -> Result<Passkey, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(server_error("Passkey name is required"));
+    }
+    let Ceremony::Registration { user_id, state } = take(&challenge_id)? else {
+        return Err(server_error("Not a registration challenge"));
+    };
+    if user_id != claims.sub {
+        return Err(server_error("Passkey challenge expired, try again"));
+    }
+
+    let answer: RegisterPublicKeyCredential =
+        serde_json::from_str(&credential).map_err(server_error)?;
+    let passkey = webauthn()?
+        .finish_passkey_registration(&answer, &state)
+        .map_err(server_error)?;
+
+    Passkey::create(
+        &credential_key(passkey.cred_id())?,
+        &claims.sub,
+        name,
+        &serde_json::to_string(&passkey).map_err(server_error)?,
+    )
+    .await
+    .map_err(server_error)
+}
+
+/// Starts signing `username` in with one of their passkeys.
+#[server]
+pub async fn start_passkey_login(username: String) -> Result<PasskeyChallenge, ServerFnError> {
+    if demo::enabled() {
+        return Err(server_error("Passkeys are disabled in demo mode"));
+    }
+
+    let webauthn = webauthn()?;
+    // Same answer for unknown users and users without passkeys.
+    let no_passkey = || server_error("No passkey registered for this user");
+    let user = User::get_by_username(username.trim())
+        .await
+        .map_err(|_| no_passkey())?;
+    let credentials = Passkey::get_all_by_user(&user.id)
+        .await
+        .map_err(server_error)?
+        .iter()
+        .map(parse_credential)
+        .collect::<Result<Vec<_>, _>>()?;
+    if credentials.is_empty() {
+        return Err(no_passkey());
+    }
+
+    let (options, state) = webauthn
+        .start_passkey_authentication(&credentials)
+        .map_err(server_error)?;
+
+    Ok(PasskeyChallenge {
+        challenge_id: remember(Ceremony::Authentication {
+            user_id: user.id,
+            state,
+        }),
+        options: serde_json::to_string(&options).map_err(server_error)?,
+    })
+}
+
+/// Signs in with the browser's answer to a sign-in challenge.
+#[server]
+pub async fn finish_passkey_login(
+    challenge_id: String,
+    credential: String,
+) -> Result<AuthResponse, ServerFnError> {
+    let Ceremony::Authentication { user_id, state } = take(&challenge_id)? else {
+        return Err(server_error("Not a sign-in challenge"));
+    };
+
+    let answer: PublicKeyCredential = serde_json::from_str(&credential).map_err(server_error)?;
+    let result = webauthn()?
+        .finish_passkey_authentication(&answer, &state)
+        .map_err(|_| server_error("Passkey not recognized"))?;
+
+    let key = credential_key(result.cred_id())?;
+    let passkeys = Passkey::get_all_by_user(&user_id)
+        .await
+        .map_err(server_error)?;
+    // The passkey may have been removed since the challenge was issued.
+    let passkey = passkeys
+        .iter()
+        .find(|p| p.id == key)
+        .ok_or_else(|| server_error("Passkey not recognized"))?;
+    let mut stored = parse_credential(passkey)?;
+    stored.update_credential(&result);
+    let stored = serde_json::to_string(&stored).map_err(server_error)?;
+    if let Err(e) = Passkey::mark_used(&passkey.id, &stored).await {
+        tracing::warn!("Failed to update passkey {}: {}", passkey.id, e);
+    }
+
+    let user = User::get(&user_id).await.map_err(server_error)?;
+    auth::create_tokens(user.id, user.username, user.is_admin, false).map_err(server_error)
+}
+
+#[server]
+pub async fn get_passkeys(token: String) -> Result<Vec<Passkey>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    Passkey::get_all_by_user(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn delete_passkey(token: String, id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    Passkey::delete(&claims.sub, &id)
+        .await
+        .map_err(server_error)
+}
@@ -0,0 +1,189 @@
+//! Permissions and ownership of imported files, set per library folder so
+//! that the media server reading the library can open them, whatever user the
+//! server process runs as.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::FolderPermissions;
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{Folder, Job, User},
+    library, server_error,
+};
+#[cfg(feature = "server")]
+use soulbeet::beets;
+#[cfg(feature = "server")]
+use std::{
+    collections::HashSet,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+};
+
+/// Parses an octal mode, keeping only the permission bits: setuid, setgid
+/// and sticky are never set on imported files.
+#[cfg(feature = "server")]
+fn parse_mode(mode: &str) -> Result<u32, String> {
+    u32::from_str_radix(mode, 8)
+        .ok()
+        .filter(|mode| *mode <= 0o7777)
+        .map(|mode| mode & 0o777)
+        .ok_or_else(|| format!("{mode} is not an octal mode such as 644"))
+}
+
+/// Trims the settings and checks the modes parse and the ids fit a uid or gid.
+/// The ids are not looked up: the media server may run as a user only known
+/// to its own container.
+#[cfg(feature = "server")]
+fn validate(permissions: FolderPermissions) -> Result<FolderPermissions, String> {
+    let mode = |mode: Option<String>| -> Result<Option<String>, String> {
+        match mode.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(mode) => parse_mode(mode).map(|mode| Some(format!("{mode:o}"))),
+        }
+    };
+    let permissions = FolderPermissions {
+        file_mode: mode(permissions.file_mode)?,
+        dir_mode: mode(permissions.dir_mode)?,
+        ..permissions
+    };
+    let ids = [permissions.owner_uid, permissions.owner_gid];
+    if let Some(id) = ids
+        .into_iter()
+        .flatten()
+        .find(|id| u32::try_from(*id).is_err())
+    {
+        return Err(format!("{id} is not a valid uid or gid"));
+    }
+    Ok(permissions)
+}
+
+#[cfg(feature = "server")]
+fn apply(path: &Path, mode: Option<u32>, uid: Option<u32>, gid: Option<u32>) -> Result<(), String> {
+    if let Some(mode) = mode {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+            .map_err(|e| format!("chmod {}: {e}", path.display()))?;
+    }
+    if uid.is_some() || gid.is_some() {
+        std::os::unix::fs::chown(path, uid, gid)
+            .map_err(|e| format!("chown {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Sets the folder's permissions and ownership on the files `job` imported,
+/// with the covers and lyrics next to them, and on their album and artist
+/// folders. Returns how many files were changed.
+#[cfg(feature = "server")]
+pub async fn apply_for_job(job: &Job) -> Result<usize, String> {
+    let Some(folder) = Folder::get_by_path(&job.user_id, &job.target_folder).await? else {
+        return Ok(0);
+    };
+    let library = library::beets_library(&job.user_id, Some(&folder));
+    let permissions = folder.permissions;
+    if permissions == FolderPermissions::default() {
+        return Ok(0);
+    }
+    let file_mode = permissions
+        .file_mode
+        .as_deref()
+        .map(parse_mode)
+        .transpose()?;
+    let dir_mode = permissions
+        .dir_mode
+        .as_deref()
+        .map(parse_mode)
+        .transpose()?;
+    let uid = permissions.owner_uid.map(|id| id as u32);
+    let gid = permissions.owner_gid.map(|id| id as u32);
+
+    let items = beets::list_items(&library, &library::job_query(job))
+        .await
+        .map_err(|e| e.to_string())?;
+    let root = Path::new(&folder.path);
+    let album_dirs: HashSet<PathBuf> = items
+        .iter()
+        .filter_map(|item| item.path.parent().map(Path::to_path_buf))
+        .collect();
+    // The album folders and those between them and the library folder.
+    let mut dirs: HashSet<PathBuf> = HashSet::new();
+    for album_dir in &album_dirs {
+        for dir in album_dir.ancestors() {
+            if dir == root || !dir.starts_with(root) || !dirs.insert(dir.to_path_buf()) {
+                break;
+            }
+        }
+    }
+
+    let mut changed = 0;
+    for dir in &album_dirs {
+        let mut entries = tokio::fs::read_dir(dir).await.map_err(|e| e.to_string())?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            if !entry.file_type().await.is_ok_and(|t| t.is_file()) {
+                continue;
+            }
+            match apply(&entry.path(), file_mode, uid, gid) {
+                Ok(()) => changed += 1,
+                Err(e) => tracing::warn!("Failed to set permissions: {}", e),
+            }
+        }
+    }
+    for dir in &dirs {
+        if let Err(e) = apply(dir, dir_mode, uid, gid) {
+            tracing::warn!("Failed to set permissions: {}", e);
+        }
+    }
+    Ok(changed)
+}
+
+/// A library folder with the name of the user it belongs to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserFolder {
+    pub username: String,
+    pub folder: crate::db::Folder,
+}
+
+/// Every user's library folders, for admins to set their permissions.
+#[server]
+pub async fn get_all_folders(token: String) -> Result<Vec<UserFolder>, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let users = User::get_all().await.map_err(server_error)?;
+    let mut folders: Vec<UserFolder> = Folder::get_all()
+        .await
+        .map_err(server_error)?
+        .into_iter()
+        .filter_map(|folder| {
+            let user = users.iter().find(|u| u.id == folder.user_id)?;
+            Some(UserFolder {
+                username: user.username.clone(),
+                folder,
+            })
+        })
+        .collect();
+    folders.sort_by(|a, b| (&a.username, &a.folder.name).cmp(&(&b.username, &b.folder.name)));
+    Ok(folders)
+}
+
+/// Saves the permissions and ownership given to files imported into the
+/// folder from now on, and returns them cleaned up. Admins only, as the
+/// server chowns and chmods files with it, for any user's folder.
+#[server]
+pub async fn set_folder_permissions(
+    token: String,
+    folder_id: String,
+    permissions: FolderPermissions,
+) -> Result<FolderPermissions, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let permissions = validate(permissions).map_err(server_error)?;
+    Folder::set_permissions(&folder_id, &permissions)
+        .await
+        .map_err(server_error)?;
+    Ok(permissions)
+}
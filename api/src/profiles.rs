@@ -0,0 +1,178 @@
+//! Quality profiles: the built-in presets plus the ones users cloned from
+//! them and tweaked.
+
+use dioxus::prelude::*;
+use shared::{profiles::QualityProfile, slskd::ScoringConfig};
+
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{StoredProfile, StoredScoringConfig},
+    server_error,
+};
+#[cfg(feature = "server")]
+use shared::profiles::DEFAULT_PROFILE_ID;
+
+/// The profile a search runs with: a preset, one of the user's profiles, or
+/// the default preset when none is chosen.
+#[cfg(feature = "server")]
+pub async fn resolve_profile(user_id: &str, id: Option<&str>) -> Result<QualityProfile, String> {
+    let id = id.unwrap_or(DEFAULT_PROFILE_ID);
+    if let Some(preset) = QualityProfile::find_preset(id) {
+        return Ok(preset);
+    }
+    StoredProfile::get(user_id, id)
+        .await?
+        .ok_or_else(|| "Quality profile not found".to_string())
+}
+
+/// The presets followed by the user's own profiles.
+#[server]
+pub async fn get_quality_profiles(token: String) -> Result<Vec<QualityProfile>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut profiles = QualityProfile::presets();
+    profiles.extend(
+        StoredProfile::get_all_by_user(&claims.sub)
+            .await
+            .map_err(server_error)?,
+    );
+    Ok(profiles)
+}
+
+/// Saves one of the user's profiles. Saving a preset stores an editable copy
+/// of it instead.
+#[server]
+pub async fn save_quality_profile(
+    token: String,
+    profile: QualityProfile,
+) -> Result<QualityProfile, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut profile = profile;
+    profile.name = profile.name.trim().to_string();
+    if profile.name.is_empty() {
+        return Err(server_error("Profile name is required"));
+    }
+    if !(0.5..=1.0).contains(&profile.min_completeness) {
+        return Err(server_error("Completeness must be between 50% and 100%"));
+    }
+    profile.formats = profile
+        .formats
+        .iter()
+        .map(|f| f.trim().trim_start_matches('.').to_lowercase())
+        .filter(|f| !f.is_empty())
+        .collect();
+    if profile.built_in || QualityProfile::find_preset(&profile.id).is_some() {
+        profile.id = String::new();
+        profile.built_in = false;
+    }
+
+    StoredProfile::save(&claims.sub, &profile)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn delete_quality_profile(token: String, id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    StoredProfile::delete(&claims.sub, &id)
+        .await
+        .map_err(server_error)
+}
+
+/// The user's scoring adjustments, applied on top of every profile.
+#[server]
+pub async fn get_scoring_config(token: String) -> Result<ScoringConfig, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    StoredScoringConfig::get(&claims.sub)
+        .await
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn save_scoring_config(
+    token: String,
+    config: ScoringConfig,
+) -> Result<ScoringConfig, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let mut config = config;
+    validate_scoring(&mut config).map_err(server_error)?;
+    StoredScoringConfig::save(&claims.sub, &config)
+        .await
+        .map_err(server_error)?;
+    Ok(config)
+}
+
+/// Checks scoring adjustments from a user or a search, sorting the bonuses
+/// by bitrate and lowercasing the title suffixes.
+#[cfg(feature = "server")]
+pub fn validate_scoring(config: &mut ScoringConfig) -> Result<(), String> {
+    if let Some(weights) = config.weights {
+        let all = [
+            weights.match_score,
+            weights.completeness,
+            weights.format,
+            weights.availability,
+        ];
+        if all.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err("Weights cannot be negative".to_string());
+        }
+        if weights.total() <= 0.0 {
+            return Err("At least one weight must be above 0".to_string());
+        }
+    }
+    if let Some(min) = config.min_match_score {
+        if !(0.0..=1.0).contains(&min) {
+            return Err("The minimum match score must be between 0 and 1".to_string());
+        }
+    }
+    for bonus in &config.bitrate_bonuses {
+        if !(1..=10_000).contains(&bonus.min_bitrate) {
+            return Err("Bonus bitrates must be between 1 and 10000 kbps".to_string());
+        }
+        if !(-1.0..=1.0).contains(&bonus.bonus) {
+            return Err("Bitrate bonuses must be between -1 and 1".to_string());
+        }
+    }
+    config.bitrate_bonuses.sort_by_key(|b| b.min_bitrate);
+    config.bitrate_bonuses.dedup_by_key(|b| b.min_bitrate);
+    if let Some(penalty) = config.suffix_penalty {
+        if !(0.0..=1.0).contains(&penalty) {
+            return Err("The suffix penalty must be between 0 and 1".to_string());
+        }
+    }
+    if config.title_suffixes.iter().any(|s| s.contains(',')) {
+        return Err("Title suffixes cannot contain commas".to_string());
+    }
+    let mut suffixes: Vec<String> = Vec::new();
+    for suffix in config
+        .title_suffixes
+        .iter()
+        .map(|s| s.trim().to_lowercase())
+    {
+        if !suffix.is_empty() && !suffixes.contains(&suffix) {
+            suffixes.push(suffix);
+        }
+    }
+    config.title_suffixes = suffixes;
+    Ok(())
+}
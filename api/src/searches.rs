@@ -0,0 +1,342 @@
+//! Soulseek searches, run in the background.
+//!
+//! slskd only allows so many searches per window, so a search can wait minutes
+//! for its turn. Instead of holding the request open that long, the search
+//! server functions return a [`SearchTicket`] right away, with the search's
+//! place in line, and the UI polls [`search_status`] until its results are in.
+//! While an album search runs, the polls also return the albums ranked so far.
+//!
+//! Each user runs at most `MAX_SEARCHES_PER_USER` searches at once, so one
+//! user cannot fill the shared rate limit window. Their next searches wait,
+//! in order, for one of the running ones to end before joining the slskd queue.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use shared::slskd::{AlbumResult, SearchDiagnosis, SearchQueuePosition, SearchResult};
+
+#[cfg(feature = "server")]
+use crate::{auth, browse, demo, search_id_or_new, server_error, soulseek};
+#[cfg(feature = "server")]
+use soulbeet::slskd::AlbumSearch;
+#[cfg(feature = "server")]
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, LazyLock, Mutex,
+    },
+    time::{Duration, Instant},
+};
+#[cfg(feature = "server")]
+use tokio::sync::Semaphore;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SearchStatus {
+    /// Waiting for one of the user's other searches to end, 1 for the next
+    /// search to start.
+    WaitingForOwnSearches(usize),
+    /// Waiting for the search rate limit.
+    Queued(SearchQueuePosition),
+    Running,
+    /// Still running, with the albums ranked from the responses so far.
+    Partial(Vec<AlbumResult>),
+    Done(Vec<AlbumResult>),
+    /// An album search found nothing, and why.
+    NothingFound(SearchDiagnosis),
+    /// Files found by a raw search, see [`search_raw`].
+    Files(Vec<SearchResult>),
+    Failed(String),
+}
+
+#[cfg(feature = "server")]
+impl From<AlbumSearch> for SearchStatus {
+    fn from(search: AlbumSearch) -> Self {
+        match search.diagnosis {
+            Some(diagnosis) => SearchStatus::NothingFound(diagnosis),
+            None => SearchStatus::Done(search.albums),
+        }
+    }
+}
+
+/// A search started in the background, to poll with [`search_status`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchTicket {
+    pub search_id: String,
+    pub status: SearchStatus,
+}
+
+/// Results nobody came back for are dropped after this long.
+#[cfg(feature = "server")]
+const RESULT_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Searches a user runs at once unless `MAX_SEARCHES_PER_USER` is set, 0
+/// lifting the limit.
+#[cfg(feature = "server")]
+const DEFAULT_MAX_SEARCHES_PER_USER: usize = 2;
+
+#[cfg(feature = "server")]
+struct Search {
+    owner: String,
+    /// Order in which the search started waiting for one of its owner's
+    /// slots, `None` once it got one.
+    waiting: Option<u64>,
+    /// Albums ranked so far, see [`report_partial`].
+    partial: Option<Vec<AlbumResult>>,
+    /// `Done`, `NothingFound`, `Files` or `Failed` once the search is over,
+    /// with when it ended.
+    result: Option<(Instant, SearchStatus)>,
+}
+
+#[cfg(feature = "server")]
+static SEARCHES: LazyLock<Mutex<HashMap<String, Search>>> = LazyLock::new(Default::default);
+
+/// Running searches allowed to each user, by user id.
+#[cfg(feature = "server")]
+static USER_SLOTS: LazyLock<Mutex<HashMap<String, Arc<Semaphore>>>> =
+    LazyLock::new(Default::default);
+
+#[cfg(feature = "server")]
+static NEXT_WAITING: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "server")]
+fn max_searches_per_user() -> usize {
+    std::env::var("MAX_SEARCHES_PER_USER")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_SEARCHES_PER_USER)
+}
+
+/// The user's search slots, `None` without a limit. Semaphores hand permits
+/// out in the order they were asked for, so waiting searches keep their order.
+#[cfg(feature = "server")]
+fn user_slots(user_id: &str) -> Option<Arc<Semaphore>> {
+    let max = max_searches_per_user();
+    if max == 0 {
+        return None;
+    }
+    let mut slots = USER_SLOTS.lock().unwrap();
+    Some(
+        slots
+            .entry(user_id.to_string())
+            .or_insert_with(|| Arc::new(Semaphore::new(max)))
+            .clone(),
+    )
+}
+
+/// Runs `search` once one of the user's search slots is free, holding it
+/// meanwhile, for searches the user waits on rather than follows, such as
+/// availability probes.
+#[cfg(feature = "server")]
+pub(crate) async fn in_user_slot<T>(user_id: &str, search: impl Future<Output = T>) -> T {
+    let _slot = match user_slots(user_id) {
+        Some(slots) => slots.acquire_owned().await.ok(),
+        None => None,
+    };
+    search.await
+}
+
+/// Place of a search among its owner's searches waiting for a slot, `None`
+/// when it is not waiting.
+#[cfg(feature = "server")]
+fn own_queue_position(searches: &HashMap<String, Search>, search_id: &str) -> Option<usize> {
+    let search = searches.get(search_id)?;
+    let order = search.waiting?;
+    let ahead = searches
+        .values()
+        .filter(|s| s.owner == search.owner && s.waiting.is_some_and(|o| o < order))
+        .count();
+    Some(ahead + 1)
+}
+
+/// Runs `search` in the background under `search_id`, on behalf of `owner`.
+/// The id must also be the one `search` hands to slskd, so that its place in
+/// the rate limit queue can be followed.
+#[cfg(feature = "server")]
+pub(crate) fn spawn<F>(owner: &str, search_id: String, search: F) -> SearchTicket
+where
+    F: Future<Output = Result<SearchStatus, ServerFnError>> + Send + 'static,
+{
+    let slots = user_slots(owner);
+    let waiting = {
+        let mut searches = SEARCHES.lock().unwrap();
+        searches.retain(|_, s| {
+            s.result
+                .as_ref()
+                .is_none_or(|(ended, _)| ended.elapsed() < RESULT_TTL)
+        });
+        searches.insert(
+            search_id.clone(),
+            Search {
+                owner: owner.to_string(),
+                waiting: slots
+                    .is_some()
+                    .then(|| NEXT_WAITING.fetch_add(1, Ordering::Relaxed)),
+                partial: None,
+                result: None,
+            },
+        );
+        match &slots {
+            Some(slots) if slots.available_permits() == 0 => {
+                own_queue_position(&searches, &search_id)
+            }
+            _ => None,
+        }
+    };
+
+    // The search only joins the queue once its task runs.
+    let status = match (waiting, soulseek().next_search_position()) {
+        (Some(position), _) => SearchStatus::WaitingForOwnSearches(position),
+        (None, Some(place)) => SearchStatus::Queued(place),
+        (None, None) => SearchStatus::Running,
+    };
+    let id = search_id.clone();
+    tokio::spawn(async move {
+        // Held until the search ends, freeing the slot for the owner's next one.
+        let _slot = match slots {
+            Some(slots) => match slots.acquire_owned().await {
+                Ok(permit) => Some(permit),
+                Err(_) => return,
+            },
+            None => None,
+        };
+        match SEARCHES.lock().unwrap().get_mut(&id) {
+            // Cancelled while it waited.
+            Some(search) if search.result.is_some() => return,
+            Some(search) => search.waiting = None,
+            None => return,
+        }
+
+        let result = search
+            .await
+            .unwrap_or_else(|e| SearchStatus::Failed(e.to_string()));
+        if let Some(search) = SEARCHES.lock().unwrap().get_mut(&id) {
+            search.result = Some((Instant::now(), result));
+        }
+    });
+    SearchTicket { search_id, status }
+}
+
+/// Keeps the albums a running search has ranked so far, for the next poll.
+#[cfg(feature = "server")]
+pub(crate) fn report_partial(search_id: &str, albums: Vec<AlbumResult>) {
+    if let Some(search) = SEARCHES.lock().unwrap().get_mut(search_id) {
+        search.partial = Some(albums);
+    }
+}
+
+/// Whether `user_id` started the search `search_id`.
+#[cfg(feature = "server")]
+fn is_owner(user_id: &str, search_id: &str) -> bool {
+    SEARCHES
+        .lock()
+        .unwrap()
+        .get(search_id)
+        .is_some_and(|s| s.owner == user_id)
+}
+
+/// Where one of the user's searches stands. Results are handed out once.
+#[server]
+pub async fn search_status(
+    token: String,
+    search_id: String,
+) -> Result<SearchStatus, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if !is_owner(&claims.sub, &search_id) {
+        return Err(server_error("Search not found"));
+    }
+    let mut searches = SEARCHES.lock().unwrap();
+    if searches.get(&search_id).is_some_and(|s| s.result.is_some()) {
+        return match searches.remove(&search_id).and_then(|s| s.result) {
+            Some((_, status)) => Ok(status),
+            None => Err(server_error("Search not found")),
+        };
+    }
+    if let Some(position) = own_queue_position(&searches, &search_id) {
+        return Ok(SearchStatus::WaitingForOwnSearches(position));
+    }
+    let partial = searches.get(&search_id).and_then(|s| s.partial.clone());
+    drop(searches);
+
+    if let Some(place) = soulseek().search_queue_position(&search_id) {
+        return Ok(SearchStatus::Queued(place));
+    }
+    Ok(match partial {
+        Some(albums) => SearchStatus::Partial(albums),
+        None => SearchStatus::Running,
+    })
+}
+
+/// Starts a search for `query` as typed, in the background. Its results are
+/// every audio file found, not grouped into albums nor matched to tracks.
+#[server]
+pub async fn search_raw(
+    token: String,
+    query: String,
+    search_id: Option<String>,
+) -> Result<SearchTicket, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let query = query.trim().to_string();
+    if query.is_empty() {
+        return Err(server_error("Search query is required"));
+    }
+    let search_id = search_id_or_new(search_id);
+    if claims.is_demo {
+        demo::check_rate_limit().map_err(server_error)?;
+        return Ok(SearchTicket {
+            search_id,
+            status: SearchStatus::Files(demo::mock_raw_results(&query)),
+        });
+    }
+
+    let id = search_id.clone();
+    Ok(spawn(&claims.sub, search_id, async move {
+        soulseek()
+            .search_raw(&query, chrono::Duration::seconds(45), Some(&id))
+            .await
+            .map(SearchStatus::Files)
+            .map_err(server_error)
+    }))
+}
+
+/// Stops one of the user's searches, running or still queued, which then
+/// fails with "Search was cancelled".
+#[server]
+pub async fn cancel_search(token: String, search_id: String) -> Result<(), ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    if !is_owner(&claims.sub, &search_id) {
+        return Err(server_error("Search not found"));
+    }
+    {
+        let mut searches = SEARCHES.lock().unwrap();
+        if let Some(search) = searches.get_mut(&search_id).filter(|s| s.waiting.is_some()) {
+            // It never reached slskd, its task gives up once it gets a slot.
+            search.waiting = None;
+            search.result = Some((
+                Instant::now(),
+                SearchStatus::Failed("Search was cancelled".to_string()),
+            ));
+            return Ok(());
+        }
+    }
+    soulseek().cancel_search(&search_id).await;
+    Ok(())
+}
@@ -0,0 +1,300 @@
+//! Public, time-limited links to an imported album.
+//!
+//! Whoever has the link sees the track list and can stream each track or
+//! download the whole album as a zip, without an account. Audio is served
+//! through the signed media URLs of [`crate::media`], issued here once the
+//! link and its optional password check out.
+
+use dioxus::fullstack::FileStream;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::{
+    auth,
+    db::{Job, JobState, ShareLink},
+    library, server_error,
+};
+#[cfg(feature = "server")]
+use soulbeet::beets::{self, LibraryItem};
+#[cfg(feature = "server")]
+use std::path::{Path, PathBuf};
+
+/// Longest a share link can stay valid.
+pub const MAX_SHARE_DAYS: i64 = 30;
+
+/// A share link as shown to its owner.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShareLinkSummary {
+    pub id: String,
+    pub job_id: String,
+    pub expires_at: String,
+    pub protected: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedTrack {
+    pub track: Option<u32>,
+    pub artist: String,
+    pub title: String,
+    /// Signed URL streaming the track.
+    pub url: String,
+}
+
+/// What the public page of a share link shows.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SharedAlbum {
+    pub artist: String,
+    pub album: String,
+    pub expires_at: String,
+    pub tracks: Vec<SharedTrack>,
+    /// Signed URL downloading the whole album as a zip.
+    pub zip_url: String,
+}
+
+#[cfg(feature = "server")]
+impl From<ShareLink> for ShareLinkSummary {
+    fn from(link: ShareLink) -> Self {
+        ShareLinkSummary {
+            protected: link.password_hash.is_some(),
+            id: link.id,
+            job_id: link.job_id,
+            expires_at: link.expires_at,
+        }
+    }
+}
+
+/// Where album zips are built, one folder per share link.
+#[cfg(feature = "server")]
+fn share_zips_dir() -> PathBuf {
+    PathBuf::from(std::env::var("SHARE_ZIPS_PATH").unwrap_or_else(|_| "share-zips".to_string()))
+}
+
+/// The library items a job imported, in track order.
+#[cfg(feature = "server")]
+async fn imported_items(job: &Job) -> Result<Vec<LibraryItem>, String> {
+    let query = library::job_query(job);
+    let mut items = beets::list_items(&library::job_library(job).await, &query)
+        .await
+        .map_err(|e| e.to_string())?;
+    if items.is_empty() {
+        return Err("The album is no longer in the library".to_string());
+    }
+    items.sort_by_key(|item| item.track);
+    Ok(items)
+}
+
+/// Removes expired links and their zips.
+#[cfg(feature = "server")]
+pub async fn remove_expired() -> Result<usize, String> {
+    let expired = ShareLink::delete_expired().await?;
+    for id in &expired {
+        let dir = share_zips_dir().join(id);
+        if dir.is_dir() {
+            if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+                tracing::warn!("Failed to remove {}: {}", dir.display(), e);
+            }
+        }
+    }
+    Ok(expired.len())
+}
+
+#[cfg(feature = "server")]
+fn write_zip(path: &Path, files: &[PathBuf]) -> Result<(), String> {
+    let file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    // Audio is already compressed, store it as is.
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    for source in files {
+        let name = source
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "track".to_string());
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        let mut source = std::fs::File::open(source).map_err(|e| e.to_string())?;
+        std::io::copy(&mut source, &mut zip).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Builds the album zip of a share link, or reuses the one built earlier.
+#[cfg(feature = "server")]
+async fn album_zip(link: &ShareLink, items: &[LibraryItem]) -> Result<PathBuf, String> {
+    let first = items.first().ok_or("Nothing to download")?;
+    let name = format!("{} - {}.zip", first.artist, first.album).replace(['/', '\\', '"'], "_");
+    let dir = share_zips_dir().join(&link.id);
+    let path = dir.join(name);
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| e.to_string())?;
+    // Build under a unique name so concurrent downloads never read a partial zip.
+    let partial = dir.join(format!("{}.part", uuid::Uuid::new_v4()));
+    let files: Vec<PathBuf> = items.iter().map(|item| item.path.clone()).collect();
+    let target = partial.clone();
+    tokio::task::spawn_blocking(move || write_zip(&target, &files))
+        .await
+        .map_err(|e| e.to_string())??;
+    tokio::fs::rename(&partial, &path)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+#[get("/api/share/{share_id}/tracks/{index}")]
+#[middleware(dioxus::fullstack::axum::middleware::from_fn(auth::require_signed_url))]
+pub async fn shared_track(share_id: String, index: usize) -> Result<FileStream, ServerFnError> {
+    let link = ShareLink::get_active(&share_id)
+        .await
+        .map_err(server_error)?;
+    let job = Job::get(&link.job_id).await.map_err(server_error)?;
+    let items = imported_items(&job).await.map_err(server_error)?;
+    let item = items
+        .get(index)
+        .ok_or_else(|| server_error("Track not found"))?;
+
+    FileStream::from_path(&item.path)
+        .await
+        .map_err(server_error)
+}
+
+#[get("/api/share/{share_id}/zip")]
+#[middleware(dioxus::fullstack::axum::middleware::from_fn(auth::require_signed_url))]
+pub async fn shared_album_zip(share_id: String) -> Result<FileStream, ServerFnError> {
+    let link = ShareLink::get_active(&share_id)
+        .await
+        .map_err(server_error)?;
+    let job = Job::get(&link.job_id).await.map_err(server_error)?;
+    let items = imported_items(&job).await.map_err(server_error)?;
+    let path = album_zip(&link, &items).await.map_err(server_error)?;
+
+    FileStream::from_path(&path).await.map_err(server_error)
+}
+
+/// Creates a link to one of the user's imported jobs, valid for `days`.
+#[server]
+pub async fn create_share_link(
+    token: String,
+    job_id: String,
+    days: i64,
+    password: Option<String>,
+) -> Result<ShareLinkSummary, ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let job = Job::get(&job_id).await.map_err(server_error)?;
+    if job.user_id != claims.sub {
+        return Err(server_error("Job not found"));
+    }
+    if job.state != JobState::Imported {
+        return Err(server_error("Only imported albums can be shared"));
+    }
+    if !(1..=MAX_SHARE_DAYS).contains(&days) {
+        return Err(server_error(format!(
+            "Links can last between 1 and {MAX_SHARE_DAYS} days"
+        )));
+    }
+    // Fail now rather than on the public page if beets cannot find the files.
+    imported_items(&job).await.map_err(server_error)?;
+
+    let password = password
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    let expires_at = (chrono::Utc::now() + chrono::Duration::days(days)).to_rfc3339();
+    ShareLink::create(&claims.sub, &job.id, password.as_deref(), &expires_at)
+        .await
+        .map(ShareLinkSummary::from)
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn get_share_links(
+    token: String,
+    job_id: String,
+) -> Result<Vec<ShareLinkSummary>, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    let job = Job::get(&job_id).await.map_err(server_error)?;
+    if job.user_id != claims.sub {
+        return Err(server_error("Job not found"));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    ShareLink::get_by_job(&job.id)
+        .await
+        .map(|links| {
+            links
+                .into_iter()
+                .filter(|link| link.expires_at > now)
+                .map(ShareLinkSummary::from)
+                .collect()
+        })
+        .map_err(server_error)
+}
+
+#[server]
+pub async fn revoke_share_link(token: String, share_id: String) -> Result<(), ServerFnError> {
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    ShareLink::delete(&share_id, &claims.sub)
+        .await
+        .map_err(server_error)?;
+    let dir = share_zips_dir().join(&share_id);
+    if dir.is_dir() {
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+    Ok(())
+}
+
+/// The album behind a share link. Needs no account, only the password if the
+/// link has one.
+#[server]
+pub async fn get_shared_album(
+    share_id: String,
+    password: Option<String>,
+) -> Result<SharedAlbum, ServerFnError> {
+    let link = ShareLink::get_active(&share_id)
+        .await
+        .map_err(server_error)?;
+    link.verify_password(password.as_deref())
+        .map_err(server_error)?;
+
+    let job = Job::get(&link.job_id).await.map_err(server_error)?;
+    let items = imported_items(&job).await.map_err(server_error)?;
+
+    let mut tracks = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        let path = format!("/api/share/{}/tracks/{}", link.id, index);
+        tracks.push(SharedTrack {
+            track: item.track,
+            artist: item.artist.clone(),
+            title: item.title.clone(),
+            url: auth::sign_media_url(&link.id, &path).map_err(server_error)?,
+        });
+    }
+    let zip_url = auth::sign_media_url(&link.id, &format!("/api/share/{}/zip", link.id))
+        .map_err(server_error)?;
+
+    Ok(SharedAlbum {
+        artist: items[0].artist.clone(),
+        album: items[0].album.clone(),
+        expires_at: link.expires_at,
+        tracks,
+        zip_url,
+    })
+}
@@ -0,0 +1,106 @@
+//! Opt-in sharing of users' library folders through slskd.
+
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use crate::{auth, browse, db, server_error, soulseek};
+#[cfg(feature = "server")]
+use std::collections::HashSet;
+
+/// Registers the user's library folders as slskd shares, if they opted in.
+#[cfg(feature = "server")]
+pub async fn sync_user_shares(user_id: &str) -> Result<(), String> {
+    let user = db::User::get(user_id).await?;
+    if !user.share_library {
+        return Ok(());
+    }
+
+    let folders: Vec<String> = user
+        .get_folders()
+        .await?
+        .into_iter()
+        .map(|f| f.path)
+        .collect();
+    soulseek()
+        .add_shares(&folders)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Removes the user's library folders from the slskd shares, except those
+/// another user still shares.
+#[cfg(feature = "server")]
+pub async fn unshare_user_folders(user_id: &str) -> Result<(), String> {
+    let sharing: HashSet<String> = db::User::get_all()
+        .await?
+        .into_iter()
+        .filter(|user| user.share_library && user.id != user_id)
+        .map(|user| user.id)
+        .collect();
+    let folders = db::Folder::get_all().await?;
+    let still_shared: HashSet<&str> = folders
+        .iter()
+        .filter(|f| sharing.contains(&f.user_id))
+        .map(|f| f.path.as_str())
+        .collect();
+    let unshared: Vec<String> = folders
+        .iter()
+        .filter(|f| f.user_id == user_id && !still_shared.contains(f.path.as_str()))
+        .map(|f| f.path.clone())
+        .collect();
+    soulseek()
+        .remove_shares(&unshared)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Rescans the slskd shares after an import into a shared library.
+#[cfg(feature = "server")]
+pub async fn rescan_after_import(user_id: &str) {
+    match db::User::get(user_id).await {
+        Ok(user) if user.share_library => {
+            if let Err(e) = soulseek().rescan_shares().await {
+                tracing::warn!("Failed to rescan slskd shares: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load user {}: {}", user_id, e),
+    }
+}
+
+#[server]
+pub async fn get_library_sharing(token: String) -> Result<bool, ServerFnError> {
+    let claims = match auth::verify_token(&token, "access") {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+    if claims.is_demo {
+        return Ok(false);
+    }
+
+    db::User::get(&claims.sub)
+        .await
+        .map(|user| user.share_library)
+        .map_err(server_error)
+}
+
+/// Turns library sharing on or off, sharing or unsharing the user's folders
+/// in slskd right away.
+#[server]
+pub async fn set_library_sharing(token: String, enabled: bool) -> Result<(), ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_writable(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+
+    db::User::set_share_library(&claims.sub, enabled)
+        .await
+        .map_err(server_error)?;
+    match enabled {
+        true => sync_user_shares(&claims.sub).await,
+        false => unshare_user_folders(&claims.sub).await,
+    }
+    .map_err(server_error)
+}
@@ -0,0 +1,150 @@
+//! The Soulseek account slskd logs in with, managed from Soulbeet so that a
+//! banned or changed account can be fixed without editing slskd's YAML.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use shared::slskd::{SearchRateLimit, SearchRateUsage, ServerState, SoulseekAccount};
+
+#[cfg(feature = "server")]
+use crate::{auth, browse, db::StoredRateLimit, server_error, soulseek};
+
+/// Bounds of the search rate limit admins can set.
+#[cfg(feature = "server")]
+const MAX_SEARCHES: std::ops::RangeInclusive<usize> = 1..=500;
+#[cfg(feature = "server")]
+const WINDOW_SECONDS: std::ops::RangeInclusive<i64> = 10..=3600;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoulseekStatus {
+    pub account: SoulseekAccount,
+    pub state: ServerState,
+}
+
+#[server]
+pub async fn get_soulseek_status(token: String) -> Result<SoulseekStatus, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let account = soulseek().soulseek_account().await.map_err(server_error)?;
+    let state = soulseek().server_state().await.map_err(server_error)?;
+    Ok(SoulseekStatus { account, state })
+}
+
+/// Saves the Soulseek account in the slskd configuration and reconnects with
+/// it. An empty password keeps the current one.
+#[server]
+pub async fn update_soulseek_account(
+    token: String,
+    account: SoulseekAccount,
+    password: Option<String>,
+) -> Result<ServerState, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let mut account = account;
+    account.username = account.username.trim().to_string();
+    if account.username.is_empty() {
+        return Err(server_error("Username is required"));
+    }
+    if account.listen_port == Some(0) {
+        return Err(server_error("Listen port must be between 1 and 65535"));
+    }
+    let password = password.filter(|p| !p.is_empty());
+
+    soulseek()
+        .update_soulseek_account(&account, password.as_deref())
+        .await
+        .map_err(|e| {
+            server_error(format!(
+                "slskd refused the configuration change, make sure remote_configuration is enabled: {e}"
+            ))
+        })?;
+    soulseek().reconnect().await.map_err(server_error)
+}
+
+/// Reconnects slskd to the Soulseek server and reports whether it logged in.
+#[server]
+pub async fn test_soulseek_connection(token: String) -> Result<ServerState, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    soulseek().reconnect().await.map_err(server_error)
+}
+
+/// Whether slskd is connected and logged in to Soulseek, for any signed in
+/// user to see why searches come back empty.
+#[server]
+pub async fn get_connection_status(token: String) -> Result<ServerState, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_token(&token, "access") {
+        return Err(server_error(e));
+    }
+
+    soulseek()
+        .server_state()
+        .await
+        .map_err(|e| server_error(format!("slskd is unreachable: {e}")))
+}
+
+/// Puts back the search rate limit an admin saved, at startup.
+#[cfg(feature = "server")]
+pub(crate) async fn load_search_rate_limit() {
+    match StoredRateLimit::get().await {
+        Ok(Some(limit)) => soulseek().set_search_rate_limit(limit),
+        Ok(None) => {}
+        Err(e) => tracing::error!("Failed to read the search rate limit: {}", e),
+    }
+}
+
+/// The search rate limit and how much of the current window is used.
+#[server]
+pub async fn get_search_rate_usage(token: String) -> Result<SearchRateUsage, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_token(&token, "access") {
+        return Err(server_error(e));
+    }
+
+    Ok(soulseek().search_rate_usage())
+}
+
+/// Changes the search rate limit right away and keeps it across restarts.
+#[server]
+pub async fn set_search_rate_limit(
+    token: String,
+    limit: SearchRateLimit,
+) -> Result<SearchRateUsage, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    if !MAX_SEARCHES.contains(&limit.max_searches) {
+        return Err(server_error(format!(
+            "Searches per window must be between {} and {}",
+            MAX_SEARCHES.start(),
+            MAX_SEARCHES.end()
+        )));
+    }
+    if !WINDOW_SECONDS.contains(&limit.window_seconds) {
+        return Err(server_error(format!(
+            "The window must be between {} and {} seconds",
+            WINDOW_SECONDS.start(),
+            WINDOW_SECONDS.end()
+        )));
+    }
+    StoredRateLimit::save(&limit).await.map_err(server_error)?;
+    soulseek().set_search_rate_limit(limit);
+    Ok(soulseek().search_rate_usage())
+}
@@ -0,0 +1,402 @@
+//! Background tasks run on cron schedules.
+//!
+//! Every [`Task`] has a default schedule, stored in `scheduled_tasks` the first
+//! time the server starts so admins can change or disable it afterwards. The
+//! scheduler wakes up periodically, starts the tasks that are due, and records
+//! every run in `task_runs`. New periodic work is added as a [`Task`] variant.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::db::{ScheduledTask, TaskRun};
+#[cfg(feature = "server")]
+use crate::{
+    auth, cron::CronSchedule, db, feedback, followed_artists, jobs, library, server_error,
+    share_links,
+};
+#[cfg(feature = "server")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "server")]
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{LazyLock, Mutex},
+};
+
+/// How often the scheduler checks for due tasks.
+#[cfg(feature = "server")]
+const TICK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How many past runs admins can browse.
+#[cfg(feature = "server")]
+const RUN_HISTORY: i64 = 50;
+
+/// Names of the tasks currently running, so a slow run is never overlapped.
+#[cfg(feature = "server")]
+static RUNNING: LazyLock<Mutex<HashSet<&'static str>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Task {
+    Cleanup,
+    Backup,
+    FeedbackReport,
+    LibraryIndex,
+    ReleaseWatch,
+}
+
+impl Task {
+    pub const ALL: [Task; 5] = [
+        Task::Cleanup,
+        Task::Backup,
+        Task::FeedbackReport,
+        Task::LibraryIndex,
+        Task::ReleaseWatch,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Task::Cleanup => "cleanup",
+            Task::Backup => "backup",
+            Task::FeedbackReport => "feedback_report",
+            Task::LibraryIndex => "library_index",
+            Task::ReleaseWatch => "release_watch",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Task> {
+        Task::ALL.into_iter().find(|task| task.name() == name)
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Task::Cleanup => {
+                "Deletes archived review rejects older than ARCHIVE_RETENTION_DAYS, expired share links and the transfer speed samples of finished downloads"
+            }
+            Task::Backup => {
+                "Copies the database to BACKUP_PATH, keeping the last BACKUP_KEEP copies"
+            }
+            Task::FeedbackReport => "Logs how job feedback correlates with ranking scores",
+            Task::LibraryIndex => {
+                "Reconciles the library index with beets, picking up files changed outside Soulbeet"
+            }
+            Task::ReleaseWatch => {
+                "Checks MusicBrainz for new albums of followed artists and queues them for those followed with auto-download"
+            }
+        }
+    }
+
+    /// Cron expression and jitter in seconds used until an admin changes them.
+    #[cfg(feature = "server")]
+    fn default_schedule(self) -> (&'static str, i64) {
+        match self {
+            Task::Cleanup => ("0 4 * * *", 600),
+            Task::Backup => ("30 3 * * *", 600),
+            Task::FeedbackReport => ("0 6 * * 1", 0),
+            Task::LibraryIndex => ("15 * * * *", 300),
+            Task::ReleaseWatch => ("0 5 * * *", 1800),
+        }
+    }
+
+    /// Runs the task, returning a summary for the run history.
+    #[cfg(feature = "server")]
+    async fn run(self) -> Result<String, String> {
+        match self {
+            Task::Cleanup => {
+                let archive = cleanup_archive().await?;
+                let links = share_links::remove_expired().await?;
+                let samples = db::JobFile::prune_speed_samples().await?;
+                Ok(format!(
+                    "{archive}, removed {links} expired share links and {samples} speed samples"
+                ))
+            }
+            Task::Backup => backup_database().await,
+            Task::FeedbackReport => {
+                let report = feedback::analyze().await?;
+                tracing::info!("Feedback report:\n{}", report);
+                Ok(format!(
+                    "Analyzed {} upvoted and {} downvoted jobs",
+                    report.up, report.down
+                ))
+            }
+            Task::LibraryIndex => library::reconcile().await,
+            Task::ReleaseWatch => followed_artists::check_new_releases().await,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn env_or<T: std::str::FromStr>(var: &str, default: T) -> T {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+#[cfg(feature = "server")]
+async fn cleanup_archive() -> Result<String, String> {
+    let retention_days: u64 = env_or("ARCHIVE_RETENTION_DAYS", 30);
+    let archive = jobs::archive_root();
+    if !archive.is_dir() {
+        return Ok("Nothing archived".to_string());
+    }
+
+    let cutoff = std::time::SystemTime::now()
+        - std::time::Duration::from_secs(retention_days * 24 * 60 * 60);
+    let mut entries = tokio::fs::read_dir(&archive)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut removed = 0;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let modified = entry.metadata().await.and_then(|m| m.modified());
+        if !matches!(modified, Ok(time) if time < cutoff) {
+            continue;
+        }
+        let path = entry.path();
+        let result = if path.is_dir() {
+            tokio::fs::remove_dir_all(&path).await
+        } else {
+            tokio::fs::remove_file(&path).await
+        };
+        match result {
+            Ok(()) => removed += 1,
+            Err(e) => tracing::warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(format!(
+        "Removed {removed} archive entries older than {retention_days} days"
+    ))
+}
+
+#[cfg(feature = "server")]
+async fn backup_database() -> Result<String, String> {
+    let dir = PathBuf::from(std::env::var("BACKUP_PATH").unwrap_or_else(|_| "backups".to_string()));
+    let keep: usize = env_or("BACKUP_KEEP", 7);
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let path = dir.join(format!(
+        "soulbeet-{}.db",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    db::backup_to(&path).await?;
+
+    // Backup names sort chronologically, drop the oldest beyond `keep`.
+    let mut backups = Vec::new();
+    let mut entries = tokio::fs::read_dir(&dir).await.map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("soulbeet-") && name.ends_with(".db") {
+            backups.push(entry.path());
+        }
+    }
+    backups.sort();
+    let excess = backups.len().saturating_sub(keep.max(1));
+    for old in &backups[..excess] {
+        if let Err(e) = tokio::fs::remove_file(old).await {
+            tracing::warn!("Failed to remove old backup {}: {}", old.display(), e);
+        }
+    }
+
+    Ok(format!("Saved {}", path.display()))
+}
+
+/// Next run of `task` after `after`, jitter included.
+#[cfg(feature = "server")]
+fn next_run(task: &ScheduledTask, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let next = CronSchedule::parse(&task.cron).ok()?.next_after(after)?;
+    let jitter = if task.jitter_seconds > 0 {
+        rand::random_range(0..=task.jitter_seconds)
+    } else {
+        0
+    };
+    Some(next + chrono::Duration::seconds(jitter))
+}
+
+/// Starts `task` in the background unless it is already running.
+#[cfg(feature = "server")]
+fn spawn_run(task: Task) -> bool {
+    if !RUNNING.lock().unwrap().insert(task.name()) {
+        tracing::info!("Task {} is still running, skipping", task.name());
+        return false;
+    }
+
+    tokio::spawn(async move {
+        let run = TaskRun::start(task.name()).await;
+        tracing::info!("Running task {}", task.name());
+        let (success, message) = match task.run().await {
+            Ok(message) => (true, message),
+            Err(e) => {
+                tracing::error!("Task {} failed: {}", task.name(), e);
+                (false, e)
+            }
+        };
+        match run {
+            Ok(run) => {
+                let _ = TaskRun::finish(&run.id, success, &message).await;
+            }
+            Err(e) => tracing::warn!("Failed to record run of {}: {}", task.name(), e),
+        }
+        RUNNING.lock().unwrap().remove(task.name());
+    });
+    true
+}
+
+#[cfg(feature = "server")]
+async fn run_due_tasks() {
+    let now = Utc::now();
+    let scheduled = match ScheduledTask::get_all().await {
+        Ok(scheduled) => scheduled,
+        Err(e) => {
+            tracing::warn!("Failed to load scheduled tasks: {}", e);
+            return;
+        }
+    };
+
+    for scheduled in scheduled.iter().filter(|s| s.enabled) {
+        let Some(task) = Task::from_name(&scheduled.name) else {
+            continue;
+        };
+        let due_at = scheduled
+            .next_run_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok());
+        let due = match due_at {
+            Some(at) if at > now => continue,
+            Some(_) => true,
+            // New or rescheduled task, only compute its next run.
+            None => false,
+        };
+
+        let next = next_run(scheduled, now).map(|at| at.to_rfc3339());
+        if next.is_none() {
+            tracing::warn!(
+                "Task {} has no upcoming run for '{}'",
+                scheduled.name,
+                scheduled.cron
+            );
+        }
+        let _ = ScheduledTask::set_next_run(&scheduled.name, next.as_deref()).await;
+
+        if due {
+            spawn_run(task);
+        }
+    }
+}
+
+/// Registers the default schedules and starts the scheduler loop.
+#[cfg(feature = "server")]
+pub async fn start_scheduler() {
+    for task in Task::ALL {
+        let (cron, jitter) = task.default_schedule();
+        if let Err(e) = ScheduledTask::ensure(task.name(), cron, jitter).await {
+            tracing::error!("Failed to register task {}: {}", task.name(), e);
+        }
+    }
+    if let Err(e) = TaskRun::fail_interrupted().await {
+        tracing::warn!("Failed to close interrupted task runs: {}", e);
+    }
+
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(TICK);
+        loop {
+            interval.tick().await;
+            run_due_tasks().await;
+        }
+    });
+}
+
+/// A scheduled task as shown to admins.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TaskOverview {
+    pub task: ScheduledTask,
+    pub description: String,
+    pub running: bool,
+    /// The next few times the cron expression fires, before jitter.
+    pub upcoming: Vec<String>,
+}
+
+#[server]
+pub async fn get_scheduled_tasks(token: String) -> Result<Vec<TaskOverview>, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let running = RUNNING.lock().unwrap().clone();
+    let scheduled = ScheduledTask::get_all().await.map_err(server_error)?;
+    Ok(scheduled
+        .into_iter()
+        .filter_map(|scheduled| {
+            let task = Task::from_name(&scheduled.name)?;
+            let upcoming = CronSchedule::parse(&scheduled.cron)
+                .map(|cron| {
+                    std::iter::successors(cron.next_after(Utc::now()), |at| cron.next_after(*at))
+                        .take(3)
+                        .map(|at| at.to_rfc3339())
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(TaskOverview {
+                description: task.description().to_string(),
+                running: running.contains(task.name()),
+                upcoming,
+                task: scheduled,
+            })
+        })
+        .collect())
+}
+
+#[server]
+pub async fn get_task_runs(token: String) -> Result<Vec<TaskRun>, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    TaskRun::get_recent(RUN_HISTORY).await.map_err(server_error)
+}
+
+#[server]
+pub async fn update_scheduled_task(
+    token: String,
+    name: String,
+    cron: String,
+    jitter_seconds: i64,
+    enabled: bool,
+) -> Result<(), ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    if Task::from_name(&name).is_none() {
+        return Err(server_error("Task not found"));
+    }
+    let cron = cron.trim();
+    if let Err(e) = CronSchedule::parse(cron) {
+        return Err(server_error(format!("Invalid cron expression: {e}")));
+    }
+    if jitter_seconds < 0 {
+        return Err(server_error("Jitter cannot be negative"));
+    }
+
+    ScheduledTask::update(&name, cron, jitter_seconds, enabled)
+        .await
+        .map_err(server_error)
+}
+
+/// Runs a task right away, outside of its schedule.
+#[server]
+pub async fn run_task_now(token: String, name: String) -> Result<(), ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    let Some(task) = Task::from_name(&name) else {
+        return Err(server_error("Task not found"));
+    };
+    if !spawn_run(task) {
+        return Err(server_error("Task is already running"));
+    }
+    Ok(())
+}
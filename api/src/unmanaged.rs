@@ -0,0 +1,63 @@
+//! Downloads slskd completed without a job, found when the server starts,
+//! for admins to import into a folder or dismiss.
+
+use dioxus::prelude::*;
+
+use crate::db::{Job, UnmanagedDownload};
+#[cfg(feature = "server")]
+use crate::{auth, browse, db::Folder, dispatch, server_error};
+
+#[server]
+pub async fn get_unmanaged_downloads(
+    token: String,
+) -> Result<Vec<UnmanagedDownload>, ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    UnmanagedDownload::get_pending().await.map_err(server_error)
+}
+
+/// Imports the unmanaged downloads `ids` into one of the admin's folders, as a
+/// job of theirs.
+#[server]
+pub async fn import_unmanaged_downloads(
+    token: String,
+    ids: Vec<String>,
+    target_folder: String,
+) -> Result<Job, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    let claims = match auth::verify_admin(&token) {
+        Ok(c) => c,
+        Err(e) => return Err(server_error(e)),
+    };
+    if Folder::get_by_path(&claims.sub, &target_folder)
+        .await
+        .map_err(server_error)?
+        .is_none()
+    {
+        return Err(server_error("Folder not found"));
+    }
+
+    let downloads = UnmanagedDownload::take(&ids).await.map_err(server_error)?;
+    if downloads.is_empty() {
+        return Err(server_error("Nothing left to import"));
+    }
+    dispatch::import_unmanaged(&claims.sub, &target_folder, downloads)
+        .await
+        .map_err(server_error)
+}
+
+/// Leaves the unmanaged downloads `ids` alone. They are not offered again.
+#[server]
+pub async fn dismiss_unmanaged_downloads(
+    token: String,
+    ids: Vec<String>,
+) -> Result<(), ServerFnError> {
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    UnmanagedDownload::dismiss(&ids).await.map_err(server_error)
+}
@@ -0,0 +1,40 @@
+//! What other Soulseek users download from the shares, for admins to see who
+//! pulls files from the server and stop them.
+
+use dioxus::prelude::*;
+use shared::slskd::FileEntry;
+
+#[cfg(feature = "server")]
+use crate::{auth, browse, server_error, soulseek};
+
+#[server]
+pub async fn get_uploads(token: String) -> Result<Vec<FileEntry>, ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    soulseek().get_all_uploads().await.map_err(server_error)
+}
+
+/// Cancels an upload still queued or running, or takes a finished one off the
+/// list when `remove` is set.
+#[server]
+pub async fn cancel_upload(
+    token: String,
+    username: String,
+    upload_id: String,
+    remove: bool,
+) -> Result<(), ServerFnError> {
+    browse::check().map_err(server_error)?;
+
+    if let Err(e) = auth::verify_admin(&token) {
+        return Err(server_error(e));
+    }
+
+    soulseek()
+        .cancel_upload(&username, &upload_id, remove)
+        .await
+        .map_err(server_error)
+}
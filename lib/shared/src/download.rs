@@ -1,9 +1,44 @@
 use serde::{Deserialize, Serialize};
 
-use crate::musicbrainz::{Album, Track};
+use crate::{
+    musicbrainz::{Album, Track},
+    slskd::SearchRefinements,
+};
 
 #[derive(Serialize, Clone, PartialEq, Deserialize, Debug)]
 pub struct DownloadQuery {
     pub album: Album,
     pub tracks: Vec<Track>,
+    /// Quality profile the search runs with, the default preset when unset.
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    /// UUID chosen by the client, to cancel the search while it runs.
+    #[serde(default)]
+    pub search_id: Option<String>,
+    /// Text sent to Soulseek instead of "artist album". The tracks are still
+    /// matched against the album.
+    #[serde(default)]
+    pub query: Option<String>,
+    /// Filters picked for this search only.
+    #[serde(default)]
+    pub refinements: SearchRefinements,
+    /// The user picked this edition of the album, files naming another one
+    /// rank lower.
+    #[serde(default)]
+    pub edition_picked: bool,
+}
+
+/// A search for one track on its own, downloaded and imported as a single.
+#[derive(Serialize, Clone, PartialEq, Deserialize, Debug)]
+pub struct TrackDownloadQuery {
+    pub track: Track,
+    /// Quality profile the search runs with, the default preset when unset.
+    #[serde(default)]
+    pub profile_id: Option<String>,
+    /// UUID chosen by the client, to cancel the search while it runs.
+    #[serde(default)]
+    pub search_id: Option<String>,
+    /// Filters picked for this search only.
+    #[serde(default)]
+    pub refinements: SearchRefinements,
 }
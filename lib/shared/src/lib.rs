@@ -1,3 +1,4 @@
 pub mod download;
 pub mod musicbrainz;
+pub mod profiles;
 pub mod slskd;
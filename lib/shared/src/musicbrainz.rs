@@ -1,12 +1,57 @@
 use serde::{Deserialize, Serialize};
 
-/// Represents a search result which can be either a track or an album.
+/// Represents a search result which can be a track, an album or an artist.
 /// The `kind` tag is used by serde to distinguish between the variants.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind")]
 pub enum SearchResult {
     Track(Track),
     Album(Album),
+    Artist(Artist),
+}
+
+impl SearchResult {
+    /// The genres of the track or album, none for an artist.
+    pub fn genres(&self) -> &[String] {
+        match self {
+            SearchResult::Track(track) => &track.genres,
+            SearchResult::Album(album) => &album.genres,
+            SearchResult::Artist(_) => &[],
+        }
+    }
+
+    /// The MusicBrainz Identifier of the track, album or artist.
+    pub fn id(&self) -> &str {
+        match self {
+            SearchResult::Track(track) => &track.id,
+            SearchResult::Album(album) => &album.id,
+            SearchResult::Artist(artist) => &artist.id,
+        }
+    }
+}
+
+/// A page of search results, with how many MusicBrainz found in all. Pages
+/// can come back with fewer results than asked for, or none, as releases that
+/// are not albums and repeated recordings are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPage {
+    pub results: Vec<SearchResult>,
+    /// Results MusicBrainz has for the query, before any is left out.
+    pub count: u32,
+}
+
+/// An artist found by name, to browse their discography.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Artist {
+    /// The MusicBrainz Identifier (MBID).
+    pub id: String,
+    /// The name of the artist.
+    pub name: String,
+    /// What tells the artist apart from others of the same name, e.g.
+    /// "UK punk band". Empty when MusicBrainz has none.
+    pub disambiguation: String,
+    /// Country code of the artist's origin.
+    pub country: Option<String>,
 }
 
 /// A detailed structure to hold search results for a track.
@@ -26,6 +71,17 @@ pub struct Track {
     pub release_date: Option<String>,
     /// The duration of the track in a formatted MM:SS string.
     pub duration: Option<String>,
+    /// Genres, then free-form tags, voted on the recording, most voted first.
+    #[serde(default)]
+    pub genres: Vec<String>,
+}
+
+impl Track {
+    /// The duration in seconds, `None` when MusicBrainz has none.
+    pub fn duration_secs(&self) -> Option<i32> {
+        let (minutes, seconds) = self.duration.as_deref()?.split_once(':')?;
+        Some(minutes.parse::<i32>().ok()? * 60 + seconds.parse::<i32>().ok()?)
+    }
 }
 
 /// A detailed structure to hold search results for an album.
@@ -37,8 +93,14 @@ pub struct Album {
     pub title: String,
     /// A formatted string of the artist(s).
     pub artist: String,
+    /// The MusicBrainz Identifier of the first credited artist.
+    #[serde(default)]
+    pub artist_id: Option<String>,
     /// The release date of the album (YYYY-MM-DD).
     pub release_date: Option<String>,
+    /// Genres, then free-form tags, voted on the album, most voted first.
+    #[serde(default)]
+    pub genres: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -46,3 +108,115 @@ pub struct AlbumWithTracks {
     pub album: Album,
     pub tracks: Vec<Track>,
 }
+
+/// Words in a release's title or MusicBrainz comment, or in a file's path,
+/// that tell one edition of an album from another.
+pub const EDITION_KEYWORDS: &[&str] = &[
+    "remaster",
+    "deluxe",
+    "expanded",
+    "anniversary",
+    "reissue",
+    "bonus",
+    "special edition",
+    "collector",
+];
+
+/// The spellings of an edition keyword found in paths, the keyword first:
+/// "remaster", "remastered", "remasters".
+pub fn keyword_forms(keyword: &'static str) -> Vec<&'static str> {
+    let inflections: &[&'static str] = match keyword {
+        "remaster" => &["remastered", "remasters"],
+        "reissue" => &["reissued", "reissues"],
+        "anniversary" => &["anniversaries"],
+        "collector" => &["collectors"],
+        _ => &[],
+    };
+    std::iter::once(keyword)
+        .chain(inflections.iter().copied())
+        .collect()
+}
+
+/// The [`EDITION_KEYWORDS`] found in `text` as whole words, in any of their
+/// [`keyword_forms`].
+pub fn edition_keywords(text: &str) -> Vec<&'static str> {
+    let words: Vec<String> = text
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect();
+    let text = format!(" {} ", words.join(" "));
+    EDITION_KEYWORDS
+        .iter()
+        .copied()
+        .filter(|keyword| {
+            keyword_forms(keyword)
+                .iter()
+                .any(|form| text.contains(&format!(" {form} ")))
+        })
+        .collect()
+}
+
+/// One release of an album's release group: the original, a remaster, a
+/// deluxe or regional edition.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Edition {
+    /// The MusicBrainz Identifier (MBID) of the release.
+    pub id: String,
+    pub title: String,
+    /// Free-text comment from MusicBrainz, e.g. "2009 remaster".
+    pub disambiguation: Option<String>,
+    /// The release date (YYYY-MM-DD, or a prefix of it).
+    pub date: Option<String>,
+    pub country: Option<String>,
+}
+
+impl Edition {
+    /// The [`EDITION_KEYWORDS`] in the release's title or comment.
+    pub fn keywords(&self) -> Vec<&'static str> {
+        edition_keywords(&format!(
+            "{} {}",
+            self.title,
+            self.disambiguation.as_deref().unwrap_or_default()
+        ))
+    }
+
+    /// "1997, GB, 2009 remaster", leaving out what MusicBrainz lacks.
+    pub fn label(&self) -> String {
+        let year = self.date.as_deref().map(|d| d.get(..4).unwrap_or(d));
+        [
+            year,
+            self.country.as_deref(),
+            self.disambiguation.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// Which edition of an album is opened by default.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct EditionPreference {
+    /// Country code (e.g. "GB") whose earliest official release is preferred.
+    /// The album's original release otherwise.
+    pub country: Option<String>,
+}
+
+impl EditionPreference {
+    /// The preferred one of `editions`, given oldest first.
+    pub fn pick<'a>(&self, editions: &'a [Edition]) -> Option<&'a Edition> {
+        self.country
+            .as_deref()
+            .and_then(|country| {
+                editions.iter().find(|e| {
+                    e.country
+                        .as_deref()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(country))
+                })
+            })
+            .or_else(|| editions.first())
+    }
+}
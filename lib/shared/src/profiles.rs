@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::slskd::{BitrateCutoffs, ScoringStrategyKind, SearchFilters};
+
+/// Id of the profile used when none is chosen.
+pub const DEFAULT_PROFILE_ID: &str = "balanced";
+
+/// A named bundle of search settings: how sources are ranked, which formats
+/// are kept, how complete an album must be and whether the best candidate is
+/// picked automatically.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityProfile {
+    /// The preset name for built-in profiles, a UUID for user profiles.
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub scoring: ScoringStrategyKind,
+    /// Lowercase extensions to keep. Every audio format is kept when empty.
+    pub formats: Vec<String>,
+    /// Share of the album's tracks a candidate must have, in `0.0..=1.0`.
+    pub min_completeness: f64,
+    /// Preselect the tracks of the best candidate in the results.
+    pub auto_pick: bool,
+    /// Built-in presets cannot be edited, only cloned.
+    pub built_in: bool,
+}
+
+impl QualityProfile {
+    fn preset(
+        id: &str,
+        name: &str,
+        description: &str,
+        scoring: ScoringStrategyKind,
+        formats: &[&str],
+        min_completeness: f64,
+        auto_pick: bool,
+    ) -> QualityProfile {
+        QualityProfile {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            scoring,
+            formats: formats.iter().map(|f| f.to_string()).collect(),
+            min_completeness,
+            auto_pick,
+            built_in: true,
+        }
+    }
+
+    /// The profiles shipped with Soulbeet.
+    pub fn presets() -> Vec<QualityProfile> {
+        vec![
+            QualityProfile::preset(
+                DEFAULT_PROFILE_ID,
+                "Balanced",
+                "Best quality available, complete albums only",
+                ScoringStrategyKind::QualityFirst,
+                &[],
+                1.0,
+                false,
+            ),
+            QualityProfile::preset(
+                "fastest",
+                "Fastest",
+                "Sources that will finish soonest, whatever the format",
+                ScoringStrategyKind::AvailabilityFirst,
+                &[],
+                1.0,
+                false,
+            ),
+            QualityProfile::preset(
+                "archivist",
+                "Archivist",
+                "FLAC only, full albums",
+                ScoringStrategyKind::QualityFirst,
+                &["flac"],
+                1.0,
+                false,
+            ),
+            QualityProfile::preset(
+                "commuter",
+                "Commuter",
+                "Smallest good-quality files, best pick preselected",
+                ScoringStrategyKind::SizeFirst,
+                &["mp3", "m4a", "aac", "ogg"],
+                1.0,
+                true,
+            ),
+            QualityProfile::preset(
+                "completionist",
+                "Completionist",
+                "Accepts albums with 80% of the tracks or more, best pick preselected",
+                ScoringStrategyKind::QualityFirst,
+                &[],
+                0.8,
+                true,
+            ),
+        ]
+    }
+
+    pub fn find_preset(id: &str) -> Option<QualityProfile> {
+        QualityProfile::presets().into_iter().find(|p| p.id == id)
+    }
+
+    /// Search filters of the profile, on top of the user's bitrate cutoffs.
+    pub fn filters(&self, min_bitrates: BitrateCutoffs) -> SearchFilters {
+        SearchFilters {
+            min_bitrates,
+            formats: self.formats.clone(),
+            min_completeness: self.min_completeness,
+            ..SearchFilters::default()
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
+use crate::musicbrainz::EDITION_KEYWORDS;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadRequest {
     pub username: String,
@@ -11,10 +15,17 @@ pub struct DownloadRequest {
     pub file_size: i64,
 }
 
+/// Transfer ID recorded for a file slskd queued without telling its ID.
+pub const PENDING_TRANSFER_ID: &str = "pending";
+
 #[derive(Serialize, Deserialize)]
 pub struct DownloadResponse {
     pub id: String,
+    /// Spelling slskd accepted, which differs from the requested one when it
+    /// went through on a retry.
     pub filename: String,
+    /// Filename as it was requested, to find the file this download is for.
+    pub original_filename: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -26,6 +37,12 @@ pub enum DownloadState {
     Aborted,
     Cancelled,
     Errored,
+    Rejected,
+    TimedOut,
+    /// Alongside `Queued`, waiting in the uploader's queue.
+    Remotely,
+    /// Alongside `Queued`, waiting in slskd's own queue.
+    Locally,
     Unknown(String),
 }
 
@@ -39,11 +56,29 @@ impl From<String> for DownloadState {
             "Aborted" => DownloadState::Aborted,
             "Cancelled" => DownloadState::Cancelled,
             "Errored" => DownloadState::Errored,
+            "Rejected" => DownloadState::Rejected,
+            "TimedOut" => DownloadState::TimedOut,
+            "Remotely" => DownloadState::Remotely,
+            "Locally" => DownloadState::Locally,
             _ => DownloadState::Unknown(s),
         }
     }
 }
 
+impl DownloadState {
+    /// Outcomes slskd reports for a transfer that did not go through.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            DownloadState::Aborted
+                | DownloadState::Cancelled
+                | DownloadState::Errored
+                | DownloadState::Rejected
+                | DownloadState::TimedOut
+        )
+    }
+}
+
 // The exact structure of a single file entry
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -75,12 +110,50 @@ pub struct FileEntry {
     pub remaining_time: Option<String>,
     #[serde(default)]
     pub exception: Option<String>,
+    /// Place in the uploader's queue, as of the last time slskd asked.
+    #[serde(default)]
+    pub place_in_queue: Option<u32>,
 }
 
 impl FileEntry {
     pub fn get_state(&self) -> Vec<DownloadState> {
         self.state.clone()
     }
+
+    /// slskd reports compound states such as "Completed, Succeeded" or
+    /// "Completed, Errored": `Completed` only means the transfer is over.
+    pub fn is_finished(&self) -> bool {
+        self.state.iter().any(|s| {
+            matches!(s, DownloadState::Completed | DownloadState::Succeeded) || s.is_failure()
+        })
+    }
+
+    pub fn is_succeeded(&self) -> bool {
+        self.state.contains(&DownloadState::Succeeded)
+    }
+
+    pub fn is_in_progress(&self) -> bool {
+        self.state.contains(&DownloadState::InProgress)
+    }
+
+    /// Waiting in the uploader's queue, as opposed to slskd's own.
+    pub fn is_queued_remotely(&self) -> bool {
+        self.state.contains(&DownloadState::Queued) && self.state.contains(&DownloadState::Remotely)
+    }
+
+    /// Why slskd gave up on this transfer (e.g. "File not shared."), verbatim.
+    /// `None` while the transfer is still running or if it succeeded.
+    pub fn failure_reason(&self) -> Option<String> {
+        let failed = self.state.iter().any(DownloadState::is_failure);
+        if !failed || self.is_succeeded() {
+            return None;
+        }
+
+        self.exception
+            .clone()
+            .filter(|e| !e.trim().is_empty())
+            .or_else(|| Some(self.state_description.clone()))
+    }
 }
 
 fn deserialize_download_state<'de, D>(deserializer: D) -> Result<Vec<DownloadState>, D::Error>
@@ -140,7 +213,418 @@ impl<'de> Deserialize<'de> for FlattenedFiles {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// A file in a user's shares.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedFile {
+    /// Full remote path, usable to download the file.
+    pub filename: String,
+    pub size: i64,
+    pub bitrate: Option<i32>,
+    pub duration: Option<i32>,
+}
+
+/// A folder in a user's shares, with its files and subfolders.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SharedDirectory {
+    /// Last segment of the path.
+    pub name: String,
+    /// Full remote path, with `\` separators.
+    pub path: String,
+    pub files: Vec<SharedFile>,
+    pub children: Vec<SharedDirectory>,
+}
+
+impl SharedDirectory {
+    /// Number of files in this folder and all of its subfolders.
+    pub fn total_files(&self) -> usize {
+        self.files.len()
+            + self
+                .children
+                .iter()
+                .map(SharedDirectory::total_files)
+                .sum::<usize>()
+    }
+}
+
+/// The Soulseek account slskd logs in with. The password is never read back.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoulseekAccount {
+    pub username: String,
+    /// Port other peers connect to, which must be reachable from outside.
+    pub listen_port: Option<u16>,
+}
+
+/// Connection of slskd to the Soulseek server.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerState {
+    /// slskd's own description, e.g. "Connected, LoggedIn".
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub is_connected: bool,
+    #[serde(default)]
+    pub is_logged_in: bool,
+    /// Soulseek username slskd is logged in as.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Folders and files slskd shares, as of its last scan.
+    #[serde(default)]
+    pub shared_directories: Option<u64>,
+    #[serde(default)]
+    pub shared_files: Option<u64>,
+}
+
+/// Built-in ranking policies, selectable per search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoringStrategyKind {
+    #[default]
+    QualityFirst,
+    AvailabilityFirst,
+    SizeFirst,
+}
+
+/// How much each signal counts in an album's score. Only their ratios
+/// matter, a weight of 0 leaves the signal out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoringWeights {
+    /// How well the files match the searched artist, album and tracks.
+    pub match_score: f64,
+    /// Share of the album's tracks that were found.
+    pub completeness: f64,
+    /// Format and bitrate of the files, as the strategy scores them.
+    pub format: f64,
+    /// Free upload slot, upload speed and queue length of the uploader.
+    pub availability: f64,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        ScoringWeights {
+            match_score: 0.3,
+            completeness: 0.3,
+            format: 0.4,
+            availability: 0.0,
+        }
+    }
+}
+
+impl ScoringWeights {
+    pub fn total(&self) -> f64 {
+        self.match_score + self.completeness + self.format + self.availability
+    }
+}
+
+/// Extra file score for files at or above a bitrate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BitrateBonus {
+    /// In kbps.
+    pub min_bitrate: i32,
+    pub bonus: f64,
+}
+
+/// Share of the match score lost by a file when only one of its title and
+/// the searched one has a version suffix, or when it names another edition
+/// than the one picked.
+pub const DEFAULT_SUFFIX_PENALTY: f64 = 0.05;
+
+/// How version suffixes of titles are handled when matching files. A
+/// trailing part of a title holding one of the phrases, as in "Song (2011
+/// Remaster)" or "Album - Deluxe Edition", which file and folder names often
+/// lack, is left out of both titles before they are compared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleSuffixes {
+    /// Lowercase phrases marking a version suffix, the [`EDITION_KEYWORDS`]
+    /// by default.
+    pub phrases: Vec<String>,
+    /// Share of the match score lost when the suffixes of the file's and the
+    /// searched titles differ, in `0.0..=1.0`.
+    pub penalty: f64,
+}
+
+impl Default for TitleSuffixes {
+    fn default() -> Self {
+        TitleSuffixes {
+            phrases: EDITION_KEYWORDS.iter().map(|s| s.to_string()).collect(),
+            penalty: DEFAULT_SUFFIX_PENALTY,
+        }
+    }
+}
+
+/// Adjustments to how a scoring strategy ranks albums, saved by a user or
+/// picked for one search. The default leaves the strategy as it is.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    /// Replaces the strategy's weights.
+    #[serde(default)]
+    pub weights: Option<ScoringWeights>,
+    /// Files matching the search worse than this are dropped, in `0.0..=1.0`.
+    #[serde(default)]
+    pub min_match_score: Option<f64>,
+    /// Only the highest bonus a file reaches counts.
+    #[serde(default)]
+    pub bitrate_bonuses: Vec<BitrateBonus>,
+    /// Lowercase phrases marking a version suffix to ignore at the end of
+    /// track and album titles, on top of the [`EDITION_KEYWORDS`].
+    #[serde(default)]
+    pub title_suffixes: Vec<String>,
+    /// Replaces [`DEFAULT_SUFFIX_PENALTY`], in `0.0..=1.0`.
+    #[serde(default)]
+    pub suffix_penalty: Option<f64>,
+}
+
+impl ScoringConfig {
+    pub fn is_default(&self) -> bool {
+        self == &ScoringConfig::default()
+    }
+
+    /// These settings, with those `other` sets replacing them.
+    pub fn merged(&self, other: &ScoringConfig) -> ScoringConfig {
+        ScoringConfig {
+            weights: other.weights.or(self.weights),
+            min_match_score: other.min_match_score.or(self.min_match_score),
+            bitrate_bonuses: if other.bitrate_bonuses.is_empty() {
+                self.bitrate_bonuses.clone()
+            } else {
+                other.bitrate_bonuses.clone()
+            },
+            title_suffixes: if other.title_suffixes.is_empty() {
+                self.title_suffixes.clone()
+            } else {
+                other.title_suffixes.clone()
+            },
+            suffix_penalty: other.suffix_penalty.or(self.suffix_penalty),
+        }
+    }
+
+    /// The default [`TitleSuffixes`] with these settings on top.
+    pub fn title_suffixes(&self) -> TitleSuffixes {
+        let mut suffixes = TitleSuffixes::default();
+        suffixes.phrases.extend(self.title_suffixes.iter().cloned());
+        if let Some(penalty) = self.suffix_penalty {
+            suffixes.penalty = penalty;
+        }
+        suffixes
+    }
+
+    /// Bonus earned by a file of `bitrate`, 0 when it is unknown.
+    pub fn bitrate_bonus(&self, bitrate: Option<i32>) -> f64 {
+        let Some(bitrate) = bitrate else {
+            return 0.0;
+        };
+        self.bitrate_bonuses
+            .iter()
+            .filter(|b| bitrate >= b.min_bitrate)
+            .map(|b| b.bonus)
+            .reduce(f64::max)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Minimum acceptable bitrate in kbps per format, keyed by lowercase
+/// extension (e.g. `mp3` → 256). Formats without an entry are not limited.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BitrateCutoffs(pub HashMap<String, i32>);
+
+impl BitrateCutoffs {
+    /// Whether a file of `format` at `bitrate` meets the cutoff. Files whose
+    /// bitrate is not reported are kept, since many peers do not share it.
+    pub fn allows(&self, format: &AudioFormat, bitrate: Option<i32>) -> bool {
+        match (self.0.get(format.extension()), bitrate) {
+            (Some(min), Some(bitrate)) => bitrate >= *min,
+            _ => true,
+        }
+    }
+}
+
+/// Place of a search waiting for the slskd search rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SearchQueuePosition {
+    /// 1 for the next search to start.
+    pub position: usize,
+    /// Rough wait before the search starts.
+    pub eta_seconds: i64,
+}
+
+/// How many searches may start per window, to stay clear of Soulseek server
+/// bans for searching too often.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SearchRateLimit {
+    pub max_searches: usize,
+    pub window_seconds: i64,
+}
+
+impl Default for SearchRateLimit {
+    fn default() -> Self {
+        SearchRateLimit {
+            max_searches: 35,
+            window_seconds: 220,
+        }
+    }
+}
+
+/// Where the search rate limit stands right now.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchRateUsage {
+    pub limit: SearchRateLimit,
+    /// Searches started within the current window.
+    pub used: usize,
+    /// Searches waiting for a slot.
+    pub waiting: usize,
+    /// Seconds until the oldest search leaves the window, `None` when no
+    /// search counts against it.
+    pub next_slot_seconds: Option<i64>,
+}
+
+/// Why an album search found nothing, from the files it left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchDiagnosis {
+    /// Users who answered with audio files.
+    pub users: usize,
+    pub audio_files: usize,
+    pub reasons: Vec<EmptySearchReason>,
+    pub suggestions: Vec<SearchSuggestion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EmptySearchReason {
+    /// Nobody answered with audio files.
+    NoResponses,
+    /// Files too far from the searched artist, album or tracks.
+    NoMatch { files: usize },
+    /// Matching files of a format the quality profile leaves out.
+    FormatExcluded { format: String, files: usize },
+    /// Matching files below the minimum bitrate of their format.
+    BitrateTooLow { format: String, files: usize },
+    /// Matching files of users without a free slot or below the minimum speed.
+    UploaderTooSlow { files: usize },
+    /// The most complete album had `found` of the `required` tracks.
+    Incomplete { found: usize, required: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SearchSuggestion {
+    /// Search Soulseek as typed, without matching files to the album.
+    RawSearch,
+    /// Use a quality profile allowing these formats.
+    AllowFormats(Vec<String>),
+    /// Lower the minimum bitrates of these formats.
+    LowerBitrateCutoffs(Vec<String>),
+    /// Use a quality profile accepting albums with `found` of `expected` tracks.
+    AcceptPartial { found: usize, expected: usize },
+}
+
+/// Which files and albums a search keeps, before ranking.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchFilters {
+    pub min_bitrates: BitrateCutoffs,
+    /// Lowercase extensions to keep. Every audio format is kept when empty.
+    pub formats: Vec<String>,
+    /// Share of the expected tracks an album must have, in `0.0..=1.0`.
+    pub min_completeness: f64,
+    /// Soulseek users whose files are all left out.
+    #[serde(default)]
+    pub blocked_users: Vec<String>,
+    /// Leaves out users with no free upload slot, whose files would wait in a queue.
+    #[serde(default)]
+    pub free_upload_slot_only: bool,
+    /// Leaves out users uploading slower than this, in bytes per second.
+    #[serde(default)]
+    pub min_upload_speed: Option<i32>,
+    /// Leaves out files below this bitrate in kbps, whatever their format.
+    /// Files not reporting a bitrate are kept.
+    #[serde(default)]
+    pub min_bitrate: Option<i32>,
+    /// Words added to the Soulseek query as `-word`, leaving out every
+    /// result whose path contains them.
+    #[serde(default)]
+    pub excluded_terms: Vec<String>,
+}
+
+impl Default for SearchFilters {
+    fn default() -> Self {
+        SearchFilters {
+            min_bitrates: BitrateCutoffs::default(),
+            formats: Vec::new(),
+            min_completeness: 1.0,
+            blocked_users: Vec::new(),
+            free_upload_slot_only: false,
+            min_upload_speed: None,
+            min_bitrate: None,
+            excluded_terms: Vec::new(),
+        }
+    }
+}
+
+impl SearchFilters {
+    /// Whether a file of `format` at `bitrate` passes the format and bitrate filters.
+    pub fn allows(&self, format: &AudioFormat, bitrate: Option<i32>) -> bool {
+        let format_allowed = self.formats.is_empty() || self.formats.iter().any(|f| format.is(f));
+        let bitrate_allowed = match (self.min_bitrate, bitrate) {
+            (Some(min), Some(bitrate)) => bitrate >= min,
+            _ => true,
+        };
+        format_allowed && bitrate_allowed && self.min_bitrates.allows(format, bitrate)
+    }
+
+    /// Whether a user's slots or speed leave all their files out.
+    pub fn skips_uploader(&self, has_free_upload_slot: bool, upload_speed: i32) -> bool {
+        (self.free_upload_slot_only && !has_free_upload_slot)
+            || self.min_upload_speed.is_some_and(|min| upload_speed < min)
+    }
+
+    /// Whether `username`'s files are left out.
+    pub fn blocks(&self, username: &str) -> bool {
+        self.blocked_users.iter().any(|u| u == username)
+    }
+
+    /// How many of `expected` tracks an album needs to be kept, at least one.
+    pub fn required_tracks(&self, expected: usize) -> usize {
+        // The epsilon keeps e.g. 0.8 * 10 from rounding up to 9.
+        let required = (self.min_completeness.clamp(0.0, 1.0) * expected as f64 - 1e-9).ceil();
+        (required as usize).clamp(1, expected.max(1))
+    }
+}
+
+/// Filters picked for one search, on top of its quality profile's.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchRefinements {
+    pub free_upload_slot_only: bool,
+    /// In KB/s.
+    pub min_upload_speed: Option<i32>,
+    /// In kbps.
+    pub min_bitrate: Option<i32>,
+    /// Lowercase extensions, replacing the profile's formats when not empty.
+    pub formats: Vec<String>,
+    /// Scoring adjustments for this search, on top of the user's.
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+    /// Leaves out files naming another edition of the album than the one
+    /// searched for (remaster, deluxe...), instead of only ranking them lower.
+    #[serde(default)]
+    pub exclude_other_editions: bool,
+}
+
+impl SearchRefinements {
+    pub fn is_empty(&self) -> bool {
+        self == &SearchRefinements::default()
+    }
+
+    pub fn apply(&self, filters: &mut SearchFilters) {
+        filters.free_upload_slot_only |= self.free_upload_slot_only;
+        if let Some(speed) = self.min_upload_speed {
+            filters.min_upload_speed = Some(speed.saturating_mul(1024));
+        }
+        if self.min_bitrate.is_some() {
+            filters.min_bitrate = self.min_bitrate;
+        }
+        if !self.formats.is_empty() {
+            filters.formats = self.formats.clone();
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MatchResult {
     pub guessed_artist: String,
     pub guessed_album: String,
@@ -149,6 +633,21 @@ pub struct MatchResult {
     pub album_score: f64,
     pub track_score: f64,
     pub total_score: f64,
+    /// Disc of a multi-disc album, from a `CD2/` or `Disc 2/` folder above
+    /// the file.
+    #[serde(default)]
+    pub disc: Option<u32>,
+    /// Number the file name starts with, as in "03 - Title.flac".
+    #[serde(default)]
+    pub track_number: Option<u32>,
+    /// How close the file's length is to the matched track's, `None` when
+    /// either is unknown. The total score is scaled down by it.
+    #[serde(default)]
+    pub duration_score: Option<f64>,
+    /// Version suffixes left out of the file's title and of the matched
+    /// track's before comparing them, e.g. "2011 Remaster".
+    #[serde(default)]
+    pub stripped_suffixes: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -159,16 +658,52 @@ pub struct TrackResult {
     pub title: String,
     pub album: String,
     pub match_score: f64,
+    /// How the file was matched, with each sub-score. `None` for files
+    /// picked from a raw search.
+    #[serde(default)]
+    pub match_details: Option<MatchResult>,
+    /// Disc the file is on, for albums shared as one folder per disc.
+    #[serde(default)]
+    pub disc_number: Option<u32>,
+    /// Position of the file on its disc, from its name.
+    #[serde(default)]
+    pub track_number: Option<u32>,
 }
 
 impl TrackResult {
     pub fn new(base: SearchResult, matched: MatchResult) -> Self {
         Self {
             base,
-            artist: matched.guessed_artist,
-            title: matched.matched_track,
-            album: matched.guessed_album,
+            artist: matched.guessed_artist.clone(),
+            title: matched.matched_track.clone(),
+            album: matched.guessed_album.clone(),
             match_score: matched.total_score,
+            disc_number: matched.disc,
+            track_number: matched.track_number,
+            match_details: Some(matched),
+        }
+    }
+
+    /// A file picked from a raw search, which was never matched to a track:
+    /// its title is the file name and its album the folder holding it.
+    pub fn from_file(base: SearchResult) -> Self {
+        let mut parts = base.filename.rsplit(['\\', '/']);
+        let file = parts.next().unwrap_or_default();
+        let title = Path::new(file)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(file)
+            .to_string();
+        let album = parts.next().unwrap_or_default().to_string();
+        Self {
+            base,
+            artist: String::new(),
+            title,
+            album,
+            match_score: 0.0,
+            match_details: None,
+            disc_number: None,
+            track_number: None,
         }
     }
 }
@@ -181,35 +716,305 @@ pub struct SearchResult {
     pub size: i64,
     pub bitrate: Option<i32>,
     pub duration: Option<i32>,
+    /// In Hz, as reported by the peer.
+    #[serde(default)]
+    pub sample_rate: Option<i32>,
+    /// In bits per sample, as reported by the peer.
+    #[serde(default)]
+    pub bit_depth: Option<i32>,
     pub has_free_upload_slot: bool,
     pub upload_speed: i32,
     pub queue_length: i32,
+    /// Other uploaders sharing what looks like the same file (same size,
+    /// bitrate and duration). The dispatcher may download from any of them.
+    #[serde(default)]
+    pub alternate_sources: Vec<FileSource>,
+}
+
+/// One uploader's copy of a file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSource {
+    pub username: String,
+    pub filename: String,
+    pub has_free_upload_slot: bool,
+    pub upload_speed: i32,
+    pub queue_length: i32,
+}
+
+/// Audio format of a shared file, told by its extension.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AudioFormat {
+    Flac,
+    Wav,
+    Ape,
+    WavPack,
+    /// AAC or lossless ALAC, the extension does not tell.
+    M4a,
+    Aac,
+    Ogg,
+    Mp3 {
+        /// In kbps, when the peer reports it.
+        bitrate: Option<i32>,
+    },
+    Wma,
+    /// Anything else, by its lowercase extension, "unknown" without one.
+    Other(String),
+}
+
+impl Default for AudioFormat {
+    fn default() -> Self {
+        AudioFormat::Other("unknown".to_string())
+    }
+}
+
+impl AudioFormat {
+    /// The format of files with `extension`, any case. The bitrate is only
+    /// kept for MP3s.
+    pub fn from_extension(extension: &str, bitrate: Option<i32>) -> Self {
+        match extension.to_lowercase().as_str() {
+            "flac" => AudioFormat::Flac,
+            "wav" => AudioFormat::Wav,
+            "ape" => AudioFormat::Ape,
+            "wv" => AudioFormat::WavPack,
+            "m4a" => AudioFormat::M4a,
+            "aac" => AudioFormat::Aac,
+            "ogg" => AudioFormat::Ogg,
+            "mp3" => AudioFormat::Mp3 { bitrate },
+            "wma" => AudioFormat::Wma,
+            other => AudioFormat::Other(other.to_string()),
+        }
+    }
+
+    /// The format of `filename`, a local or a remote path.
+    pub fn from_filename(filename: &str, bitrate: Option<i32>) -> Self {
+        let name = filename.rsplit(['\\', '/']).next().unwrap_or_default();
+        match Path::new(name).extension().and_then(|s| s.to_str()) {
+            Some(extension) => Self::from_extension(extension, bitrate),
+            None => Self::default(),
+        }
+    }
+
+    /// Lowercase extension, as profiles and bitrate cutoffs list formats.
+    pub fn extension(&self) -> &str {
+        match self {
+            AudioFormat::Flac => "flac",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Ape => "ape",
+            AudioFormat::WavPack => "wv",
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Aac => "aac",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Mp3 { .. } => "mp3",
+            AudioFormat::Wma => "wma",
+            AudioFormat::Other(extension) => extension,
+        }
+    }
+
+    /// Whether this is the format listed as `extension`, any case.
+    pub fn is(&self, extension: &str) -> bool {
+        self.extension().eq_ignore_ascii_case(extension)
+    }
+
+    /// Whether files of this format are audio at all.
+    pub fn is_audio(&self) -> bool {
+        !matches!(self, AudioFormat::Other(_))
+    }
+
+    /// Whether the format keeps all of the original audio. An `.m4a` may be
+    /// ALAC, but is counted as lossy.
+    pub fn is_lossless(&self) -> bool {
+        matches!(
+            self,
+            AudioFormat::Flac | AudioFormat::Wav | AudioFormat::Ape | AudioFormat::WavPack
+        )
+    }
+
+    /// How good the format is on its own, between 0 and 1.
+    pub fn weight(&self) -> f64 {
+        match self {
+            AudioFormat::Flac => 1.0,
+            AudioFormat::Wav => 0.85,
+            AudioFormat::Ape | AudioFormat::WavPack => 0.8,
+            AudioFormat::M4a | AudioFormat::Aac => 0.65,
+            AudioFormat::Ogg => 0.6,
+            AudioFormat::Mp3 { .. } => 0.55,
+            AudioFormat::Wma => 0.4,
+            AudioFormat::Other(_) => 0.3,
+        }
+    }
+}
+
+/// Formats are ordered by [`AudioFormat::weight`], then MP3s by bitrate.
+impl Ord for AudioFormat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.weight()
+            .total_cmp(&other.weight())
+            .then_with(|| match (self, other) {
+                (AudioFormat::Mp3 { bitrate: a }, AudioFormat::Mp3 { bitrate: b }) => a.cmp(b),
+                (AudioFormat::Other(a), AudioFormat::Other(b)) => a.cmp(b),
+                _ => self.extension().cmp(other.extension()),
+            })
+    }
+}
+
+impl PartialOrd for AudioFormat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
+impl fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AudioFormat::WavPack => write!(f, "WavPack"),
+            AudioFormat::Mp3 {
+                bitrate: Some(bitrate),
+            } => write!(f, "MP3 {bitrate}"),
+            other => write!(f, "{}", other.extension().to_uppercase()),
+        }
+    }
+}
+
+/// Extensions of cover scans shared with an album.
+const ARTWORK_EXTENSIONS: [&str; 3] = ["jpg", "jpeg", "png"];
+/// Extensions of the files a CD ripper writes next to the tracks.
+const RIP_LOG_EXTENSIONS: [&str; 2] = ["log", "cue"];
+
+/// Whether `filename`, a local or a remote path, is an image such as a
+/// cover scan.
+pub fn is_artwork_file(filename: &str) -> bool {
+    let format = AudioFormat::from_filename(filename, None);
+    ARTWORK_EXTENSIONS.iter().any(|ext| format.is(ext))
+}
+
+/// Lowest average bitrate, in kbps, of a real lossless file. Quiet, sparse or
+/// mono recordings compress down to about 250, a FLAC made from a low
+/// bitrate MP3 goes under it.
+const MIN_LOSSLESS_KBPS: f64 = 200.0;
+/// Size set aside for tags and embedded artwork before the bitrate of a lossy
+/// file is checked, in bytes. Large scans would pass for extra audio.
+const ARTWORK_HEADROOM_BYTES: i64 = 2 * 1024 * 1024;
+/// Highest average bitrate, in kbps, of the audio of an MP3, which tops out
+/// at 320, with some slack for frame headers.
+const MAX_MP3_KBPS: f64 = 340.0;
+/// Same for the other lossy formats, whose encoders go up to about 500.
+const MAX_LOSSY_KBPS: f64 = 600.0;
+/// Shorter files are skipped, their size is mostly tags and artwork.
+const MIN_TRANSCODE_CHECK_SECS: i32 = 60;
+/// Quality score taken off a suspected transcode.
+const TRANSCODE_PENALTY: f64 = 0.4;
+/// Share of the quality score only hi-res lossless files get, so they rank
+/// above CD quality ones that otherwise score the same.
+const HI_RES_BONUS: f64 = 0.05;
+
 impl SearchResult {
-    pub fn quality(&self) -> String {
-        Path::new(&self.filename)
-            .extension()
-            .and_then(|s| s.to_str())
-            .unwrap_or("unknown")
-            .to_lowercase()
+    /// The primary source of this result.
+    pub fn source(&self) -> FileSource {
+        FileSource {
+            username: self.username.clone(),
+            filename: self.filename.clone(),
+            has_free_upload_slot: self.has_free_upload_slot,
+            upload_speed: self.upload_speed,
+            queue_length: self.queue_length,
+        }
+    }
+
+    /// The primary source followed by all alternates.
+    pub fn sources(&self) -> Vec<FileSource> {
+        std::iter::once(self.source())
+            .chain(self.alternate_sources.iter().cloned())
+            .collect()
+    }
+
+    /// Returns a copy of this result downloading from `source` instead, keeping
+    /// every other known source as an alternate.
+    pub fn with_primary_source(&self, source: &FileSource) -> SearchResult {
+        let alternate_sources = self
+            .sources()
+            .into_iter()
+            .filter(|s| s.username != source.username || s.filename != source.filename)
+            .collect();
+        SearchResult {
+            username: source.username.clone(),
+            filename: source.filename.clone(),
+            has_free_upload_slot: source.has_free_upload_slot,
+            upload_speed: source.upload_speed,
+            queue_length: source.queue_length,
+            alternate_sources,
+            ..self.clone()
+        }
+    }
+
+    pub fn format(&self) -> AudioFormat {
+        AudioFormat::from_filename(&self.filename, self.bitrate)
+    }
+
+    /// Whether the file is an image, such as a cover scan.
+    pub fn is_artwork(&self) -> bool {
+        is_artwork_file(&self.filename)
+    }
+
+    /// Whether the file is the log or cue sheet of a CD rip.
+    pub fn is_rip_log(&self) -> bool {
+        let format = self.format();
+        RIP_LOG_EXTENSIONS.iter().any(|ext| format.is(ext))
+    }
+
+    /// Bit depth and sample rate of a lossless file, as in "24/96", when the
+    /// peer reports them.
+    pub fn resolution(&self) -> Option<String> {
+        if !self.format().is_lossless() {
+            return None;
+        }
+        let khz = self.sample_rate.map(|rate| rate as f64 / 1000.0);
+        match (self.bit_depth, khz) {
+            (Some(depth), Some(khz)) => Some(format!("{depth}/{khz}")),
+            (None, Some(khz)) => Some(format!("{khz} kHz")),
+            (Some(depth), None) => Some(format!("{depth}-bit")),
+            (None, None) => None,
+        }
+    }
+
+    /// Whether the file is lossless and holds more than CD audio, deeper than
+    /// 16 bits or sampled above 48 kHz.
+    pub fn is_hi_res(&self) -> bool {
+        self.format().is_lossless()
+            && (self.bit_depth.is_some_and(|depth| depth > 16)
+                || self.sample_rate.is_some_and(|rate| rate > 48_000))
+    }
+
+    /// Average bitrate from the size and length of the file, in kbps, which
+    /// unlike the reported bitrate cannot be made up.
+    pub fn measured_kbps(&self) -> Option<f64> {
+        let duration = self.duration.filter(|d| *d >= MIN_TRANSCODE_CHECK_SECS)?;
+        Some(self.size as f64 * 8.0 / duration as f64 / 1000.0)
+    }
+
+    /// Whether the file is too small for its lossless format, likely
+    /// transcoded from a lossy one, or too large for its lossy format even
+    /// with embedded artwork.
+    pub fn suspected_transcode(&self) -> bool {
+        let Some(kbps) = self.measured_kbps() else {
+            return false;
+        };
+        let duration = self.duration.unwrap_or_default().max(1) as f64;
+        let audio_kbps =
+            (self.size - ARTWORK_HEADROOM_BYTES).max(0) as f64 * 8.0 / duration / 1000.0;
+        match self.format() {
+            format if format.is_lossless() => kbps < MIN_LOSSLESS_KBPS,
+            AudioFormat::Mp3 { .. } => audio_kbps > MAX_MP3_KBPS,
+            AudioFormat::M4a => false, // AAC or lossless ALAC, no telling
+            AudioFormat::Aac | AudioFormat::Ogg | AudioFormat::Wma => audio_kbps > MAX_LOSSY_KBPS,
+            _ => false,
+        }
     }
 
     pub fn quality_score(&self) -> f64 {
-        let quality_weights: HashMap<&str, f64> = [
-            ("flac", 1.0),
-            ("wav", 0.85),
-            ("m4a", 0.65),
-            ("aac", 0.65),
-            ("mp3", 0.55),
-            ("ogg", 0.6),
-            ("wma", 0.4),
-        ]
-        .iter()
-        .cloned()
-        .collect();
-
-        let mut base_score = *quality_weights.get(self.quality().as_str()).unwrap_or(&0.3);
+        let mut base_score = self.format().weight();
+        if self.suspected_transcode() {
+            base_score -= TRANSCODE_PENALTY;
+        }
 
         if let Some(br) = self.bitrate {
             if br >= 320 {
@@ -231,10 +1036,23 @@ impl SearchResult {
             base_score -= 0.1;
         }
 
-        base_score.min(1.0)
+        let hi_res = if self.is_hi_res() { HI_RES_BONUS } else { 0.0 };
+        base_score.min(1.0 - HI_RES_BONUS) + hi_res
     }
 }
 
+/// How an uploader shares an album.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AlbumLayout {
+    /// One file per track.
+    #[default]
+    Tracks,
+    /// One audio file holding the whole album, and the cue sheet telling
+    /// where each track starts. The album's `tracks` are these two files,
+    /// split into tracks once downloaded.
+    CueImage,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AlbumResult {
     pub username: String,
@@ -244,18 +1062,152 @@ pub struct AlbumResult {
     pub track_count: usize,
     pub total_size: i64,
     pub tracks: Vec<TrackResult>,
-    pub dominant_quality: String,
+    pub dominant_quality: AudioFormat,
     pub has_free_upload_slot: bool,
     pub upload_speed: i32,
     pub queue_length: i32,
     pub score: f64,
+    /// Estimated seconds until the whole album is downloaded, see
+    /// [`AlbumResult::estimate_eta`].
+    #[serde(default)]
+    pub eta_seconds: Option<f64>,
+    /// Several tracks had the same file as their best match. Each still got
+    /// a file of its own, or none, but the picks are worth a look.
+    #[serde(default)]
+    pub ambiguous_matches: bool,
+    /// Tracks the search looked for, of which `track_count` were found.
+    #[serde(default)]
+    pub expected_tracks: usize,
+    /// Share of the files downloaded from the uploader before that arrived,
+    /// `None` when none were.
+    #[serde(default)]
+    pub uploader_reliability: Option<f64>,
+    /// Titles of the searched tracks this uploader has no file for, in
+    /// tracklist order. Only albums above the profile's minimum completeness
+    /// are kept with gaps.
+    #[serde(default)]
+    pub missing_tracks: Vec<String>,
+    #[serde(default)]
+    pub layout: AlbumLayout,
+    /// Other files shared next to the tracks: cover scans, and the log and
+    /// cue sheet of a CD rip.
+    #[serde(default)]
+    pub companions: Vec<SearchResult>,
 }
 
+/// Speed assumed for an uploader advertising none and never downloaded from, in bytes/s.
+const DEFAULT_UPLOAD_SPEED: f64 = 50_000.0;
+/// Fixed cost of each transfer (connection setup, slskd bookkeeping), in seconds.
+const PER_FILE_OVERHEAD_SECS: f64 = 3.0;
+/// Weight of the speed measured on past downloads against the advertised one.
+const MEASURED_SPEED_WEIGHT: f64 = 0.7;
+
 impl AlbumResult {
+    /// Estimates how long downloading the album takes, in seconds.
+    ///
+    /// The speed blends the uploader's advertised `upload_speed` with
+    /// `measured_speed`, the average of our past downloads from them, which is
+    /// trusted more since advertised speeds tend to be optimistic. Without a free
+    /// slot, every entry in the uploader's queue is assumed to be a file the size
+    /// of this album's average track, served at the same speed.
+    pub fn estimate_eta(&self, measured_speed: Option<f64>) -> f64 {
+        let advertised_speed = (self.upload_speed > 0).then_some(self.upload_speed as f64);
+        let speed = match (measured_speed, advertised_speed) {
+            (Some(measured), Some(advertised)) => {
+                MEASURED_SPEED_WEIGHT * measured + (1.0 - MEASURED_SPEED_WEIGHT) * advertised
+            }
+            (Some(speed), None) | (None, Some(speed)) => speed,
+            (None, None) => DEFAULT_UPLOAD_SPEED,
+        }
+        .max(1.0);
+
+        let track_count = self.track_count.max(1) as f64;
+        let average_track_size = self.total_size as f64 / track_count;
+        let queue_wait = if self.has_free_upload_slot {
+            0.0
+        } else {
+            self.queue_length.max(1) as f64 * average_track_size / speed
+        };
+
+        queue_wait + self.total_size as f64 / speed + track_count * PER_FILE_OVERHEAD_SECS
+    }
+
     pub fn size_mb(&self) -> i64 {
         self.total_size / (1024 * 1024)
     }
 
+    /// The album's cover scans, to download with its tracks.
+    pub fn artwork(&self) -> Vec<TrackResult> {
+        self.companions
+            .iter()
+            .filter(|file| file.is_artwork())
+            .cloned()
+            .map(TrackResult::from_file)
+            .collect()
+    }
+
+    /// Whether the uploader shares the log or cue sheet of their rip.
+    pub fn has_rip_log(&self) -> bool {
+        self.companions.iter().any(SearchResult::is_rip_log)
+    }
+
+    /// The [`SearchResult::resolution`] most of the album's files have.
+    pub fn resolution(&self) -> Option<String> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for resolution in self.tracks.iter().filter_map(|t| t.base.resolution()) {
+            *counts.entry(resolution).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .max_by_key(|&(_, count)| count)
+            .map(|(resolution, _)| resolution)
+    }
+
+    /// Other copies of the `chosen` tracks among search `results`, best result
+    /// first, at most `per_track` for each track. They are the fallbacks when a
+    /// chosen file fails to download.
+    pub fn alternate_sources(
+        chosen: &[TrackResult],
+        results: &[AlbumResult],
+        per_track: usize,
+    ) -> Vec<TrackResult> {
+        let mut alternates = Vec::new();
+        for track in chosen {
+            alternates.extend(
+                results
+                    .iter()
+                    .filter(|album| album.username != track.base.username)
+                    .flat_map(|album| album.tracks.iter())
+                    .filter(|other| {
+                        other.title.eq_ignore_ascii_case(&track.title)
+                            && other.base.filename != track.base.filename
+                    })
+                    .take(per_track)
+                    .cloned(),
+            );
+        }
+        alternates
+    }
+
+    /// Copies of this album's `missing_tracks` among search `results`, best
+    /// result first, at most `per_track` for each track. The download monitor
+    /// requests them once the album's own files are in, to fill its gaps.
+    pub fn gap_sources(&self, results: &[AlbumResult], per_track: usize) -> Vec<TrackResult> {
+        let mut sources = Vec::new();
+        for title in &self.missing_tracks {
+            sources.extend(
+                results
+                    .iter()
+                    .filter(|album| album.username != self.username)
+                    .flat_map(|album| album.tracks.iter())
+                    .filter(|other| other.title.eq_ignore_ascii_case(title))
+                    .take(per_track)
+                    .cloned(),
+            );
+        }
+        sources
+    }
+
     pub fn average_track_size_mb(&self) -> f64 {
         if self.track_count > 0 {
             self.size_mb() as f64 / self.track_count as f64
@@ -0,0 +1,75 @@
+//! Limits for the CPU and disk heavy processes run in the background, such as
+//! beets tagging and moving imported files, so they do not starve the rest of
+//! a small server.
+//!
+//! - `BACKGROUND_NICENESS`: `nice` level of the processes, 0 to 19.
+//! - `BACKGROUND_IO_PRIORITY`: `idle` or `best-effort` I/O scheduling class.
+//! - `BACKGROUND_CPU_BUDGET`: share of the CPU cores, in percent, that
+//!   background processes may keep busy at once. Each takes one core.
+
+use std::sync::LazyLock;
+use tokio::{
+    process::Command,
+    sync::{Semaphore, SemaphorePermit},
+};
+use tracing::info;
+
+/// Processes allowed to run at once under `BACKGROUND_CPU_BUDGET`.
+static WORKERS: LazyLock<Semaphore> = LazyLock::new(|| {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let budget = std::env::var("BACKGROUND_CPU_BUDGET")
+        .ok()
+        .and_then(|v| v.trim_end_matches('%').parse::<usize>().ok())
+        .unwrap_or(100);
+    let workers = (cores * budget).div_ceil(100).max(1);
+    info!("Running up to {workers} background processes at once ({budget}% of {cores} cores)");
+    Semaphore::new(workers)
+});
+
+fn niceness() -> Option<u8> {
+    std::env::var("BACKGROUND_NICENESS")
+        .ok()
+        .and_then(|v| v.parse::<u8>().ok())
+        .filter(|n| *n > 0)
+        .map(|n| n.min(19))
+}
+
+/// `ionice` class for the I/O priority.
+fn io_class() -> Option<&'static str> {
+    match std::env::var("BACKGROUND_IO_PRIORITY").ok()?.as_str() {
+        "idle" => Some("3"),
+        "best-effort" => Some("2"),
+        _ => None,
+    }
+}
+
+/// A command running `program` with the configured CPU and I/O priorities,
+/// through `ionice` and `nice` when they are set.
+pub fn command(program: &str) -> Command {
+    let mut wrapped: Vec<String> = Vec::new();
+    if let Some(class) = io_class() {
+        wrapped.extend(["ionice".into(), "-c".into(), class.into()]);
+    }
+    if let Some(niceness) = niceness() {
+        wrapped.extend(["nice".into(), "-n".into(), niceness.to_string()]);
+    }
+
+    match wrapped.split_first() {
+        Some((wrapper, args)) => {
+            let mut cmd = Command::new(wrapper);
+            cmd.args(args).arg(program);
+            cmd
+        }
+        None => Command::new(program),
+    }
+}
+
+/// Waits for room in the CPU budget. Hold the permit while the process runs.
+pub async fn worker() -> SemaphorePermit<'static> {
+    WORKERS
+        .acquire()
+        .await
+        .expect("the background worker semaphore is never closed")
+}
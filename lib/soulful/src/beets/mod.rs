@@ -1,35 +1,160 @@
 use std::{
-    io::{Error, Result},
-    path::Path,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+    process::ExitStatus,
+    time::Duration,
 };
 use tokio::process::Command;
-use tracing::info;
+use tracing::{info, warn};
 
-pub async fn import(sources: Vec<String>, target: &Path) -> Result<()> {
-    let config_path =
-        std::env::var("BEETS_CONFIG").unwrap_or_else(|_| "beets_config.yaml".to_string());
+use crate::background;
 
+fn config_path() -> String {
+    std::env::var("BEETS_CONFIG").unwrap_or_else(|_| "beets_config.yaml".to_string())
+}
+
+/// How long an import may run before its `beet` process is killed, from
+/// `IMPORT_TIMEOUT` in seconds. `None` when it is 0.
+fn import_timeout() -> Option<Duration> {
+    let seconds = std::env::var("IMPORT_TIMEOUT")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(1800);
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+/// Runs an import, killing it once it runs past the import timeout, e.g.
+/// when beets waits on a lock or a prompt despite `-q`. A killed import
+/// fails with [`ErrorKind::TimedOut`].
+async fn run_import(cmd: &mut Command) -> Result<ExitStatus> {
+    let mut child = cmd.kill_on_drop(true).spawn()?;
+    let Some(timeout) = import_timeout() else {
+        return child.wait().await;
+    };
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(status) => status,
+        Err(_) => {
+            warn!("Beet import still running after {:?}, killing it", timeout);
+            child.kill().await?;
+            Err(Error::new(
+                ErrorKind::TimedOut,
+                format!("Beet import did not finish within {}s", timeout.as_secs()),
+            ))
+        }
+    }
+}
+
+/// The beets library database a command works on.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Library(Option<PathBuf>);
+
+impl Library {
+    /// The library set in the beets configuration, shared by everyone.
+    pub fn shared() -> Self {
+        Library(None)
+    }
+
+    /// The library database at `path`, which beets creates on the first
+    /// import into it.
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Library(Some(path.into()))
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.0.as_deref()
+    }
+
+    /// A `beet` command loading the configuration and working on this library.
+    fn command(&self) -> Result<Command> {
+        let mut cmd = background::command("beet");
+        cmd.arg("-c").arg(config_path());
+        if let Some(path) = &self.0 {
+            if !path.exists() {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                info!("Initializing beets library {:?}", path);
+            }
+            cmd.arg("-l").arg(path);
+        }
+        Ok(cmd)
+    }
+}
+
+/// A track of the beets library, as listed by `beet ls`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LibraryItem {
+    pub track: Option<u32>,
+    pub artist: String,
+    pub album: String,
+    pub title: String,
+    /// Audio format as named by beets, e.g. "FLAC" or "MP3".
+    pub format: String,
+    /// Bitrate in kbps.
+    pub bitrate: Option<u32>,
+    pub mb_albumid: Option<String>,
+    pub mb_trackid: Option<String>,
+    /// Modification time of the file when beets last read it.
+    pub mtime: String,
+    pub path: PathBuf,
+}
+
+/// Loads the beets configuration, returning the error beets reports if it is
+/// missing or does not parse.
+pub async fn check_config() -> Result<()> {
+    let config_path = config_path();
+    if !Path::new(&config_path).is_file() {
+        return Err(Error::other(format!("{config_path} does not exist")));
+    }
+
+    let output = background::command("beet")
+        .arg("-c")
+        .arg(&config_path)
+        .arg("config")
+        .output()
+        .await?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Imports `sources` into `target`. Each of `fields` is a `field=value` pair
+/// written to every imported item.
+pub async fn import(
+    library: &Library,
+    sources: Vec<String>,
+    target: &Path,
+    fields: &[String],
+) -> Result<()> {
     info!(
         "Starting beet import for {} items to {:?} using config {}",
         sources.len(),
         target,
-        config_path
+        config_path()
     );
 
-    let mut cmd = Command::new("beet");
-    cmd.arg("-c")
-        .arg(&config_path)
-        .arg("-d") // destination directory
+    let _worker = background::worker().await;
+    let mut cmd = library.command()?;
+    cmd.arg("-d") // destination directory
         .arg(target)
         .arg("import")
         .arg("-s") // singleton mode
         .arg("-q"); // quiet mode: do not ask for confirmation
 
+    for field in fields {
+        cmd.arg("--set").arg(field);
+    }
+
     for source in sources {
         cmd.arg(source);
     }
 
-    let status = cmd.status().await?;
+    let status = run_import(&mut cmd).await?;
 
     if status.success() {
         info!("Beet import successful");
@@ -38,3 +163,125 @@ pub async fn import(sources: Vec<String>, target: &Path) -> Result<()> {
         Err(Error::other("Beet import failed"))
     }
 }
+
+/// Imports a single track, matched against the given MusicBrainz recording
+/// rather than guessed from its tags, and flagged so it lands under `Singles/`.
+pub async fn import_single(
+    library: &Library,
+    sources: Vec<String>,
+    target: &Path,
+    recording_id: &str,
+    fields: &[String],
+) -> Result<()> {
+    info!(
+        "Starting beet single import of recording {} to {:?} using config {}",
+        recording_id,
+        target,
+        config_path()
+    );
+
+    let _worker = background::worker().await;
+    let mut cmd = library.command()?;
+    cmd.arg("-d")
+        .arg(target)
+        .arg("import")
+        .arg("-s")
+        .arg("-q")
+        .arg("--search-id")
+        .arg(recording_id)
+        .arg("--set")
+        .arg("single=1") // picked up by the `single:1` path template
+        .args(fields.iter().flat_map(|field| ["--set", field.as_str()]))
+        .args(sources);
+    let status = run_import(&mut cmd).await?;
+
+    if status.success() {
+        info!("Beet single import successful");
+        Ok(())
+    } else {
+        Err(Error::other("Beet single import failed"))
+    }
+}
+
+/// Embeds `image` into every library item matching the beets `query`,
+/// replacing any artwork already present.
+pub async fn embed_art(library: &Library, image: &Path, query: &[String]) -> Result<()> {
+    info!("Embedding {:?} into items matching {:?}", image, query);
+
+    let _worker = background::worker().await;
+    let status = library
+        .command()?
+        .arg("embedart")
+        .arg("-y") // do not ask for confirmation
+        .arg("-f")
+        .arg(image)
+        .args(query)
+        .status()
+        .await?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::other("Beet embedart failed"))
+    }
+}
+
+/// Sets `fields` (as `field=value`) on every library item matching the beets
+/// `query`, and writes them to the files' tags.
+pub async fn modify(library: &Library, query: &[String], fields: &[String]) -> Result<()> {
+    let _worker = background::worker().await;
+    let status = library
+        .command()?
+        .arg("modify")
+        .arg("-y") // do not ask for confirmation
+        .arg("-w") // write tags even if import.write is off
+        .args(query)
+        .args(fields)
+        .status()
+        .await?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::other("Beet modify failed"))
+    }
+}
+
+/// Lists the library items matching the beets `query`.
+pub async fn list_items(library: &Library, query: &[String]) -> Result<Vec<LibraryItem>> {
+    let output = library
+        .command()?
+        .arg("ls")
+        .arg("-f")
+        .arg("$track\t$artist\t$album\t$title\t$format\t$bitrate\t$mb_albumid\t$mb_trackid\t$mtime\t$path")
+        .args(query)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(10, '\t');
+            let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+            Some(LibraryItem {
+                track: fields.next()?.parse().ok(),
+                artist: fields.next()?.to_string(),
+                album: fields.next()?.to_string(),
+                title: fields.next()?.to_string(),
+                format: fields.next()?.to_string(),
+                // Formatted by beets as e.g. "320kbps".
+                bitrate: fields.next()?.trim_end_matches("kbps").parse().ok(),
+                mb_albumid: optional(fields.next()?),
+                mb_trackid: optional(fields.next()?),
+                mtime: fields.next()?.to_string(),
+                path: PathBuf::from(fields.next()?),
+            })
+        })
+        .collect())
+}
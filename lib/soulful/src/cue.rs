@@ -0,0 +1,258 @@
+//! Albums shared as one audio file and a cue sheet, split into one file per
+//! track with `ffmpeg` so beets can match them like any other album.
+
+use std::{
+    io::{Error, Result},
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+use crate::background;
+
+/// Cue sheet timestamps count 75 frames per second.
+const FRAMES_PER_SECOND: f64 = 75.0;
+
+/// A track of a cue sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CueTrack {
+    pub number: u32,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    /// Where the track starts in the image, in seconds.
+    pub start: f64,
+}
+
+/// The album described by a cue sheet.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CueSheet {
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    /// Name of the image as written in the sheet, often out of date.
+    pub file: Option<String>,
+    pub tracks: Vec<CueTrack>,
+}
+
+/// The value of a command, unquoted.
+fn value(rest: &str) -> String {
+    let rest = rest.trim();
+    match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next().unwrap_or_default().to_string(),
+        None => rest.to_string(),
+    }
+}
+
+/// Seconds of an `mm:ss:ff` timestamp.
+fn timestamp(text: &str) -> Option<f64> {
+    let mut parts = text.trim().split(':').map(|p| p.parse::<u32>().ok());
+    let (Some(Some(minutes)), Some(Some(seconds)), Some(Some(frames))) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    Some(minutes as f64 * 60.0 + seconds as f64 + frames as f64 / FRAMES_PER_SECOND)
+}
+
+/// Reads a cue sheet. Tracks without an `INDEX 01` are left out.
+pub fn parse(content: &str) -> CueSheet {
+    let mut sheet = CueSheet::default();
+    let mut current: Option<(u32, Option<String>, Option<String>)> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match (command.to_uppercase().as_str(), current.as_mut()) {
+            ("TITLE", Some(track)) => track.1 = Some(value(rest)),
+            ("TITLE", None) => sheet.title = Some(value(rest)),
+            ("PERFORMER", Some(track)) => track.2 = Some(value(rest)),
+            ("PERFORMER", None) => sheet.performer = Some(value(rest)),
+            ("FILE", _) => {
+                // The type follows the name, e.g. `FILE "album.flac" WAVE`.
+                let name = match rest.trim().starts_with('"') {
+                    true => value(rest),
+                    false => rest
+                        .rsplit_once(' ')
+                        .map_or(rest, |(name, _)| name)
+                        .to_string(),
+                };
+                sheet.file.get_or_insert(name);
+            }
+            ("TRACK", _) => {
+                current = rest
+                    .split_whitespace()
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .map(|number| (number, None, None));
+            }
+            ("INDEX", Some((number, title, performer))) => {
+                let Some(("01", time)) = rest.trim().split_once(' ') else {
+                    continue;
+                };
+                if let Some(start) = timestamp(time) {
+                    sheet.tracks.push(CueTrack {
+                        number: *number,
+                        title: title.clone(),
+                        performer: performer.clone(),
+                        start,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    sheet
+}
+
+/// A file name without the characters file systems refuse.
+fn file_name(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Splits `image` into FLAC files along the tracks of the cue sheet at `cue`,
+/// tagged from the sheet. They are written next to the image, in track order,
+/// and the image and its sheet are removed once every track is.
+pub async fn split(cue: &Path, image: &Path) -> Result<Vec<PathBuf>> {
+    // Older rips are rarely UTF-8, a mangled accent does not matter to beets.
+    let sheet = parse(&String::from_utf8_lossy(&std::fs::read(cue)?));
+    if sheet.tracks.is_empty() {
+        return Err(Error::other(format!("{} lists no tracks", cue.display())));
+    }
+    let dir = image.parent().unwrap_or(Path::new("."));
+    info!(
+        "Splitting {} into {} tracks",
+        image.display(),
+        sheet.tracks.len()
+    );
+
+    let mut outputs = Vec::new();
+    for (i, track) in sheet.tracks.iter().enumerate() {
+        let title = track
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("Track {}", track.number));
+        let output = dir.join(format!("{:02} - {}.flac", track.number, file_name(&title)));
+
+        let _worker = background::worker().await;
+        let mut cmd = background::command("ffmpeg");
+        cmd.arg("-nostdin")
+            .arg("-y")
+            .arg("-loglevel")
+            .arg("error")
+            // Seeking before the input jumps to the track rather than decoding
+            // the whole image up to it.
+            .arg("-ss")
+            .arg(format!("{:.3}", track.start))
+            .arg("-i")
+            .arg(image);
+        if let Some(next) = sheet.tracks.get(i + 1) {
+            cmd.arg("-t")
+                .arg(format!("{:.3}", next.start - track.start));
+        }
+        // Drop the embedded cover and the image's own tags.
+        cmd.arg("-map").arg("0:a").arg("-map_metadata").arg("-1");
+        let tags = [
+            ("title", Some(title)),
+            (
+                "artist",
+                track.performer.clone().or(sheet.performer.clone()),
+            ),
+            ("album_artist", sheet.performer.clone()),
+            ("album", sheet.title.clone()),
+            (
+                "track",
+                Some(format!("{}/{}", track.number, sheet.tracks.len())),
+            ),
+        ];
+        for (key, value) in tags {
+            if let Some(value) = value {
+                cmd.arg("-metadata").arg(format!("{key}={value}"));
+            }
+        }
+        let result = cmd.arg("-c:a").arg("flac").arg(&output).output().await?;
+        if !result.status.success() {
+            return Err(Error::other(format!(
+                "ffmpeg failed on track {}: {}",
+                track.number,
+                String::from_utf8_lossy(&result.stderr).trim()
+            )));
+        }
+        outputs.push(output);
+    }
+
+    for split in [image, cue] {
+        if let Err(e) = std::fs::remove_file(split) {
+            warn!(
+                "Failed to remove {} after splitting: {}",
+                split.display(),
+                e
+            );
+        }
+    }
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHEET: &str = r#"REM GENRE Rock
+PERFORMER "The Band"
+TITLE "The Album"
+FILE "The Band - The Album.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "First"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Second"
+    PERFORMER "Guest"
+    INDEX 00 03:58:40
+    INDEX 01 04:00:37
+  TRACK 03 AUDIO
+    TITLE "No index"
+"#;
+
+    #[test]
+    fn parses_the_album_and_tracks() {
+        let sheet = parse(SHEET);
+        assert_eq!(sheet.title.as_deref(), Some("The Album"));
+        assert_eq!(sheet.performer.as_deref(), Some("The Band"));
+        assert_eq!(sheet.file.as_deref(), Some("The Band - The Album.flac"));
+        assert_eq!(
+            sheet.tracks,
+            [
+                CueTrack {
+                    number: 1,
+                    title: Some("First".to_string()),
+                    performer: None,
+                    start: 0.0,
+                },
+                CueTrack {
+                    number: 2,
+                    title: Some("Second".to_string()),
+                    performer: Some("Guest".to_string()),
+                    start: 240.0 + 37.0 / FRAMES_PER_SECOND,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_unquoted_file_names() {
+        let sheet = parse("FILE album image.wav WAVE\nTRACK 1 AUDIO\nINDEX 01 00:00:00");
+        assert_eq!(sheet.file.as_deref(), Some("album image.wav"));
+        assert_eq!(sheet.tracks.len(), 1);
+    }
+
+    #[test]
+    fn skips_bad_timestamps() {
+        assert_eq!(timestamp("01:02:03"), Some(62.0 + 3.0 / FRAMES_PER_SECOND));
+        assert_eq!(timestamp("01:02"), None);
+        assert!(parse("TRACK 01 AUDIO\nINDEX 01 xx:00:00").tracks.is_empty());
+    }
+}
@@ -11,6 +11,12 @@ pub enum SoulseekError {
     #[error("URL parsing error: {0}")]
     UrlParse(#[from] url::ParseError),
 
+    #[error("Invalid slskd configuration: {0}")]
+    Config(#[from] serde_yaml::Error),
+
+    #[error("slskd hub error: {0}")]
+    Hub(String),
+
     #[error("API error: {status} - {message}")]
     Api { status: u16, message: String },
 
@@ -20,8 +26,20 @@ pub enum SoulseekError {
     #[error("Search timed out")]
     SearchTimeout,
 
+    #[error("Search was cancelled")]
+    SearchCancelled,
+
     #[error("Could not find a username for the given download ID")]
     UsernameNotFound,
 }
 
 pub type Result<T> = std::result::Result<T, SoulseekError>;
+
+#[derive(Error, Debug)]
+pub enum MusicBrainzError {
+    #[error("MusicBrainz error: {0}")]
+    Request(#[from] musicbrainz_rs::Error),
+
+    #[error("MusicBrainz did not answer in time")]
+    Timeout,
+}
@@ -0,0 +1,45 @@
+//! Minimal Last.fm client, used for album tags.
+
+use serde::Deserialize;
+
+const API_URL: &str = "https://ws.audioscrobbler.com/2.0/";
+
+#[derive(Deserialize)]
+struct TopTagsResponse {
+    toptags: TopTags,
+}
+
+#[derive(Deserialize)]
+struct TopTags {
+    #[serde(default)]
+    tag: Vec<Tag>,
+}
+
+#[derive(Deserialize)]
+struct Tag {
+    name: String,
+}
+
+/// Top user tags of an album, most used first.
+pub async fn album_tags(
+    api_key: &str,
+    artist: &str,
+    album: &str,
+) -> Result<Vec<String>, reqwest::Error> {
+    let response: TopTagsResponse = reqwest::Client::new()
+        .get(API_URL)
+        .query(&[
+            ("method", "album.gettoptags"),
+            ("artist", artist),
+            ("album", album),
+            ("api_key", api_key),
+            ("format", "json"),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(response.toptags.tag.into_iter().map(|t| t.name).collect())
+}
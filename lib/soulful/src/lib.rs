@@ -1,4 +1,9 @@
+pub mod background;
 pub mod beets;
+pub mod cue;
 pub mod error;
+pub mod lastfm;
+pub mod lrclib;
 pub mod musicbrainz;
+pub mod provider;
 pub mod slskd;
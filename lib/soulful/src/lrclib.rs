@@ -0,0 +1,71 @@
+//! Minimal LRCLIB client, used for lyrics of imported tracks.
+
+use serde::Deserialize;
+
+const API_URL: &str = "https://lrclib.net/api/search";
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    #[serde(default)]
+    instrumental: bool,
+    plain_lyrics: Option<String>,
+    synced_lyrics: Option<String>,
+}
+
+/// Lyrics of a track. Synced lyrics are in LRC format, with timestamps.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Lyrics {
+    pub synced: Option<String>,
+    pub plain: Option<String>,
+}
+
+impl Lyrics {
+    /// Synced lyrics when there are some, plain ones otherwise.
+    pub fn best(&self) -> Option<&str> {
+        self.synced.as_deref().or(self.plain.as_deref())
+    }
+}
+
+/// Looks up the lyrics of a track, preferring a match with synced lyrics.
+/// `None` when LRCLIB has no lyrics for it, or the track is instrumental.
+pub async fn find_lyrics(
+    artist: &str,
+    title: &str,
+    album: &str,
+) -> Result<Option<Lyrics>, reqwest::Error> {
+    let records: Vec<Record> = reqwest::Client::new()
+        .get(API_URL)
+        .header(
+            "User-Agent",
+            concat!(
+                "Soulbeet/",
+                env!("CARGO_PKG_VERSION"),
+                " (https://github.com/terry90/soulbeet)"
+            ),
+        )
+        .query(&[
+            ("artist_name", artist),
+            ("track_name", title),
+            ("album_name", album),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let non_empty = |text: Option<String>| text.filter(|t| !t.trim().is_empty());
+    let mut lyrics: Vec<Lyrics> = records
+        .into_iter()
+        .filter(|record| !record.instrumental)
+        .map(|record| Lyrics {
+            synced: non_empty(record.synced_lyrics),
+            plain: non_empty(record.plain_lyrics),
+        })
+        .filter(|lyrics| lyrics.best().is_some())
+        .collect();
+
+    let synced = lyrics.iter().position(|l| l.synced.is_some()).unwrap_or(0);
+    Ok((!lyrics.is_empty()).then(|| lyrics.swap_remove(synced)))
+}
@@ -1,14 +1,45 @@
 use musicbrainz_rs::{
     entity::{
+        artist::{Artist as MusicBrainzArtist, ArtistSearchQuery},
         artist_credit::ArtistCredit,
+        genre::Genre,
         recording::{Recording, RecordingSearchQuery},
+        relations::RelationContent,
         release::{Release, ReleaseStatus},
         release_group::{ReleaseGroup, ReleaseGroupPrimaryType, ReleaseGroupSearchQuery},
+        tag::Tag,
     },
-    Fetch, MusicBrainzClient, Search,
+    Browse, Fetch, MusicBrainzClient, Search,
 };
-use shared::musicbrainz::{Album, AlbumWithTracks, SearchResult, Track};
-use std::{collections::HashSet, sync::OnceLock};
+use shared::musicbrainz::{
+    Album, AlbumWithTracks, Artist, Edition, SearchPage, SearchResult, Track,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::OnceLock,
+    time::Duration,
+};
+use tokio::{
+    sync::Mutex,
+    time::{sleep_until, timeout, Instant},
+};
+use tracing::warn;
+
+use crate::error::MusicBrainzError;
+
+/// Relationships between artists whose music tends to be alike, the closest
+/// first.
+const SIMILAR_RELATIONS: &[&str] = &[
+    "member of band",
+    "subgroup",
+    "collaboration",
+    "founder",
+    "tribute",
+];
+
+/// Members of a band whose other bands are looked up, one request each.
+const MAX_MEMBERS_FOLLOWED: usize = 5;
 
 // This ensures the client is initialized only once with a proper user agent.
 fn musicbrainz_client() -> &'static MusicBrainzClient {
@@ -22,6 +53,77 @@ fn musicbrainz_client() -> &'static MusicBrainzClient {
     })
 }
 
+/// MusicBrainz serves one request per second to each client.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Times a request turned away with a 503 is sent again, waiting twice as
+/// long each time.
+const MAX_RETRIES: u32 = 4;
+
+/// How long a request may take before it is given up on, so that one hung
+/// request does not hold up the lookups queued after it.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// When the next request to MusicBrainz may be sent. Requests take their turn
+/// under its lock in the order they came, so it is also the queue they are
+/// sent from.
+static NEXT_REQUEST: Mutex<Option<Instant>> = Mutex::const_new(None);
+
+/// Waits for the turn of a request starting `pause` after the last one, or
+/// after now if that is later.
+async fn take_turn(pause: Duration) {
+    let turn = {
+        let mut next_request = NEXT_REQUEST.lock().await;
+        let now = Instant::now();
+        let turn = next_request.map_or(now, |next| next.max(now)) + pause;
+        *next_request = Some(turn + MIN_REQUEST_INTERVAL);
+        turn
+    };
+    sleep_until(turn).await;
+}
+
+/// Whether MusicBrainz turned the request away for going over its rate limit.
+fn is_throttled(error: &musicbrainz_rs::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            if error.status() == Some(reqwest::StatusCode::SERVICE_UNAVAILABLE) {
+                return true;
+            }
+        }
+        source = error.source();
+    }
+    false
+}
+
+/// Sends `request` in its turn, a second after the one before it, so that
+/// concurrent searches never get the instance throttled. It is sent again
+/// after a growing pause when MusicBrainz answers 503 all the same, which the
+/// requests queued meanwhile wait out as well.
+async fn send<T, F, Fut>(request: F) -> Result<T, MusicBrainzError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, musicbrainz_rs::Error>>,
+{
+    let mut pause = Duration::ZERO;
+    let mut retries = 0;
+    loop {
+        take_turn(pause).await;
+        match timeout(REQUEST_TIMEOUT, request()).await {
+            Err(_) => return Err(MusicBrainzError::Timeout),
+            Ok(Err(e)) if retries < MAX_RETRIES && is_throttled(&e) => {
+                retries += 1;
+                pause = MIN_REQUEST_INTERVAL * 2u32.pow(retries);
+                warn!(
+                    "MusicBrainz is throttling requests, retrying in {}s",
+                    pause.as_secs()
+                );
+            }
+            Ok(result) => return Ok(result?),
+        }
+    }
+}
+
 /// Formats the artist credits into a single, comma-separated string.
 fn format_artist_credit(credits: &Option<Vec<ArtistCredit>>) -> String {
     credits
@@ -36,6 +138,33 @@ fn format_artist_credit(credits: &Option<Vec<ArtistCredit>>) -> String {
         .unwrap_or_else(|| "Unknown Artist".to_string())
 }
 
+/// Genres shown with a search result or album at most.
+const MAX_GENRES: usize = 5;
+
+/// Genre names, then tag names, each ordered by vote count, without repeats.
+fn genre_names(genres: &Option<Vec<Genre>>, tags: &Option<Vec<Tag>>) -> Vec<String> {
+    let mut genres = genres.clone().unwrap_or_default();
+    genres.sort_by_key(|g| std::cmp::Reverse(g.count));
+    let mut tags = tags.clone().unwrap_or_default();
+    tags.sort_by_key(|t| std::cmp::Reverse(t.count));
+
+    let mut seen = HashSet::new();
+    genres
+        .into_iter()
+        .map(|g| g.name)
+        .chain(tags.into_iter().map(|t| t.name))
+        .filter(|name| seen.insert(name.to_lowercase()))
+        .collect()
+}
+
+/// The MBID of the first credited artist.
+fn first_artist_id(credits: &Option<Vec<ArtistCredit>>) -> Option<String> {
+    credits
+        .as_ref()
+        .and_then(|credits| credits.first())
+        .map(|credit| credit.artist.id.clone())
+}
+
 /// Formats a duration from milliseconds to a MM:SS string.
 fn format_duration(duration_ms: &Option<u32>) -> Option<String> {
     duration_ms.map(|ms| {
@@ -53,14 +182,18 @@ pub enum SearchType {
 }
 
 /// Performs a refined search for music, prioritizing canonical releases.
+/// `offset` skips that many MusicBrainz results, to page through them
+/// `limit` at a time.
 pub async fn search(
     artist: &Option<String>,
     query: &str,
     search_type: SearchType,
     limit: u8,
-) -> Result<Vec<SearchResult>, musicbrainz_rs::Error> {
+    offset: u16,
+) -> Result<SearchPage, MusicBrainzError> {
     let client = musicbrainz_client();
     let mut results = Vec::new();
+    let count;
 
     match search_type {
         SearchType::Track => {
@@ -68,13 +201,18 @@ pub async fn search(
             if let Some(ref artist) = artist {
                 recording_query.artist_name(artist).and();
             }
-            let search_query = recording_query.recording(query).build();
+            let search_query = &recording_query.recording(query).build();
 
-            let search_results = Recording::search(search_query)
-                .limit(limit)
-                .with_releases()
-                .execute_with_client(client)
-                .await?;
+            let search_results = send(move || async move {
+                Recording::search(search_query.clone())
+                    .limit(limit)
+                    .offset(offset)
+                    .with_releases()
+                    .execute_with_client(client)
+                    .await
+            })
+            .await?;
+            count = search_results.count;
 
             let mut unique_tracks = HashSet::new();
 
@@ -104,6 +242,10 @@ pub async fn search(
                         album_title: first_release.map(|r| r.title.clone()),
                         release_date: first_release.and_then(|r| r.date.clone().map(|d| d.0)),
                         duration: format_duration(&recording.length),
+                        genres: genre_names(&recording.genres, &recording.tags)
+                            .into_iter()
+                            .take(MAX_GENRES)
+                            .collect(),
                     };
                     unique_tracks.insert(key);
                     results.push(SearchResult::Track(track));
@@ -115,53 +257,338 @@ pub async fn search(
             if let Some(ref artist) = artist {
                 album_query.artist(artist).and();
             }
-            let search_query = album_query.release_group(query).build();
+            let search_query = &album_query.release_group(query).build();
 
-            let search_results = ReleaseGroup::search(search_query)
-                .limit(limit)
-                .with_releases()
-                .execute_with_client(client)
-                .await?;
+            let search_results = send(move || async move {
+                ReleaseGroup::search(search_query.clone())
+                    .limit(limit)
+                    .offset(offset)
+                    .with_releases()
+                    .execute_with_client(client)
+                    .await
+            })
+            .await?;
+            count = search_results.count;
 
             for release_group in search_results.entities {
                 if release_group.primary_type != Some(ReleaseGroupPrimaryType::Album) {
                     continue;
                 }
-
-                if let Some(best_release) = release_group.releases.as_ref().and_then(|releases| {
-                    releases
-                        .iter()
-                        .filter(|r| r.status == Some(ReleaseStatus::Official))
-                        .min_by_key(|release| release.date.as_ref().map(|d| &d.0))
-                }) {
-                    // If no official release was found, take the first one available
-                    let final_release = best_release.clone();
-
-                    results.push(SearchResult::Album(Album {
-                        id: final_release.id.clone(),
-                        title: release_group.title.clone(),
-                        artist: format_artist_credit(&release_group.artist_credit),
-                        release_date: final_release.date.as_ref().map(|d| d.0.clone()),
-                    }));
+                if let Some(album) = canonical_album(&release_group) {
+                    results.push(SearchResult::Album(album));
                 }
             }
         }
     }
 
-    Ok(results)
+    Ok(SearchPage {
+        results,
+        count: count as u32,
+    })
 }
 
-/// Fetches a release (album) by its ID and returns it with its full tracklist.
-pub async fn find_album(release_id: &str) -> Result<AlbumWithTracks, musicbrainz_rs::Error> {
+/// The album a release group stands for: its earliest official release.
+fn canonical_album(release_group: &ReleaseGroup) -> Option<Album> {
+    let release = release_group.releases.as_ref().and_then(|releases| {
+        releases
+            .iter()
+            .filter(|r| r.status == Some(ReleaseStatus::Official))
+            .min_by_key(|release| release.date.as_ref().map(|d| &d.0))
+    })?;
+
+    Some(Album {
+        id: release.id.clone(),
+        title: release_group.title.clone(),
+        artist: format_artist_credit(&release_group.artist_credit),
+        artist_id: first_artist_id(&release_group.artist_credit),
+        release_date: release.date.as_ref().map(|d| d.0.clone()),
+        genres: genre_names(&release_group.genres, &release_group.tags)
+            .into_iter()
+            .take(MAX_GENRES)
+            .collect(),
+    })
+}
+
+/// The studio albums credited to `artist`, oldest first. Compilations, live
+/// albums and the like are left out, as are albums of similarly named artists.
+pub async fn artist_albums(artist: &str) -> Result<Vec<Album>, MusicBrainzError> {
+    let client = musicbrainz_client();
+    let search_query = &ReleaseGroupSearchQuery::query_builder()
+        .artist(artist)
+        .build();
+
+    let search_results = send(move || async move {
+        ReleaseGroup::search(search_query.clone())
+            .limit(100)
+            .with_releases()
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
+
+    let mut albums: Vec<Album> = search_results
+        .entities
+        .iter()
+        .filter(|rg| rg.primary_type == Some(ReleaseGroupPrimaryType::Album))
+        .filter(|rg| rg.secondary_types.is_empty())
+        .filter_map(canonical_album)
+        .filter(|album| album.artist.eq_ignore_ascii_case(artist))
+        .collect();
+    albums.sort_by(|a, b| a.release_date.cmp(&b.release_date));
+
+    Ok(albums)
+}
+
+/// Artists whose name matches `query`, best match first, `limit` of them
+/// after skipping `offset`.
+pub async fn search_artist(
+    query: &str,
+    limit: u8,
+    offset: u16,
+) -> Result<SearchPage, MusicBrainzError> {
+    let client = musicbrainz_client();
+    let search_query = &ArtistSearchQuery::query_builder().artist(query).build();
+
+    let search_results = send(move || async move {
+        MusicBrainzArtist::search(search_query.clone())
+            .limit(limit)
+            .offset(offset)
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
+
+    Ok(SearchPage {
+        results: search_results
+            .entities
+            .into_iter()
+            .map(|artist| SearchResult::Artist(to_artist(artist)))
+            .collect(),
+        count: search_results.count as u32,
+    })
+}
+
+fn to_artist(artist: MusicBrainzArtist) -> Artist {
+    Artist {
+        id: artist.id,
+        name: artist.name,
+        disambiguation: artist.disambiguation,
+        country: artist.country,
+    }
+}
+
+/// The artists related to the one with MBID `artist_mbid`, as it would be a
+/// short way to their music: the other bands of its members, or the bands of
+/// a person, its subgroups, collaborations and the like, the closest
+/// relationships first. The members of a band are left out.
+pub async fn similar_artists(artist_mbid: &str) -> Result<Vec<Artist>, MusicBrainzError> {
+    let related = related_artists(artist_mbid).await?;
+
+    // A band's members rarely make the music it is liked for on their own,
+    // their other bands are what comes closest.
+    let is_member = |r: &Related| r.relation == "member of band" && r.backward;
+    let members: Vec<String> = related
+        .iter()
+        .filter(|r| is_member(r))
+        .map(|r| r.artist.id.clone())
+        .take(MAX_MEMBERS_FOLLOWED)
+        .collect();
+    let mut bands = Vec::new();
+    for member in members {
+        bands.extend(
+            related_artists(&member)
+                .await?
+                .into_iter()
+                .filter(|r| r.relation == "member of band" && !r.backward),
+        );
+    }
+
+    let mut seen = HashSet::from([artist_mbid.to_string()]);
+    let mut artists: Vec<(usize, Artist)> = related
+        .into_iter()
+        .filter(|r| !is_member(r))
+        .chain(bands)
+        .filter_map(|r| {
+            let closeness = SIMILAR_RELATIONS.iter().position(|t| *t == r.relation)?;
+            Some((closeness, r.artist))
+        })
+        .filter(|(_, artist)| seen.insert(artist.id.clone()))
+        .collect();
+    artists.sort_by_key(|(closeness, _)| *closeness);
+
+    Ok(artists.into_iter().map(|(_, artist)| artist).collect())
+}
+
+/// An artist with a MusicBrainz relationship to another one.
+struct Related {
+    /// The relationship's type, e.g. "member of band".
+    relation: String,
+    /// This artist is the source of the relationship, e.g. a member of the
+    /// band for "member of band", rather than its target.
+    backward: bool,
+    artist: Artist,
+}
+
+/// The artists the one with MBID `artist_mbid` has a relationship with.
+async fn related_artists(artist_mbid: &str) -> Result<Vec<Related>, MusicBrainzError> {
+    let client = musicbrainz_client();
+    let artist = send(move || async move {
+        MusicBrainzArtist::fetch()
+            .id(artist_mbid)
+            .with_artist_relations()
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
+
+    Ok(artist
+        .relations
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|relation| match relation.content {
+            RelationContent::Artist(artist) => Some(Related {
+                relation: relation.relation_type,
+                backward: relation.direction == "backward",
+                artist: to_artist(*artist),
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+/// The studio albums of the artist with MBID `artist_mbid`, oldest first and
+/// undated ones, usually announced but not out yet, last. Unlike
+/// [`artist_albums`], release groups are browsed by the artist's id, so
+/// nothing from namesakes slips in and prolific artists are not cut short.
+pub async fn artist_releases(artist_mbid: &str) -> Result<Vec<Album>, MusicBrainzError> {
     let client = musicbrainz_client();
+    let mut release_groups: Vec<ReleaseGroup> = Vec::new();
+    let mut browsed = 0;
+    loop {
+        let offset = browsed as u16;
+        let page = send(move || async move {
+            ReleaseGroup::browse()
+                .by_artist(artist_mbid)
+                .with_artist_credits()
+                .with_tags()
+                .with_genres()
+                .limit(100)
+                .offset(offset)
+                .execute_with_client(client)
+                .await
+        })
+        .await?;
+        let fetched = page.entities.len();
+        browsed += fetched;
+        release_groups.extend(page.entities.into_iter().filter(|rg| {
+            rg.primary_type == Some(ReleaseGroupPrimaryType::Album) && rg.secondary_types.is_empty()
+        }));
+        if fetched == 0 || browsed >= page.count as usize {
+            break;
+        }
+    }
+
+    let mut earliest = earliest_releases(artist_mbid, &release_groups).await?;
+    let mut albums: Vec<Album> = release_groups
+        .iter()
+        .filter_map(|release_group| {
+            let release = earliest.remove(&release_group.id)?;
+            Some(Album {
+                id: release.id,
+                title: release_group.title.clone(),
+                artist: format_artist_credit(&release_group.artist_credit),
+                artist_id: first_artist_id(&release_group.artist_credit),
+                release_date: release_group
+                    .first_release_date
+                    .as_ref()
+                    .or(release.date.as_ref())
+                    .map(|d| d.0.clone())
+                    .filter(|date| !date.is_empty()),
+                genres: genre_names(&release_group.genres, &release_group.tags)
+                    .into_iter()
+                    .take(MAX_GENRES)
+                    .collect(),
+            })
+        })
+        .collect();
+    albums.sort_by(|a, b| match (&a.release_date, &b.release_date) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (a, b) => a.is_none().cmp(&b.is_none()),
+    });
 
-    // Fetch the release with recordings (tracks) and artist credits for the tracks.
-    let release = Release::fetch()
-        .id(release_id)
-        .with_recordings()
-        .with_artist_credits()
-        .execute_with_client(client)
+    Ok(albums)
+}
+
+/// The earliest official release found of each of `release_groups`, by
+/// release group id. The artist's releases are browsed until every release
+/// group has one, rather than through all the reissues of a prolific artist.
+async fn earliest_releases(
+    artist_mbid: &str,
+    release_groups: &[ReleaseGroup],
+) -> Result<HashMap<String, Release>, MusicBrainzError> {
+    let client = musicbrainz_client();
+    let wanted: HashSet<&str> = release_groups.iter().map(|rg| rg.id.as_str()).collect();
+    let mut earliest: HashMap<String, Release> = HashMap::new();
+    let mut browsed = 0;
+    while earliest.len() < wanted.len() {
+        let offset = browsed as u16;
+        let page = send(move || async move {
+            Release::browse()
+                .by_artist(artist_mbid)
+                .with_release_groups()
+                .limit(100)
+                .offset(offset)
+                .execute_with_client(client)
+                .await
+        })
         .await?;
+        let fetched = page.entities.len();
+        browsed += fetched;
+        for release in page.entities {
+            let Some(group_id) = release.release_group.as_ref().map(|rg| rg.id.clone()) else {
+                continue;
+            };
+            if release.status != Some(ReleaseStatus::Official)
+                || !wanted.contains(group_id.as_str())
+            {
+                continue;
+            }
+            // Undated releases are only kept when no dated one is known.
+            let date = |r: &Release| {
+                let date = r.date.as_ref().map(|d| d.0.clone());
+                (date.is_none(), date)
+            };
+            match earliest.get(&group_id) {
+                Some(kept) if date(kept) <= date(&release) => {}
+                _ => {
+                    earliest.insert(group_id, release);
+                }
+            }
+        }
+        if fetched == 0 || browsed >= page.count as usize {
+            break;
+        }
+    }
+    Ok(earliest)
+}
+
+/// Fetches a release (album) by its ID and returns it with its full tracklist.
+pub async fn find_album(release_id: &str) -> Result<AlbumWithTracks, MusicBrainzError> {
+    let client = musicbrainz_client();
+
+    // Fetch the release with recordings (tracks), artist credits for the
+    // tracks and its genres.
+    let release = send(move || async move {
+        Release::fetch()
+            .id(release_id)
+            .with_recordings()
+            .with_artist_credits()
+            .with_genres()
+            .with_tags()
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
 
     let mut tracks = Vec::new();
 
@@ -179,6 +606,7 @@ pub async fn find_album(release_id: &str) -> Result<AlbumWithTracks, musicbrainz
                             album_title: Some(release.title.clone()),
                             release_date: release.date.as_ref().map(|d| d.0.clone()),
                             duration: format_duration(&recording.length),
+                            genres: genre_names(&recording.genres, &recording.tags),
                         });
                     }
                 }
@@ -191,7 +619,12 @@ pub async fn find_album(release_id: &str) -> Result<AlbumWithTracks, musicbrainz
         id: release.id,
         title: release.title,
         artist: format_artist_credit(&release.artist_credit),
+        artist_id: first_artist_id(&release.artist_credit),
         release_date: release.date.map(|d| d.0),
+        genres: genre_names(&release.genres, &release.tags)
+            .into_iter()
+            .take(MAX_GENRES)
+            .collect(),
     };
 
     // Then, package it into the new struct along with the tracks.
@@ -199,3 +632,113 @@ pub async fn find_album(release_id: &str) -> Result<AlbumWithTracks, musicbrainz
 
     Ok(album_with_tracks)
 }
+
+/// The official releases of the album `release_id` belongs to, oldest first:
+/// the original, remasters, deluxe and regional editions.
+pub async fn album_editions(release_id: &str) -> Result<Vec<Edition>, MusicBrainzError> {
+    let client = musicbrainz_client();
+
+    let release = send(move || async move {
+        Release::fetch()
+            .id(release_id)
+            .with_release_groups()
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
+    let releases = match &release.release_group {
+        Some(release_group) => send(move || async move {
+            ReleaseGroup::fetch()
+                .id(&release_group.id)
+                .with_releases()
+                .execute_with_client(client)
+                .await
+        })
+        .await?
+        .releases
+        .unwrap_or_default(),
+        None => vec![release],
+    };
+
+    let mut editions: Vec<Edition> = releases
+        .into_iter()
+        .filter(|r| r.status == Some(ReleaseStatus::Official) || r.id == release_id)
+        .map(|r| Edition {
+            id: r.id,
+            title: r.title,
+            disambiguation: r.disambiguation.filter(|d| !d.trim().is_empty()),
+            date: r.date.map(|d| d.0),
+            country: r.country,
+        })
+        .collect();
+    // Undated releases last.
+    editions.sort_by(|a, b| match (&a.date, &b.date) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    });
+
+    Ok(editions)
+}
+
+/// What sets a release apart from other releases of the same album.
+pub struct ReleaseVersion {
+    pub artist: String,
+    pub title: String,
+    /// Free-text comment from MusicBrainz, e.g. "2009 remaster" or "deluxe edition".
+    pub disambiguation: Option<String>,
+    /// Release date (YYYY-MM-DD, or a prefix of it).
+    pub date: Option<String>,
+    pub country: Option<String>,
+}
+
+/// Fetches the edition details of a release.
+pub async fn release_version(release_id: &str) -> Result<ReleaseVersion, MusicBrainzError> {
+    let client = musicbrainz_client();
+
+    let release = send(move || async move {
+        Release::fetch()
+            .id(release_id)
+            .with_artist_credits()
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
+
+    Ok(ReleaseVersion {
+        artist: format_artist_credit(&release.artist_credit),
+        title: release.title,
+        disambiguation: release.disambiguation.filter(|d| !d.trim().is_empty()),
+        date: release.date.map(|d| d.0),
+        country: release.country,
+    })
+}
+
+/// A release's credited artist, title and genre tags.
+pub struct ReleaseTags {
+    pub artist: String,
+    pub title: String,
+    /// Genres first, then free-form tags, each ordered by vote count.
+    pub tags: Vec<String>,
+}
+
+/// Fetches the genres and tags voted on a release.
+pub async fn release_tags(release_id: &str) -> Result<ReleaseTags, MusicBrainzError> {
+    let client = musicbrainz_client();
+
+    let release = send(move || async move {
+        Release::fetch()
+            .id(release_id)
+            .with_artist_credits()
+            .with_genres()
+            .with_tags()
+            .execute_with_client(client)
+            .await
+    })
+    .await?;
+
+    Ok(ReleaseTags {
+        artist: format_artist_credit(&release.artist_credit),
+        tags: genre_names(&release.genres, &release.tags),
+        title: release.title,
+    })
+}
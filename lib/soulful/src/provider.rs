@@ -0,0 +1,77 @@
+//! Where albums and tracks are acquired from.
+//!
+//! A [`DownloadProvider`] finds candidates for an album, fetches their files
+//! and reports the transfers. Jobs, scoring and imports only go through it,
+//! so another source (a second slskd, HTTP or FTP servers, local rips) plugs
+//! into the same pipeline by implementing it. Soulseek, through
+//! [`crate::slskd::SoulseekBackend`], is the first.
+//!
+//! Files are named by a `(source, filename, size)` triple, the source being
+//! whatever the provider fetches them from, e.g. the Soulseek user sharing
+//! them. Transfers are reported as slskd ones, the ID being the provider's.
+
+use async_trait::async_trait;
+use shared::slskd::{DownloadResponse, FileEntry, TrackResult};
+use tokio::sync::broadcast;
+
+use crate::{
+    error::Result,
+    slskd::{AlbumSearch, SearchRequest, TransferUpdate},
+};
+
+#[async_trait]
+pub trait DownloadProvider: Send + Sync {
+    /// Short name of the provider in logs, e.g. "soulseek".
+    fn name(&self) -> &str;
+
+    /// Searches for an album and ranks the results against its tracks with
+    /// the request's `strategy`. Its `search_id` lets the search be cancelled,
+    /// and `on_partial` is given the ranked results found so far while it runs.
+    async fn search(&self, request: SearchRequest<'_>) -> Result<AlbumSearch>;
+
+    /// Starts fetching `(source, filename, size)` files. A file may come back
+    /// under another filename, the one its transfer is known by.
+    async fn fetch(&self, files: Vec<(String, String, i64)>) -> Result<Vec<DownloadResponse>>;
+
+    /// Every transfer the provider knows about.
+    async fn get_all_downloads(&self) -> Result<Vec<FileEntry>>;
+
+    /// One transfer from `source`, `None` once it is no longer known.
+    async fn get_download(&self, source: &str, id: &str) -> Result<Option<FileEntry>>;
+
+    /// Snapshots of the transfers, sent whenever one changes.
+    fn progress(&self) -> broadcast::Receiver<TransferUpdate>;
+
+    /// Stops a transfer, and forgets it when `remove` is set.
+    async fn cancel_download(&self, source: &str, id: &str, remove: bool) -> Result<()>;
+
+    /// Swaps each track to its best source right now. Providers knowing a
+    /// single source per file keep the tracks as they are.
+    async fn resolve_sources(&self, tracks: Vec<TrackResult>) -> Vec<TrackResult> {
+        tracks
+    }
+
+    /// Where a transfer is in its source's queue, `None` when not known.
+    async fn get_queue_position(&self, _source: &str, _id: &str) -> Result<Option<u32>> {
+        Ok(None)
+    }
+
+    /// Stops a transfer until [`Self::resume_download`]. It is cancelled
+    /// and forgotten unless the provider can hold it.
+    async fn pause_download(&self, source: &str, id: &str) -> Result<()> {
+        self.cancel_download(source, id, true).await
+    }
+
+    /// Fetches a paused file again, `None` when the source refused it.
+    async fn resume_download(
+        &self,
+        source: &str,
+        filename: &str,
+        size: i64,
+    ) -> Result<Option<DownloadResponse>> {
+        let fetched = self
+            .fetch(vec![(source.to_string(), filename.to_string(), size)])
+            .await?;
+        Ok(fetched.into_iter().next())
+    }
+}
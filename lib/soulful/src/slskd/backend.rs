@@ -0,0 +1,223 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use shared::slskd::{
+    DownloadResponse, FileEntry, SearchQueuePosition, SearchRateLimit, SearchRateUsage,
+    SearchResult, ServerState, SharedDirectory, SoulseekAccount, TrackResult,
+};
+use std::collections::BTreeMap;
+use tokio::sync::broadcast;
+
+use super::{AlbumSearch, SearchRequest, SoulseekClient, TransferUpdate};
+use crate::{error::Result, provider::DownloadProvider};
+
+/// What Soulbeet needs from a Soulseek client on top of acquiring files:
+/// [`SoulseekClient`] talking to slskd, or [`super::MockBackend`] in memory.
+/// See the [`SoulseekClient`] methods of the same names for the details.
+#[async_trait]
+pub trait SoulseekBackend: DownloadProvider {
+    /// Searches for `query` as typed, without matching the files to an album.
+    async fn search_raw(
+        &self,
+        query: &str,
+        timeout: Duration,
+        search_id: Option<&str>,
+    ) -> Result<Vec<SearchResult>>;
+
+    /// Stops a search, returning whether it was running or waiting.
+    async fn cancel_search(&self, search_id: &str) -> bool;
+
+    /// Where a search is in line for the rate limit, `None` once it started.
+    fn search_queue_position(&self, search_id: &str) -> Option<SearchQueuePosition>;
+
+    /// Where a search started now would be in line.
+    fn next_search_position(&self) -> Option<SearchQueuePosition>;
+
+    fn set_search_rate_limit(&self, limit: SearchRateLimit);
+
+    fn search_rate_usage(&self) -> SearchRateUsage;
+
+    /// Counts the users sharing audio files matching `artist album`.
+    async fn probe_availability(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<usize>;
+
+    /// Counts the users sharing audio files matching `artist album` in each
+    /// format, by lowercase extension.
+    async fn probe_formats(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<BTreeMap<String, usize>>;
+
+    /// Everything `username` shares, as a tree of folders.
+    async fn browse_user(&self, username: &str) -> Result<Vec<SharedDirectory>>;
+
+    /// Files other users are downloading from our shares.
+    async fn get_all_uploads(&self) -> Result<Vec<FileEntry>>;
+
+    async fn cancel_upload(&self, username: &str, upload_id: &str, remove: bool) -> Result<()>;
+
+    async fn add_shares(&self, directories: &[String]) -> Result<()>;
+
+    async fn remove_shares(&self, directories: &[String]) -> Result<()>;
+
+    async fn rescan_shares(&self) -> Result<()>;
+
+    async fn soulseek_account(&self) -> Result<SoulseekAccount>;
+
+    /// Saves the account, the password is left untouched when `None`.
+    async fn update_soulseek_account(
+        &self,
+        account: &SoulseekAccount,
+        password: Option<&str>,
+    ) -> Result<()>;
+
+    async fn server_state(&self) -> Result<ServerState>;
+
+    /// Connects to the Soulseek server again.
+    async fn reconnect(&self) -> Result<ServerState>;
+
+    /// Checks that the client is reachable and accepts our credentials.
+    async fn check_session(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl DownloadProvider for SoulseekClient {
+    fn name(&self) -> &str {
+        "soulseek"
+    }
+
+    async fn search(&self, request: SearchRequest<'_>) -> Result<AlbumSearch> {
+        SoulseekClient::search(self, request).await
+    }
+
+    async fn fetch(&self, files: Vec<(String, String, i64)>) -> Result<Vec<DownloadResponse>> {
+        SoulseekClient::download_files(self, files).await
+    }
+
+    async fn get_all_downloads(&self) -> Result<Vec<FileEntry>> {
+        SoulseekClient::get_all_downloads(self).await
+    }
+
+    async fn get_download(&self, source: &str, id: &str) -> Result<Option<FileEntry>> {
+        SoulseekClient::get_download(self, source, id).await
+    }
+
+    fn progress(&self) -> broadcast::Receiver<TransferUpdate> {
+        SoulseekClient::transfer_updates(self)
+    }
+
+    async fn cancel_download(&self, source: &str, id: &str, remove: bool) -> Result<()> {
+        SoulseekClient::cancel_download(self, source, id, remove).await
+    }
+
+    async fn resolve_sources(&self, tracks: Vec<TrackResult>) -> Vec<TrackResult> {
+        SoulseekClient::resolve_sources(self, tracks).await
+    }
+
+    async fn get_queue_position(&self, source: &str, id: &str) -> Result<Option<u32>> {
+        SoulseekClient::get_queue_position(self, source, id).await
+    }
+}
+
+#[async_trait]
+impl SoulseekBackend for SoulseekClient {
+    async fn search_raw(
+        &self,
+        query: &str,
+        timeout: Duration,
+        search_id: Option<&str>,
+    ) -> Result<Vec<SearchResult>> {
+        SoulseekClient::search_raw(self, query, timeout, search_id).await
+    }
+
+    async fn cancel_search(&self, search_id: &str) -> bool {
+        SoulseekClient::cancel_search(self, search_id).await
+    }
+
+    fn search_queue_position(&self, search_id: &str) -> Option<SearchQueuePosition> {
+        SoulseekClient::search_queue_position(self, search_id)
+    }
+
+    fn next_search_position(&self) -> Option<SearchQueuePosition> {
+        SoulseekClient::next_search_position(self)
+    }
+
+    fn set_search_rate_limit(&self, limit: SearchRateLimit) {
+        SoulseekClient::set_search_rate_limit(self, limit)
+    }
+
+    fn search_rate_usage(&self) -> SearchRateUsage {
+        SoulseekClient::search_rate_usage(self)
+    }
+
+    async fn probe_availability(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<usize> {
+        SoulseekClient::probe_availability(self, artist, album, timeout).await
+    }
+
+    async fn probe_formats(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<BTreeMap<String, usize>> {
+        SoulseekClient::probe_formats(self, artist, album, timeout).await
+    }
+
+    async fn browse_user(&self, username: &str) -> Result<Vec<SharedDirectory>> {
+        SoulseekClient::browse_user(self, username).await
+    }
+
+    async fn get_all_uploads(&self) -> Result<Vec<FileEntry>> {
+        SoulseekClient::get_all_uploads(self).await
+    }
+
+    async fn cancel_upload(&self, username: &str, upload_id: &str, remove: bool) -> Result<()> {
+        SoulseekClient::cancel_upload(self, username, upload_id, remove).await
+    }
+
+    async fn add_shares(&self, directories: &[String]) -> Result<()> {
+        SoulseekClient::add_shares(self, directories).await
+    }
+
+    async fn remove_shares(&self, directories: &[String]) -> Result<()> {
+        SoulseekClient::remove_shares(self, directories).await
+    }
+
+    async fn rescan_shares(&self) -> Result<()> {
+        SoulseekClient::rescan_shares(self).await
+    }
+
+    async fn soulseek_account(&self) -> Result<SoulseekAccount> {
+        SoulseekClient::soulseek_account(self).await
+    }
+
+    async fn update_soulseek_account(
+        &self,
+        account: &SoulseekAccount,
+        password: Option<&str>,
+    ) -> Result<()> {
+        SoulseekClient::update_soulseek_account(self, account, password).await
+    }
+
+    async fn server_state(&self) -> Result<ServerState> {
+        SoulseekClient::server_state(self).await
+    }
+
+    async fn reconnect(&self) -> Result<ServerState> {
+        SoulseekClient::reconnect(self).await
+    }
+
+    async fn check_session(&self) -> Result<()> {
+        SoulseekClient::check_session(self).await
+    }
+}
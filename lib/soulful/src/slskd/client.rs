@@ -1,39 +1,163 @@
-// Make sure the new utils module is accessible, e.g., mod utils;
-use super::utils;
+use super::scoring::{self, AlbumScoreInputs, Compilation, ScoringStrategy};
 use crate::{
     error::{Result, SoulseekError},
-    slskd::models::{DownloadRequestFile, SearchResponse},
+    slskd::{
+        filename_variants,
+        hub::HubConnection,
+        models::{
+            BrowseDirectory, BrowseResponse, DownloadRequestFile, SearchResponse,
+            SearchResponseFile, UserInfo,
+        },
+        queue::SearchQueue,
+        utils::{max_weight_assignment, track_similarity},
+    },
 };
-use chrono::{DateTime, Duration, Utc};
+use chrono::{Duration, Utc};
+use futures_util::{future::join_all, FutureExt};
 use itertools::Itertools;
+use rayon::prelude::*;
 use reqwest::{Client, Method, Response};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use shared::{
     musicbrainz::Track,
     slskd::{
-        AlbumResult, DownloadResponse, FileEntry, FlattenedFiles, MatchResult, SearchResult,
-        TrackResult,
+        AlbumLayout, AlbumResult, AudioFormat, DownloadResponse, EmptySearchReason, FileEntry,
+        FileSource, FlattenedFiles, MatchResult, SearchDiagnosis, SearchFilters,
+        SearchQueuePosition, SearchRateLimit, SearchRateUsage, SearchResult, SearchSuggestion,
+        ServerState, SharedDirectory, SharedFile, SoulseekAccount, TrackResult,
+        PENDING_TRANSFER_ID,
     },
 };
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
 use tracing::{debug, info, warn};
 use url::Url;
 
+/// Files whose `MatchResult::total_score` is below this are dropped from
+/// search results, unless the scoring strategy sets its own threshold.
+pub const MIN_MATCH_SCORE: f64 = 0.6;
+
 #[derive(Debug, Clone)]
 pub struct SoulseekClient {
     base_url: Url,
     api_key: Option<String>,
     // download_path: PathBuf,
     client: Client,
-    search_timestamps: Arc<Mutex<Vec<DateTime<Utc>>>>,
+    search_queue: Arc<std::sync::Mutex<SearchQueue>>,
     active_searches: Arc<Mutex<HashSet<String>>>,
-    max_searches_per_window: usize,
-    rate_limit_window: Duration,
+    /// Changed at runtime with [`SoulseekClient::set_search_rate_limit`].
+    rate_limit: Arc<std::sync::RwLock<SearchRateLimit>>,
+    transfers: broadcast::Sender<TransferUpdate>,
+    watching_transfers: Arc<AtomicBool>,
+    early_exit: Option<EarlyExit>,
+}
+
+/// When an album search can stop before its timeout: once it has found
+/// `min_albums` albums with every expected track, scoring at least
+/// `min_score`, in one of `formats`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarlyExit {
+    pub min_albums: usize,
+    pub min_score: f64,
+    /// Lowercase extensions, any format counts when empty.
+    pub formats: Vec<String>,
+}
+
+impl Default for EarlyExit {
+    fn default() -> Self {
+        EarlyExit {
+            min_albums: 3,
+            min_score: 0.85,
+            formats: vec!["flac".to_string()],
+        }
+    }
+}
+
+impl EarlyExit {
+    /// Whether `albums`, searched for `expected` tracks, are good enough to stop.
+    fn is_met(&self, albums: &[AlbumResult], expected: usize) -> bool {
+        albums
+            .iter()
+            .filter(|album| {
+                album.tracks.len() >= expected
+                    && album.score >= self.min_score
+                    && (self.formats.is_empty()
+                        || self.formats.iter().any(|f| album.dominant_quality.is(f)))
+            })
+            .count()
+            >= self.min_albums
+    }
+}
+
+/// The albums an album search found, best first, and why there are none
+/// when that is the case.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlbumSearch {
+    pub albums: Vec<AlbumResult>,
+    pub diagnosis: Option<SearchDiagnosis>,
+}
+
+/// An album search: what to look for on Soulseek and how to rank the files
+/// found against the album.
+pub struct SearchRequest<'a> {
+    pub artist: String,
+    pub album: String,
+    /// Sent instead of `artist album` when set.
+    pub query: Option<String>,
+    /// The album's tracks, files are matched against them.
+    pub tracks: Vec<Track>,
+    pub timeout: Duration,
+    pub strategy: &'a dyn ScoringStrategy,
+    pub filters: &'a SearchFilters,
+    /// Lets the search be followed while it waits, and cancelled.
+    pub search_id: Option<&'a str>,
+    /// Given the ranked results found so far while the search runs.
+    pub on_partial: Option<PartialResults<'a>>,
+}
+
+/// How long a source's live user info is waited for when picking the source
+/// of a download, before falling back on what its search response said.
+const USER_INFO_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Partial search results are ranked and reported at most this often.
+const PARTIAL_RESULTS_INTERVAL: Duration = Duration::seconds(2);
+
+/// Called with the albums ranked so far while a search runs, best first.
+pub type PartialResults<'a> = &'a (dyn Fn(Vec<AlbumResult>) + Send + Sync);
+
+/// Called with the responses gathered so far while a search runs, returns
+/// whether they are enough to stop.
+type PartialResponses<'a> = &'a (dyn Fn(&[SearchResponse]) -> bool + Send + Sync);
+
+/// How often the transfer watcher asks slskd for its downloads while its
+/// transfers hub cannot be reached.
+const TRANSFER_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How long the transfer watcher polls before trying the hub again.
+const HUB_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Transfer events come in bursts, those within this long are handled as one.
+const HUB_EVENT_WINDOW: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// How often the watcher checks for subscribers while the hub is quiet.
+const HUB_IDLE_CHECK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// One look at slskd's downloads, sent to every [`SoulseekClient::transfer_updates`]
+/// subscriber.
+#[derive(Debug, Clone)]
+pub struct TransferUpdate {
+    /// Every download slskd knows about.
+    pub downloads: Arc<Vec<FileEntry>>,
+    /// IDs of the downloads that appeared or changed state since the previous
+    /// update.
+    pub changed: Vec<String>,
 }
 
 #[derive(Default)]
@@ -43,6 +167,7 @@ pub struct SoulseekClientBuilder {
     download_path: Option<PathBuf>,
     max_searches_per_window: Option<usize>,
     rate_limit_window_seconds: Option<i64>,
+    early_exit: Option<EarlyExit>,
 }
 
 impl SoulseekClientBuilder {
@@ -79,6 +204,12 @@ impl SoulseekClientBuilder {
         self
     }
 
+    /// Lets album searches stop early once they found good enough albums.
+    pub fn early_exit(mut self, early_exit: EarlyExit) -> Self {
+        self.early_exit = Some(early_exit);
+        self
+    }
+
     pub fn build(self) -> Result<SoulseekClient> {
         let base_url_str = self.base_url.ok_or(SoulseekError::NotConfigured)?;
         let base_url = Url::parse(base_url_str.trim_end_matches('/'))?;
@@ -90,14 +221,38 @@ impl SoulseekClientBuilder {
             api_key: self.api_key,
             // download_path,
             client: Client::new(),
-            search_timestamps: Arc::new(Mutex::new(Vec::new())),
+            search_queue: Default::default(),
             active_searches: Arc::new(Mutex::new(HashSet::new())),
-            max_searches_per_window: self.max_searches_per_window.unwrap_or(35),
-            rate_limit_window: Duration::seconds(self.rate_limit_window_seconds.unwrap_or(220)),
+            rate_limit: Arc::new(std::sync::RwLock::new(SearchRateLimit {
+                max_searches: self
+                    .max_searches_per_window
+                    .unwrap_or(SearchRateLimit::default().max_searches),
+                window_seconds: self
+                    .rate_limit_window_seconds
+                    .unwrap_or(SearchRateLimit::default().window_seconds),
+            })),
+            transfers: broadcast::channel(16).0,
+            watching_transfers: Arc::new(AtomicBool::new(false)),
+            early_exit: self.early_exit,
         })
     }
 }
 
+/// A place in the search queue, given up if the waiting search is dropped so
+/// that it does not hold up the searches behind it.
+struct Ticket<'a> {
+    queue: &'a std::sync::Mutex<SearchQueue>,
+    id: String,
+}
+
+impl Drop for Ticket<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut queue) = self.queue.lock() {
+            queue.leave(&self.id);
+        }
+    }
+}
+
 impl SoulseekClient {
     async fn make_request<T: DeserializeOwned, B: Serialize>(
         &self,
@@ -145,55 +300,311 @@ impl SoulseekClient {
         }
     }
 
-    async fn wait_for_rate_limit(&self) -> Result<()> {
-        let mut timestamps = self.search_timestamps.lock().await;
-        let now = Utc::now();
-        let window_start = now - self.rate_limit_window;
-        timestamps.retain(|&ts| ts > window_start);
-        if timestamps.len() >= self.max_searches_per_window {
-            if let Some(&oldest) = timestamps.first() {
-                let wait_duration = (oldest + self.rate_limit_window) - now;
-                if !wait_duration.is_zero() {
-                    info!(
-                        "Rate limit reached ({}/{}), waiting for {:.1}s",
-                        timestamps.len(),
-                        self.max_searches_per_window,
-                        wait_duration.as_seconds_f64()
-                    );
-                    tokio::time::sleep(tokio::time::Duration::from_millis(
-                        wait_duration.num_milliseconds() as u64,
-                    ))
-                    .await;
+    /// Waits in line for the search rate limit, then counts a search against
+    /// it. Fails with [`SoulseekError::SearchCancelled`] when the search is
+    /// cancelled while waiting.
+    async fn acquire_search_slot(&self, search_id: Option<&str>) -> Result<()> {
+        let ticket = Ticket {
+            queue: &self.search_queue,
+            id: self.lock_queue().join(search_id),
+        };
+        let mut logged = false;
+        loop {
+            {
+                // Read on every try, the limit may change while searches wait.
+                let (max, window) = self.search_window();
+                let mut queue = self.lock_queue();
+                if !queue.contains(&ticket.id) {
+                    return Err(SoulseekError::SearchCancelled);
+                }
+                if queue.try_start(&ticket.id, max, window) {
+                    return Ok(());
+                }
+                if !logged {
+                    if let Some(place) = queue.position(&ticket.id, max, window) {
+                        info!(
+                            "Rate limit reached, search is #{} in line, about {}s to wait",
+                            place.position, place.eta_seconds
+                        );
+                    }
+                    logged = true;
                 }
             }
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
         }
-        timestamps.push(now);
-        Ok(())
     }
 
-    pub async fn search(
+    /// Where a search is in line for the rate limit, `None` once it started.
+    pub fn search_queue_position(&self, search_id: &str) -> Option<SearchQueuePosition> {
+        let (max, window) = self.search_window();
+        self.lock_queue().position(search_id, max, window)
+    }
+
+    /// Where a search started now would be in line, `None` if it would start
+    /// right away.
+    pub fn next_search_position(&self) -> Option<SearchQueuePosition> {
+        let (max, window) = self.search_window();
+        self.lock_queue().next_position(max, window)
+    }
+
+    pub fn search_rate_limit(&self) -> SearchRateLimit {
+        *self
+            .rate_limit
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Changes the search rate limit. Waiting searches follow the new one
+    /// right away, searches already counted stay counted.
+    pub fn set_search_rate_limit(&self, limit: SearchRateLimit) {
+        info!(
+            "Search rate limit set to {} searches per {}s",
+            limit.max_searches, limit.window_seconds
+        );
+        *self
+            .rate_limit
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = limit;
+    }
+
+    /// The search rate limit with how much of its window is used.
+    pub fn search_rate_usage(&self) -> SearchRateUsage {
+        let limit = self.search_rate_limit();
+        let (used, waiting, next_slot) = self
+            .lock_queue()
+            .usage(Duration::seconds(limit.window_seconds));
+        SearchRateUsage {
+            limit,
+            used,
+            waiting,
+            next_slot_seconds: next_slot.map(|d| d.num_seconds().max(0)),
+        }
+    }
+
+    /// Searches allowed per window, and the window.
+    fn search_window(&self) -> (usize, Duration) {
+        let limit = self.search_rate_limit();
+        (limit.max_searches, Duration::seconds(limit.window_seconds))
+    }
+
+    fn lock_queue(&self) -> std::sync::MutexGuard<'_, SearchQueue> {
+        self.search_queue
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Searches for the request's query, `artist album` by default, and ranks
+    /// the results against the album, once the rate limit allows it. Its
+    /// `search_id`, a UUID chosen by the caller, lets
+    /// [`Self::search_queue_position`] follow it while it waits and
+    /// [`Self::cancel_search`] stop it early; slskd picks one otherwise.
+    /// `on_partial` gets the results ranked so far as responses come in,
+    /// before the search times out, which it does early once the results meet
+    /// the client's [`EarlyExit`] condition.
+    pub async fn search(&self, request: SearchRequest<'_>) -> Result<AlbumSearch> {
+        let SearchRequest {
+            artist,
+            album,
+            query,
+            tracks,
+            timeout,
+            strategy,
+            filters,
+            search_id,
+            on_partial,
+        } = request;
+        self.acquire_search_slot(search_id).await?;
+
+        // Compilations are ranked against the artist of each track.
+        let compilation =
+            scoring::is_compilation(&artist).then(|| Compilation::new(strategy, &tracks));
+        let strategy: &dyn ScoringStrategy = match &compilation {
+            Some(compilation) => compilation,
+            None => strategy,
+        };
+        let track_titles: Vec<&str> = tracks.iter().map(|t| t.title.as_str()).collect();
+        let track_durations: Vec<Option<i32>> = tracks.iter().map(Track::duration_secs).collect();
+        let rank = |responses: &[SearchResponse]| {
+            self.process_search_responses(
+                responses,
+                &artist,
+                &album,
+                &track_titles,
+                &track_durations,
+                strategy,
+                filters,
+            )
+        };
+        let early_exit = self.early_exit.as_ref();
+        let on_responses = (on_partial.is_some() || early_exit.is_some()).then_some(
+            |responses: &[SearchResponse]| {
+                let albums = rank(responses);
+                let enough = early_exit.is_some_and(|e| e.is_met(&albums, track_titles.len()));
+                if let Some(report) = on_partial {
+                    report(albums);
+                }
+                enough
+            },
+        );
+
+        let query = query
+            .map(|q| q.trim().to_string())
+            .filter(|q| !q.is_empty())
+            .unwrap_or_else(|| match compilation {
+                // Files of a compilation hardly ever mention "Various Artists".
+                Some(_) => album.trim().to_string(),
+                None => format!("{} {}", artist.trim(), album.trim()),
+            });
+        // Soulseek leaves out the results containing a word prefixed with "-".
+        let query = std::iter::once(query)
+            .chain(filters.excluded_terms.iter().map(|term| format!("-{term}")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let all_responses = self
+            .collect_responses(
+                &query,
+                timeout,
+                search_id,
+                on_responses.as_ref().map(|f| f as PartialResponses),
+            )
+            .await?;
+
+        let albums = rank(&all_responses);
+        let diagnosis = albums.is_empty().then(|| {
+            self.diagnose(
+                &all_responses,
+                &artist,
+                &album,
+                &track_titles,
+                &track_durations,
+                strategy,
+                filters,
+            )
+        });
+
+        info!(
+            "Search completed. Final results: {} albums/tracks",
+            albums.len()
+        );
+        Ok(AlbumSearch { albums, diagnosis })
+    }
+
+    /// Sends `query` to Soulseek as typed and returns every audio file found,
+    /// without matching them to an album, sources with a free slot and short
+    /// queue first. For bootlegs, live sets and releases MusicBrainz does not
+    /// know about.
+    pub async fn search_raw(
         &self,
-        artist: String,
-        album: String,
-        tracks: Vec<Track>,
+        query: &str,
         timeout: Duration,
-    ) -> Result<Vec<AlbumResult>> {
-        self.wait_for_rate_limit().await?;
+        search_id: Option<&str>,
+    ) -> Result<Vec<SearchResult>> {
+        self.acquire_search_slot(search_id).await?;
 
-        let track_titles: Vec<&str> = tracks.iter().map(|t| t.title.as_str()).collect();
+        let responses = self
+            .collect_responses(query.trim(), timeout, search_id, None)
+            .await?;
 
+        let mut files: Vec<SearchResult> = responses
+            .iter()
+            .flat_map(|resp| {
+                resp.files
+                    .iter()
+                    .filter(|f| is_audio_file(&f.filename))
+                    .map(|file| resp.search_result(file))
+            })
+            .collect();
+        files.sort_by_key(|f| {
+            (
+                !f.has_free_upload_slot,
+                f.queue_length,
+                std::cmp::Reverse(f.upload_speed),
+            )
+        });
+
+        info!("Raw search completed: {} files", files.len());
+        Ok(files)
+    }
+
+    /// Counts the users sharing audio files matching `artist album`, using a
+    /// short search. Gives a rough idea of availability without ranking anything.
+    pub async fn probe_availability(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<usize> {
+        let responses = self.probe(artist, album, timeout).await?;
+        Ok(responses
+            .iter()
+            .filter(|resp| resp.files.iter().any(|f| is_audio_file(&f.filename)))
+            .map(|resp| resp.username.as_str())
+            .unique()
+            .count())
+    }
+
+    /// Counts the users sharing audio files matching `artist album` in each
+    /// format, by lowercase extension, using a short search like
+    /// [`Self::probe_availability`].
+    pub async fn probe_formats(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<BTreeMap<String, usize>> {
+        let responses = self.probe(artist, album, timeout).await?;
+        let mut users: BTreeMap<String, HashSet<&str>> = BTreeMap::new();
+        for resp in &responses {
+            for file in resp.files.iter().filter(|f| is_audio_file(&f.filename)) {
+                users
+                    .entry(file.format().extension().to_string())
+                    .or_default()
+                    .insert(resp.username.as_str());
+            }
+        }
+        Ok(users
+            .into_iter()
+            .map(|(format, users)| (format, users.len()))
+            .collect())
+    }
+
+    /// A short `artist album` search, once the rate limit allows it.
+    async fn probe(
+        &self,
+        artist: &str,
+        album: &str,
+        timeout: Duration,
+    ) -> Result<Vec<SearchResponse>> {
+        self.acquire_search_slot(None).await?;
         let query = format!("{} {}", artist.trim(), album.trim());
+        self.collect_responses(&query, timeout, None, None).await
+    }
+
+    /// Runs a search in slskd and gathers its responses until `timeout`, or
+    /// until it is cancelled. `on_responses` is shown the responses gathered so
+    /// far when new ones come in, at most every [`PARTIAL_RESULTS_INTERVAL`],
+    /// and ends the search early when it returns `true`.
+    async fn collect_responses(
+        &self,
+        query: &str,
+        timeout: Duration,
+        search_id: Option<&str>,
+        on_responses: Option<PartialResponses<'_>>,
+    ) -> Result<Vec<SearchResponse>> {
         info!("Starting search for: '{}'", query);
 
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct SearchRequest<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            id: Option<&'a str>,
             search_text: &'a str,
             timeout: i64,
             filter_responses: bool,
         }
         let request_body = SearchRequest {
-            search_text: &query,
+            id: search_id,
+            search_text: query,
             timeout: timeout.num_milliseconds(),
             filter_responses: true,
         };
@@ -212,10 +623,13 @@ impl SoulseekClient {
         let start_time = Utc::now();
         let poll_interval = Duration::seconds(1);
         let mut all_responses: Vec<SearchResponse> = Vec::new();
+        let mut cancelled = false;
+        let mut last_reported: Option<(chrono::DateTime<Utc>, usize)> = None;
 
         while (Utc::now() - start_time) < timeout {
             if !self.active_searches.lock().await.contains(&search_id) {
                 info!("Search {search_id} was cancelled, stopping.");
+                cancelled = true;
                 break;
             }
             let endpoint = format!("searches/{search_id}/responses");
@@ -236,6 +650,18 @@ impl SoulseekClient {
                 Err(SoulseekError::Api { status: 404, .. }) => break,
                 Err(e) => warn!("Error polling for search results: {:?}", e),
             }
+            if let Some(on_responses) = on_responses {
+                let due = last_reported.is_none_or(|(at, count)| {
+                    count < all_responses.len() && Utc::now() - at >= PARTIAL_RESULTS_INTERVAL
+                });
+                if due && !all_responses.is_empty() {
+                    last_reported = Some((Utc::now(), all_responses.len()));
+                    if on_responses(&all_responses) {
+                        info!("Search {search_id} found enough good results, stopping early.");
+                        break;
+                    }
+                }
+            }
             tokio::time::sleep(tokio::time::Duration::from_millis(
                 poll_interval.num_milliseconds() as u64,
             ))
@@ -245,89 +671,225 @@ impl SoulseekClient {
         self.active_searches.lock().await.remove(&search_id);
         let _ = self.delete_search(&search_id).await;
 
-        let mut albums =
-            self.process_search_responses(&all_responses, &artist, &album, &track_titles);
-
-        albums.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+        if cancelled {
+            return Err(SoulseekError::SearchCancelled);
+        }
+        Ok(all_responses)
+    }
 
-        info!(
-            "Search completed. Final results: {} albums/tracks",
-            albums.len()
-        );
-        Ok(albums)
+    /// Stops a search started with this id, whether it is running or still
+    /// waiting for the rate limit. Returns whether it was either.
+    pub async fn cancel_search(&self, search_id: &str) -> bool {
+        let waiting = self.lock_queue().leave(search_id);
+        waiting || self.active_searches.lock().await.remove(search_id)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_search_responses(
         &self,
         responses: &[SearchResponse],
         searched_artist: &str,
         searched_album: &str,
         expected_tracks: &[&str],
+        expected_durations: &[Option<i32>],
+        strategy: &dyn ScoringStrategy,
+        filters: &SearchFilters,
     ) -> Vec<AlbumResult> {
-        const MIN_SCORE_THRESHOLD: f64 = 0.6;
-        let audio_extensions: HashSet<&str> = ["flac", "wav", "m4a", "ogg", "aac", "wma", "mp3"]
+        let uploaders: Vec<&SearchResponse> = responses
             .iter()
-            .copied()
+            .filter(|resp| !filters.blocks(&resp.username))
+            .filter(|resp| !filters.skips_uploader(resp.has_free_upload_slot, resp.upload_speed))
             .collect();
-
-        let scored_files: Vec<(MatchResult, SearchResult)> = responses
-            .iter()
-            .flat_map(|resp| {
-                resp.files.iter().filter_map(|file| {
-                    let path = Path::new(&file.filename);
-                    let ext = path
-                        .extension()
-                        .and_then(|s| s.to_str())
-                        .map(|s| s.to_lowercase());
-
-                    if let Some(ext) = ext {
-                        if !audio_extensions.contains(ext.as_str()) {
-                            return None;
-                        }
-                    }
-
-                    let rank_result = utils::rank_match(
+        let candidates = unique_files(uploaders.iter().flat_map(|resp| {
+            resp.files
+                .iter()
+                .filter(|file| {
+                    let format = file.format();
+                    format.is_audio() && filters.allows(&format, file.bit_rate)
+                })
+                .map(move |file| (*resp, file))
+        }));
+        let score = |candidates: &[Candidate]| -> Vec<(MatchResult, SearchResult)> {
+            candidates
+                .iter()
+                .filter_map(|(response, file)| {
+                    let mut rank_result = strategy.rank_match(
                         &file.filename,
+                        file.length,
                         Some(searched_artist),
                         Some(searched_album),
                         expected_tracks,
+                        expected_durations,
                     );
 
-                    if rank_result.total_score < MIN_SCORE_THRESHOLD {
+                    if rank_result.total_score < strategy.min_match_score() {
                         return None;
                     }
+                    rank_result.total_score *= strategy.match_factor(&file.filename);
 
-                    let search_result = SearchResult {
-                        username: resp.username.clone(),
-                        filename: file.filename.clone(),
-                        size: file.size,
-                        bitrate: file.bit_rate,
-                        duration: file.length,
-                        has_free_upload_slot: resp.has_free_upload_slot,
-                        upload_speed: resp.upload_speed,
-                        queue_length: resp.queue_length,
-                    };
-                    Some((rank_result, search_result))
+                    Some((rank_result, response.search_result(file)))
                 })
-            })
-            .collect();
+                .collect()
+        };
+        let scored_files = score_in_chunks(&candidates, score);
 
-        self.find_best_albums(&scored_files, expected_tracks)
+        let albums = self.find_best_albums(
+            &scored_files,
+            expected_tracks,
+            strategy,
+            filters.required_tracks(expected_tracks.len()),
+        );
+        let mut albums = merge_identical_files(albums, &scored_files);
+        albums.extend(self.find_cue_images(
+            responses,
+            searched_artist,
+            searched_album,
+            expected_tracks,
+            expected_durations,
+            strategy,
+            filters,
+        ));
+        attach_companions(&mut albums, &uploaders);
+        albums.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        albums
+    }
+
+    /// Why `responses` gave no album: the files the match threshold and the
+    /// filters left out, and what would have kept some.
+    #[allow(clippy::too_many_arguments)]
+    fn diagnose(
+        &self,
+        responses: &[SearchResponse],
+        searched_artist: &str,
+        searched_album: &str,
+        expected_tracks: &[&str],
+        expected_durations: &[Option<i32>],
+        strategy: &dyn ScoringStrategy,
+        filters: &SearchFilters,
+    ) -> SearchDiagnosis {
+        let mut users = HashSet::new();
+        let mut audio_files = 0;
+        let mut unmatched = 0;
+        let mut excluded_formats: BTreeMap<String, usize> = BTreeMap::new();
+        let mut low_bitrates: BTreeMap<String, usize> = BTreeMap::new();
+        let mut slow_uploaders = 0;
+        let mut kept = Vec::new();
+
+        for resp in responses.iter().filter(|r| !filters.blocks(&r.username)) {
+            for file in resp.files.iter().filter(|f| is_audio_file(&f.filename)) {
+                audio_files += 1;
+                users.insert(resp.username.as_str());
+
+                let rank_result = strategy.rank_match(
+                    &file.filename,
+                    file.length,
+                    Some(searched_artist),
+                    Some(searched_album),
+                    expected_tracks,
+                    expected_durations,
+                );
+                if rank_result.total_score < strategy.min_match_score() {
+                    unmatched += 1;
+                    continue;
+                }
+                let format = resp.search_result(file).format();
+                if !filters.formats.is_empty() && !filters.formats.iter().any(|f| format.is(f)) {
+                    *excluded_formats
+                        .entry(format.extension().to_string())
+                        .or_default() += 1;
+                } else if !filters.allows(&format, file.bit_rate) {
+                    *low_bitrates
+                        .entry(format.extension().to_string())
+                        .or_default() += 1;
+                } else if filters.skips_uploader(resp.has_free_upload_slot, resp.upload_speed) {
+                    slow_uploaders += 1;
+                } else {
+                    kept.push((rank_result, resp.search_result(file)));
+                }
+            }
+        }
+
+        let best_album_tracks = self
+            .find_best_albums(&kept, expected_tracks, strategy, 1)
+            .iter()
+            .map(|album| album.tracks.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut reasons = Vec::new();
+        let mut suggestions = Vec::new();
+        if audio_files == 0 {
+            reasons.push(EmptySearchReason::NoResponses);
+        }
+        if unmatched > 0 {
+            reasons.push(EmptySearchReason::NoMatch { files: unmatched });
+        }
+        if audio_files == 0 || unmatched == audio_files {
+            suggestions.push(SearchSuggestion::RawSearch);
+        }
+        for (format, files) in &excluded_formats {
+            reasons.push(EmptySearchReason::FormatExcluded {
+                format: format.clone(),
+                files: *files,
+            });
+        }
+        if !excluded_formats.is_empty() {
+            suggestions.push(SearchSuggestion::AllowFormats(
+                excluded_formats.into_keys().collect(),
+            ));
+        }
+        for (format, files) in &low_bitrates {
+            reasons.push(EmptySearchReason::BitrateTooLow {
+                format: format.clone(),
+                files: *files,
+            });
+        }
+        if !low_bitrates.is_empty() {
+            suggestions.push(SearchSuggestion::LowerBitrateCutoffs(
+                low_bitrates.into_keys().collect(),
+            ));
+        }
+        if slow_uploaders > 0 {
+            reasons.push(EmptySearchReason::UploaderTooSlow {
+                files: slow_uploaders,
+            });
+        }
+        if best_album_tracks > 0 {
+            reasons.push(EmptySearchReason::Incomplete {
+                found: best_album_tracks,
+                required: filters.required_tracks(expected_tracks.len()),
+            });
+            suggestions.push(SearchSuggestion::AcceptPartial {
+                found: best_album_tracks,
+                expected: expected_tracks.len(),
+            });
+        }
+
+        SearchDiagnosis {
+            users: users.len(),
+            audio_files,
+            reasons,
+            suggestions,
+        }
     }
 
     fn find_best_albums(
         &self,
         scored_files: &[(MatchResult, SearchResult)],
         expected_tracks: &[&str],
+        strategy: &dyn ScoringStrategy,
+        required_tracks: usize,
     ) -> Vec<AlbumResult> {
         if expected_tracks.is_empty() {
             return vec![];
         }
 
+        // Disc folders never make the guessed album, so an album shared as
+        // `CD1/`, `CD2/` folders groups into a single candidate.
         let album_groups = scored_files.iter().into_group_map_by(|(rank, search)| {
             (
                 search.username.clone(),
@@ -339,38 +901,34 @@ impl SoulseekClient {
         album_groups
             .into_iter()
             .filter_map(|((username, artist, album_title), files_in_group)| {
-                // Specific search: find the single best file for each expected track.
-                let mut best_files_for_album = HashMap::new();
-
-                for expected_track_title in expected_tracks {
-                    if let Some(best_file_for_track) = files_in_group
-                        .iter()
-                        // Find all files that matched this specific track
-                        .filter(|(rank, _)| &rank.matched_track == expected_track_title)
-                        // Find the best one among them
-                        .max_by(|(r1, s1), (r2, s2)| {
-                            r1.total_score
-                                .partial_cmp(&r2.total_score)
-                                .unwrap_or(std::cmp::Ordering::Equal)
-                                .then_with(|| {
-                                    s1.quality_score().partial_cmp(&s2.quality_score()).unwrap()
-                                })
-                        })
-                    {
-                        best_files_for_album.insert(*expected_track_title, best_file_for_track);
-                    }
-                }
+                let (assignment, ambiguous_matches) =
+                    assign_files(expected_tracks, &files_in_group, strategy);
 
-                // If we didn't find a file for every track we were looking for, this album is incomplete.
-                if best_files_for_album.len() != expected_tracks.len() {
+                // Too few of the tracks we were looking for, this album is incomplete.
+                if assignment.len() < required_tracks {
                     return None;
                 }
 
-                let final_tracks: Vec<_> = best_files_for_album
-                    .values()
-                    .map(|(mr, sr)| TrackResult::new(sr.clone(), mr.clone()))
+                let missing_tracks = expected_tracks
+                    .iter()
+                    .filter(|title| !assignment.iter().any(|(track, _)| track == *title))
+                    .map(|title| title.to_string())
                     .collect();
 
+                let mut final_tracks: Vec<_> = assignment
+                    .into_iter()
+                    .map(|(track, (mr, sr))| {
+                        let mut matched = mr.clone();
+                        matched.matched_track = track.to_string();
+                        TrackResult::new(sr.clone(), matched)
+                    })
+                    .collect();
+                // In the order of the files when they are all numbered, the
+                // searched tracklist's otherwise.
+                if final_tracks.iter().all(|t| t.track_number.is_some()) {
+                    final_tracks.sort_by_key(|t| (t.disc_number, t.track_number));
+                }
+
                 if final_tracks.is_empty() {
                     return None;
                 }
@@ -382,13 +940,14 @@ impl SoulseekClient {
                 };
 
                 let total_size: i64 = final_tracks.iter().map(|t| t.base.size).sum();
+                // MP3s of one album differ in bitrate, the lowest is shown.
                 let dominant_quality = final_tracks
                     .iter()
-                    .map(|t| t.base.quality())
-                    .counts()
-                    .into_iter()
-                    .max_by_key(|&(_, count)| count)
-                    .map(|(val, _)| val)
+                    .map(|t| t.base.format())
+                    .into_group_map_by(|format| format.extension().to_string())
+                    .into_values()
+                    .max_by_key(Vec::len)
+                    .and_then(|formats| formats.into_iter().min())
                     .unwrap_or_default();
 
                 let first_track = final_tracks[0].base.clone();
@@ -396,14 +955,20 @@ impl SoulseekClient {
 
                 let avg_score: f64 = final_tracks.iter().map(|t| t.match_score).sum::<f64>()
                     / final_tracks.len() as f64;
-                let avg_format_score = final_tracks
+                let avg_file_score = final_tracks
                     .iter()
-                    .map(|t| t.base.quality_score())
+                    .map(|t| strategy.file_score(&t.base))
                     .sum::<f64>()
                     / final_tracks.len() as f64;
 
-                let album_quality_score =
-                    (avg_score * 0.3) + (completeness * 0.3) + (avg_format_score * 0.4);
+                let album_quality_score = strategy.album_score(&AlbumScoreInputs {
+                    avg_match_score: avg_score,
+                    completeness,
+                    avg_file_score,
+                    has_free_upload_slot: first_track.has_free_upload_slot,
+                    upload_speed: first_track.upload_speed,
+                    queue_length: first_track.queue_length,
+                });
 
                 Some(AlbumResult {
                     username,
@@ -418,14 +983,184 @@ impl SoulseekClient {
                     upload_speed: first_track.upload_speed,
                     queue_length: first_track.queue_length,
                     score: album_quality_score,
+                    eta_seconds: None,
+                    ambiguous_matches,
+                    expected_tracks: expected_tracks.len(),
+                    uploader_reliability: None,
+                    missing_tracks,
+                    layout: AlbumLayout::Tracks,
+                    companions: Vec::new(),
                 })
             })
             .collect()
     }
 
+    /// Albums shared as a single audio file and its cue sheet, alone in their
+    /// folder. The file is matched against the searched artist and album, and
+    /// its length, when known, against the tracklist's.
+    #[allow(clippy::too_many_arguments)]
+    fn find_cue_images(
+        &self,
+        responses: &[SearchResponse],
+        searched_artist: &str,
+        searched_album: &str,
+        expected_tracks: &[&str],
+        expected_durations: &[Option<i32>],
+        strategy: &dyn ScoringStrategy,
+        filters: &SearchFilters,
+    ) -> Vec<AlbumResult> {
+        if expected_tracks.len() < 2 {
+            return vec![];
+        }
+        let album_length: Option<i32> = expected_durations.iter().copied().sum();
+
+        let mut albums = Vec::new();
+        for resp in responses
+            .iter()
+            .filter(|resp| !filters.blocks(&resp.username))
+            .filter(|resp| !filters.skips_uploader(resp.has_free_upload_slot, resp.upload_speed))
+        {
+            let folders = resp
+                .files
+                .iter()
+                .into_group_map_by(|file| parent_folder(&file.filename));
+            for files in folders.values() {
+                let cues: Vec<_> = files.iter().filter(|f| f.format().is("cue")).collect();
+                let audio: Vec<_> = files.iter().filter(|f| f.format().is_audio()).collect();
+                let ([cue], [image]) = (cues.as_slice(), audio.as_slice()) else {
+                    continue;
+                };
+                // Images are lossless, a lone MP3 is a single or a mix.
+                let image_format = image.format();
+                if !image_format.is_lossless() || !filters.allows(&image_format, image.bit_rate) {
+                    continue;
+                }
+                // Another release, or a single track named after the album.
+                if let (Some(length), Some(expected)) = (image.length, album_length) {
+                    if (length - expected).abs() > (expected / 20).max(30) {
+                        continue;
+                    }
+                }
+
+                let mut matched = strategy.rank_match(
+                    &image.filename,
+                    None,
+                    Some(searched_artist),
+                    Some(searched_album),
+                    &[],
+                    &[],
+                );
+                if matched.total_score < strategy.min_match_score() {
+                    continue;
+                }
+                matched.total_score *= strategy.match_factor(&image.filename);
+                matched.matched_track = searched_album.to_string();
+                let image = TrackResult::new(resp.search_result(image), matched.clone());
+                matched.matched_track = "Cue sheet".to_string();
+                let cue = TrackResult::new(resp.search_result(cue), matched.clone());
+
+                let score = strategy.album_score(&AlbumScoreInputs {
+                    avg_match_score: image.match_score,
+                    completeness: 1.0,
+                    avg_file_score: strategy.file_score(&image.base),
+                    has_free_upload_slot: resp.has_free_upload_slot,
+                    upload_speed: resp.upload_speed,
+                    queue_length: resp.queue_length,
+                });
+                albums.push(AlbumResult {
+                    username: resp.username.clone(),
+                    album_path: image.base.filename.clone(),
+                    album_title: matched.guessed_album,
+                    artist: Some(matched.guessed_artist),
+                    track_count: expected_tracks.len(),
+                    total_size: image.base.size + cue.base.size,
+                    dominant_quality: image_format,
+                    tracks: vec![image, cue],
+                    has_free_upload_slot: resp.has_free_upload_slot,
+                    upload_speed: resp.upload_speed,
+                    queue_length: resp.queue_length,
+                    score,
+                    eta_seconds: None,
+                    ambiguous_matches: false,
+                    expected_tracks: expected_tracks.len(),
+                    uploader_reliability: None,
+                    missing_tracks: Vec::new(),
+                    layout: AlbumLayout::CueImage,
+                    companions: Vec::new(),
+                });
+            }
+        }
+        albums
+    }
+
     pub async fn download(&self, req: Vec<TrackResult>) -> Result<Vec<DownloadResponse>> {
+        self.download_files(
+            req.into_iter()
+                .map(|t| (t.base.username, t.base.filename, t.base.size)),
+        )
+        .await
+    }
+
+    /// Queues `(username, filename, size)` files for download in slskd.
+    ///
+    /// Filenames are sent exactly as the peer reported them, separators and
+    /// Unicode form included, since peers only match their own spelling. When
+    /// the peer rejects a request, each file is tried again on its own with the
+    /// other spellings from [`filename_variants`]; the response then carries
+    /// the spelling that went through in `filename`. Any other error from
+    /// slskd is returned.
+    pub async fn download_files(
+        &self,
+        files: impl IntoIterator<Item = (String, String, i64)>,
+    ) -> Result<Vec<DownloadResponse>> {
+        let files: Vec<_> = files.into_iter().collect();
         let mut requests_by_username: HashMap<String, Vec<DownloadRequestFile>> = HashMap::new();
 
+        info!("Attempting to download: {} files...", files.len());
+        for (username, filename, size) in files {
+            let list = requests_by_username.entry(username).or_default();
+            list.push(DownloadRequestFile { filename, size });
+        }
+
+        let mut res = vec![];
+
+        for (username, file_requests) in requests_by_username.into_iter() {
+            if let Some(downloads) = self.request_downloads(&username, &file_requests).await? {
+                res.extend(downloads);
+                continue;
+            }
+
+            for file in &file_requests {
+                for filename in filename_variants(&file.filename) {
+                    info!("Retrying {} as {}", file.filename, filename);
+                    let variant = DownloadRequestFile {
+                        filename,
+                        size: file.size,
+                    };
+                    if let Some(downloads) = self
+                        .request_downloads(&username, std::slice::from_ref(&variant))
+                        .await?
+                    {
+                        res.extend(downloads.into_iter().map(|d| DownloadResponse {
+                            original_filename: file.filename.clone(),
+                            ..d
+                        }));
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// Sends one download request to slskd. `None` when the peer rejected it,
+    /// usually because it shares no file with that name.
+    async fn request_downloads(
+        &self,
+        username: &str,
+        file_requests: &[DownloadRequestFile],
+    ) -> Result<Option<Vec<DownloadResponse>>> {
         #[derive(Deserialize, Debug)]
         #[serde(rename_all = "camelCase")]
         struct SlskdDownloadResponse {
@@ -433,79 +1168,134 @@ impl SoulseekClient {
             filename: String,
         }
 
-        info!("Attempting to download: {} files...", req.len());
-        for req in req {
-            let list = requests_by_username.entry(req.base.username).or_default();
-            list.push(DownloadRequestFile {
-                filename: req.base.filename,
-                size: req.base.size,
+        let endpoint = format!("transfers/downloads/{username}");
+        let url = self.base_url.join(&format!("api/v0/{endpoint}"))?;
+
+        info!(
+            "Sending download request to {} with {} files",
+            url,
+            file_requests.len()
+        );
+        debug!(
+            "Payload: {:?}",
+            serde_json::to_string(&file_requests).unwrap_or_default()
+        );
+
+        let response = self
+            .client
+            .post(url)
+            .header("X-API-Key", self.api_key.as_deref().unwrap_or(""))
+            .json(&file_requests)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let resp_text = response.text().await?;
+
+        if is_rejection(status, &resp_text) {
+            warn!("{} rejected the download request: {}", username, resp_text);
+            return Ok(None);
+        }
+        if !status.is_success() {
+            return Err(SoulseekError::Api {
+                status: status.as_u16(),
+                message: resp_text,
             });
         }
 
+        let downloaded = |id: String, filename: String| DownloadResponse {
+            id,
+            original_filename: filename.clone(),
+            filename,
+        };
         let mut res = vec![];
+        if resp_text.trim().is_empty() {
+            info!("Slskd returned empty success response. Assuming files queued.");
+            for req_file in file_requests {
+                res.push(downloaded(
+                    PENDING_TRANSFER_ID.to_string(),
+                    req_file.filename.clone(),
+                ));
+            }
+            // TODO: Check slskd response
+        } else if let Ok(single_res) = serde_json::from_str::<SlskdDownloadResponse>(&resp_text) {
+            res.push(downloaded(single_res.id, single_res.filename));
+        } else if let Ok(multi_res) = serde_json::from_str::<Vec<SlskdDownloadResponse>>(&resp_text)
+        {
+            res.extend(multi_res.into_iter().map(|d| downloaded(d.id, d.filename)));
+        } else {
+            tracing::error!("Failed to parse response from slskd: '{}'", resp_text);
+        }
 
-        for (username, file_requests) in requests_by_username.into_iter() {
-            let endpoint = format!("transfers/downloads/{username}");
-            let url = self.base_url.join(&format!("api/v0/{endpoint}"))?;
-
-            info!(
-                "Sending download request to {} with {} files",
-                url,
-                file_requests.len()
-            );
-            debug!(
-                "Payload: {:?}",
-                serde_json::to_string(&file_requests).unwrap_or_default()
-            );
-
-            let response = self
-                .client
-                .post(url)
-                .header("X-API-Key", self.api_key.as_deref().unwrap_or(""))
-                .json(&file_requests)
-                .send()
-                .await?;
+        Ok(Some(res))
+    }
 
-            let status = response.status();
-            let resp_text = response.text().await?;
+    /// Swaps each track to whichever of its known sources can deliver fastest
+    /// right now, based on live user info from slskd.
+    pub async fn resolve_sources(&self, tracks: Vec<TrackResult>) -> Vec<TrackResult> {
+        let usernames: HashSet<String> = tracks
+            .iter()
+            .filter(|track| !track.base.alternate_sources.is_empty())
+            .flat_map(|track| track.base.sources())
+            .map(|source| source.username)
+            .collect();
+        // Asked all at once, a peer slow to answer only costs its own info.
+        let lookups = usernames.into_iter().map(|username| async move {
+            let endpoint = format!("users/{username}/info");
+            let info = tokio::time::timeout(
+                USER_INFO_TIMEOUT,
+                self.make_request::<UserInfo, ()>(Method::GET, &endpoint, None),
+            )
+            .await;
+            let info = match info {
+                Ok(Ok(info)) => Some(info),
+                Ok(Err(e)) => {
+                    debug!("No user info for {}: {}", username, e);
+                    None
+                }
+                Err(_) => {
+                    debug!("User info for {} timed out", username);
+                    None
+                }
+            };
+            (username, info)
+        });
+        let user_info: HashMap<String, Option<UserInfo>> =
+            join_all(lookups).await.into_iter().collect();
 
-            if !status.is_success() {
-                tracing::error!(
-                    "Slskd returned error status: {} - Body: {}",
-                    status,
-                    resp_text
-                );
+        let mut resolved = Vec::with_capacity(tracks.len());
+        for mut track in tracks {
+            if track.base.alternate_sources.is_empty() {
+                resolved.push(track);
                 continue;
             }
 
-            if resp_text.trim().is_empty() {
-                info!("Slskd returned empty success response. Assuming files queued.");
-                for req_file in &file_requests {
-                    res.push(DownloadResponse {
-                        id: "pending".to_string(),
-                        filename: req_file.filename.clone(),
-                    });
+            let mut best: Option<(FileSource, (bool, i32, i32))> = None;
+            for source in track.base.sources() {
+                let (free_slot, queue_length) = match user_info.get(&source.username) {
+                    Some(Some(info)) => (info.has_free_upload_slot, info.queue_length),
+                    _ => (source.has_free_upload_slot, source.queue_length),
+                };
+                // Ranked like merged copies: free slot, then speed, then queue.
+                let rank = (free_slot, source.upload_speed, -queue_length);
+                if best.as_ref().is_none_or(|(_, best_rank)| rank > *best_rank) {
+                    best = Some((source, rank));
+                }
+            }
+
+            if let Some((source, _)) = best {
+                if source.username != track.base.username {
+                    info!(
+                        "Using {} instead of {} for {}",
+                        source.username, track.base.username, track.title
+                    );
                 }
-                // TODO: Check slskd response
-            } else if let Ok(single_res) = serde_json::from_str::<SlskdDownloadResponse>(&resp_text)
-            {
-                res.push(DownloadResponse {
-                    id: single_res.id,
-                    filename: single_res.filename,
-                });
-            } else if let Ok(multi_res) =
-                serde_json::from_str::<Vec<SlskdDownloadResponse>>(&resp_text)
-            {
-                res.extend(multi_res.into_iter().map(|d| DownloadResponse {
-                    id: d.id,
-                    filename: d.filename,
-                }));
-            } else {
-                tracing::error!("Failed to parse response from slskd: '{}'", resp_text);
+                track.base = track.base.with_primary_source(&source);
             }
+            resolved.push(track);
         }
 
-        Ok(res)
+        resolved
     }
 
     pub async fn get_all_downloads(&self) -> Result<Vec<FileEntry>> {
@@ -515,6 +1305,120 @@ impl SoulseekClient {
         Ok(flattened.0)
     }
 
+    /// One of `username`'s downloads, by transfer ID. `None` once slskd no
+    /// longer knows it, e.g. after it was removed from the transfer list.
+    pub async fn get_download(&self, username: &str, id: &str) -> Result<Option<FileEntry>> {
+        let endpoint = format!("transfers/downloads/{username}/{id}");
+        match self.make_request(Method::GET, &endpoint, None::<()>).await {
+            Ok(download) => Ok(Some(download)),
+            Err(SoulseekError::Api { status: 404, .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Asks the uploader where one of `username`'s downloads is in their
+    /// queue. `None` when slskd no longer knows the transfer.
+    pub async fn get_queue_position(&self, username: &str, id: &str) -> Result<Option<u32>> {
+        let endpoint = format!("transfers/downloads/{username}/{id}/position");
+        match self.make_request(Method::GET, &endpoint, None::<()>).await {
+            Ok(position) => Ok(Some(position)),
+            Err(SoulseekError::Api { status: 404, .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Follows slskd's downloads. A single watcher listens to slskd's
+    /// transfers hub for the whole server while anyone subscribes, and stops
+    /// once the last receiver is dropped. A receiver that lags behind only
+    /// misses snapshots, the next one is complete again.
+    pub fn transfer_updates(&self) -> broadcast::Receiver<TransferUpdate> {
+        let receiver = self.transfers.subscribe();
+        if !self.watching_transfers.swap(true, Ordering::SeqCst) {
+            tokio::spawn(self.clone().watch_transfers());
+        }
+        receiver
+    }
+
+    /// Reads the downloads whenever the transfers hub tells something
+    /// changed, polling them instead while the hub cannot be reached.
+    async fn watch_transfers(self) {
+        info!("Watching slskd transfers");
+        let mut states: HashMap<String, String> = HashMap::new();
+        let mut hub: Option<HubConnection> = None;
+        let mut next_connect = tokio::time::Instant::now();
+        loop {
+            if self.transfers.receiver_count() == 0 {
+                self.watching_transfers.store(false, Ordering::SeqCst);
+                // Someone may have subscribed before the flag was reset, without
+                // starting another watcher.
+                if self.transfers.receiver_count() == 0
+                    || self.watching_transfers.swap(true, Ordering::SeqCst)
+                {
+                    break;
+                }
+            }
+
+            if hub.is_none() && tokio::time::Instant::now() >= next_connect {
+                match HubConnection::connect(
+                    &self.base_url,
+                    "hub/transfers",
+                    self.api_key.as_deref(),
+                )
+                .await
+                {
+                    Ok(connection) => {
+                        info!("Following slskd transfers through its hub");
+                        hub = Some(connection);
+                    }
+                    Err(e) => {
+                        warn!("slskd transfers hub unavailable, polling instead: {}", e);
+                        next_connect = tokio::time::Instant::now() + HUB_RETRY_INTERVAL;
+                    }
+                }
+            }
+            match &mut hub {
+                Some(connection) => {
+                    match tokio::time::timeout(HUB_IDLE_CHECK, connection.next_event()).await {
+                        Ok(Ok(())) => {
+                            tokio::time::sleep(HUB_EVENT_WINDOW).await;
+                            while let Some(Ok(())) = connection.next_event().now_or_never() {}
+                        }
+                        Ok(Err(e)) => {
+                            warn!("Lost the slskd transfers hub, polling instead: {}", e);
+                            hub = None;
+                            next_connect = tokio::time::Instant::now() + TRANSFER_POLL_INTERVAL;
+                        }
+                        // Nothing happened, only check whether anyone still listens.
+                        Err(_) => continue,
+                    }
+                }
+                None => tokio::time::sleep(TRANSFER_POLL_INTERVAL).await,
+            }
+
+            let downloads = match self.get_all_downloads().await {
+                Ok(downloads) => downloads,
+                Err(e) => {
+                    warn!("Error fetching download status: {}", e);
+                    continue;
+                }
+            };
+            let changed = downloads
+                .iter()
+                .filter(|d| states.get(&d.id) != Some(&d.state_description))
+                .map(|d| d.id.clone())
+                .collect();
+            states = downloads
+                .iter()
+                .map(|d| (d.id.clone(), d.state_description.clone()))
+                .collect();
+            let _ = self.transfers.send(TransferUpdate {
+                downloads: Arc::new(downloads),
+                changed,
+            });
+        }
+        info!("Stopped watching slskd transfers");
+    }
+
     pub async fn cancel_download(
         &self,
         username: &str,
@@ -527,6 +1431,24 @@ impl SoulseekClient {
             .await
     }
 
+    /// Files other Soulseek users are downloading from our shares, or did
+    /// until slskd cleared them.
+    pub async fn get_all_uploads(&self) -> Result<Vec<FileEntry>> {
+        let flattened: FlattenedFiles = self
+            .make_request(Method::GET, "transfers/uploads", None::<()>)
+            .await?;
+        Ok(flattened.0)
+    }
+
+    /// Stops sending a file to `username`. With `remove`, a finished upload is
+    /// also taken off the transfer list.
+    pub async fn cancel_upload(&self, username: &str, upload_id: &str, remove: bool) -> Result<()> {
+        let endpoint = format!("transfers/uploads/{username}/{upload_id}?remove={remove}");
+        info!("Cancelling upload: {}", upload_id);
+        self.make_request(Method::DELETE, &endpoint, None::<()>)
+            .await
+    }
+
     pub async fn clear_all_completed_downloads(&self) -> Result<()> {
         info!("Clearing all completed downloads");
         self.make_request(
@@ -550,9 +1472,524 @@ impl SoulseekClient {
         }
     }
 
+    /// Everything `username` shares, as a tree of folders. slskd waits for the
+    /// peer to send its whole file list, which takes a while for large shares.
+    pub async fn browse_user(&self, username: &str) -> Result<Vec<SharedDirectory>> {
+        let endpoint = format!("users/{username}/browse");
+        let response: BrowseResponse = self
+            .make_request(Method::GET, &endpoint, None::<()>)
+            .await?;
+        Ok(build_directory_tree(response.directories))
+    }
+
     pub async fn check_connection(&self) -> bool {
+        self.check_session().await.is_ok()
+    }
+
+    /// Adds `directories` to the slskd shares, then rescans them.
+    ///
+    /// slskd only reads shares from its YAML configuration, so this edits it
+    /// through the remote configuration API, which must be enabled in slskd
+    /// (`remote_configuration: true`).
+    pub async fn add_shares(&self, directories: &[String]) -> Result<()> {
+        let shared = self.shared_directories().await?;
+        let missing: Vec<String> = directories
+            .iter()
+            .filter(|dir| !shared.contains(dir))
+            .cloned()
+            .collect();
+
+        if !missing.is_empty() {
+            info!("Adding slskd shares: {:?}", missing);
+            let yaml = self.options_yaml().await?;
+            let updated = edit_share_directories(&yaml, |shared| shared.extend(missing))?;
+            self.make_request::<(), _>(Method::POST, "options/yaml", Some(updated))
+                .await?;
+        }
+
+        self.rescan_shares().await
+    }
+
+    /// Removes `directories` from the slskd shares, then rescans them. Like
+    /// [`Self::add_shares`], this requires `remote_configuration: true`.
+    pub async fn remove_shares(&self, directories: &[String]) -> Result<()> {
+        let shared = self.shared_directories().await?;
+        if !directories.iter().any(|dir| shared.contains(dir)) {
+            return Ok(());
+        }
+
+        info!("Removing slskd shares: {:?}", directories);
+        let yaml = self.options_yaml().await?;
+        let updated = edit_share_directories(&yaml, |shared| {
+            shared.retain(|dir| !directories.contains(dir))
+        })?;
+        self.make_request::<(), _>(Method::POST, "options/yaml", Some(updated))
+            .await?;
+        self.rescan_shares().await
+    }
+
+    /// The directories slskd currently shares.
+    async fn shared_directories(&self) -> Result<Vec<String>> {
+        let options: serde_json::Value = self
+            .make_request(Method::GET, "options", None::<()>)
+            .await?;
+        Ok(options["shares"]["directories"]
+            .as_array()
+            .map(|dirs| {
+                dirs.iter()
+                    .filter_map(|d| d.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// The raw slskd YAML configuration, served as text rather than JSON.
+    async fn options_yaml(&self) -> Result<String> {
+        let url = self.base_url.join("api/v0/options/yaml")?;
+        let mut request = self.client.get(url);
+        if let Some(key) = &self.api_key {
+            request = request.header("X-API-Key", key);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let yaml = response.text().await?;
+        if !status.is_success() {
+            return Err(SoulseekError::Api {
+                status: status.as_u16(),
+                message: yaml,
+            });
+        }
+        Ok(yaml)
+    }
+
+    /// The Soulseek account and listen port slskd currently runs with.
+    pub async fn soulseek_account(&self) -> Result<SoulseekAccount> {
+        let options: serde_json::Value = self
+            .make_request(Method::GET, "options", None::<()>)
+            .await?;
+        let soulseek = &options["soulseek"];
+        Ok(SoulseekAccount {
+            username: soulseek["username"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            listen_port: soulseek["listenPort"]
+                .as_u64()
+                .and_then(|port| u16::try_from(port).ok()),
+        })
+    }
+
+    /// Writes the Soulseek account to the slskd configuration. The password is
+    /// left untouched when `None`. Like [`Self::add_shares`], this requires
+    /// `remote_configuration: true` in slskd, and slskd only logs in with the
+    /// new account after [`Self::reconnect`].
+    pub async fn update_soulseek_account(
+        &self,
+        account: &SoulseekAccount,
+        password: Option<&str>,
+    ) -> Result<()> {
+        let yaml = self.options_yaml().await?;
+        let updated = edit_yaml_section(&yaml, "soulseek", |soulseek| {
+            soulseek.insert("username".into(), account.username.as_str().into());
+            if let Some(password) = password {
+                soulseek.insert("password".into(), password.into());
+            }
+            if let Some(port) = account.listen_port {
+                soulseek.insert("listen_port".into(), port.into());
+            }
+        })?;
+        self.make_request::<(), _>(Method::POST, "options/yaml", Some(updated))
+            .await
+    }
+
+    /// Connection of slskd to the Soulseek server, with the username it is
+    /// logged in as and the size of its shares. Those two are left empty when
+    /// slskd's application state can't be read.
+    pub async fn server_state(&self) -> Result<ServerState> {
+        let mut state: ServerState = self.make_request(Method::GET, "server", None::<()>).await?;
+        match self
+            .make_request::<serde_json::Value, ()>(Method::GET, "application", None)
+            .await
+        {
+            Ok(application) => {
+                state.username = application["user"]["username"].as_str().map(str::to_string);
+                state.shared_directories = application["shares"]["directories"].as_u64();
+                state.shared_files = application["shares"]["files"].as_u64();
+            }
+            Err(e) => debug!("Failed to read the slskd application state: {}", e),
+        }
+        Ok(state)
+    }
+
+    /// Disconnects slskd from the Soulseek server and connects it again, then
+    /// waits a few seconds for the login to go through.
+    pub async fn reconnect(&self) -> Result<ServerState> {
+        match self
+            .make_request::<(), _>(Method::DELETE, "server", Some("Reconnecting"))
+            .await
+        {
+            Ok(()) | Err(SoulseekError::Api { status: 404, .. }) => {}
+            Err(e) => return Err(e),
+        }
+        self.make_request::<(), ()>(Method::PUT, "server", None)
+            .await?;
+
+        let mut state = self.server_state().await?;
+        for _ in 0..10 {
+            if state.is_logged_in {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            state = self.server_state().await?;
+        }
+        Ok(state)
+    }
+
+    /// Asks slskd to rescan its shared folders.
+    pub async fn rescan_shares(&self) -> Result<()> {
+        self.make_request::<(), ()>(Method::PUT, "shares", None)
+            .await
+    }
+
+    /// Checks that slskd is reachable and accepts the configured API key.
+    pub async fn check_session(&self) -> Result<()> {
         self.make_request::<serde_json::Value, ()>(Method::GET, "session", None)
             .await
-            .is_ok()
+            .map(|_| ())
+    }
+}
+
+/// Whether slskd answered a download request with the peer's rejection. slskd
+/// reports it as a server error carrying the rejection message, e.g. "File
+/// not shared", the same status as its own failures.
+fn is_rejection(status: reqwest::StatusCode, body: &str) -> bool {
+    status == reqwest::StatusCode::INTERNAL_SERVER_ERROR && body.to_lowercase().contains("rejected")
+}
+
+/// Turns the flat list of directories slskd returns for a browse into a tree.
+/// Folders that only exist as parents of shared ones are created along the way.
+fn build_directory_tree(directories: Vec<BrowseDirectory>) -> Vec<SharedDirectory> {
+    fn insert(
+        level: &mut Vec<SharedDirectory>,
+        parent: &str,
+        segments: &[&str],
+        files: Vec<SharedFile>,
+    ) {
+        let Some((name, rest)) = segments.split_first() else {
+            return;
+        };
+        let position = match level.iter().position(|d| d.name == *name) {
+            Some(i) => i,
+            None => {
+                level.push(SharedDirectory {
+                    name: name.to_string(),
+                    path: if parent.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{parent}\\{name}")
+                    },
+                    ..Default::default()
+                });
+                level.len() - 1
+            }
+        };
+        if rest.is_empty() {
+            level[position].files = files;
+        } else {
+            let path = level[position].path.clone();
+            insert(&mut level[position].children, &path, rest, files);
+        }
+    }
+
+    let mut roots = Vec::new();
+    for directory in directories {
+        let files = directory
+            .files
+            .into_iter()
+            .map(|file| SharedFile {
+                filename: format!("{}\\{}", directory.name, file.filename),
+                size: file.size,
+                bitrate: file.bit_rate,
+                duration: file.length,
+            })
+            .collect();
+        let segments: Vec<&str> = directory
+            .name
+            .split('\\')
+            .filter(|s| !s.is_empty())
+            .collect();
+        insert(&mut roots, "", &segments, files);
+    }
+    roots
+}
+
+/// Parses a slskd YAML configuration, applies `edit` to its `section`
+/// mapping, created if missing, and serializes it back. Comments are not kept.
+fn edit_yaml_section(
+    yaml: &str,
+    section: &str,
+    edit: impl FnOnce(&mut serde_yaml::Mapping),
+) -> Result<String> {
+    let mut config: serde_yaml::Value = serde_yaml::from_str(yaml)?;
+    if !config.is_mapping() {
+        config = serde_yaml::Value::Mapping(Default::default());
+    }
+    if !config[section].is_mapping() {
+        config[section] = serde_yaml::Value::Mapping(Default::default());
+    }
+    if let Some(mapping) = config[section].as_mapping_mut() {
+        edit(mapping);
+    }
+
+    Ok(serde_yaml::to_string(&config)?)
+}
+
+/// Applies `edit` to the `shares.directories` list of a slskd YAML
+/// configuration, creating the section if needed. Comments are not kept.
+fn edit_share_directories(yaml: &str, edit: impl FnOnce(&mut Vec<String>)) -> Result<String> {
+    edit_yaml_section(yaml, "shares", |shares| {
+        let mut directories: Vec<String> = shares
+            .get("directories")
+            .and_then(serde_yaml::Value::as_sequence)
+            .map(|dirs| {
+                dirs.iter()
+                    .filter_map(|d| d.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        edit(&mut directories);
+        shares.insert("directories".into(), directories.into());
+    })
+}
+
+/// Below this many files, results are ranked on the calling thread.
+const PARALLEL_RANKING_MIN_FILES: usize = 2_000;
+
+/// A file of a search response, with the response it came in.
+type Candidate<'a> = (&'a SearchResponse, &'a SearchResponseFile);
+
+/// The files worth ranking among `files`: a user's file listed in several of
+/// their responses is kept once. Other users' copies of a file are ranked
+/// too, and only become alternate sources once albums are assembled.
+fn unique_files<'a>(files: impl Iterator<Item = Candidate<'a>>) -> Vec<Candidate<'a>> {
+    let mut seen: HashSet<(&str, &str)> = HashSet::new();
+    files
+        .filter(|(response, file)| seen.insert((&response.username, &file.filename)))
+        .collect()
+}
+
+/// Files scored together by one task of the ranking pool.
+const RANKING_CHUNK_FILES: usize = 500;
+
+/// Runs `score` over chunks of `candidates` on rayon's pool, which is sized to
+/// the cores and shared by every search, for searches of popular artists
+/// returning tens of thousands of files. Each file is scored on its own, so a
+/// chunk can split an uploader's files. The results keep the order of
+/// `candidates`.
+fn score_in_chunks<C: Sync, T: Send>(
+    candidates: &[C],
+    score: impl Fn(&[C]) -> Vec<T> + Sync,
+) -> Vec<T> {
+    if candidates.len() < PARALLEL_RANKING_MIN_FILES {
+        return score(candidates);
+    }
+
+    let run = || {
+        candidates
+            .par_chunks(RANKING_CHUNK_FILES)
+            .flat_map_iter(&score)
+            .collect()
+    };
+    // A multi-threaded runtime moves its other tasks off this worker thread
+    // while it waits on the pool.
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(run)
+        }
+        _ => run(),
+    }
+}
+
+/// Album score added when the uploader shares the cover with the tracks.
+const ARTWORK_BONUS: f64 = 0.02;
+/// Album score added when they share the log or cue sheet of their rip, a
+/// sign of a careful rip rather than files gathered from everywhere.
+const RIP_LOG_BONUS: f64 = 0.03;
+
+/// Gives each album the artwork, logs and cue sheets shared in the folders
+/// of its tracks, or above a disc folder, and raises its score for them.
+fn attach_companions(albums: &mut [AlbumResult], uploaders: &[&SearchResponse]) {
+    let mut companions: HashMap<(&str, &str), Vec<SearchResult>> = HashMap::new();
+    for resp in uploaders {
+        for file in &resp.files {
+            let result = resp.search_result(file);
+            if result.is_artwork() || result.is_rip_log() {
+                companions
+                    .entry((resp.username.as_str(), parent_folder(&file.filename)))
+                    .or_default()
+                    .push(result);
+            }
+        }
+    }
+
+    for album in albums {
+        let mut folders: HashSet<&str> = HashSet::new();
+        for track in &album.tracks {
+            let folder = parent_folder(&track.base.filename);
+            folders.insert(folder);
+            if track.disc_number.is_some() {
+                folders.insert(parent_folder(folder));
+            }
+        }
+        let found: Vec<SearchResult> = folders
+            .into_iter()
+            .filter_map(|folder| companions.get(&(album.username.as_str(), folder)))
+            .flatten()
+            .filter(|file| {
+                !album
+                    .tracks
+                    .iter()
+                    .any(|t| t.base.filename == file.filename)
+            })
+            .cloned()
+            .collect();
+        if found.iter().any(SearchResult::is_artwork) {
+            album.score += ARTWORK_BONUS;
+        }
+        if found.iter().any(SearchResult::is_rip_log) {
+            album.score += RIP_LOG_BONUS;
+        }
+        album.score = album.score.min(1.0);
+        album.companions = found;
+    }
+}
+
+/// Folder of a shared file, whichever separator the peer uses.
+fn parent_folder(filename: &str) -> &str {
+    filename
+        .rfind(['\\', '/'])
+        .map_or("", |pos| &filename[..pos])
+}
+
+fn is_audio_file(filename: &str) -> bool {
+    AudioFormat::from_filename(filename, None).is_audio()
+}
+
+/// A file of a search response and how well it matched the search.
+type ScoredFile = (MatchResult, SearchResult);
+
+/// A file may stand in for an expected track other than its best match when
+/// its title is at least this close.
+const TRACK_MATCH_FLOOR: f64 = 0.7;
+
+/// Gives each expected track its own file from one candidate album, picking
+/// the pairs that match best overall, so that similar titles ("Intro" and
+/// "Intro (Reprise)") never end up downloading the same file twice. Tracks
+/// come back in the expected order; the flag is set when several tracks had
+/// the same file as their best match and some had to settle for another one,
+/// or for none.
+fn assign_files<'a>(
+    expected_tracks: &[&'a str],
+    files: &[&'a ScoredFile],
+    strategy: &dyn ScoringStrategy,
+) -> (Vec<(&'a str, &'a ScoredFile)>, bool) {
+    let weights: Vec<Vec<Option<f64>>> = expected_tracks
+        .iter()
+        .map(|track| {
+            files
+                .iter()
+                .map(|(rank, file)| {
+                    let similarity = track_similarity(&file.filename, track);
+                    (rank.matched_track == *track || similarity >= TRACK_MATCH_FLOOR)
+                        // File quality only breaks ties between equally close titles.
+                        .then(|| similarity + strategy.file_score(file) * 1e-3)
+                })
+                .collect()
+        })
+        .collect();
+
+    let best_files: Vec<usize> = weights
+        .iter()
+        .filter_map(|row| {
+            row.iter()
+                .enumerate()
+                .filter_map(|(i, w)| w.map(|w| (i, w)))
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i)
+        })
+        .collect();
+    let ambiguous = best_files.iter().unique().count() < best_files.len();
+
+    let assignment = max_weight_assignment(&weights)
+        .into_iter()
+        .zip(expected_tracks)
+        .filter_map(|(file, track)| file.map(|f| (*track, files[f])))
+        .collect();
+    (assignment, ambiguous)
+}
+
+/// Size, bitrate, duration and format of a file.
+type ContentKey = (i64, Option<i32>, Option<i32>, AudioFormat);
+
+/// Gives each album track the other uploaders' copies of its file, those
+/// that look byte-identical (same size, bitrate, duration and format), as
+/// alternate sources, the most available first. Albums that are only copies
+/// of the tracks of a better scored album are dropped, they would be the same
+/// download. Run once albums are assembled, so that an album split across
+/// uploaders keeps the tracks of each.
+fn merge_identical_files(
+    mut albums: Vec<AlbumResult>,
+    scored_files: &[ScoredFile],
+) -> Vec<AlbumResult> {
+    let key = |file: &SearchResult| -> Option<ContentKey> {
+        (file.size > 0).then(|| (file.size, file.bitrate, file.duration, file.format()))
+    };
+    let mut copies: HashMap<ContentKey, Vec<&SearchResult>> = HashMap::new();
+    for (_, file) in scored_files {
+        if let Some(key) = key(file) {
+            copies.entry(key).or_default().push(file);
+        }
+    }
+
+    albums.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.username.cmp(&b.username))
+            .then_with(|| a.album_path.cmp(&b.album_path))
+    });
+    let mut kept_files: HashSet<ContentKey> = HashSet::new();
+    let mut merged = Vec::with_capacity(albums.len());
+    for mut album in albums {
+        let keys: Vec<Option<ContentKey>> = album.tracks.iter().map(|t| key(&t.base)).collect();
+        if keys
+            .iter()
+            .all(|key| key.as_ref().is_some_and(|key| kept_files.contains(key)))
+        {
+            continue;
+        }
+        for (track, key) in album.tracks.iter_mut().zip(keys) {
+            let Some(key) = key else {
+                continue;
+            };
+            let mut sources: Vec<FileSource> = copies
+                .get(&key)
+                .into_iter()
+                .flatten()
+                .filter(|copy| copy.username != track.base.username)
+                .unique_by(|copy| copy.username.as_str())
+                .map(|copy| copy.source())
+                .collect();
+            sources.sort_by_key(|source| {
+                (
+                    std::cmp::Reverse(source.has_free_upload_slot),
+                    std::cmp::Reverse(source.upload_speed),
+                    source.queue_length,
+                )
+            });
+            track.base.alternate_sources = sources;
+            kept_files.insert(key);
+        }
+        merged.push(album);
     }
+    merged
 }
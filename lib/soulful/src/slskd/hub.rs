@@ -0,0 +1,118 @@
+//! A minimal client for slskd's SignalR hubs, over WebSockets with the JSON
+//! protocol. It only tells when the hub sent something, which is all the
+//! transfer watcher needs to know it should look at the downloads again.
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    tungstenite::{client::IntoClientRequest, http::HeaderValue, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+use url::Url;
+
+use crate::error::{Result, SoulseekError};
+
+/// Ends every SignalR message.
+const RECORD_SEPARATOR: char = '\u{1e}';
+
+/// SignalR closes connections it has not heard from in 30 seconds.
+const PING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// SignalR message types, as sent in their `type` field.
+const INVOCATION: u64 = 1;
+const CLOSE: u64 = 7;
+
+pub(super) struct HubConnection {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    ping: tokio::time::Interval,
+}
+
+impl HubConnection {
+    /// Connects to the hub at `path` of the slskd at `base_url`, skipping the
+    /// negotiation WebSockets do not need.
+    pub(super) async fn connect(base_url: &Url, path: &str, api_key: Option<&str>) -> Result<Self> {
+        let mut url = base_url.join(path)?;
+        let scheme = match url.scheme() {
+            "https" => "wss",
+            _ => "ws",
+        };
+        let _ = url.set_scheme(scheme);
+
+        let mut request = url.as_str().into_client_request().map_err(hub_error)?;
+        if let Some(key) = api_key {
+            let key = HeaderValue::from_str(key).map_err(hub_error)?;
+            request.headers_mut().insert("X-API-Key", key);
+        }
+        let (mut stream, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .map_err(hub_error)?;
+
+        let handshake = format!(r#"{{"protocol":"json","version":1}}{RECORD_SEPARATOR}"#);
+        stream
+            .send(Message::text(handshake))
+            .await
+            .map_err(hub_error)?;
+        // The handshake answer is an empty object, or one with an error.
+        match stream.next().await {
+            Some(Ok(Message::Text(text))) => {
+                let answer: serde_json::Value =
+                    serde_json::from_str(text.trim_end_matches(RECORD_SEPARATOR))
+                        .unwrap_or_default();
+                if let Some(error) = answer["error"].as_str() {
+                    return Err(hub_error(error));
+                }
+            }
+            Some(Ok(other)) => return Err(hub_error(format!("unexpected handshake {other:?}"))),
+            Some(Err(e)) => return Err(hub_error(e)),
+            None => return Err(hub_error("closed during the handshake")),
+        }
+
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        ping.reset();
+        Ok(HubConnection { stream, ping })
+    }
+
+    /// Waits until the hub invokes anything on us, keeping the connection
+    /// alive meanwhile. Fails once the connection is closed.
+    pub(super) async fn next_event(&mut self) -> Result<()> {
+        loop {
+            tokio::select! {
+                frame = self.stream.next() => {
+                    let text = match frame {
+                        Some(Ok(Message::Text(text))) => text,
+                        Some(Ok(Message::Close(_))) | None => return Err(hub_error("closed")),
+                        Some(Ok(_)) => continue,
+                        Some(Err(e)) => return Err(hub_error(e)),
+                    };
+                    let mut invoked = false;
+                    for message in text.split(RECORD_SEPARATOR).filter(|m| !m.is_empty()) {
+                        let message: serde_json::Value =
+                            serde_json::from_str(message).unwrap_or_default();
+                        match message["type"].as_u64() {
+                            Some(INVOCATION) => invoked = true,
+                            Some(CLOSE) => {
+                                let error = message["error"].as_str().unwrap_or("closed");
+                                return Err(hub_error(error));
+                            }
+                            _ => {}
+                        }
+                    }
+                    if invoked {
+                        return Ok(());
+                    }
+                }
+                _ = self.ping.tick() => {
+                    let ping = format!(r#"{{"type":6}}{RECORD_SEPARATOR}"#);
+                    self.stream
+                        .send(Message::text(ping))
+                        .await
+                        .map_err(hub_error)?;
+                }
+            }
+        }
+    }
+}
+
+fn hub_error(error: impl std::fmt::Display) -> SoulseekError {
+    SoulseekError::Hub(error.to_string())
+}
@@ -0,0 +1,333 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use itertools::Itertools;
+use shared::slskd::{
+    AlbumResult, DownloadResponse, DownloadState, FileEntry, SearchQueuePosition, SearchRateLimit,
+    SearchRateUsage, SearchResult, ServerState, SharedDirectory, SoulseekAccount,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::sync::broadcast;
+
+use super::{AlbumSearch, SearchRequest, SoulseekBackend, TransferUpdate};
+use crate::{error::Result, provider::DownloadProvider};
+
+/// A [`SoulseekBackend`] kept in memory, to run the API without slskd or the
+/// Soulseek network. Searches return the albums and files it was given, and
+/// downloads stay queued until moved along with [`MockBackend::set_state`].
+/// It is always logged in, never rate limited and nobody downloads from it.
+pub struct MockBackend {
+    albums: Vec<AlbumResult>,
+    files: Vec<SearchResult>,
+    downloads: Mutex<Vec<FileEntry>>,
+    next_id: AtomicU64,
+    transfers: broadcast::Sender<TransferUpdate>,
+    shares: Mutex<Vec<String>>,
+    account: Mutex<SoulseekAccount>,
+    rate_limit: Mutex<SearchRateLimit>,
+}
+
+impl Default for MockBackend {
+    fn default() -> Self {
+        MockBackend {
+            albums: Vec::new(),
+            files: Vec::new(),
+            downloads: Mutex::new(Vec::new()),
+            next_id: AtomicU64::new(1),
+            transfers: broadcast::channel(16).0,
+            shares: Mutex::new(Vec::new()),
+            account: Mutex::new(SoulseekAccount::default()),
+            rate_limit: Mutex::new(SearchRateLimit::default()),
+        }
+    }
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Albums every album search finds, as given.
+    pub fn with_albums(mut self, albums: Vec<AlbumResult>) -> Self {
+        self.albums = albums;
+        self
+    }
+
+    /// Files every raw search finds.
+    pub fn with_files(mut self, files: Vec<SearchResult>) -> Self {
+        self.files = files;
+        self
+    }
+
+    /// Every download requested and not removed.
+    pub fn downloads(&self) -> Vec<FileEntry> {
+        self.downloads.lock().unwrap().clone()
+    }
+
+    /// The shared directories, in the order they were added.
+    pub fn shares(&self) -> Vec<String> {
+        self.shares.lock().unwrap().clone()
+    }
+
+    /// Moves a download to `state`, as slskd spells it (e.g. "Completed,
+    /// Succeeded"), and tells the transfer subscribers.
+    pub fn set_state(&self, id: &str, state: &str) {
+        let mut downloads = self.downloads.lock().unwrap();
+        let Some(download) = downloads.iter_mut().find(|d| d.id == id) else {
+            return;
+        };
+        download.state = state
+            .split(',')
+            .map(|part| DownloadState::from(part.trim().to_string()))
+            .collect();
+        download.state_description = state.to_string();
+        if download.is_succeeded() {
+            download.bytes_transferred = download.size;
+            download.bytes_remaining = 0;
+            download.percent_complete = 100.0;
+        }
+        if download.is_finished() {
+            download.ended_at = Some(Utc::now().to_rfc3339());
+        }
+        self.notify(&downloads, vec![id.to_string()]);
+    }
+
+    fn notify(&self, downloads: &[FileEntry], changed: Vec<String>) {
+        let _ = self.transfers.send(TransferUpdate {
+            downloads: Arc::new(downloads.to_vec()),
+            changed,
+        });
+    }
+
+    fn queue(&self, username: String, filename: String, size: i64) -> FileEntry {
+        let id = format!("mock-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let now = Utc::now().to_rfc3339();
+        let size = size.max(0) as u64;
+        FileEntry {
+            id,
+            username,
+            direction: "Download".to_string(),
+            filename,
+            size,
+            start_offset: 0,
+            state: vec![DownloadState::Queued, DownloadState::Locally],
+            state_description: "Queued, Locally".to_string(),
+            requested_at: now.clone(),
+            enqueued_at: now,
+            started_at: None,
+            ended_at: None,
+            bytes_transferred: 0,
+            average_speed: 0.0,
+            bytes_remaining: size,
+            elapsed_time: None,
+            percent_complete: 0.0,
+            remaining_time: None,
+            exception: None,
+            place_in_queue: None,
+        }
+    }
+}
+
+#[async_trait]
+impl DownloadProvider for MockBackend {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    async fn search(&self, request: SearchRequest<'_>) -> Result<AlbumSearch> {
+        if let Some(on_partial) = request.on_partial {
+            on_partial(self.albums.clone());
+        }
+        Ok(AlbumSearch {
+            albums: self.albums.clone(),
+            diagnosis: None,
+        })
+    }
+
+    async fn fetch(&self, files: Vec<(String, String, i64)>) -> Result<Vec<DownloadResponse>> {
+        let mut downloads = self.downloads.lock().unwrap();
+        let mut responses = Vec::new();
+        for (username, filename, size) in files {
+            let entry = self.queue(username, filename, size);
+            responses.push(DownloadResponse {
+                id: entry.id.clone(),
+                filename: entry.filename.clone(),
+                original_filename: entry.filename.clone(),
+            });
+            downloads.push(entry);
+        }
+        let changed = responses.iter().map(|r| r.id.clone()).collect();
+        self.notify(&downloads, changed);
+        Ok(responses)
+    }
+
+    async fn get_all_downloads(&self) -> Result<Vec<FileEntry>> {
+        Ok(self.downloads())
+    }
+
+    async fn get_download(&self, source: &str, id: &str) -> Result<Option<FileEntry>> {
+        Ok(self
+            .downloads
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|d| d.username == source && d.id == id)
+            .cloned())
+    }
+
+    fn progress(&self) -> broadcast::Receiver<TransferUpdate> {
+        self.transfers.subscribe()
+    }
+
+    async fn cancel_download(&self, source: &str, id: &str, remove: bool) -> Result<()> {
+        if remove {
+            let mut downloads = self.downloads.lock().unwrap();
+            downloads.retain(|d| !(d.username == source && d.id == id));
+            self.notify(&downloads, Vec::new());
+        } else {
+            self.set_state(id, "Completed, Cancelled");
+        }
+        Ok(())
+    }
+
+    async fn get_queue_position(&self, source: &str, id: &str) -> Result<Option<u32>> {
+        Ok(self
+            .get_download(source, id)
+            .await?
+            .and_then(|d| d.place_in_queue))
+    }
+}
+
+#[async_trait]
+impl SoulseekBackend for MockBackend {
+    async fn search_raw(
+        &self,
+        _query: &str,
+        _timeout: Duration,
+        _search_id: Option<&str>,
+    ) -> Result<Vec<SearchResult>> {
+        Ok(self.files.clone())
+    }
+
+    async fn cancel_search(&self, _search_id: &str) -> bool {
+        false
+    }
+
+    fn search_queue_position(&self, _search_id: &str) -> Option<SearchQueuePosition> {
+        None
+    }
+
+    fn next_search_position(&self) -> Option<SearchQueuePosition> {
+        None
+    }
+
+    fn set_search_rate_limit(&self, limit: SearchRateLimit) {
+        *self.rate_limit.lock().unwrap() = limit;
+    }
+
+    fn search_rate_usage(&self) -> SearchRateUsage {
+        SearchRateUsage {
+            limit: *self.rate_limit.lock().unwrap(),
+            ..Default::default()
+        }
+    }
+
+    async fn probe_availability(
+        &self,
+        _artist: &str,
+        _album: &str,
+        _timeout: Duration,
+    ) -> Result<usize> {
+        Ok(self.albums.iter().map(|a| &a.username).unique().count())
+    }
+
+    async fn probe_formats(
+        &self,
+        _artist: &str,
+        _album: &str,
+        _timeout: Duration,
+    ) -> Result<BTreeMap<String, usize>> {
+        let mut formats: BTreeMap<String, usize> = BTreeMap::new();
+        for (extension, _) in self
+            .files
+            .iter()
+            .map(|f| (f.format().extension().to_string(), &f.username))
+            .unique()
+        {
+            *formats.entry(extension).or_default() += 1;
+        }
+        Ok(formats)
+    }
+
+    async fn browse_user(&self, _username: &str) -> Result<Vec<SharedDirectory>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_all_uploads(&self) -> Result<Vec<FileEntry>> {
+        Ok(Vec::new())
+    }
+
+    async fn cancel_upload(&self, _username: &str, _upload_id: &str, _remove: bool) -> Result<()> {
+        Ok(())
+    }
+
+    async fn add_shares(&self, directories: &[String]) -> Result<()> {
+        let mut shares = self.shares.lock().unwrap();
+        for directory in directories {
+            if !shares.contains(directory) {
+                shares.push(directory.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn remove_shares(&self, directories: &[String]) -> Result<()> {
+        self.shares
+            .lock()
+            .unwrap()
+            .retain(|share| !directories.contains(share));
+        Ok(())
+    }
+
+    async fn rescan_shares(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn soulseek_account(&self) -> Result<SoulseekAccount> {
+        Ok(self.account.lock().unwrap().clone())
+    }
+
+    async fn update_soulseek_account(
+        &self,
+        account: &SoulseekAccount,
+        _password: Option<&str>,
+    ) -> Result<()> {
+        *self.account.lock().unwrap() = account.clone();
+        Ok(())
+    }
+
+    async fn server_state(&self) -> Result<ServerState> {
+        let shares = self.shares.lock().unwrap().len() as u64;
+        Ok(ServerState {
+            state: "Connected, LoggedIn".to_string(),
+            is_connected: true,
+            is_logged_in: true,
+            username: Some(self.account.lock().unwrap().username.clone()),
+            shared_directories: Some(shares),
+            shared_files: None,
+        })
+    }
+
+    async fn reconnect(&self) -> Result<ServerState> {
+        self.server_state().await
+    }
+
+    async fn check_session(&self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,5 +1,13 @@
+mod backend;
 mod client;
+mod hub;
+mod mock;
 mod models;
+mod queue;
+pub mod scoring;
 mod utils;
 
+pub use backend::SoulseekBackend;
 pub use client::*;
+pub use mock::MockBackend;
+pub use utils::{filename_variants, rank_match};
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use shared::slskd::{AudioFormat, SearchResult};
 
 // Internal structs for deserializing raw API responses
 #[derive(Deserialize, Debug)]
@@ -8,6 +9,10 @@ pub(crate) struct SearchResponseFile {
     pub size: i64,
     pub bit_rate: Option<i32>,
     pub length: Option<i32>,
+    #[serde(default)]
+    pub sample_rate: Option<i32>,
+    #[serde(default)]
+    pub bit_depth: Option<i32>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -20,8 +25,69 @@ pub(crate) struct SearchResponse {
     pub queue_length: i32,
 }
 
+impl SearchResponseFile {
+    pub fn format(&self) -> AudioFormat {
+        AudioFormat::from_filename(&self.filename, self.bit_rate)
+    }
+}
+
+impl SearchResponse {
+    /// One of the response's files, with what the user told about their slots.
+    pub fn search_result(&self, file: &SearchResponseFile) -> SearchResult {
+        SearchResult {
+            username: self.username.clone(),
+            filename: file.filename.clone(),
+            size: file.size,
+            bitrate: file.bit_rate,
+            duration: file.length,
+            sample_rate: file.sample_rate,
+            bit_depth: file.bit_depth,
+            has_free_upload_slot: self.has_free_upload_slot,
+            upload_speed: self.upload_speed,
+            queue_length: self.queue_length,
+            alternate_sources: vec![],
+        }
+    }
+}
+
+/// One file of a download request. `filename` must be the path exactly as the
+/// peer shared it, backslashes and Unicode form included: peers look their
+/// files up by that exact string.
 #[derive(Serialize)]
 pub(crate) struct DownloadRequestFile {
     pub filename: String,
     pub size: i64,
 }
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BrowseFile {
+    /// Name of the file, without its directory.
+    pub filename: String,
+    pub size: i64,
+    pub bit_rate: Option<i32>,
+    pub length: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BrowseDirectory {
+    /// Full path of the directory.
+    pub name: String,
+    #[serde(default)]
+    pub files: Vec<BrowseFile>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BrowseResponse {
+    #[serde(default)]
+    pub directories: Vec<BrowseDirectory>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UserInfo {
+    pub has_free_upload_slot: bool,
+    pub queue_length: i32,
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Duration, Utc};
+use shared::slskd::SearchQueuePosition;
+use std::collections::VecDeque;
+
+/// Searches waiting for the slskd rate limit, in the order they asked for a
+/// slot, and the start times of the searches still counting against it.
+#[derive(Debug, Default)]
+pub(crate) struct SearchQueue {
+    started: Vec<DateTime<Utc>>,
+    waiting: VecDeque<String>,
+    next_ticket: u64,
+}
+
+impl SearchQueue {
+    /// Takes a place in line, under `id` or an internal ticket.
+    pub fn join(&mut self, id: Option<&str>) -> String {
+        let ticket = match id {
+            Some(id) => id.to_string(),
+            None => {
+                self.next_ticket += 1;
+                format!("internal-{}", self.next_ticket)
+            }
+        };
+        self.waiting.push_back(ticket.clone());
+        ticket
+    }
+
+    /// Gives up a place in line. Returns whether the ticket was waiting.
+    pub fn leave(&mut self, ticket: &str) -> bool {
+        let before = self.waiting.len();
+        self.waiting.retain(|t| t != ticket);
+        self.waiting.len() != before
+    }
+
+    pub fn contains(&self, ticket: &str) -> bool {
+        self.waiting.iter().any(|t| t == ticket)
+    }
+
+    /// Starts the search holding `ticket` if it is first in line and a slot is
+    /// free. Returns whether it did.
+    pub fn try_start(&mut self, ticket: &str, max: usize, window: Duration) -> bool {
+        self.prune(window);
+        if self.waiting.front().map(String::as_str) != Some(ticket) || self.started.len() >= max {
+            return false;
+        }
+        self.waiting.pop_front();
+        self.started.push(Utc::now());
+        true
+    }
+
+    /// Place of `ticket` in line, and roughly when its search will start.
+    pub fn position(
+        &mut self,
+        ticket: &str,
+        max: usize,
+        window: Duration,
+    ) -> Option<SearchQueuePosition> {
+        self.prune(window);
+        let index = self.waiting.iter().position(|t| t == ticket)?;
+        Some(SearchQueuePosition {
+            position: index + 1,
+            eta_seconds: self.eta(index, max, window).num_seconds().max(0),
+        })
+    }
+
+    /// Where a search joining the line now would be, `None` if it would start
+    /// right away.
+    pub fn next_position(&mut self, max: usize, window: Duration) -> Option<SearchQueuePosition> {
+        self.prune(window);
+        let index = self.waiting.len();
+        if index < max.saturating_sub(self.started.len()) {
+            return None;
+        }
+        Some(SearchQueuePosition {
+            position: index + 1,
+            eta_seconds: self.eta(index, max, window).num_seconds().max(0),
+        })
+    }
+
+    /// Slots free up as the oldest searches leave the window, one per waiting
+    /// search ahead; a line longer than the limit waits whole windows more.
+    fn eta(&self, index: usize, max: usize, window: Duration) -> Duration {
+        let free = max.saturating_sub(self.started.len());
+        if index < free || self.started.is_empty() {
+            return Duration::zero();
+        }
+        let behind = index - free;
+        let oldest = self.started[behind % self.started.len()];
+        let extra_windows = (behind / self.started.len()) as i32;
+        oldest + window * (extra_windows + 1) - Utc::now()
+    }
+
+    /// Searches started within the window, searches waiting, and how long
+    /// until the oldest started one leaves the window.
+    pub fn usage(&mut self, window: Duration) -> (usize, usize, Option<Duration>) {
+        self.prune(window);
+        let next_slot = self
+            .started
+            .iter()
+            .min()
+            .map(|&oldest| oldest + window - Utc::now());
+        (self.started.len(), self.waiting.len(), next_slot)
+    }
+
+    fn prune(&mut self, window: Duration) {
+        let window_start = Utc::now() - window;
+        self.started.retain(|&ts| ts > window_start);
+    }
+}
@@ -0,0 +1,347 @@
+use shared::{
+    musicbrainz::{edition_keywords, keyword_forms, Edition, Track, EDITION_KEYWORDS},
+    slskd::{
+        AudioFormat, MatchResult, ScoringConfig, ScoringStrategyKind, SearchResult, TitleSuffixes,
+    },
+};
+use std::collections::HashMap;
+
+use super::{utils, MIN_MATCH_SCORE};
+
+/// Aggregated signals for one candidate album, fed to [`ScoringStrategy::album_score`].
+#[derive(Debug, Clone)]
+pub struct AlbumScoreInputs {
+    /// Average `MatchResult::total_score` of the album's tracks.
+    pub avg_match_score: f64,
+    /// Share of the expected tracks that were found, in `0.0..=1.0`.
+    pub completeness: f64,
+    /// Average [`ScoringStrategy::file_score`] of the album's tracks.
+    pub avg_file_score: f64,
+    pub has_free_upload_slot: bool,
+    pub upload_speed: i32,
+    pub queue_length: i32,
+}
+
+/// Ranking policy used to turn raw search responses into ordered album candidates.
+pub trait ScoringStrategy: Send + Sync {
+    /// Scores how well a remote file matches what was searched for.
+    fn rank_match(
+        &self,
+        filename: &str,
+        file_duration: Option<i32>,
+        searched_artist: Option<&str>,
+        searched_album: Option<&str>,
+        expected_tracks: &[&str],
+        expected_durations: &[Option<i32>],
+    ) -> MatchResult {
+        utils::rank_match(
+            filename,
+            file_duration,
+            searched_artist,
+            searched_album,
+            expected_tracks,
+            expected_durations,
+            &self.title_suffixes(),
+        )
+    }
+
+    /// How version suffixes at the end of track and album titles are handled.
+    fn title_suffixes(&self) -> TitleSuffixes {
+        TitleSuffixes::default()
+    }
+
+    /// Scores a single file, used to pick between files matching the same track.
+    fn file_score(&self, file: &SearchResult) -> f64 {
+        file.quality_score()
+    }
+
+    /// Final score of a candidate album.
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64;
+
+    /// Files whose `MatchResult::total_score` is below this are dropped.
+    fn min_match_score(&self) -> f64 {
+        MIN_MATCH_SCORE
+    }
+
+    /// Factor applied to the `MatchResult::total_score` of a file kept by
+    /// [`ScoringStrategy::min_match_score`], to rank it lower without
+    /// dropping it.
+    fn match_factor(&self, _filename: &str) -> f64 {
+        1.0
+    }
+}
+
+/// How quickly the uploader will deliver, in `0.0..=1.0`: free slot, upload
+/// speed and queue length.
+fn availability(inputs: &AlbumScoreInputs) -> f64 {
+    let slot = if inputs.has_free_upload_slot { 1.0 } else { 0.0 };
+    // Upload speed is reported in bytes/s, saturate around 1 MB/s.
+    let speed = (inputs.upload_speed.max(0) as f64 / 1_000_000.0).min(1.0);
+    let queue = 1.0 / (1.0 + inputs.queue_length.max(0) as f64 / 5.0);
+    (slot * 0.4) + (speed * 0.3) + (queue * 0.3)
+}
+
+/// Favours lossless, high bitrate sources. This is the historical behaviour.
+pub struct QualityFirst;
+
+impl ScoringStrategy for QualityFirst {
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64 {
+        (inputs.avg_match_score * 0.3) + (inputs.completeness * 0.3) + (inputs.avg_file_score * 0.4)
+    }
+}
+
+/// Favours sources that will actually deliver quickly: free slots, fast
+/// uploaders and short queues, at the expense of format.
+pub struct AvailabilityFirst;
+
+impl ScoringStrategy for AvailabilityFirst {
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64 {
+        (inputs.avg_match_score * 0.3)
+            + (inputs.completeness * 0.3)
+            + (inputs.avg_file_score * 0.1)
+            + (availability(inputs) * 0.3)
+    }
+}
+
+/// Favours compact lossy files that still sound good, for listening on the go
+/// or on small devices: 192-256 kbps lossy ranks above lossless and above
+/// very low bitrates.
+pub struct SizeFirst;
+
+impl ScoringStrategy for SizeFirst {
+    fn file_score(&self, file: &SearchResult) -> f64 {
+        let format: f64 = match file.format() {
+            AudioFormat::Mp3 { .. } | AudioFormat::M4a | AudioFormat::Aac | AudioFormat::Ogg => 0.7,
+            format if format.is_lossless() => 0.2,
+            _ => 0.4,
+        };
+        let bitrate = match file.bitrate {
+            Some(br) if br < 160 => -0.3,
+            Some(br) if br <= 256 => 0.3,
+            Some(br) if br <= 320 => 0.2,
+            _ => 0.0,
+        };
+        (format + bitrate).clamp(0.0, 1.0)
+    }
+
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64 {
+        (inputs.avg_match_score * 0.3) + (inputs.completeness * 0.3) + (inputs.avg_file_score * 0.4)
+    }
+}
+
+/// A strategy with a user's or a search's [`ScoringConfig`] on top.
+pub struct Configured<'a> {
+    pub strategy: &'a dyn ScoringStrategy,
+    pub config: ScoringConfig,
+}
+
+// Files are matched by the default `rank_match`, with the config's title
+// suffixes: the strategies it wraps have no matching of their own.
+impl ScoringStrategy for Configured<'_> {
+    fn title_suffixes(&self) -> TitleSuffixes {
+        self.config.title_suffixes()
+    }
+
+    fn file_score(&self, file: &SearchResult) -> f64 {
+        self.strategy.file_score(file) + self.config.bitrate_bonus(file.bitrate)
+    }
+
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64 {
+        match self.config.weights {
+            Some(weights) if weights.total() > 0.0 => {
+                ((inputs.avg_match_score * weights.match_score)
+                    + (inputs.completeness * weights.completeness)
+                    + (inputs.avg_file_score * weights.format)
+                    + (availability(inputs) * weights.availability))
+                    / weights.total()
+            }
+            _ => self.strategy.album_score(inputs),
+        }
+    }
+
+    fn min_match_score(&self) -> f64 {
+        self.config
+            .min_match_score
+            .unwrap_or_else(|| self.strategy.min_match_score())
+    }
+
+    fn match_factor(&self, filename: &str) -> f64 {
+        self.strategy.match_factor(filename)
+    }
+}
+
+/// Whether `artist` is the one MusicBrainz credits compilations to.
+pub fn is_compilation(artist: &str) -> bool {
+    let artist = artist.trim();
+    artist.eq_ignore_ascii_case("Various Artists") || artist.eq_ignore_ascii_case("VA")
+}
+
+/// A strategy ranking the files of a compilation, whose tracks are each by
+/// their own artist. A file is matched to a track first, then scored against
+/// that track's artist instead of "Various Artists", which no file carries.
+pub struct Compilation<'a> {
+    pub strategy: &'a dyn ScoringStrategy,
+    /// Artist of each track, by title.
+    artists: HashMap<String, String>,
+}
+
+impl<'a> Compilation<'a> {
+    pub fn new(strategy: &'a dyn ScoringStrategy, tracks: &[Track]) -> Self {
+        let artists = tracks
+            .iter()
+            .map(|t| (t.title.clone(), t.artist.clone()))
+            .collect();
+        Compilation { strategy, artists }
+    }
+}
+
+impl ScoringStrategy for Compilation<'_> {
+    fn rank_match(
+        &self,
+        filename: &str,
+        file_duration: Option<i32>,
+        searched_artist: Option<&str>,
+        searched_album: Option<&str>,
+        expected_tracks: &[&str],
+        expected_durations: &[Option<i32>],
+    ) -> MatchResult {
+        let matched = self.strategy.rank_match(
+            filename,
+            file_duration,
+            None,
+            searched_album,
+            expected_tracks,
+            expected_durations,
+        );
+        let Some(artist) = self.artists.get(&matched.matched_track) else {
+            return matched;
+        };
+        let duration = expected_tracks
+            .iter()
+            .position(|t| *t == matched.matched_track)
+            .and_then(|i| expected_durations.get(i).copied().flatten());
+        let mut ranked = self.strategy.rank_match(
+            filename,
+            file_duration,
+            Some(artist),
+            searched_album,
+            &[matched.matched_track.as_str()],
+            &[duration],
+        );
+        // Albums are grouped by uploader and album only, whoever each track is by.
+        ranked.guessed_artist = searched_artist.unwrap_or_default().to_string();
+        ranked
+    }
+
+    fn title_suffixes(&self) -> TitleSuffixes {
+        self.strategy.title_suffixes()
+    }
+
+    fn file_score(&self, file: &SearchResult) -> f64 {
+        self.strategy.file_score(file)
+    }
+
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64 {
+        self.strategy.album_score(inputs)
+    }
+
+    fn min_match_score(&self) -> f64 {
+        self.strategy.min_match_score()
+    }
+
+    fn match_factor(&self, filename: &str) -> f64 {
+        self.strategy.match_factor(filename)
+    }
+}
+
+/// A strategy preferring the files of the edition of an album the user
+/// picked: those whose path carries an edition keyword ("remastered",
+/// "deluxe"...) the edition lacks pay the title suffix penalty, instead of
+/// every file whose title suffixes differ from the searched ones. They are
+/// still kept when they match well enough.
+pub struct PreferredEdition<'a> {
+    pub strategy: &'a dyn ScoringStrategy,
+    /// Keywords of the edition searched for.
+    keywords: Vec<&'static str>,
+}
+
+impl<'a> PreferredEdition<'a> {
+    pub fn new(strategy: &'a dyn ScoringStrategy, edition: &Edition) -> Self {
+        PreferredEdition {
+            strategy,
+            keywords: edition.keywords(),
+        }
+    }
+
+    /// Single-word keywords of the other editions, in every spelling, to
+    /// leave out of a search.
+    pub fn excluded_terms(&self) -> Vec<String> {
+        EDITION_KEYWORDS
+            .iter()
+            .filter(|k| !k.contains(' ') && !self.keywords.contains(k))
+            .flat_map(|k| keyword_forms(k))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl ScoringStrategy for PreferredEdition<'_> {
+    fn rank_match(
+        &self,
+        filename: &str,
+        file_duration: Option<i32>,
+        searched_artist: Option<&str>,
+        searched_album: Option<&str>,
+        expected_tracks: &[&str],
+        expected_durations: &[Option<i32>],
+    ) -> MatchResult {
+        self.strategy.rank_match(
+            filename,
+            file_duration,
+            searched_artist,
+            searched_album,
+            expected_tracks,
+            expected_durations,
+        )
+    }
+
+    fn title_suffixes(&self) -> TitleSuffixes {
+        TitleSuffixes {
+            penalty: 0.0,
+            ..self.strategy.title_suffixes()
+        }
+    }
+
+    fn file_score(&self, file: &SearchResult) -> f64 {
+        self.strategy.file_score(file)
+    }
+
+    fn album_score(&self, inputs: &AlbumScoreInputs) -> f64 {
+        self.strategy.album_score(inputs)
+    }
+
+    fn min_match_score(&self) -> f64 {
+        self.strategy.min_match_score()
+    }
+
+    fn match_factor(&self, filename: &str) -> f64 {
+        let other_edition = edition_keywords(filename)
+            .iter()
+            .any(|k| !self.keywords.contains(k));
+        let factor = if other_edition {
+            1.0 - self.strategy.title_suffixes().penalty
+        } else {
+            1.0
+        };
+        factor * self.strategy.match_factor(filename)
+    }
+}
+
+/// Resolves a serializable strategy choice to its implementation.
+pub fn strategy_for(kind: ScoringStrategyKind) -> &'static dyn ScoringStrategy {
+    match kind {
+        ScoringStrategyKind::QualityFirst => &QualityFirst,
+        ScoringStrategyKind::AvailabilityFirst => &AvailabilityFirst,
+        ScoringStrategyKind::SizeFirst => &SizeFirst,
+    }
+}
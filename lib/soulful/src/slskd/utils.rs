@@ -1,5 +1,5 @@
 use regex::Regex;
-use shared::slskd::MatchResult;
+use shared::slskd::{MatchResult, TitleSuffixes};
 use std::{collections::HashSet, path::Path, sync::LazyLock};
 
 static RE_NON_WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^\w\s]").unwrap());
@@ -9,6 +9,117 @@ static RE_TRAIL_BRACKET: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\s*\[\s*[^\]]*\]\s*$").unwrap());
 static RE_TRAIL_YEAR: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\s*[-\(\[]?\d{4}[-\)\]]?\s*$").unwrap());
+/// Featured artists in brackets, as in "Song (feat. X)" or "Song [with X]".
+static RE_FEAT_BRACKET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\s*[\(\[]\s*(?:feat\.?|ft\.?|featuring|with)\s[^\)\]]*[\)\]]").unwrap()
+});
+/// Featured artists after the title, as in "Song feat. X", up to a " - ".
+static RE_FEAT_TRAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\s+(?:feat\.?|ft\.?|featuring)\s.*?(\s-\s|$)").unwrap());
+/// Folders holding one disc of an album, e.g. "CD1", "Disc 2" or "disk_3 - Live".
+static RE_DISC_FOLDER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*[\[\(]?\s*(?:cd|disc|disk)\s*[-_. ]?\s*(\d{1,2})\b").unwrap()
+});
+
+/// Latin spelling of the Cyrillic and Greek letters, lowercase and without
+/// their diacritics: "й" and "ά" come here as "и" and "α".
+fn transliterate(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'е' | 'э' => "e",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'ю' => "yu",
+        'я' => "ya",
+        'є' => "ye",
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        'ø' => "o",
+        'đ' | 'ð' => "d",
+        'ł' => "l",
+        'þ' => "th",
+        'ı' => "i",
+        _ => return None,
+    })
+}
+
+/// Lowercase ASCII spelling of `s` where it has one: accents stripped
+/// ("Björk" is "bjork") and Cyrillic and Greek transliterated ("Мельница" is
+/// "melnitsa"), so files named by uploaders from elsewhere still match.
+/// Other scripts are kept as they are. Letters are decomposed first, so
+/// precomposed and combining accents fold alike.
+fn fold(s: &str) -> String {
+    use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+    let mut folded = String::with_capacity(s.len());
+    for c in s
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+    {
+        match transliterate(c) {
+            Some(latin) => folded.push_str(latin),
+            None => folded.push(c),
+        }
+    }
+    folded
+}
+
+/// Articles and joining words, which uploaders drop, move or translate: "The
+/// National" is shared as "National" or "National, The", "Die Ärzte" as
+/// "Ärzte", "Simon & Garfunkel" as "Simon and Garfunkel".
+const NOISE_WORDS: [&str; 17] = [
+    "the", "a", "an", "le", "la", "les", "l", "die", "der", "das", "el", "los", "las", "il", "and",
+    "et", "und",
+];
 
 // A struct to hold pre-processed text for efficient comparisons.
 #[derive(Debug, Clone)]
@@ -20,10 +131,9 @@ struct CleanedText {
 impl CleanedText {
     fn new(s: &str) -> Self {
         let original = s.to_string();
-        let s = s.replace('_', " ");
+        let s = fold(s).replace('_', " ");
         let cleaned = RE_NON_WORD.replace_all(&s, " ").to_string();
         let words = cleaned
-            .to_lowercase()
             .split_whitespace()
             .filter(|w| !w.trim().is_empty())
             .map(|w| w.to_string())
@@ -34,6 +144,24 @@ impl CleanedText {
     pub fn words(&self) -> &HashSet<String> {
         &self.words
     }
+
+    /// The same text without its noise words, unless it is nothing else, as
+    /// for "The The".
+    fn significant(&self) -> Self {
+        let words: HashSet<String> = self
+            .words
+            .iter()
+            .filter(|w| !NOISE_WORDS.contains(&w.as_str()))
+            .cloned()
+            .collect();
+        if words.is_empty() {
+            return self.clone();
+        }
+        CleanedText {
+            original: self.original.clone(),
+            words,
+        }
+    }
 }
 
 fn jaccard_sim(a: &CleanedText, b: &CleanedText) -> f64 {
@@ -72,6 +200,63 @@ fn clean_name(name: &str) -> String {
     cleaned.trim().to_string()
 }
 
+/// `title` without its trailing version suffixes, as in "Song (2011
+/// Remaster)" or "Song - Deluxe Edition", and the suffixes left out. A
+/// suffix is a trailing bracketed or " - " separated part holding one of
+/// `suffixes`.
+pub(crate) fn strip_title_suffixes(title: &str, suffixes: &[String]) -> (String, Vec<String>) {
+    let mut stripped = title.trim().to_string();
+    let mut removed = Vec::new();
+    loop {
+        let split = match stripped.chars().last() {
+            Some(close @ (')' | ']')) => {
+                let open = if close == ')' { '(' } else { '[' };
+                stripped
+                    .rfind(open)
+                    .map(|pos| (pos, &stripped[pos + 1..stripped.len() - 1]))
+            }
+            _ => stripped.rfind(" - ").map(|pos| (pos, &stripped[pos + 3..])),
+        };
+        let Some((pos, suffix)) = split else {
+            break;
+        };
+        let folded = fold(suffix);
+        let is_version = suffixes
+            .iter()
+            .any(|s| !s.is_empty() && folded.contains(&fold(s)));
+        let rest = stripped[..pos].trim();
+        if !is_version || rest.is_empty() {
+            break;
+        }
+        removed.push(suffix.trim().to_string());
+        stripped = rest.to_string();
+    }
+    (stripped, removed)
+}
+
+/// `title` without its featured artists, which MusicBrainz and uploaders
+/// credit inconsistently: "Song (feat. X)", "Song ft. X" and "Song [with X]"
+/// are all "Song".
+fn strip_featuring(title: &str) -> String {
+    let title = RE_FEAT_BRACKET.replace_all(title, "");
+    RE_FEAT_TRAIL.replace_all(&title, "$1").trim().to_string()
+}
+
+/// `title` without its featured artists and version suffixes, and the
+/// suffixes left out.
+fn normalize_title(title: &str, suffixes: &[String]) -> (String, Vec<String>) {
+    strip_title_suffixes(&strip_featuring(title), suffixes)
+}
+
+/// The title part of a file name, without the track number it starts with,
+/// so that "05 - Anniversary" is not taken for a version suffix of "05".
+fn stem_title(stem: &str) -> String {
+    RE_LEAD_TRACK_FIXED
+        .replace(&stem.replace('_', " "), "")
+        .trim()
+        .to_string()
+}
+
 fn extract_track_title(stem: &str) -> String {
     let stem_clean = clean_name(stem);
     if let Some(pos) = stem_clean.rfind(" - ") {
@@ -81,10 +266,28 @@ fn extract_track_title(stem: &str) -> String {
     }
 }
 
+/// Track number a file name starts with, as in "03 - Title" or "3. Title".
+/// Vinyl sides ("A1 - Title") give none.
+fn track_number(stem: &str) -> Option<u32> {
+    RE_LEAD_TRACK_FIXED
+        .captures(&stem.replace('_', " "))
+        .and_then(|c| c[1].parse().ok())
+        .filter(|n| *n > 0)
+}
+
+/// Disc number of a folder named like "CD2" or "Disc 2".
+fn disc_number(folder: &str) -> Option<u32> {
+    RE_DISC_FOLDER
+        .captures(folder)
+        .and_then(|c| c[1].parse().ok())
+}
+
 #[derive(Debug)]
 struct PathInfo {
+    /// Outermost first, without the disc folder.
     parent_folders: Vec<String>,
     stem: String,
+    disc: Option<u32>,
 }
 
 impl PathInfo {
@@ -109,20 +312,31 @@ impl PathInfo {
         let mut reversed_folders = parent_folders;
         reversed_folders.reverse();
 
+        // The disc folder is left out, so the album is guessed from the
+        // folder above it, the same for every disc.
+        let disc = reversed_folders.last().and_then(|f| disc_number(f));
+        if disc.is_some() {
+            reversed_folders.pop();
+        }
+
         Self {
             parent_folders: reversed_folders,
             stem,
+            disc,
         }
     }
 }
 
+/// Artist and album names are compared without their noise words.
 fn score_album(folders: &[CleanedText], target_album: &CleanedText) -> (f64, CleanedText) {
+    let target_album = &target_album.significant();
     folders
         .iter()
+        .map(CleanedText::significant)
         .map(|folder| {
             let score =
-                (jaccard_sim(folder, target_album) + containment_sim(folder, target_album)) / 2.0;
-            (score, folder.clone())
+                (jaccard_sim(&folder, target_album) + containment_sim(&folder, target_album)) / 2.0;
+            (score, folder)
         })
         .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
         .unwrap_or((0.0, CleanedText::new("")))
@@ -133,9 +347,11 @@ fn score_artist(
     stem: &CleanedText,
     target_artist: &CleanedText,
 ) -> (f64, CleanedText) {
+    let target_artist = &target_artist.significant();
     let folder_candidate = folders
         .iter()
-        .map(|folder| (containment_sim(folder, target_artist), folder.clone()))
+        .map(CleanedText::significant)
+        .map(|folder| (containment_sim(&folder, target_artist), folder))
         .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
         .unwrap_or((0.0, CleanedText::new("")));
 
@@ -144,7 +360,7 @@ fn score_artist(
     } else {
         clean_name(&stem.original)
     };
-    let stem_candidate_c = CleanedText::new(&stem_artist_part);
+    let stem_candidate_c = CleanedText::new(&stem_artist_part).significant();
     let stem_score = containment_sim(&stem_candidate_c, target_artist);
 
     if stem_score > folder_candidate.0 {
@@ -178,11 +394,129 @@ fn score_track(stem: &CleanedText, expected_tracks: &[CleanedText]) -> (f64, Cle
         .unwrap_or((0.0, CleanedText::new("")))
 }
 
+/// How well the file at `filename` matches one expected track title, scored
+/// like the track part of [`rank_match`].
+pub(crate) fn track_similarity(filename: &str, expected_track: &str) -> f64 {
+    let suffixes = TitleSuffixes::default().phrases;
+    let (stem, _) = normalize_title(&stem_title(&PathInfo::from_path(filename).stem), &suffixes);
+    let (expected_track, _) = normalize_title(expected_track, &suffixes);
+    score_track(
+        &CleanedText::new(&stem),
+        &[CleanedText::new(&expected_track)],
+    )
+    .0
+}
+
+/// Pairs each row with a distinct column so that the summed weight is the
+/// highest possible (Hungarian algorithm). `weights[row][col]` is `None` when
+/// the pair is not allowed; rows that can only get such a pair are left out.
+pub(crate) fn max_weight_assignment(weights: &[Vec<Option<f64>>]) -> Vec<Option<usize>> {
+    let rows = weights.len();
+    let cols = weights.first().map_or(0, Vec::len);
+    let n = rows.max(cols);
+    // Minimizes the negated weights on a square matrix, padded with pairs
+    // worth nothing.
+    let cost = |row: usize, col: usize| -> f64 {
+        weights
+            .get(row)
+            .and_then(|r| r.get(col))
+            .copied()
+            .flatten()
+            .map_or(0.0, |w| -w)
+    };
+
+    // Potentials and matching are 1-indexed, column 0 is a sentinel.
+    let mut row_potential = vec![0.0; n + 1];
+    let mut col_potential = vec![0.0; n + 1];
+    let mut row_of_col = vec![0usize; n + 1];
+    let mut previous_col = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        row_of_col[0] = row;
+        let mut col = 0;
+        let mut min_slack = vec![f64::INFINITY; n + 1];
+        let mut visited = vec![false; n + 1];
+        loop {
+            visited[col] = true;
+            let current_row = row_of_col[col];
+            let mut delta = f64::INFINITY;
+            let mut next_col = 0;
+            for c in 1..=n {
+                if visited[c] {
+                    continue;
+                }
+                let slack =
+                    cost(current_row - 1, c - 1) - row_potential[current_row] - col_potential[c];
+                if slack < min_slack[c] {
+                    min_slack[c] = slack;
+                    previous_col[c] = col;
+                }
+                if min_slack[c] < delta {
+                    delta = min_slack[c];
+                    next_col = c;
+                }
+            }
+            for c in 0..=n {
+                if visited[c] {
+                    row_potential[row_of_col[c]] += delta;
+                    col_potential[c] -= delta;
+                } else {
+                    min_slack[c] -= delta;
+                }
+            }
+            col = next_col;
+            if row_of_col[col] == 0 {
+                break;
+            }
+        }
+        while col != 0 {
+            let previous = previous_col[col];
+            row_of_col[col] = row_of_col[previous];
+            col = previous;
+        }
+    }
+
+    let mut assignment = vec![None; rows];
+    for (col, &row) in row_of_col.iter().enumerate().skip(1) {
+        if (1..=rows).contains(&row) && col <= cols && weights[row - 1][col - 1].is_some() {
+            assignment[row - 1] = Some(col - 1);
+        }
+    }
+    assignment
+}
+
+/// Files whose length is this close to the track's, in seconds, are not
+/// penalized.
+const DURATION_TOLERANCE: i32 = 5;
+/// Files this far off the track's length, or more, in seconds, get
+/// [`MIN_DURATION_FACTOR`] of their score: edits, live versions, other songs.
+const DURATION_CUTOFF: i32 = 30;
+const MIN_DURATION_FACTOR: f64 = 0.5;
+
+/// How close a file's length is to the track's, from 1 within
+/// [`DURATION_TOLERANCE`] down to 0 at [`DURATION_CUTOFF`] and beyond.
+fn duration_score(file_duration: Option<i32>, track_duration: Option<i32>) -> Option<f64> {
+    let gap = (file_duration? - track_duration?).abs();
+    let over = (gap - DURATION_TOLERANCE).max(0) as f64;
+    Some(1.0 - (over / (DURATION_CUTOFF - DURATION_TOLERANCE) as f64).min(1.0))
+}
+
+/// Scores `filename` against the searched artist, album and tracks.
+/// `expected_durations` are the lengths of the `expected_tracks`, in seconds,
+/// in the same order; the score of a file whose `file_duration` is far off the
+/// one of the track it matched is scaled down. Version suffixes holding one
+/// of the `title_suffixes` phrases are left out of the track and album titles
+/// on both sides, see [`strip_title_suffixes`]; the score of a file is
+/// lowered by their penalty when only one side of a title had some. Featured
+/// artists are left out of every title.
 pub fn rank_match(
     filename: &str,
+    file_duration: Option<i32>,
     searched_artist: Option<&str>,
     searched_album: Option<&str>,
     expected_tracks: &[&str],
+    expected_durations: &[Option<i32>],
+    title_suffixes: &TitleSuffixes,
 ) -> MatchResult {
     const ALBUM_WEIGHT: f64 = 0.4;
     const TRACK_WEIGHT: f64 = 0.4;
@@ -191,13 +525,21 @@ pub fn rank_match(
     // and we don't penalize the score for it.
     const ALBUM_INFO_THRESHOLD: f64 = 0.25;
 
+    let phrases = &title_suffixes.phrases;
+    // Titles are compared without their suffixes, reported as they are.
+    let without_suffixes = |title: &str| CleanedText {
+        original: title.to_string(),
+        ..CleanedText::new(&normalize_title(title, phrases).0)
+    };
+
     let path_info = PathInfo::from_path(filename);
     let path_folders_c: Vec<_> = path_info
         .parent_folders
         .iter()
-        .map(|f| CleanedText::new(&clean_name(f)))
+        .map(|f| without_suffixes(&clean_name(f)))
         .collect();
-    let stem_c = CleanedText::new(&path_info.stem);
+    let (stem, stem_suffixes) = normalize_title(&stem_title(&path_info.stem), phrases);
+    let stem_c = CleanedText::new(&stem);
 
     let (artist_score, best_artist_guess) = if let Some(artist_str) = searched_artist {
         let searched_artist_c = CleanedText::new(artist_str);
@@ -207,7 +549,7 @@ pub fn rank_match(
     };
 
     let (album_score, best_album_folder) = if let Some(album_str) = searched_album {
-        let searched_album_c = CleanedText::new(album_str);
+        let searched_album_c = without_suffixes(album_str);
         score_album(&path_folders_c, &searched_album_c)
     } else {
         (0.0, CleanedText::new(""))
@@ -216,7 +558,7 @@ pub fn rank_match(
     let (track_score, best_track_match) = if !expected_tracks.is_empty() {
         let expected_tracks_c: Vec<_> = expected_tracks
             .iter()
-            .map(|t| CleanedText::new(t))
+            .map(|t| without_suffixes(t))
             .collect();
         score_track(&stem_c, &expected_tracks_c)
     } else {
@@ -246,8 +588,47 @@ pub fn rank_match(
         }
     }
 
+    let track_duration = expected_tracks
+        .iter()
+        .position(|t| *t == best_track_match.original)
+        .and_then(|i| expected_durations.get(i).copied().flatten());
+    let duration_score = duration_score(file_duration, track_duration);
+
+    // Suffixes of the file's and the searched titles, in pairs.
+    let mut suffix_pairs = Vec::new();
+    if !expected_tracks.is_empty() {
+        let track_suffixes = normalize_title(&best_track_match.original, phrases).1;
+        suffix_pairs.push((stem_suffixes, track_suffixes));
+    }
+    if let Some(album_str) = searched_album.filter(|_| album_score > ALBUM_INFO_THRESHOLD) {
+        suffix_pairs.push((
+            normalize_title(&best_album_folder.original, phrases).1,
+            normalize_title(album_str, phrases).1,
+        ));
+    }
+    let suffixes_differ = suffix_pairs
+        .iter()
+        .any(|(file, searched)| file.is_empty() != searched.is_empty());
+    let mut stripped_suffixes: Vec<String> = Vec::new();
+    for suffix in suffix_pairs
+        .into_iter()
+        .flat_map(|(a, b)| a.into_iter().chain(b))
+    {
+        if !stripped_suffixes.contains(&suffix) {
+            stripped_suffixes.push(suffix);
+        }
+    }
+
+    let duration_factor = duration_score.map_or(1.0, |s| {
+        MIN_DURATION_FACTOR + (1.0 - MIN_DURATION_FACTOR) * s
+    });
+    let suffix_factor = if suffixes_differ {
+        1.0 - title_suffixes.penalty
+    } else {
+        1.0
+    };
     let total_score = if total_weight > 0.0 {
-        weighted_sum / total_weight
+        weighted_sum / total_weight * duration_factor * suffix_factor
     } else {
         0.0
     };
@@ -260,5 +641,149 @@ pub fn rank_match(
         album_score,
         track_score,
         total_score,
+        disc: path_info.disc,
+        track_number: track_number(&path_info.stem),
+        duration_score,
+        stripped_suffixes,
+    }
+}
+
+/// Other spellings of a shared filename, to retry with when a peer rejects the
+/// original one. Peers on Windows share backslash-separated paths and macOS
+/// filesystems store decomposed Unicode, so a path that went through any
+/// normalization on the way may no longer match what the peer has.
+pub fn filename_variants(filename: &str) -> Vec<String> {
+    use unicode_normalization::UnicodeNormalization;
+
+    let candidates = [
+        filename.nfc().collect::<String>(),
+        filename.nfd().collect::<String>(),
+        filename.replace('/', "\\"),
+        filename.replace('/', "\\").nfc().collect::<String>(),
+    ];
+
+    let mut variants: Vec<String> = Vec::new();
+    for candidate in candidates {
+        if candidate != filename && !variants.contains(&candidate) {
+            variants.push(candidate);
+        }
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suffixes() -> Vec<String> {
+        TitleSuffixes::default().phrases
+    }
+
+    #[test]
+    fn strips_version_suffixes() {
+        let (title, removed) = strip_title_suffixes("Song (2011 Remaster)", &suffixes());
+        assert_eq!(title, "Song");
+        assert_eq!(removed, ["2011 Remaster"]);
+
+        let (title, removed) =
+            strip_title_suffixes("Song - Deluxe Edition [Bonus Track]", &suffixes());
+        assert_eq!(title, "Song");
+        assert_eq!(removed, ["Bonus Track", "Deluxe Edition"]);
+
+        let (title, removed) = strip_title_suffixes("Song (Live)", &suffixes());
+        assert_eq!(title, "Song (Live)");
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn keeps_titles_that_are_only_a_suffix() {
+        assert_eq!(
+            strip_title_suffixes("(Bonus)", &suffixes()).0,
+            "(Bonus)".to_string()
+        );
+    }
+
+    #[test]
+    fn track_numbers_are_not_titles() {
+        assert_eq!(stem_title("05 - Anniversary"), "Anniversary");
+        assert_eq!(stem_title("12_-_Bonus"), "Bonus");
+        assert_eq!(
+            normalize_title(&stem_title("05 - Anniversary"), &suffixes()).0,
+            "Anniversary"
+        );
+        assert_eq!(
+            normalize_title(&stem_title("12 - Bonus"), &suffixes()).0,
+            "Bonus"
+        );
+        assert!(track_similarity("Artist/Album/05 - Anniversary.flac", "Anniversary") > 0.99);
+        assert!(track_similarity("Artist/Album/12 - Bonus.flac", "Bonus") > 0.99);
+    }
+
+    #[test]
+    fn strips_featured_artists() {
+        assert_eq!(strip_featuring("Song (feat. X)"), "Song");
+        assert_eq!(strip_featuring("Song [with X]"), "Song");
+        assert_eq!(strip_featuring("Song ft. X - Remix"), "Song - Remix");
+    }
+
+    #[test]
+    fn folds_accents_and_scripts() {
+        assert_eq!(fold("Björk"), "bjork");
+        assert_eq!(fold("Мельница"), "melnitsa");
+        assert_eq!(fold("Χάρις"), "charis");
+        assert_eq!(fold("Straße"), "strasse");
+        // Precomposed and combining accents fold alike.
+        assert_eq!(fold("e\u{301}te\u{301}"), fold("été"));
+        assert_eq!(fold("東京"), "東京");
+    }
+
+    #[test]
+    fn leaves_out_noise_words() {
+        let significant = CleanedText::new("The National").significant();
+        assert_eq!(
+            significant.words(),
+            &HashSet::from(["national".to_string()])
+        );
+
+        let significant = CleanedText::new("Simon & Garfunkel").significant();
+        assert_eq!(
+            significant.words(),
+            CleanedText::new("Simon and Garfunkel")
+                .significant()
+                .words()
+        );
+
+        // A name made of noise words only is kept whole.
+        let significant = CleanedText::new("The The").significant();
+        assert_eq!(significant.words(), &HashSet::from(["the".to_string()]));
+    }
+
+    #[test]
+    fn reads_track_numbers() {
+        assert_eq!(track_number("03 - Title"), Some(3));
+        assert_eq!(track_number("3. Title"), Some(3));
+        assert_eq!(track_number("12_-_Title"), Some(12));
+        assert_eq!(track_number("A1 - Title"), None);
+        assert_eq!(track_number("00 - Intro"), None);
+        assert_eq!(track_number("Title"), None);
+    }
+
+    #[test]
+    fn assigns_the_heaviest_pairs() {
+        // Greedy would pair row 0 with column 0 and leave row 1 worse off.
+        let weights = vec![vec![Some(0.9), Some(0.8)], vec![Some(0.85), Some(0.1)]];
+        assert_eq!(max_weight_assignment(&weights), [Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn leaves_out_forbidden_pairs() {
+        let weights = vec![vec![Some(0.5), None], vec![None, None]];
+        assert_eq!(max_weight_assignment(&weights), [Some(0), None]);
+
+        // More rows than columns.
+        let weights = vec![vec![Some(0.2)], vec![Some(0.7)], vec![None]];
+        assert_eq!(max_weight_assignment(&weights), [None, Some(0), None]);
+
+        assert!(max_weight_assignment(&[]).is_empty());
     }
 }
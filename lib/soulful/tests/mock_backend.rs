@@ -0,0 +1,65 @@
+//! The download flow jobs go through, against the in-memory backend.
+
+use soulbeet::{provider::DownloadProvider, slskd::MockBackend};
+
+const USER: &str = "uploader";
+const FILE: &str = "Music\\Artist\\Album\\01 - Track.flac";
+
+#[tokio::test]
+async fn transfers_are_reported_until_they_finish() {
+    let backend = MockBackend::new();
+    let provider: &dyn DownloadProvider = &backend;
+    let mut updates = provider.progress();
+
+    let fetched = provider
+        .fetch(vec![(USER.to_string(), FILE.to_string(), 1024)])
+        .await
+        .unwrap();
+    assert_eq!(fetched.len(), 1);
+    let id = fetched[0].id.clone();
+
+    let queued = updates.recv().await.unwrap();
+    assert_eq!(queued.changed, vec![id.clone()]);
+    assert!(!queued.downloads[0].is_finished());
+
+    backend.set_state(&id, "Completed, Succeeded");
+    let finished = updates.recv().await.unwrap();
+    assert_eq!(finished.changed, vec![id.clone()]);
+    assert!(finished.downloads[0].is_succeeded());
+
+    let download = provider.get_download(USER, &id).await.unwrap().unwrap();
+    assert_eq!(download.bytes_transferred, 1024);
+    assert!(provider
+        .get_download("someone else", &id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn paused_files_are_requested_again_on_resume() {
+    let backend = MockBackend::new();
+    let provider: &dyn DownloadProvider = &backend;
+
+    let fetched = provider
+        .fetch(vec![(USER.to_string(), FILE.to_string(), 1024)])
+        .await
+        .unwrap();
+    let id = fetched[0].id.clone();
+
+    provider.pause_download(USER, &id).await.unwrap();
+    assert!(backend.downloads().is_empty());
+
+    let resumed = provider
+        .resume_download(USER, FILE, 1024)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_ne!(resumed.id, id);
+    assert_eq!(resumed.filename, FILE);
+    assert!(provider
+        .get_download(USER, &resumed.id)
+        .await
+        .unwrap()
+        .is_some());
+}
@@ -42,6 +42,15 @@ impl Auth {
         self.state.read().as_ref().map(|a| a.username.clone())
     }
 
+    pub fn is_admin(&self) -> bool {
+        self.state.read().as_ref().is_some_and(|a| a.is_admin)
+    }
+
+    /// Whether this is the read-only session of a public demo.
+    pub fn is_demo(&self) -> bool {
+        self.state.read().as_ref().is_some_and(|a| a.is_demo)
+    }
+
     pub fn is_logged_in(&self) -> bool {
         self.state.read().is_some()
     }
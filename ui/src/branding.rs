@@ -0,0 +1,78 @@
+use api::db::Branding;
+use dioxus::prelude::*;
+
+pub const DEFAULT_INSTANCE_NAME: &str = "Soulbeet";
+
+/// The instance's branding, loaded once for the whole app.
+#[derive(Clone, Copy, Debug)]
+pub struct BrandingContext {
+    state: Signal<Branding>,
+}
+
+impl BrandingContext {
+    pub fn name(&self) -> String {
+        self.state
+            .read()
+            .instance_name
+            .clone()
+            .unwrap_or_else(|| DEFAULT_INSTANCE_NAME.to_string())
+    }
+
+    pub fn logo_url(&self) -> Option<String> {
+        self.state.read().logo_url.clone()
+    }
+
+    /// Applies saved settings without reloading the page.
+    pub fn set(&mut self, branding: Branding) {
+        self.state.set(branding);
+    }
+}
+
+pub fn use_branding() -> BrandingContext {
+    use_context::<BrandingContext>()
+}
+
+/// Stylesheet repainting the teal utility classes with `color`, lighter and
+/// darker shades being mixed from it.
+fn accent_css(color: &str) -> String {
+    let shade =
+        |amount: u8, toward: &str| format!("color-mix(in srgb, {color}, {toward} {amount}%)");
+    let light = shade(30, "white");
+    let lighter = shade(60, "white");
+    let dark = shade(20, "black");
+    let darker = shade(50, "black");
+    format!(
+        ".text-teal-400 {{ color: {color}; }}
+.text-teal-300, .hover\\:text-teal-300:hover {{ color: {light}; }}
+.text-teal-200, .hover\\:text-teal-200:hover {{ color: {lighter}; }}
+.bg-teal-500, .bg-teal-600, .hover\\:bg-teal-600:hover {{ background-color: {color}; }}
+.hover\\:bg-teal-700:hover {{ background-color: {dark}; }}
+.bg-teal-800 {{ background-color: {darker}; }}
+.border-teal-400, .border-teal-500, .focus\\:border-teal-500:focus {{ border-color: {color}; }}
+.focus\\:ring-teal-500:focus {{ --tw-ring-color: {color}; }}"
+    )
+}
+
+/// Loads the branding and provides it to `children`, along with the page
+/// title and the accent color stylesheet.
+#[component]
+pub fn BrandingProvider(children: Element) -> Element {
+    let mut state = use_signal(Branding::default);
+    let branding = use_context_provider(|| BrandingContext { state });
+
+    use_future(move || async move {
+        if let Ok(fetched) = api::get_branding().await {
+            state.set(fetched);
+        }
+    });
+
+    let accent = state.read().accent_color.as_deref().map(accent_css);
+
+    rsx! {
+      document::Title { "{branding.name()}" }
+      if let Some(css) = accent {
+        document::Style { "{css}" }
+      }
+      {children}
+    }
+}
@@ -0,0 +1,36 @@
+use dioxus::prelude::*;
+
+/// Whether the instance runs in read-only browse mode, loaded once for the
+/// whole app.
+#[derive(Clone, Copy, Debug)]
+pub struct BrowseMode {
+    state: Signal<bool>,
+}
+
+impl BrowseMode {
+    /// Whether only the library, streaming and history are offered.
+    pub fn enabled(&self) -> bool {
+        *self.state.read()
+    }
+}
+
+pub fn use_browse_mode() -> BrowseMode {
+    use_context::<BrowseMode>()
+}
+
+/// Loads the deployment mode and provides it to `children`.
+#[component]
+pub fn BrowseModeProvider(children: Element) -> Element {
+    let mut state = use_signal(|| false);
+    use_context_provider(|| BrowseMode { state });
+
+    use_future(move || async move {
+        if let Ok(enabled) = api::browse_mode().await {
+            state.set(enabled);
+        }
+    });
+
+    rsx! {
+      {children}
+    }
+}
@@ -0,0 +1,52 @@
+use dioxus::prelude::*;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    /// The release shown.
+    release_id: String,
+    /// Called with the id of the release picked instead.
+    #[props(into)]
+    on_pick: EventHandler<String>,
+}
+
+/// The other releases of the album shown, original, remasters, deluxe and
+/// regional editions, to search for one of them instead.
+#[component]
+pub fn EditionPicker(props: Props) -> Element {
+    let release_id = props.release_id.clone();
+    let editions = use_resource(move || {
+        let id = release_id.clone();
+        async move { api::album_editions(id).await.unwrap_or_default() }
+    });
+
+    let editions = editions().unwrap_or_default();
+    // Nothing to pick from.
+    if editions.len() < 2 {
+        return rsx! {};
+    }
+
+    rsx! {
+      div { class: "flex items-center gap-2 px-4 pt-3 text-sm text-gray-400",
+        label { "Edition" }
+        select {
+          class: "flex-grow p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+          onchange: move |e: FormEvent| props.on_pick.call(e.value()),
+          for edition in editions {
+            option {
+              key: "{edition.id}",
+              value: "{edition.id}",
+              selected: edition.id == props.release_id,
+              {
+                  let label = edition.label();
+                  if label.is_empty() {
+                      edition.title.clone()
+                  } else {
+                      format!("{} ({label})", edition.title)
+                  }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -5,16 +5,45 @@ use crate::Button;
 #[derive(Props, PartialEq, Clone)]
 pub struct Props {
     is_selection_empty: bool,
-    on_select: EventHandler,
+    /// Text sent to Soulseek unless the user edits it, "artist album".
+    default_query: String,
+    /// Called with the edited query, `None` when it was left as is.
+    on_select: EventHandler<Option<String>>,
 }
 
 #[component]
 pub fn AlbumFooter(props: Props) -> Element {
+    let mut editing = use_signal(|| false);
+    let mut query = use_signal(|| props.default_query.clone());
+
     rsx! {
       div { class: "p-4 border-t border-gray-700 mt-auto",
+        if editing() {
+          div { class: "mb-3",
+            label { class: "block text-sm text-gray-400 mb-1",
+              "Sent to Soulseek, the tracks are still matched to the album"
+            }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{query}",
+              oninput: move |e| query.set(e.value()),
+              "type": "text",
+            }
+          }
+        } else {
+          button {
+            class: "text-sm text-gray-400 hover:text-white mb-3",
+            title: "Replace the \"artist album\" text sent to Soulseek, e.g. to drop a subtitle",
+            onclick: move |_| editing.set(true),
+            "Edit search query"
+          }
+        }
         Button {
-          disabled: props.is_selection_empty,
-          onclick: move |_| props.on_select.call(()),
+          disabled: props.is_selection_empty || query.read().trim().is_empty(),
+          onclick: move |_| {
+              let query = query.read().trim().to_string();
+              props.on_select.call((query != props.default_query.trim()).then_some(query));
+          },
           "Search These Tracks"
         }
       }
@@ -1,7 +1,8 @@
+use api::Availability;
 use dioxus::prelude::*;
 use shared::musicbrainz::Album;
 
-use crate::CoverArt;
+use crate::{use_auth, CoverArt, GenreChips};
 
 #[derive(Props, PartialEq, Clone)]
 pub struct Props {
@@ -10,10 +11,81 @@ pub struct Props {
 
 #[component]
 pub fn AlbumHeader(props: Props) -> Element {
+    let auth = use_auth();
+    let album_id = props.album.id.clone();
+    // Signed URL of the user's custom cover, if they uploaded one.
+    let mut custom_cover = use_signal::<Option<String>>(|| None);
+    let mut cover_error = use_signal::<Option<String>>(|| None);
+
+    let artist = props.album.artist.clone();
+    let title = props.album.title.clone();
+    let availability = use_resource(move || {
+        let (artist, title) = (artist.clone(), title.clone());
+        async move {
+            let token = auth.token()?;
+            api::probe_availability(token, artist, title).await.ok()
+        }
+    });
+
+    let id = album_id.clone();
+    let fetch_custom_cover = move || {
+        let id = id.clone();
+        async move {
+            if let Some(token) = auth.token() {
+                if let Ok(url) = api::album_cover_url(token, id).await {
+                    custom_cover.set(url);
+                }
+            }
+        }
+    };
+
+    let fetch = fetch_custom_cover.clone();
+    use_future(move || fetch());
+
+    let id = album_id.clone();
+    let upload_cover = move |evt: FormEvent| {
+        let id = id.clone();
+        let fetch_custom_cover = fetch_custom_cover.clone();
+        async move {
+            let Some(file) = evt.files().into_iter().next() else {
+                return;
+            };
+            let Some(token) = auth.token() else {
+                return;
+            };
+            cover_error.set(None);
+            match file.read_bytes().await {
+                Ok(bytes) if bytes.len() > api::MAX_COVER_SIZE => {
+                    cover_error.set(Some("Image is too large (max 10 MB)".to_string()));
+                }
+                Ok(bytes) => match api::upload_album_cover(token, id, bytes.to_vec()).await {
+                    Ok(()) => fetch_custom_cover().await,
+                    Err(e) => cover_error.set(Some(e.to_string())),
+                },
+                Err(e) => cover_error.set(Some(e.to_string())),
+            }
+        }
+    };
+
+    let id = album_id.clone();
+    let remove_cover = move |_| {
+        let id = id.clone();
+        async move {
+            if let Some(token) = auth.token() {
+                if api::remove_album_cover(token, id).await.is_ok() {
+                    custom_cover.set(None);
+                }
+            }
+        }
+    };
+
     rsx! {
       div { class: "flex items-start gap-4 p-4 border-b border-gray-700",
         CoverArt {
-          src: format!("https://coverartarchive.org/release/{}/front", props.album.id),
+          src: custom_cover()
+              .unwrap_or_else(|| {
+                  format!("https://coverartarchive.org/release/{}/front", props.album.id)
+              }),
           alt: format!("Cover for {}", props.album.title),
         }
         div { class: "flex-grow",
@@ -22,6 +94,48 @@ pub fn AlbumHeader(props: Props) -> Element {
           if let Some(date) = &props.album.release_date {
             p { class: "text-sm text-gray-500", "{date}" }
           }
+          GenreChips { genres: props.album.genres.clone() }
+          match availability() {
+              None => rsx! {
+                p { class: "text-xs text-gray-500 mt-1", "Checking Soulseek availability..." }
+              },
+              Some(Some(estimate)) => {
+                  let (label, color) = match estimate.availability {
+                      Availability::Likely => ("Likely available", "text-teal-400"),
+                      Availability::Scarce => ("Scarce", "text-yellow-400"),
+                      Availability::Unavailable => ("Not found on Soulseek", "text-red-400"),
+                  };
+                  rsx! {
+                    p { class: "text-xs mt-1 {color}", "{label} ({estimate.sources} sources)" }
+                  }
+              }
+              Some(None) => rsx! {},
+          }
+          div { class: "flex items-center gap-2 mt-2 text-xs",
+            label { class: "cursor-pointer text-teal-400 hover:text-teal-300",
+              if custom_cover().is_some() {
+                "Replace custom cover"
+              } else {
+                "Upload custom cover"
+              }
+              input {
+                class: "hidden",
+                r#type: "file",
+                accept: "image/jpeg,image/png",
+                onchange: upload_cover,
+              }
+            }
+            if custom_cover().is_some() {
+              button {
+                class: "text-gray-400 hover:text-red-400",
+                onclick: remove_cover,
+                "Remove"
+              }
+            }
+          }
+          if let Some(error) = cover_error() {
+            p { class: "text-xs text-red-400 mt-1", "{error}" }
+          }
         }
       }
     }
@@ -7,14 +7,16 @@ use std::collections::HashSet;
 
 use crate::{
     album::{footer::AlbumFooter, track_list::TrackList},
-    Button, CoverArt,
+    use_auth, Button, CoverArt,
 };
 
+mod editions;
 mod footer;
 mod header;
 mod track_item;
 mod track_list;
 
+pub use editions::EditionPicker;
 pub use header::AlbumHeader;
 
 #[derive(Props, PartialEq, Clone)]
@@ -30,6 +32,19 @@ pub struct Props {
 pub fn Album(props: Props) -> Element {
     let mut selected_tracks = use_signal(|| HashSet::<String>::new());
     let tracks = use_signal(|| props.data.tracks.clone());
+    let auth = use_auth();
+
+    // Tracks already in one of the user's library folders, on any release.
+    let owned = use_resource(move || async move {
+        let ids = tracks.read().iter().map(|t| t.id.clone()).collect();
+        match auth.token() {
+            Some(token) => api::get_owned_recordings(token, ids)
+                .await
+                .map(|owned| owned.into_iter().collect())
+                .unwrap_or_default(),
+            None => HashSet::new(),
+        }
+    });
 
     let all_selected =
         selected_tracks.read().len() == tracks.read().len() && !tracks.read().is_empty();
@@ -45,6 +60,33 @@ pub fn Album(props: Props) -> Element {
         }
     };
 
+    // Only worth offering when the album is partly in the library.
+    let missing: HashSet<String> = {
+        let owned = owned().unwrap_or_default();
+        if owned.is_empty() {
+            HashSet::new()
+        } else {
+            tracks
+                .read()
+                .iter()
+                .filter(|t| !owned.contains(&t.id))
+                .map(|t| t.id.clone())
+                .collect()
+        }
+    };
+    let missing_selected = !missing.is_empty() && *selected_tracks.read() == missing;
+
+    let handle_select_missing = {
+        let missing = missing.clone();
+        move |_| {
+            if missing_selected {
+                selected_tracks.write().clear();
+            } else {
+                selected_tracks.set(missing.clone());
+            }
+        }
+    };
+
     let handle_track_toggle = move |track_id: String| {
         let mut selected = selected_tracks.write();
         if selected.contains(&track_id) {
@@ -57,14 +99,19 @@ pub fn Album(props: Props) -> Element {
     rsx! {
         TrackList {
             tracks,
+            owned: owned().unwrap_or_default(),
             selected_tracks,
             on_toggle_select_all: handle_select_all,
+            missing_count: missing.len(),
+            missing_selected,
+            on_toggle_select_missing: handle_select_missing,
             on_track_toggle: handle_track_toggle,
             all_selected,
         }
         AlbumFooter {
             is_selection_empty: selected_tracks.read().is_empty(),
-            on_select: move |_| {
+            default_query: format!("{} {}", props.data.album.artist, props.data.album.title),
+            on_select: move |query: Option<String>| {
                 let selected_ids = selected_tracks.read();
                 let tracks: Vec<Track> = tracks
                     .read()
@@ -79,9 +126,24 @@ pub fn Album(props: Props) -> Element {
                         .call(DownloadQuery {
                             album,
                             tracks: props.data.tracks.clone(),
+                            profile_id: None,
+                            search_id: None,
+                            refinements: Default::default(),
+                            query: query.clone(),
+                            edition_picked: false,
                         });
                 } else {
-                    props.on_select.call(DownloadQuery { album, tracks });
+                    props
+                        .on_select
+                        .call(DownloadQuery {
+                            album,
+                            tracks,
+                            profile_id: None,
+                            search_id: None,
+                            refinements: Default::default(),
+                            query: query.clone(),
+                            edition_picked: false,
+                        });
                 }
             },
         }
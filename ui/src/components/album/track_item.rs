@@ -6,6 +6,7 @@ use crate::Checkbox;
 #[derive(Props, Clone, PartialEq)]
 pub struct Props {
     track: Track,
+    is_owned: bool,
     is_selected: bool,
     on_toggle: EventHandler<String>,
 }
@@ -22,6 +23,13 @@ pub fn TrackItem(props: Props) -> Element {
         Checkbox { is_selected: props.is_selected }
 
         span { class: "flex-grow text-gray-300", "{props.track.title}" }
+        if props.is_owned {
+          span {
+            class: "text-xs text-teal-300 bg-gray-700 px-2 py-0.5 rounded",
+            title: "Already in your library",
+            "In library"
+          }
+        }
         if let Some(duration) = &props.track.duration {
           span {
             class: "font-mono text-sm",
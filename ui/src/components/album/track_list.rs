@@ -3,13 +3,18 @@ use std::collections::HashSet;
 use dioxus::prelude::*;
 use shared::musicbrainz::Track;
 
-use crate::album::track_item::TrackItem;
+use crate::{album::track_item::TrackItem, Checkbox};
 
 #[derive(Props, PartialEq, Clone)]
 pub struct Props {
     tracks: Signal<Vec<Track>>,
+    owned: HashSet<String>,
     selected_tracks: Signal<HashSet<String>>,
     on_toggle_select_all: EventHandler,
+    /// Tracks not in the library, when some of the album is.
+    missing_count: usize,
+    missing_selected: bool,
+    on_toggle_select_missing: EventHandler,
     on_track_toggle: EventHandler<String>,
     all_selected: bool,
 }
@@ -28,10 +33,20 @@ pub fn TrackList(props: Props) -> Element {
           }
           span { class: "font-bold", "Select / Deselect All" }
         }
+        if props.missing_count > 0 {
+          li {
+            class: "flex items-center gap-3 p-2 rounded-md cursor-pointer hover:bg-gray-700",
+            onclick: move |_| props.on_toggle_select_missing.call(()),
+            Checkbox { is_selected: props.missing_selected }
+            span { class: "font-bold", "Select only tracks I don't have" }
+            span { class: "text-sm text-gray-400", "({props.missing_count})" }
+          }
+        }
         for track in props.tracks.read().iter() {
           TrackItem {
             key: "{track.id}",
             track: track.clone(),
+            is_owned: props.owned.contains(&track.id),
             is_selected: props.selected_tracks.read().contains(&track.id),
             on_toggle: props.on_track_toggle.clone(),
           }
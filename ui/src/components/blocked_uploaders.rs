@@ -0,0 +1,62 @@
+use dioxus::prelude::*;
+
+use crate::use_auth;
+
+/// Uploaders left out of the user's searches, to unblock them.
+#[component]
+pub fn BlockedUploaders() -> Element {
+    let auth = use_auth();
+    let mut blocked = use_signal(Vec::new);
+    let mut error = use_signal(String::new);
+
+    let fetch_blocked = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_blocked_uploaders(token).await {
+                Ok(uploaders) => blocked.set(uploaders),
+                Err(e) => error.set(format!("Failed to fetch blocked uploaders: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_blocked().await;
+    });
+
+    let handle_unblock = move |username: String| async move {
+        error.set(String::new());
+        if let Some(token) = auth.token() {
+            match api::unblock_uploader(token, username).await {
+                Ok(_) => fetch_blocked().await,
+                Err(e) => error.set(format!("Failed to unblock uploader: {e}")),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Blocked Uploaders" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "Files shared by these Soulseek users are left out of your searches. Block an uploader with \"Never use this uploader\" in the download options."
+        }
+        if blocked.read().is_empty() {
+          p { class: "text-gray-400", "No uploader blocked." }
+        } else {
+          ul { class: "space-y-2",
+            for uploader in blocked.read().iter().cloned() {
+              li { class: "bg-gray-700 p-3 rounded flex justify-between items-center",
+                span { class: "font-medium text-teal-200", "{uploader.username}" }
+                button {
+                  class: "text-gray-400 hover:text-red-400 text-sm",
+                  onclick: move |_| handle_unblock(uploader.username.clone()),
+                  "Unblock"
+                }
+              }
+            }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
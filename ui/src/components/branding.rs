@@ -0,0 +1,116 @@
+use api::db::Branding;
+use dioxus::prelude::*;
+
+use crate::{use_auth, use_branding, DEFAULT_INSTANCE_NAME};
+
+/// Admin form for the instance name, accent color and logo.
+#[component]
+pub fn BrandingSettings() -> Element {
+    let auth = use_auth();
+    let mut branding = use_branding();
+    let mut instance_name = use_signal(String::new);
+    let mut accent_color = use_signal(String::new);
+    let mut logo_url = use_signal(String::new);
+    let mut error = use_signal(String::new);
+    let mut saved = use_signal(|| false);
+
+    let mut show = move |settings: &Branding| {
+        instance_name.set(settings.instance_name.clone().unwrap_or_default());
+        accent_color.set(settings.accent_color.clone().unwrap_or_default());
+        logo_url.set(settings.logo_url.clone().unwrap_or_default());
+    };
+
+    use_future(move || async move {
+        match api::get_branding().await {
+            Ok(fetched) => show(&fetched),
+            Err(e) => error.set(e.to_string()),
+        }
+    });
+
+    let save = move |settings: Branding| async move {
+        error.set(String::new());
+        saved.set(false);
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::save_branding(token, settings).await {
+            Ok(stored) => {
+                show(&stored);
+                branding.set(stored);
+                saved.set(true);
+            }
+            Err(e) => error.set(e.to_string()),
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mt-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Branding" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "How this instance presents itself to everyone, including on the login page. Leave a field empty to use the default."
+        }
+        div { class: "grid grid-cols-1 md:grid-cols-3 gap-4 mb-4",
+          div {
+            label { class: "block text-sm font-medium mb-1", "Instance Name" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{instance_name}",
+              oninput: move |e| instance_name.set(e.value()),
+              placeholder: DEFAULT_INSTANCE_NAME,
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Accent Color" }
+            div { class: "flex gap-2",
+              input {
+                class: "h-10 w-12 rounded bg-gray-700 border border-gray-600",
+                value: if accent_color().is_empty() { "#14b8a6".to_string() } else { accent_color() },
+                oninput: move |e| accent_color.set(e.value()),
+                "type": "color",
+              }
+              input {
+                class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                value: "{accent_color}",
+                oninput: move |e| accent_color.set(e.value()),
+                placeholder: "Default teal",
+                "type": "text",
+              }
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Logo URL" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{logo_url}",
+              oninput: move |e| logo_url.set(e.value()),
+              placeholder: "https://example.com/logo.png",
+              "type": "url",
+            }
+          }
+        }
+        div { class: "flex gap-2",
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+            onclick: move |_| save(Branding {
+                instance_name: Some(instance_name()),
+                accent_color: Some(accent_color()),
+                logo_url: Some(logo_url()),
+            }),
+            "Save Branding"
+          }
+          button {
+            class: "bg-gray-600 hover:bg-gray-500 text-white font-bold py-2 px-4 rounded transition-colors",
+            onclick: move |_| save(Branding::default()),
+            "Reset to Defaults"
+          }
+        }
+        if saved() {
+          p { class: "text-sm text-green-400 mt-2", "Branding saved." }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,112 @@
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use shared::slskd::ServerState;
+
+use crate::use_auth;
+
+/// How often the connection is checked again, in milliseconds.
+const POLL_INTERVAL: u32 = 30_000;
+
+/// Dot color, short label and the reason searches may come back empty.
+fn status_line(status: &Result<ServerState, String>) -> (&'static str, &'static str, String) {
+    match status {
+        Ok(state) if state.is_logged_in => (
+            "bg-green-400",
+            "Online",
+            match &state.username {
+                Some(username) => format!("Logged in to Soulseek as {username}"),
+                None => "Logged in to Soulseek".to_string(),
+            },
+        ),
+        Ok(state) if state.is_connected => (
+            "bg-yellow-400",
+            "Not logged in",
+            format!(
+                "slskd reached the Soulseek server but is not logged in ({}), searches return nothing until it is",
+                state.state
+            ),
+        ),
+        Ok(state) => (
+            "bg-red-400",
+            "Offline",
+            format!(
+                "slskd is not connected to the Soulseek server ({}), searches return nothing until it is",
+                state.state
+            ),
+        ),
+        Err(e) => ("bg-red-400", "slskd down", e.clone()),
+    }
+}
+
+/// Header indicator of slskd's Soulseek connection, with its details and, for
+/// admins, a way to reconnect.
+#[component]
+pub fn ConnectionStatus() -> Element {
+    let auth = use_auth();
+    let mut status = use_signal::<Option<Result<ServerState, String>>>(|| None);
+    let mut open = use_signal(|| false);
+    let mut busy = use_signal(|| false);
+
+    use_future(move || async move {
+        loop {
+            if let Some(token) = auth.token() {
+                let fetched = api::get_connection_status(token)
+                    .await
+                    .map_err(|e| e.to_string());
+                status.set(Some(fetched));
+            }
+            TimeoutFuture::new(POLL_INTERVAL).await;
+        }
+    });
+
+    let reconnect = move |_| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        busy.set(true);
+        let state = api::test_soulseek_connection(token)
+            .await
+            .map_err(|e| e.to_string());
+        status.set(Some(state));
+        busy.set(false);
+    };
+
+    let Some(current) = status() else {
+        return rsx! {};
+    };
+    let (dot, label, detail) = status_line(&current);
+
+    rsx! {
+      div { class: "relative",
+        button {
+          class: "flex items-center gap-2 text-gray-300 hover:text-white px-3 py-2 rounded-md text-sm font-medium",
+          title: "{detail}",
+          onclick: move |_| open.toggle(),
+          span { class: "inline-block w-2 h-2 rounded-full {dot}" }
+          "{label}"
+        }
+        if open() {
+          div { class: "absolute right-0 mt-2 w-72 bg-gray-800 border border-gray-700 rounded-lg shadow-lg p-4 text-sm text-gray-300",
+            p { class: "mb-2", "{detail}" }
+            if let Ok(state) = &current {
+              if let (Some(directories), Some(files)) = (state.shared_directories, state.shared_files) {
+                p { class: "text-gray-400 mb-2", "Sharing {files} files in {directories} folders" }
+              }
+            }
+            if auth.is_admin() {
+              button {
+                class: "bg-indigo-600 hover:bg-indigo-700 text-white font-bold py-1 px-3 rounded transition-colors disabled:bg-gray-600",
+                disabled: busy(),
+                onclick: reconnect,
+                if busy() {
+                  "Reconnecting..."
+                } else {
+                  "Reconnect"
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,372 @@
+use api::{
+    db::{Feedback, JobState},
+    JobWithFiles, QueuedJob,
+};
+use dioxus::{
+    fullstack::{use_websocket, WebSocketOptions, WebsocketState},
+    prelude::*,
+};
+use gloo_timers::future::TimeoutFuture;
+
+use crate::{use_auth, use_browse_mode, ScoreBar};
+
+mod queue;
+mod review;
+mod share;
+mod sparkline;
+mod unmanaged;
+
+use queue::DownloadQueue;
+use review::JobReview;
+use share::ShareLinks;
+use sparkline::SpeedSparkline;
+use unmanaged::UnmanagedDownloads;
+
+fn state_label(state: &JobState) -> (&'static str, &'static str) {
+    match state {
+        JobState::Queued => ("Queued", "bg-gray-600"),
+        JobState::Dispatching => ("Dispatching", "bg-gray-600"),
+        JobState::Downloading => ("Downloading", "bg-indigo-600"),
+        JobState::AwaitingReview => ("Awaiting review", "bg-yellow-600"),
+        JobState::Importing => ("Importing", "bg-indigo-600"),
+        JobState::Imported => ("Imported", "bg-teal-600"),
+        JobState::Failed => ("Failed", "bg-red-700"),
+        JobState::ImportTimedOut => ("Import timed out", "bg-red-700"),
+    }
+}
+
+/// Whether the job's note, labels, folder or files contain `query`, ignoring case.
+fn matches_query(entry: &JobWithFiles, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return true;
+    }
+    let contains = |text: &str| text.to_lowercase().contains(&query);
+    entry.job.note.as_deref().is_some_and(contains)
+        || entry.labels.iter().any(|label| contains(label))
+        || contains(&entry.job.target_folder)
+        || entry.files.iter().any(|file| contains(&file.filename))
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+#[derive(Props, PartialEq, Clone)]
+struct FeedbackButtonsProps {
+    job_id: String,
+    feedback: Option<Feedback>,
+    on_change: EventHandler,
+}
+
+/// Thumbs up/down on whether the job picked the right album and quality.
+#[component]
+fn FeedbackButtons(props: FeedbackButtonsProps) -> Element {
+    let auth = use_auth();
+    let current = props.feedback;
+    let on_change = props.on_change;
+    let job_id = props.job_id.clone();
+
+    let rate = move |value: Feedback| {
+        let job_id = job_id.clone();
+        // Clicking the active thumb again clears the rating.
+        let feedback = (current != Some(value)).then_some(value);
+        async move {
+            if let Some(token) = auth.token() {
+                if api::rate_job(token, job_id, feedback).await.is_ok() {
+                    on_change.call(());
+                }
+            }
+        }
+    };
+    let mut rate_up = rate.clone();
+    let mut rate_down = rate;
+
+    rsx! {
+      div { class: "flex items-center gap-2 mt-2 text-sm",
+        span { class: "text-gray-400", "Right album and quality?" }
+        button {
+          class: if current == Some(Feedback::Up) { "text-teal-400" } else { "text-gray-500 hover:text-gray-300" },
+          onclick: move |_| rate_up(Feedback::Up),
+          "👍"
+        }
+        button {
+          class: if current == Some(Feedback::Down) { "text-red-400" } else { "text-gray-500 hover:text-gray-300" },
+          onclick: move |_| rate_down(Feedback::Down),
+          "👎"
+        }
+      }
+    }
+}
+
+/// Lists the user's download jobs, newest first.
+#[component]
+pub fn Downloads() -> Element {
+    let auth = use_auth();
+    // A browse-only instance shows the history without the download actions.
+    let browse = use_browse_mode();
+    let mut jobs = use_signal(Vec::<JobWithFiles>::new);
+    let mut queue = use_signal(Vec::<QueuedJob>::new);
+    let mut error = use_signal(String::new);
+    let mut search = use_signal(String::new);
+    let mut sharing = use_signal(|| None::<String>);
+
+    let fetch_jobs = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_jobs(token.clone()).await {
+                Ok(fetched) => jobs.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch downloads: {e}")),
+            }
+            if auth.is_admin() && !browse.enabled() {
+                if let Ok(fetched) = api::get_queue(token).await {
+                    queue.set(fetched);
+                }
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_jobs().await;
+    });
+
+    let mut socket = use_websocket(move || {
+        api::job_events(auth.token().unwrap_or_default(), WebSocketOptions::new())
+    });
+
+    // Refresh on every job event. Events come over the websocket when it can
+    // connect, otherwise (or once it drops) by long-polling the same stream.
+    use_future(move || async move {
+        if socket.connect().await == WebsocketState::Open {
+            while socket.recv().await.is_ok() {
+                fetch_jobs().await;
+            }
+        }
+
+        let mut cursor = 0;
+        while let Some(token) = auth.token() {
+            match api::get_job_updates(token, cursor).await {
+                Ok(updates) => {
+                    if !updates.events.is_empty() {
+                        fetch_jobs().await;
+                    }
+                    cursor = updates.cursor;
+                }
+                Err(_) => TimeoutFuture::new(5_000).await,
+            }
+        }
+    });
+
+    // Speed samples and progress are not job events, so running downloads are polled.
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(10_000).await;
+            let downloading = jobs
+                .read()
+                .iter()
+                .any(|entry| entry.job.state == JobState::Downloading);
+            if downloading {
+                fetch_jobs().await;
+            }
+        }
+    });
+
+    let replace_file = move |job_id: String, file_id: String| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::replace_file(token, job_id, file_id).await {
+            Ok(_) => fetch_jobs().await,
+            Err(e) => error.set(format!("Failed to replace file: {e}")),
+        }
+    };
+
+    let toggle_pause = move |file_id: String, paused: bool| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let result = if paused {
+            api::resume_transfer(token, file_id).await
+        } else {
+            api::pause_transfer(token, file_id).await
+        };
+        match result {
+            Ok(_) => fetch_jobs().await,
+            Err(e) => error.set(format!("Failed to pause or resume: {e}")),
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans",
+        div { class: "flex justify-between items-center mb-6",
+          h3 { class: "text-2xl font-bold text-teal-400",
+            if browse.enabled() {
+              "History"
+            } else {
+              "Downloads"
+            }
+          }
+          button {
+            class: "text-sm text-gray-300 hover:text-white",
+            onclick: move |_| async move { fetch_jobs().await },
+            "Refresh"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-red-400 mb-4", "{error}" }
+        }
+        if auth.is_admin() && !browse.enabled() {
+          DownloadQueue { queue: queue() }
+          UnmanagedDownloads { on_import: move |_| async move { fetch_jobs().await } }
+        }
+        if jobs.read().is_empty() {
+          p { class: "text-center text-gray-500 py-10", "No downloads yet." }
+        } else {
+          input {
+            class: "w-full p-2 mb-4 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            value: "{search}",
+            oninput: move |e| search.set(e.value()),
+            placeholder: "Search notes, labels, folders and files",
+            "type": "search",
+          }
+        }
+        ul { class: "space-y-4",
+          for entry in jobs.read().iter().filter(|entry| matches_query(entry, &search.read())).cloned() {
+            {
+                let (label, color) = state_label(&entry.job.state);
+                rsx! {
+                  li { key: "{entry.job.id}", class: "bg-gray-700 p-4 rounded-md",
+                    div { class: "flex justify-between items-center mb-2",
+                      div {
+                        p { class: "text-sm text-gray-400", "{entry.job.created_at}" }
+                        p { class: "text-sm text-gray-300", "→ {entry.job.target_folder}" }
+                        if let Some(note) = &entry.job.note {
+                          p { class: "text-sm text-gray-400 italic", "{note}" }
+                        }
+                        if !entry.labels.is_empty() {
+                          div { class: "flex flex-wrap gap-1 mt-1",
+                            for label in entry.labels.iter() {
+                              span { class: "text-xs bg-gray-600 text-teal-200 px-2 py-0.5 rounded", "{label}" }
+                            }
+                          }
+                        }
+                      }
+                      div { class: "flex items-center gap-2",
+                        if entry.job.recording_id.is_some() {
+                          span { class: "text-xs text-gray-400", "Single" }
+                        }
+                        if entry.job.replaces_file_id.is_some() {
+                          span { class: "text-xs text-gray-400", "Replacement" }
+                        }
+                        span { class: "text-xs font-semibold px-2 py-1 rounded {color}", "{label}" }
+                      }
+                    }
+                    ul { class: "text-sm text-gray-300 space-y-1",
+                      for file in entry.files.iter().filter(|f| !f.artwork) {
+                        li { key: "{file.id}",
+                          div { class: "flex justify-between gap-2",
+                            span { class: "truncate", "{file_name(&file.filename)}" }
+                            if file.retry_of.is_some() {
+                              span {
+                                class: "text-xs text-indigo-300 whitespace-nowrap",
+                                title: "Downloaded from another source after the previous copy failed",
+                                "Retry"
+                              }
+                            }
+                            if let Some(score) = file.match_score {
+                              ScoreBar { score }
+                            }
+                            if entry.job.state == JobState::Downloading {
+                              if let Some(speeds) = entry.speeds.get(&file.id) {
+                                SpeedSparkline { speeds: speeds.clone() }
+                              }
+                            }
+                            if let Some(state) = &file.state {
+                              span { class: "text-xs text-gray-400 whitespace-nowrap", "{state}" }
+                            } else if entry.job.state == JobState::Downloading
+                                && (file.paused || file.transfer_id.is_some())
+                            {
+                              if file.paused {
+                                span { class: "text-xs text-yellow-400 whitespace-nowrap", "Paused" }
+                              } else if let Some(position) = file.queue_position {
+                                span {
+                                  class: "text-xs text-gray-400 whitespace-nowrap",
+                                  title: "Waiting in the uploader's queue",
+                                  "Position {position} in queue"
+                                }
+                              } else if let Some(progress) = entry.progress.get(&file.id) {
+                                span { class: "text-xs text-gray-400 whitespace-nowrap", "{progress:.0}%" }
+                              }
+                              button {
+                                class: "text-xs text-teal-400 hover:text-teal-300 whitespace-nowrap",
+                                onclick: {
+                                    let file_id = file.id.clone();
+                                    let paused = file.paused;
+                                    move |_| toggle_pause(file_id.clone(), paused)
+                                },
+                                if file.paused {
+                                  "Resume"
+                                } else {
+                                  "Pause"
+                                }
+                              }
+                            }
+                            if file.replaced_by.is_some() {
+                              span { class: "text-xs text-yellow-400 whitespace-nowrap", "Replaced" }
+                            } else if entry.job.state == JobState::Imported && !browse.enabled() {
+                              button {
+                                class: "text-xs text-teal-400 hover:text-teal-300 whitespace-nowrap",
+                                title: "Download another copy of this track and swap it in",
+                                onclick: {
+                                    let job_id = entry.job.id.clone();
+                                    let file_id = file.id.clone();
+                                    move |_| replace_file(job_id.clone(), file_id.clone())
+                                },
+                                "Replace"
+                              }
+                            }
+                          }
+                          if let Some(error) = &file.error {
+                            p { class: "text-xs text-red-400", "{file.username}: {error}" }
+                          }
+                        }
+                      }
+                    }
+                    if matches!(entry.job.state, JobState::Imported | JobState::Failed | JobState::ImportTimedOut) {
+                      div { class: "flex items-center gap-3",
+                        FeedbackButtons {
+                          job_id: entry.job.id.clone(),
+                          feedback: entry.job.feedback,
+                          on_change: move |_| async move { fetch_jobs().await },
+                        }
+                        if entry.job.state == JobState::Imported {
+                          button {
+                            class: "mt-2 text-sm text-teal-400 hover:text-teal-300",
+                            onclick: {
+                                let job_id = entry.job.id.clone();
+                                move |_| {
+                                    let open = sharing().as_ref() != Some(&job_id);
+                                    sharing.set(open.then(|| job_id.clone()));
+                                }
+                            },
+                            "Share"
+                          }
+                        }
+                      }
+                      if sharing().as_ref() == Some(&entry.job.id) {
+                        ShareLinks { job_id: entry.job.id.clone() }
+                      }
+                    }
+                    if entry.job.state == JobState::AwaitingReview && !browse.enabled() {
+                      JobReview {
+                        job: entry.clone(),
+                        on_done: move |_| async move { fetch_jobs().await },
+                      }
+                    }
+                  }
+                }
+            }
+          }
+        }
+      }
+    }
+}
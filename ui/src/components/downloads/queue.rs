@@ -0,0 +1,34 @@
+use api::QueuedJob;
+use dioxus::prelude::*;
+
+use crate::ScoreBar;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    queue: Vec<QueuedJob>,
+}
+
+/// Jobs of all users waiting for a download slot, next to start first.
+#[component]
+pub fn DownloadQueue(props: Props) -> Element {
+    rsx! {
+      div { class: "bg-gray-700 p-4 rounded-md mb-6",
+        h4 { class: "text-lg font-semibold text-indigo-300 mb-2", "Queue" }
+        if props.queue.is_empty() {
+          p { class: "text-sm text-gray-400", "No jobs waiting." }
+        }
+        ol { class: "text-sm text-gray-300 space-y-1",
+          for (position , entry) in props.queue.iter().enumerate() {
+            li { key: "{entry.job.id}", class: "flex justify-between gap-2",
+              span { "{position + 1}. {entry.username}" }
+              span { class: "truncate text-gray-400", "{entry.job.target_folder}" }
+              if let Some(score) = entry.match_score {
+                ScoreBar { score }
+              }
+              span { class: "text-xs text-gray-400 whitespace-nowrap", "{entry.file_count} files" }
+            }
+          }
+        }
+      }
+    }
+}
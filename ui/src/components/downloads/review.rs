@@ -0,0 +1,116 @@
+use api::{JobWithFiles, RejectedFiles};
+use dioxus::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use crate::{use_auth, Button, Checkbox};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    job: JobWithFiles,
+    on_done: EventHandler,
+}
+
+/// Lets the user choose which staged files of a job enter the library.
+#[component]
+pub fn JobReview(props: Props) -> Element {
+    let auth = use_auth();
+    let staged: Vec<String> = props
+        .job
+        .files
+        .iter()
+        .filter_map(|f| f.local_path.clone())
+        .collect();
+    let initial = staged.clone();
+    let mut keep = use_signal(move || initial.into_iter().collect::<HashSet<_>>());
+    let mut rejected = use_signal(RejectedFiles::default);
+    let mut error = use_signal(String::new);
+    let job_id = props.job.job.id.clone();
+
+    // Signed URLs to listen to each staged file before deciding.
+    let files = props.job.files.clone();
+    let previews = use_resource(move || {
+        let files = files.clone();
+        async move {
+            let mut urls = HashMap::new();
+            if let Some(token) = auth.token() {
+                for file in files.iter().filter(|f| f.local_path.is_some()) {
+                    if let Ok(url) = api::job_file_url(token.clone(), file.id.clone()).await {
+                        urls.insert(file.local_path.clone().unwrap_or_default(), url);
+                    }
+                }
+            }
+            urls
+        }
+    });
+
+    let submit = move |_| {
+        let job_id = job_id.clone();
+        async move {
+            let Some(token) = auth.token() else {
+                return;
+            };
+            let keep = keep.read().iter().cloned().collect();
+            match api::import_reviewed(token, job_id, keep, rejected()).await {
+                Ok(()) => props.on_done.call(()),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "mt-3 border-t border-gray-600 pt-3",
+        p { class: "text-sm text-gray-300 mb-2", "Choose the files to import:" }
+        ul { class: "space-y-1 mb-3",
+          for path in staged {
+            li {
+              key: "{path}",
+              class: "flex items-center gap-2 p-1 rounded-md hover:bg-gray-600 cursor-pointer",
+              onclick: {
+                  let path = path.clone();
+                  move |_| {
+                      let mut keep = keep.write();
+                      if !keep.remove(&path) {
+                          keep.insert(path.clone());
+                      }
+                  }
+              },
+              Checkbox { is_selected: keep.read().contains(&path) }
+              span { class: "text-sm break-all flex-grow", "{path}" }
+              if let Some(url) = previews.read().as_ref().and_then(|urls| urls.get(&path)) {
+                audio {
+                  class: "h-8 flex-shrink-0",
+                  controls: true,
+                  preload: "none",
+                  src: "{url}",
+                  onclick: move |e| e.stop_propagation(),
+                }
+              }
+            }
+          }
+        }
+        div { class: "flex items-center gap-4 mb-3 text-sm",
+          span { class: "text-gray-400", "Unselected files:" }
+          label { class: "flex items-center gap-1 cursor-pointer",
+            input {
+              r#type: "radio",
+              checked: rejected() == RejectedFiles::Discard,
+              onchange: move |_| rejected.set(RejectedFiles::Discard),
+            }
+            "Discard"
+          }
+          label { class: "flex items-center gap-1 cursor-pointer",
+            input {
+              r#type: "radio",
+              checked: rejected() == RejectedFiles::Archive,
+              onchange: move |_| rejected.set(RejectedFiles::Archive),
+            }
+            "Archive"
+          }
+        }
+        Button { onclick: submit, "Import Selected" }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,133 @@
+use api::ShareLinkSummary;
+use dioxus::prelude::*;
+use web_sys::window;
+
+use crate::use_auth;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    job_id: String,
+}
+
+/// Full URL of the public page of a share link.
+fn share_url(id: &str) -> String {
+    let origin = window()
+        .and_then(|w| w.location().origin().ok())
+        .unwrap_or_default();
+    format!("{origin}/share/{id}")
+}
+
+/// Creates and revokes public links to an imported job.
+#[component]
+pub fn ShareLinks(props: Props) -> Element {
+    let auth = use_auth();
+    let mut links = use_signal(Vec::<ShareLinkSummary>::new);
+    let mut days = use_signal(|| 7i64);
+    let mut password = use_signal(String::new);
+    let mut error = use_signal(String::new);
+
+    let job_id = props.job_id.clone();
+    let fetch = move || {
+        let job_id = job_id.clone();
+        async move {
+            if let Some(token) = auth.token() {
+                match api::get_share_links(token, job_id).await {
+                    Ok(fetched) => links.set(fetched),
+                    Err(e) => error.set(e.to_string()),
+                }
+            }
+        }
+    };
+
+    let initial = fetch.clone();
+    use_future(move || {
+        let fetch = initial.clone();
+        async move { fetch().await }
+    });
+
+    let job_id = props.job_id.clone();
+    let refresh = fetch.clone();
+    let create = move |_| {
+        let job_id = job_id.clone();
+        let refresh = refresh.clone();
+        async move {
+            error.set(String::new());
+            let Some(token) = auth.token() else {
+                return;
+            };
+            let password = Some(password()).filter(|p| !p.trim().is_empty());
+            match api::create_share_link(token, job_id, days(), password).await {
+                Ok(_) => {
+                    password.set(String::new());
+                    refresh().await
+                }
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let revoke = move |share_id: String| {
+        let refresh = fetch.clone();
+        async move {
+            let Some(token) = auth.token() else {
+                return;
+            };
+            match api::revoke_share_link(token, share_id).await {
+                Ok(()) => refresh().await,
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "mt-3 border-t border-gray-600 pt-3",
+        ul { class: "space-y-1 mb-3",
+          for link in links.read().iter().cloned() {
+            li { key: "{link.id}", class: "flex items-center gap-2 text-sm",
+              input {
+                class: "flex-grow p-1 rounded bg-gray-800 border border-gray-600 text-xs font-mono",
+                value: "{share_url(&link.id)}",
+                readonly: true,
+              }
+              if link.protected {
+                span { class: "text-xs text-gray-400", title: "Password protected", "🔒" }
+              }
+              span { class: "text-xs text-gray-400 whitespace-nowrap", "until {link.expires_at}" }
+              button {
+                class: "text-xs text-red-400 hover:text-red-300",
+                onclick: {
+                    let revoke = revoke.clone();
+                    move |_| revoke(link.id.clone())
+                },
+                "Revoke"
+              }
+            }
+          }
+        }
+        div { class: "flex flex-wrap items-center gap-2",
+          select {
+            class: "p-1 rounded bg-gray-800 border border-gray-600 text-sm",
+            onchange: move |e| days.set(e.value().parse().unwrap_or(7)),
+            option { value: "1", selected: days() == 1, "1 day" }
+            option { value: "7", selected: days() == 7, "7 days" }
+            option { value: "30", selected: days() == 30, "30 days" }
+          }
+          input {
+            class: "flex-grow p-1 rounded bg-gray-800 border border-gray-600 text-sm",
+            value: "{password}",
+            oninput: move |e| password.set(e.value()),
+            placeholder: "Password (optional)",
+            "type": "password",
+          }
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white text-sm font-semibold py-1 px-3 rounded",
+            onclick: create,
+            "Create link"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
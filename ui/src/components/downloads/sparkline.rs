@@ -0,0 +1,66 @@
+use dioxus::prelude::*;
+
+const WIDTH: f64 = 80.0;
+const HEIGHT: f64 = 16.0;
+
+/// Only the most recent samples are drawn.
+const MAX_POINTS: usize = 60;
+
+fn format_speed(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1024.0 * 1024.0 {
+        format!("{:.1} MB/s", bytes_per_sec / (1024.0 * 1024.0))
+    } else {
+        format!("{:.0} KB/s", bytes_per_sec / 1024.0)
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    /// Speeds in bytes/s, oldest first.
+    speeds: Vec<f64>,
+}
+
+/// Tiny chart of a transfer's speed over time, with the latest speed. A flat
+/// line at the bottom is a stalled transfer.
+#[component]
+pub fn SpeedSparkline(props: Props) -> Element {
+    let speeds = &props.speeds[props.speeds.len().saturating_sub(MAX_POINTS)..];
+    let Some(&current) = speeds.last() else {
+        return rsx! {};
+    };
+    let peak = speeds.iter().copied().fold(0.0, f64::max).max(1.0);
+    let step = WIDTH / (speeds.len().max(2) - 1) as f64;
+    let points = speeds
+        .iter()
+        .enumerate()
+        .map(|(i, speed)| {
+            format!(
+                "{:.1},{:.1}",
+                i as f64 * step,
+                HEIGHT - speed / peak * HEIGHT
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let (color, label) = if current > 0.0 {
+        ("text-teal-400", format_speed(current))
+    } else {
+        ("text-red-400", "stalled".to_string())
+    };
+
+    rsx! {
+      span {
+        class: "flex items-center gap-1 whitespace-nowrap {color}",
+        title: "Peak {format_speed(peak)}",
+        svg {
+          class: "w-20 h-4",
+          view_box: "0 0 {WIDTH} {HEIGHT}",
+          preserve_aspect_ratio: "none",
+          fill: "none",
+          stroke: "currentColor",
+          polyline { stroke_width: "1.5", points: "{points}" }
+        }
+        span { class: "text-xs", "{label}" }
+      }
+    }
+}
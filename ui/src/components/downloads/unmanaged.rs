@@ -0,0 +1,136 @@
+use api::db::UnmanagedDownload;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+use super::file_name;
+use crate::{use_auth, Checkbox};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    /// Called once a job was created to import some of them.
+    #[props(into)]
+    on_import: EventHandler,
+}
+
+/// Downloads slskd completed without a job, e.g. from its web interface or
+/// while Soulbeet was down, to import into a folder or dismiss.
+#[component]
+pub fn UnmanagedDownloads(props: Props) -> Element {
+    let auth = use_auth();
+    let mut downloads = use_signal(Vec::<UnmanagedDownload>::new);
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut folders = use_signal(Vec::new);
+    let mut target_folder = use_signal(String::new);
+    let mut error = use_signal(String::new);
+
+    let fetch = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_unmanaged_downloads(token).await {
+                Ok(fetched) => downloads.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch unmanaged downloads: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch().await;
+        if let Some(token) = auth.token() {
+            if let Ok(user_folders) = api::get_user_folders(token).await {
+                if let Some(first) = user_folders.first() {
+                    target_folder.set(first.path.clone());
+                }
+                folders.set(user_folders);
+            }
+        }
+    });
+
+    let import = move |_| async move {
+        error.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let ids = selected().into_iter().collect();
+        match api::import_unmanaged_downloads(token, ids, target_folder()).await {
+            Ok(_) => {
+                selected.write().clear();
+                fetch().await;
+                props.on_import.call(());
+            }
+            Err(e) => error.set(format!("Failed to import: {e}")),
+        }
+    };
+
+    let dismiss = move |_| async move {
+        error.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let ids = selected().into_iter().collect();
+        match api::dismiss_unmanaged_downloads(token, ids).await {
+            Ok(_) => {
+                selected.write().clear();
+                fetch().await;
+            }
+            Err(e) => error.set(format!("Failed to dismiss: {e}")),
+        }
+    };
+
+    if downloads.read().is_empty() && error().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+      div { class: "bg-gray-700 p-4 rounded-md mb-6",
+        h4 { class: "text-lg font-semibold text-indigo-300 mb-1", "Unmanaged downloads" }
+        p { class: "text-sm text-gray-400 mb-2",
+          "Completed in slskd without a job, e.g. from its web interface or while Soulbeet was down."
+        }
+        ul { class: "text-sm text-gray-300 space-y-1 mb-3 max-h-64 overflow-y-auto",
+          for download in downloads.read().iter().cloned() {
+            li {
+              key: "{download.id}",
+              class: "flex items-center gap-2 cursor-pointer",
+              title: "{download.filename}",
+              onclick: {
+                  let id = download.id.clone();
+                  move |_| {
+                      let mut selected = selected.write();
+                      if !selected.remove(&id) {
+                          selected.insert(id.clone());
+                      }
+                  }
+              },
+              Checkbox { is_selected: selected.read().contains(&download.id) }
+              span { class: "truncate flex-grow", {file_name(&download.filename)} }
+              span { class: "text-xs text-gray-400 whitespace-nowrap", "{download.username}" }
+            }
+          }
+        }
+        div { class: "flex flex-wrap gap-2",
+          select {
+            class: "flex-grow p-1 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none text-sm",
+            value: "{target_folder}",
+            onchange: move |e| target_folder.set(e.value()),
+            for folder in folders.read().iter() {
+              option { value: "{folder.path}", "{folder.name}" }
+            }
+          }
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white text-sm py-1 px-3 rounded transition-colors disabled:bg-gray-600",
+            disabled: selected.read().is_empty() || target_folder.read().is_empty(),
+            onclick: import,
+            "Import"
+          }
+          button {
+            class: "bg-gray-600 hover:bg-gray-500 text-white text-sm py-1 px-3 rounded transition-colors disabled:opacity-50",
+            disabled: selected.read().is_empty(),
+            onclick: dismiss,
+            "Dismiss"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
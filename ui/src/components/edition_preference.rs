@@ -0,0 +1,71 @@
+use dioxus::prelude::*;
+use shared::musicbrainz::EditionPreference;
+
+use crate::use_auth;
+
+/// Which edition of an album opens from the search results: the original
+/// release, or the earliest one of a country.
+#[component]
+pub fn EditionPreferenceSettings() -> Element {
+    let auth = use_auth();
+    let mut country = use_signal(String::new);
+    let mut error = use_signal(String::new);
+    let mut success_msg = use_signal(String::new);
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_edition_preference(token).await {
+                Ok(fetched) => country.set(fetched.country.unwrap_or_default()),
+                Err(e) => error.set(format!("Failed to fetch edition preference: {e}")),
+            }
+        }
+    });
+
+    let save = move |_| async move {
+        error.set(String::new());
+        success_msg.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let preference = EditionPreference {
+            country: Some(country()).filter(|c| !c.trim().is_empty()),
+        };
+        match api::save_edition_preference(token, preference).await {
+            Ok(saved) => {
+                country.set(saved.country.unwrap_or_default());
+                success_msg.set("Edition preference saved".to_string());
+            }
+            Err(e) => error.set(e.to_string()),
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Preferred Edition" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "Albums open on their original release, unless you prefer the earliest release of a country. Files naming another edition, e.g. a remaster or a deluxe edition, rank lower in the download options."
+        }
+        div { class: "flex items-center gap-2",
+          label { class: "text-sm", "Country" }
+          input {
+            class: "w-24 p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            value: "{country}",
+            oninput: move |e| country.set(e.value()),
+            placeholder: "e.g. GB",
+            maxlength: "2",
+          }
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+            onclick: save,
+            "Save"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+        if !success_msg().is_empty() {
+          p { class: "text-sm text-teal-400 mt-2", "{success_msg}" }
+        }
+      }
+    }
+}
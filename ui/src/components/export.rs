@@ -0,0 +1,173 @@
+use api::{ExportDataset, ExportFile, ExportFormat, ExportRequest};
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+use crate::use_auth;
+
+fn dataset_label(dataset: ExportDataset) -> &'static str {
+    match dataset {
+        ExportDataset::History => "Download history",
+        ExportDataset::Wishlist => "Wishlist (queued albums)",
+        ExportDataset::Library => "Library index",
+    }
+}
+
+/// Has the browser save an exported file.
+fn save_file(file: &ExportFile) -> Result<(), String> {
+    let eval = document::eval(
+        r#"
+        const file = await dioxus.recv();
+        const url = URL.createObjectURL(new Blob([file.content], { type: file.content_type }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = file.filename;
+        link.click();
+        URL.revokeObjectURL(url);
+        "#,
+    );
+    eval.send(file).map_err(|e| e.to_string())
+}
+
+/// Exports of the user's history, wishlist and library index, as CSV for
+/// spreadsheets or JSON for other tools.
+#[component]
+pub fn DataExport() -> Element {
+    let auth = use_auth();
+    let mut dataset = use_signal(|| ExportDataset::History);
+    let mut format = use_signal(|| ExportFormat::Csv);
+    let mut fields = use_signal(|| {
+        ExportDataset::History
+            .fields()
+            .iter()
+            .map(|f| f.to_string())
+            .collect::<HashSet<_>>()
+    });
+    let mut from = use_signal(String::new);
+    let mut to = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(String::new);
+
+    let mut pick_dataset = move |value: String| {
+        let Some(picked) = ExportDataset::ALL.into_iter().find(|d| d.name() == value) else {
+            return;
+        };
+        dataset.set(picked);
+        fields.set(picked.fields().iter().map(|f| f.to_string()).collect());
+    };
+
+    let export = move |_| async move {
+        error.set(String::new());
+        if fields.read().is_empty() {
+            error.set("Pick at least one field".to_string());
+            return;
+        }
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let request = ExportRequest {
+            dataset: dataset(),
+            format: format(),
+            fields: dataset()
+                .fields()
+                .iter()
+                .filter(|f| fields.read().contains(**f))
+                .map(|f| f.to_string())
+                .collect(),
+            from: Some(from()).filter(|d| !d.is_empty()),
+            to: Some(to()).filter(|d| !d.is_empty()),
+        };
+        busy.set(true);
+        match api::export_data(token, request).await {
+            Ok(file) => {
+                if let Err(e) = save_file(&file) {
+                    error.set(format!("Failed to save the export: {e}"));
+                }
+            }
+            Err(e) => error.set(format!("Failed to export: {e}")),
+        }
+        busy.set(false);
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Export Data" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "Download your data as CSV for spreadsheets or JSON for other tools. The date range applies to when downloads were started, albums queued or tracks imported."
+        }
+        div { class: "grid grid-cols-1 md:grid-cols-4 gap-4 mb-4",
+          div {
+            label { class: "block text-sm font-medium mb-1", "Data" }
+            select {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              onchange: move |e| pick_dataset(e.value()),
+              for option_dataset in ExportDataset::ALL {
+                option {
+                  value: option_dataset.name(),
+                  selected: dataset() == option_dataset,
+                  {dataset_label(option_dataset)}
+                }
+              }
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Format" }
+            select {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              onchange: move |e| {
+                  format.set(match e.value().as_str() {
+                      "json" => ExportFormat::Json,
+                      _ => ExportFormat::Csv,
+                  })
+              },
+              option { value: "csv", selected: format() == ExportFormat::Csv, "CSV" }
+              option { value: "json", selected: format() == ExportFormat::Json, "JSON" }
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "From" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{from}",
+              oninput: move |e| from.set(e.value()),
+              "type": "date",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "To" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{to}",
+              oninput: move |e| to.set(e.value()),
+              "type": "date",
+            }
+          }
+        }
+        div { class: "flex flex-wrap gap-x-4 gap-y-1 mb-4",
+          for field in dataset().fields().iter().copied() {
+            label { class: "flex items-center gap-1 text-sm text-gray-300 cursor-pointer",
+              input {
+                "type": "checkbox",
+                checked: fields.read().contains(field),
+                onchange: move |_| {
+                    let mut fields = fields.write();
+                    if !fields.remove(field) {
+                        fields.insert(field.to_string());
+                    }
+                },
+              }
+              "{field}"
+            }
+          }
+        }
+        button {
+          class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors disabled:bg-gray-600",
+          disabled: busy(),
+          onclick: export,
+          "Export"
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,108 @@
+use api::db::FolderPermissions;
+use dioxus::prelude::*;
+
+use crate::use_auth;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct FolderPermissionsFormProps {
+    pub folder_id: String,
+    pub permissions: FolderPermissions,
+    #[props(into)]
+    pub on_saved: EventHandler<FolderPermissions>,
+}
+
+/// Modes and owner given to files imported into a folder, for media servers
+/// running as another user than Soulbeet.
+#[component]
+pub fn FolderPermissionsForm(props: FolderPermissionsFormProps) -> Element {
+    let auth = use_auth();
+    let permissions = props.permissions.clone();
+    let mut file_mode = use_signal(|| permissions.file_mode.clone().unwrap_or_default());
+    let mut dir_mode = use_signal(|| permissions.dir_mode.clone().unwrap_or_default());
+    let mut owner_uid = use_signal(|| {
+        permissions
+            .owner_uid
+            .map(|id| id.to_string())
+            .unwrap_or_default()
+    });
+    let mut owner_gid = use_signal(|| {
+        permissions
+            .owner_gid
+            .map(|id| id.to_string())
+            .unwrap_or_default()
+    });
+    let mut error = use_signal(String::new);
+
+    let save = move |_| {
+        let folder_id = props.folder_id.clone();
+        async move {
+            error.set(String::new());
+            let id = |value: String| -> Result<Option<i64>, String> {
+                match value.trim() {
+                    "" => Ok(None),
+                    id => id
+                        .parse::<i64>()
+                        .map(Some)
+                        .map_err(|_| format!("{id} is not a numeric id")),
+                }
+            };
+            let (owner_uid, owner_gid) = match (id(owner_uid()), id(owner_gid())) {
+                (Ok(uid), Ok(gid)) => (uid, gid),
+                (Err(e), _) | (_, Err(e)) => {
+                    error.set(e);
+                    return;
+                }
+            };
+            let permissions = FolderPermissions {
+                file_mode: Some(file_mode()),
+                dir_mode: Some(dir_mode()),
+                owner_uid,
+                owner_gid,
+            };
+            let Some(token) = auth.token() else {
+                return;
+            };
+            match api::set_folder_permissions(token, folder_id, permissions).await {
+                Ok(saved) => props.on_saved.call(saved),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let fields: [(&str, &str, Signal<String>); 4] = [
+        ("File mode", "644", file_mode),
+        ("Folder mode", "755", dir_mode),
+        ("Owner uid", "1000", owner_uid),
+        ("Group gid", "1000", owner_gid),
+    ];
+
+    rsx! {
+      div { class: "mt-3 pt-3 border-t border-gray-600",
+        p { class: "text-gray-400 text-xs mb-2",
+          "Applied to imported files and their album and artist folders. Leave a field empty to keep what the server creates. Changing the owner needs Soulbeet to run as root."
+        }
+        div { class: "flex flex-wrap items-end gap-2",
+          for (label , placeholder , mut value) in fields {
+            div {
+              label { class: "block text-xs text-gray-400 mb-1", {label} }
+              input {
+                class: "w-24 p-1 text-sm rounded bg-gray-600 border border-gray-500 focus:border-teal-500 focus:outline-none",
+                value: "{value}",
+                oninput: move |e| value.set(e.value()),
+                placeholder,
+                "type": "text",
+              }
+            }
+          }
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white text-sm font-bold py-1 px-3 rounded transition-colors",
+            onclick: save,
+            "Save"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,159 @@
+use api::db::{FollowedArtist, WantedRelease};
+use dioxus::prelude::*;
+
+use crate::{use_auth, Button};
+
+/// The artists the user follows and the new albums found for them, to queue
+/// those not downloaded automatically. Nothing shows until an artist is
+/// followed from their discography.
+#[component]
+pub fn FollowedArtists() -> Element {
+    let auth = use_auth();
+    let mut follows = use_signal(Vec::<FollowedArtist>::new);
+    let mut wanted = use_signal(Vec::<WantedRelease>::new);
+    let mut folders = use_signal(Vec::new);
+    let mut selected_folder = use_signal(String::new);
+    let mut error = use_signal(String::new);
+
+    let refresh = move || async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::get_followed_artists(token.clone()).await {
+            Ok(fetched) => follows.set(fetched),
+            Err(e) => error.set(e.to_string()),
+        }
+        match api::get_wanted_releases(token).await {
+            Ok(fetched) => wanted.set(fetched),
+            Err(e) => error.set(e.to_string()),
+        }
+    };
+
+    use_future(move || async move {
+        refresh().await;
+        if let Some(token) = auth.token() {
+            if let Ok(user_folders) = api::get_user_folders(token).await {
+                if let Some(first) = user_folders.first() {
+                    selected_folder.set(first.path.clone());
+                }
+                folders.set(user_folders);
+            }
+        }
+    });
+
+    let unfollow = move |id: String| async move {
+        if let Some(token) = auth.token() {
+            match api::unfollow_artist(token, id).await {
+                Ok(()) => refresh().await,
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let queue = move |id: String| async move {
+        error.set(String::new());
+        if let Some(token) = auth.token() {
+            match api::queue_wanted_release(token, id, selected_folder(), None).await {
+                Ok(_) => refresh().await,
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let dismiss = move |id: String| async move {
+        if let Some(token) = auth.token() {
+            match api::dismiss_wanted_release(token, id).await {
+                Ok(()) => refresh().await,
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    if follows.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans",
+        h4 { class: "text-2xl font-bold mb-4 text-teal-400", "Followed artists" }
+        ul { class: "flex flex-wrap gap-2 mb-6",
+          for follow in follows.read().iter().cloned() {
+            li {
+              key: "{follow.id}",
+              class: "flex items-center gap-2 bg-gray-700 rounded-full px-3 py-1 text-sm",
+              span { "{follow.name}" }
+              if follow.auto_download {
+                span {
+                  class: "text-xs text-teal-300",
+                  title: "New albums are downloaded once released",
+                  "auto"
+                }
+              }
+              button {
+                class: "text-gray-400 hover:text-red-400",
+                title: "Unfollow",
+                onclick: {
+                    let id = follow.id.clone();
+                    move |_| unfollow(id.clone())
+                },
+                "×"
+              }
+            }
+          }
+        }
+        h5 { class: "text-lg font-semibold mb-2", "Wanted" }
+        if wanted.read().is_empty() {
+          p { class: "text-sm text-gray-400", "No new albums since you followed these artists." }
+        } else {
+          div { class: "flex gap-2 items-center mb-3 text-sm",
+            label { class: "text-gray-400", "Queue into" }
+            select {
+              class: "flex-grow p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{selected_folder}",
+              onchange: move |e| selected_folder.set(e.value()),
+              for folder in folders.read().iter() {
+                option { value: "{folder.path}", "{folder.name}" }
+              }
+            }
+          }
+          ul { class: "space-y-1",
+            for release in wanted.read().iter().cloned() {
+              li {
+                key: "{release.id}",
+                class: "flex items-center gap-2 p-2 rounded-md bg-gray-700 text-sm",
+                span { class: "flex-grow",
+                  "{release.artist} - {release.title}"
+                  if let Some(date) = &release.release_date {
+                    span { class: "text-gray-400 ml-2", "{date}" }
+                  }
+                }
+                if release.request_id.is_some() {
+                  span { class: "text-xs text-green-400", "Queued" }
+                } else {
+                  Button {
+                    disabled: selected_folder.read().is_empty() || auth.is_demo(),
+                    onclick: {
+                        let id = release.id.clone();
+                        move |_| queue(id.clone())
+                    },
+                    "Queue"
+                  }
+                  button {
+                    class: "text-xs text-gray-400 hover:text-red-400",
+                    onclick: {
+                        let id = release.id.clone();
+                        move |_| dismiss(id.clone())
+                    },
+                    "Dismiss"
+                  }
+                }
+              }
+            }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,128 @@
+use api::db::LibraryEntry;
+use dioxus::prelude::*;
+
+use crate::use_auth;
+
+/// Files grouped by album, albums in the order the server sorted them.
+fn by_album(entries: &[LibraryEntry]) -> Vec<((String, String), Vec<LibraryEntry>)> {
+    let mut albums: Vec<((String, String), Vec<LibraryEntry>)> = Vec::new();
+    for entry in entries {
+        let key = (entry.artist.clone(), entry.album.clone());
+        match albums.last_mut() {
+            Some((last, files)) if *last == key => files.push(entry.clone()),
+            _ => albums.push((key, vec![entry.clone()])),
+        }
+    }
+    albums
+}
+
+/// Whether the file's artist, album or title contain `query`, ignoring case.
+fn matches_query(entry: &LibraryEntry, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty()
+        || [&entry.artist, &entry.album, &entry.title]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&query))
+}
+
+/// The music indexed in the user's library folders, playable in the browser.
+#[component]
+pub fn Library() -> Element {
+    let auth = use_auth();
+    let mut query = use_signal(String::new);
+    let mut playing = use_signal(|| None::<(LibraryEntry, String)>);
+    let mut error = use_signal(String::new);
+
+    let library = use_resource(move || async move {
+        let token = auth.token()?;
+        match api::get_library(token).await {
+            Ok(entries) => Some(entries),
+            Err(e) => {
+                error.set(format!("Failed to load the library: {e}"));
+                None
+            }
+        }
+    });
+
+    let play = move |entry: LibraryEntry| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::library_file_url(token, entry.path.clone()).await {
+            Ok(url) => playing.set(Some((entry, url))),
+            Err(e) => error.set(format!("Failed to play {}: {e}", entry.title)),
+        }
+    };
+
+    let entries: Vec<LibraryEntry> = library
+        .read()
+        .as_ref()
+        .and_then(|entries| entries.as_ref())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|e| matches_query(e, &query.read()))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let albums = by_album(&entries);
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-4xl mx-auto my-10 font-sans",
+        div { class: "flex justify-between items-center mb-4 gap-4",
+          h3 { class: "text-2xl font-bold text-teal-400", "Library" }
+          input {
+            class: "bg-gray-700 border border-gray-600 rounded-md px-3 py-1 text-sm focus:outline-none focus:border-teal-500",
+            placeholder: "Filter by artist, album or title",
+            value: "{query}",
+            oninput: move |e| query.set(e.value()),
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-red-400 mb-4", "{error}" }
+        }
+        if let Some((entry, url)) = playing() {
+          div { class: "sticky top-0 z-10 bg-gray-700 p-3 rounded mb-6 flex items-center gap-4",
+            p { class: "text-sm truncate flex-grow", "{entry.artist} - {entry.title}" }
+            audio {
+              class: "h-8 flex-shrink-0",
+              controls: true,
+              autoplay: true,
+              src: "{url}",
+            }
+          }
+        }
+        if library.read().is_some() && albums.is_empty() {
+          p { class: "text-sm text-gray-400", "Nothing in your library folders yet." }
+        }
+        for ((artist , album) , files) in albums {
+          div { key: "{artist}-{album}", class: "mb-6",
+            h4 { class: "font-semibold text-indigo-300", "{album}" }
+            p { class: "text-sm text-gray-400 mb-2", "{artist}" }
+            ul { class: "space-y-1",
+              for entry in files {
+                li {
+                  key: "{entry.path}",
+                  class: "bg-gray-700 p-2 rounded flex items-center gap-4 text-sm",
+                  span { class: "text-gray-400 w-6 text-right",
+                    {entry.track.map(|t| format!("{t:02}")).unwrap_or_default()}
+                  }
+                  span { class: "flex-grow truncate", title: "{entry.path}", "{entry.title}" }
+                  span { class: "text-gray-400 whitespace-nowrap", "{entry.format}" }
+                  button {
+                    class: "text-teal-400 hover:text-teal-300 whitespace-nowrap",
+                    onclick: {
+                        let entry = entry.clone();
+                        move |_| play(entry.clone())
+                    },
+                    "Play"
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,113 @@
+use api::MatchBreakdown;
+use dioxus::prelude::*;
+
+use crate::{Button, ScoreBar};
+
+/// Shows how a Soulseek filename scores against a MusicBrainz release, to
+/// troubleshoot files missing from search results.
+#[component]
+pub fn MatchDebugger() -> Element {
+    let mut filename = use_signal(String::new);
+    let mut album_id = use_signal(String::new);
+    let mut breakdown = use_signal::<Option<MatchBreakdown>>(|| None);
+    let mut error = use_signal(String::new);
+    let mut loading = use_signal(|| false);
+
+    let run = move |_| async move {
+        error.set(String::new());
+        if filename().trim().is_empty() || album_id().trim().is_empty() {
+            error.set("Filename and release ID are required".to_string());
+            return;
+        }
+        loading.set(true);
+        match api::debug_match(filename(), album_id()).await {
+            Ok(result) => breakdown.set(Some(result)),
+            Err(e) => error.set(e.to_string()),
+        }
+        loading.set(false);
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans",
+        h3 { class: "text-2xl font-bold mb-2 text-teal-400", "Match Debugger" }
+        p { class: "text-sm text-gray-400 mb-4",
+          "Paste a full Soulseek path and a MusicBrainz release ID to see how the file scores against the release."
+        }
+        div { class: "space-y-4 mb-4",
+          div {
+            label { class: "block text-sm font-medium mb-1", "Filename" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{filename}",
+              oninput: move |e| filename.set(e.value()),
+              placeholder: "@@music\\Artist\\Album (2020)\\01 - Track.flac",
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "MusicBrainz release ID" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{album_id}",
+              oninput: move |e| album_id.set(e.value()),
+              "type": "text",
+            }
+          }
+        }
+        Button { onclick: run, disabled: loading(), "Score" }
+        if !error().is_empty() {
+          p { class: "text-red-400 mt-4", "{error}" }
+        }
+        if let Some(breakdown) = breakdown() {
+          {
+              let result = &breakdown.result;
+              let passes = result.total_score >= breakdown.threshold;
+              rsx! {
+                div { class: "mt-6 space-y-2",
+                  p { class: "text-sm text-gray-300",
+                    "{breakdown.album.artist} - {breakdown.album.title}"
+                  }
+                  table { class: "w-full text-sm",
+                    tbody {
+                      tr {
+                        td { class: "text-gray-400 py-1", "Artist" }
+                        td { "{result.guessed_artist}" }
+                        td { ScoreBar { score: result.artist_score } }
+                      }
+                      tr {
+                        td { class: "text-gray-400 py-1", "Album" }
+                        td { "{result.guessed_album}" }
+                        td { ScoreBar { score: result.album_score } }
+                      }
+                      tr {
+                        td { class: "text-gray-400 py-1", "Track" }
+                        td { "{result.matched_track}" }
+                        td { ScoreBar { score: result.track_score } }
+                      }
+                      tr {
+                        td { class: "text-gray-400 py-1 font-semibold", "Total" }
+                        td { "{result.total_score:.3} (threshold {breakdown.threshold})" }
+                        td { ScoreBar { score: result.total_score } }
+                      }
+                    }
+                  }
+                  if passes {
+                    p { class: "text-teal-400 text-sm", "This file is kept in search results." }
+                  } else {
+                    p { class: "text-red-400 text-sm", "This file is dropped from search results." }
+                  }
+                  details { class: "text-sm text-gray-400",
+                    summary { class: "cursor-pointer", "Compared against {breakdown.tracks.len()} tracks" }
+                    ul { class: "list-disc ml-6 mt-1",
+                      for track in breakdown.tracks.iter() {
+                        li { "{track}" }
+                      }
+                    }
+                  }
+                }
+              }
+          }
+        }
+      }
+    }
+}
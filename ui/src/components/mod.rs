@@ -1,11 +1,53 @@
 pub mod album;
+pub mod blocked_uploaders;
+pub mod branding;
+pub mod connection_status;
 pub mod cover_art;
+pub mod downloads;
+pub mod edition_preference;
+pub mod export;
+pub mod folder_permissions;
+pub mod followed_artists;
+pub mod library;
+pub mod match_debugger;
 pub mod modal;
+pub mod notifications;
+pub mod passkeys;
+pub mod profiles;
+pub mod route_skeleton;
+pub mod scoring;
 pub mod search;
+pub mod search_rate_limit;
+pub mod shared_album;
 pub mod simple;
+pub mod soulseek_account;
+pub mod tasks;
+pub mod uploads;
+pub mod whats_new;
 
 pub use album::*;
+pub use blocked_uploaders::*;
+pub use branding::*;
+pub use connection_status::*;
 pub use cover_art::*;
+pub use downloads::*;
+pub use edition_preference::*;
+pub use export::*;
+pub use folder_permissions::*;
+pub use followed_artists::*;
+pub use library::*;
+pub use match_debugger::*;
 pub use modal::*;
+pub use notifications::*;
+pub use passkeys::*;
+pub use profiles::*;
+pub use route_skeleton::*;
+pub use scoring::*;
 pub use search::*;
+pub use search_rate_limit::*;
+pub use shared_album::*;
 pub use simple::*;
+pub use soulseek_account::*;
+pub use tasks::*;
+pub use uploads::*;
+pub use whats_new::*;
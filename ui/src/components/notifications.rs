@@ -0,0 +1,227 @@
+use api::db::{NotificationKind, NotificationSink};
+use dioxus::prelude::*;
+
+use crate::{use_auth, Checkbox};
+
+/// Digest windows offered, in seconds.
+const WINDOWS: [(i64, &str); 5] = [
+    (0, "Right away"),
+    (60, "1 minute"),
+    (300, "5 minutes"),
+    (900, "15 minutes"),
+    (3600, "1 hour"),
+];
+
+fn kind_label(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::Webhook => "Webhook",
+        NotificationKind::Ntfy => "ntfy",
+    }
+}
+
+fn new_sink() -> NotificationSink {
+    NotificationSink {
+        digest_window: 60,
+        notify_imported: true,
+        notify_failed: true,
+        notify_review: true,
+        ..Default::default()
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+struct SinkOptionsProps {
+    sink: NotificationSink,
+    #[props(into)]
+    on_change: EventHandler<NotificationSink>,
+}
+
+/// Digest window and events of a sink.
+#[component]
+fn SinkOptions(props: SinkOptionsProps) -> Element {
+    let sink = props.sink.clone();
+    let toggles: [(&str, bool, fn(&mut NotificationSink, bool)); 3] = [
+        ("Imported", sink.notify_imported, |s, on| {
+            s.notify_imported = on
+        }),
+        ("Failed", sink.notify_failed, |s, on| s.notify_failed = on),
+        ("Awaiting review", sink.notify_review, |s, on| {
+            s.notify_review = on
+        }),
+    ];
+
+    rsx! {
+      div { class: "flex flex-wrap items-center gap-x-4 gap-y-2 text-sm text-gray-300",
+        select {
+          class: "p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+          title: "Jobs finishing within this time of the first are sent together",
+          onchange: {
+              let mut sink = sink.clone();
+              move |e: FormEvent| {
+                  sink.digest_window = e.value().parse().unwrap_or(60);
+                  props.on_change.call(sink.clone());
+              }
+          },
+          for (seconds , label) in WINDOWS {
+            option {
+              value: "{seconds}",
+              selected: sink.digest_window == seconds,
+              {label}
+            }
+          }
+        }
+        for (label , on , set) in toggles {
+          div {
+            class: "flex items-center gap-1 cursor-pointer",
+            onclick: {
+                let mut sink = sink.clone();
+                move |_| {
+                    set(&mut sink, !on);
+                    props.on_change.call(sink.clone());
+                }
+            },
+            Checkbox { is_selected: on }
+            label { class: "cursor-pointer", {label} }
+          }
+        }
+      }
+    }
+}
+
+/// Webhooks and ntfy topics told when downloads finish, in digests.
+#[component]
+pub fn NotificationSettings() -> Element {
+    let auth = use_auth();
+    let mut sinks = use_signal(Vec::<NotificationSink>::new);
+    let mut draft = use_signal(new_sink);
+    let mut error = use_signal(String::new);
+    let mut success_msg = use_signal(String::new);
+
+    let fetch_sinks = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_notification_sinks(token).await {
+                Ok(fetched) => sinks.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch notifications: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_sinks().await;
+    });
+
+    let save = move |sink: NotificationSink| async move {
+        error.set(String::new());
+        success_msg.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let adding = sink.id.is_empty();
+        match api::save_notification_sink(token, sink).await {
+            Ok(_) => {
+                if adding {
+                    draft.set(new_sink());
+                }
+                fetch_sinks().await;
+            }
+            Err(e) => error.set(format!("Failed to save notification: {e}")),
+        }
+    };
+
+    let test = move |_| async move {
+        error.set(String::new());
+        success_msg.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::test_notification_sink(token, draft()).await {
+            Ok(_) => success_msg.set("Test notification sent".to_string()),
+            Err(e) => error.set(format!("Test notification failed: {e}")),
+        }
+    };
+
+    let delete = move |id: String| async move {
+        error.set(String::new());
+        if let Some(token) = auth.token() {
+            match api::delete_notification_sink(token, id).await {
+                Ok(_) => fetch_sinks().await,
+                Err(e) => error.set(format!("Failed to delete notification: {e}")),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Notifications" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "Get told when downloads finish. Jobs finishing close together are sent as one digest, and a job failing again after a retry is not notified twice."
+        }
+        ul { class: "space-y-2 mb-4",
+          for sink in sinks.read().iter().cloned() {
+            li { key: "{sink.id}", class: "bg-gray-700 p-3 rounded",
+              div { class: "flex justify-between items-center gap-4 mb-2",
+                span { class: "truncate",
+                  span { class: "font-medium text-teal-200 mr-2", {kind_label(sink.kind)} }
+                  "{sink.url}"
+                }
+                button {
+                  class: "text-gray-400 hover:text-red-400 text-sm",
+                  onclick: {
+                      let id = sink.id.clone();
+                      move |_| delete(id.clone())
+                  },
+                  "Delete"
+                }
+              }
+              SinkOptions { sink: sink.clone(), on_change: move |sink| save(sink) }
+            }
+          }
+        }
+        div { class: "flex gap-2 mb-2",
+          select {
+            class: "p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            onchange: move |e| {
+                draft.write().kind = match e.value().as_str() {
+                    "ntfy" => NotificationKind::Ntfy,
+                    _ => NotificationKind::Webhook,
+                };
+            },
+            option { value: "webhook", selected: draft().kind == NotificationKind::Webhook, "Webhook" }
+            option { value: "ntfy", selected: draft().kind == NotificationKind::Ntfy, "ntfy" }
+          }
+          input {
+            class: "flex-grow p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            value: "{draft.read().url}",
+            oninput: move |e| draft.write().url = e.value(),
+            placeholder: if draft().kind == NotificationKind::Ntfy { "https://ntfy.sh/my-topic" } else { "https://example.com/hook" },
+            "type": "url",
+          }
+        }
+        div { class: "flex flex-wrap justify-between items-center gap-2",
+          SinkOptions { sink: draft(), on_change: move |sink| draft.set(sink) }
+          div { class: "flex gap-2",
+            if auth.is_admin() {
+              button {
+                class: "bg-gray-600 hover:bg-gray-500 text-white py-2 px-4 rounded transition-colors",
+                disabled: draft.read().url.trim().is_empty(),
+                onclick: test,
+                "Test"
+              }
+            }
+            button {
+              class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors disabled:bg-gray-600",
+              disabled: draft.read().url.trim().is_empty(),
+              onclick: move |_| save(draft()),
+              "Add"
+            }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+        if !success_msg().is_empty() {
+          p { class: "text-sm text-teal-400 mt-2", "{success_msg}" }
+        }
+      }
+    }
+}
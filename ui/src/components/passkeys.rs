@@ -0,0 +1,193 @@
+use dioxus::prelude::*;
+
+use crate::use_auth;
+
+/// base64url conversions, for the binary fields of WebAuthn options and answers.
+const BASE64URL_JS: &str = r#"
+const fromB64 = (s) => Uint8Array.from(
+  atob(s.replace(/-/g, "+").replace(/_/g, "/").padEnd(Math.ceil(s.length / 4) * 4, "=")),
+  (c) => c.charCodeAt(0),
+).buffer;
+const toB64 = (buf) => btoa(String.fromCharCode(...new Uint8Array(buf)))
+  .replace(/\+/g, "-").replace(/\//g, "_").replace(/=+$/, "");
+const options = JSON.parse(await dioxus.recv());
+const publicKey = options.publicKey;
+publicKey.challenge = fromB64(publicKey.challenge);
+"#;
+
+const CREATE_JS: &str = r#"
+try {
+  publicKey.user.id = fromB64(publicKey.user.id);
+  (publicKey.excludeCredentials || []).forEach((c) => (c.id = fromB64(c.id)));
+  const cred = await navigator.credentials.create({ publicKey });
+  return { credential: JSON.stringify({
+    id: cred.id,
+    rawId: toB64(cred.rawId),
+    type: cred.type,
+    extensions: {},
+    response: {
+      attestationObject: toB64(cred.response.attestationObject),
+      clientDataJSON: toB64(cred.response.clientDataJSON),
+    },
+  }) };
+} catch (e) {
+  return { error: e.message };
+}
+"#;
+
+const GET_JS: &str = r#"
+try {
+  (publicKey.allowCredentials || []).forEach((c) => (c.id = fromB64(c.id)));
+  const cred = await navigator.credentials.get({ publicKey });
+  return { credential: JSON.stringify({
+    id: cred.id,
+    rawId: toB64(cred.rawId),
+    type: cred.type,
+    extensions: {},
+    response: {
+      authenticatorData: toB64(cred.response.authenticatorData),
+      clientDataJSON: toB64(cred.response.clientDataJSON),
+      signature: toB64(cred.response.signature),
+      userHandle: cred.response.userHandle ? toB64(cred.response.userHandle) : null,
+    },
+  }) };
+} catch (e) {
+  return { error: e.message };
+}
+"#;
+
+/// Runs a WebAuthn call in the browser with the server's `options`, returning
+/// its answer as JSON for the server.
+async fn run_webauthn(script: &str, options: &str) -> Result<String, String> {
+    let mut eval = document::eval(&format!("{BASE64URL_JS}{script}"));
+    eval.send(options).map_err(|e| e.to_string())?;
+    let answer = eval.await.map_err(|e| e.to_string())?;
+    match (answer.get("credential"), answer.get("error")) {
+        (Some(serde_json::Value::String(credential)), _) => Ok(credential.clone()),
+        (_, Some(serde_json::Value::String(error))) => Err(error.clone()),
+        _ => Err("The browser did not answer".to_string()),
+    }
+}
+
+/// Has the browser create a passkey, from registration options.
+pub async fn create_passkey(options: &str) -> Result<String, String> {
+    run_webauthn(CREATE_JS, options).await
+}
+
+/// Has the browser sign a challenge with a passkey, from sign-in options.
+pub async fn get_passkey(options: &str) -> Result<String, String> {
+    run_webauthn(GET_JS, options).await
+}
+
+/// The user's passkeys, to sign in without their password.
+#[component]
+pub fn PasskeySettings() -> Element {
+    let auth = use_auth();
+    let mut passkeys = use_signal(Vec::new);
+    let mut name = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(String::new);
+
+    let fetch_passkeys = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_passkeys(token).await {
+                Ok(fetched) => passkeys.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch passkeys: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_passkeys().await;
+    });
+
+    let handle_add = move |_| async move {
+        error.set(String::new());
+        if name().trim().is_empty() {
+            error.set("Give the passkey a name, e.g. the device it is on".to_string());
+            return;
+        }
+        let Some(token) = auth.token() else {
+            return;
+        };
+        busy.set(true);
+        let result = async {
+            let challenge = api::start_passkey_registration(token.clone())
+                .await
+                .map_err(|e| e.to_string())?;
+            let credential = create_passkey(&challenge.options).await?;
+            api::finish_passkey_registration(token, challenge.challenge_id, name(), credential)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        .await;
+        match result {
+            Ok(_) => {
+                name.set(String::new());
+                fetch_passkeys().await;
+            }
+            Err(e) => error.set(format!("Failed to add passkey: {e}")),
+        }
+        busy.set(false);
+    };
+
+    let handle_delete = move |id: String| async move {
+        error.set(String::new());
+        if let Some(token) = auth.token() {
+            match api::delete_passkey(token, id).await {
+                Ok(_) => fetch_passkeys().await,
+                Err(e) => error.set(format!("Failed to remove passkey: {e}")),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Security" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "Passkeys let you sign in with your device's fingerprint, face or PIN instead of your password, which keeps working too."
+        }
+        if !passkeys.read().is_empty() {
+          ul { class: "space-y-2 mb-4",
+            for passkey in passkeys.read().iter().cloned() {
+              li { class: "bg-gray-700 p-3 rounded flex justify-between items-center gap-4",
+                span { class: "font-medium text-teal-200", "{passkey.name}" }
+                span { class: "text-gray-400 text-sm flex-grow",
+                  {
+                      let added = passkey.created_at.get(..10).unwrap_or_default();
+                      match passkey.last_used_at.as_deref().and_then(|at| at.get(..10)) {
+                          Some(used) => format!("Added {added}, last used {used}"),
+                          None => format!("Added {added}, never used"),
+                      }
+                  }
+                }
+                button {
+                  class: "text-gray-400 hover:text-red-400 text-sm",
+                  onclick: move |_| handle_delete(passkey.id.clone()),
+                  "Remove"
+                }
+              }
+            }
+          }
+        }
+        div { class: "flex gap-2",
+          input {
+            class: "flex-grow p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            value: "{name}",
+            oninput: move |e| name.set(e.value()),
+            placeholder: "Passkey name, e.g. Laptop",
+            "type": "text",
+          }
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors disabled:bg-gray-600",
+            disabled: busy() || auth.is_demo(),
+            onclick: handle_add,
+            "Add a Passkey"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
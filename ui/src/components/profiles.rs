@@ -0,0 +1,263 @@
+use dioxus::prelude::*;
+use shared::{profiles::QualityProfile, slskd::ScoringStrategyKind};
+
+use crate::{use_auth, Checkbox};
+
+fn scoring_label(scoring: ScoringStrategyKind) -> &'static str {
+    match scoring {
+        ScoringStrategyKind::QualityFirst => "Best quality",
+        ScoringStrategyKind::AvailabilityFirst => "Fastest availability",
+        ScoringStrategyKind::SizeFirst => "Smallest good files",
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+struct ProfileEditorProps {
+    profile: QualityProfile,
+    on_saved: EventHandler,
+    on_cancel: EventHandler,
+}
+
+/// Form editing a user profile, or a copy of a preset.
+#[component]
+fn ProfileEditor(props: ProfileEditorProps) -> Element {
+    let auth = use_auth();
+    let profile = props.profile.clone();
+    let mut name = use_signal(|| profile.name.clone());
+    let mut description = use_signal(|| profile.description.clone());
+    let mut scoring = use_signal(|| profile.scoring);
+    let mut formats = use_signal(|| profile.formats.join(", "));
+    let mut completeness = use_signal(|| (profile.min_completeness * 100.0).round().to_string());
+    let mut auto_pick = use_signal(|| profile.auto_pick);
+    let mut error = use_signal(String::new);
+
+    let save = move |_| {
+        let profile = props.profile.clone();
+        async move {
+            error.set(String::new());
+            let Ok(percent) = completeness().trim().parse::<f64>() else {
+                error.set("Completeness must be a percentage".to_string());
+                return;
+            };
+            let Some(token) = auth.token() else {
+                return;
+            };
+            let profile = QualityProfile {
+                name: name(),
+                description: description(),
+                scoring: scoring(),
+                formats: formats().split(',').map(str::to_string).collect(),
+                min_completeness: percent / 100.0,
+                auto_pick: auto_pick(),
+                ..profile
+            };
+            match api::save_quality_profile(token, profile).await {
+                Ok(_) => props.on_saved.call(()),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-700 p-4 rounded-md mb-4 space-y-3",
+        div { class: "grid grid-cols-1 md:grid-cols-2 gap-4",
+          div {
+            label { class: "block text-sm font-medium mb-1", "Name" }
+            input {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{name}",
+              oninput: move |e| name.set(e.value()),
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Description" }
+            input {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{description}",
+              oninput: move |e| description.set(e.value()),
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Ranking" }
+            select {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              onchange: move |e| {
+                  scoring
+                      .set(
+                          match e.value().as_str() {
+                              "availability" => ScoringStrategyKind::AvailabilityFirst,
+                              "size" => ScoringStrategyKind::SizeFirst,
+                              _ => ScoringStrategyKind::QualityFirst,
+                          },
+                      )
+              },
+              option {
+                value: "quality",
+                selected: scoring() == ScoringStrategyKind::QualityFirst,
+                {scoring_label(ScoringStrategyKind::QualityFirst)}
+              }
+              option {
+                value: "availability",
+                selected: scoring() == ScoringStrategyKind::AvailabilityFirst,
+                {scoring_label(ScoringStrategyKind::AvailabilityFirst)}
+              }
+              option {
+                value: "size",
+                selected: scoring() == ScoringStrategyKind::SizeFirst,
+                {scoring_label(ScoringStrategyKind::SizeFirst)}
+              }
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Formats (comma-separated, empty for any)" }
+            input {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{formats}",
+              oninput: move |e| formats.set(e.value()),
+              placeholder: "flac, mp3",
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Minimum album completeness (%)" }
+            input {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{completeness}",
+              oninput: move |e| completeness.set(e.value()),
+              "type": "number",
+            }
+          }
+          div {
+            class: "flex items-center gap-2 cursor-pointer md:pt-6",
+            onclick: move |_| auto_pick.toggle(),
+            Checkbox { is_selected: auto_pick() }
+            label { class: "text-sm cursor-pointer", "Preselect the best album in results" }
+          }
+        }
+        div { class: "flex gap-2",
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+            onclick: save,
+            "Save Profile"
+          }
+          button {
+            class: "text-gray-300 hover:text-white py-2 px-4",
+            onclick: move |_| props.on_cancel.call(()),
+            "Cancel"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400", "{error}" }
+        }
+      }
+    }
+}
+
+/// Lists the quality profiles and lets users clone presets and edit their own.
+#[component]
+pub fn QualityProfiles() -> Element {
+    let auth = use_auth();
+    let mut profiles = use_signal(Vec::<QualityProfile>::new);
+    let mut editing = use_signal::<Option<QualityProfile>>(|| None);
+    let mut error = use_signal(String::new);
+
+    let fetch = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_quality_profiles(token).await {
+                Ok(fetched) => profiles.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch quality profiles: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch().await;
+    });
+
+    let delete = move |id: String| async move {
+        if let Some(token) = auth.token() {
+            match api::delete_quality_profile(token, id).await {
+                Ok(()) => fetch().await,
+                Err(e) => error.set(format!("Failed to delete profile: {e}")),
+            }
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Quality Profiles" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "Profiles bundle how sources are ranked, which formats are kept and how complete albums must be. Pick one when searching. Built-in presets can be cloned and tweaked."
+        }
+        if let Some(profile) = editing() {
+          ProfileEditor {
+            key: "{profile.id}-{profile.name}",
+            profile,
+            on_saved: move |_| async move {
+                editing.set(None);
+                fetch().await;
+            },
+            on_cancel: move |_| editing.set(None),
+          }
+        }
+        ul { class: "space-y-2",
+          for profile in profiles.read().iter().cloned() {
+            li {
+              key: "{profile.id}",
+              class: "bg-gray-700 p-3 rounded flex justify-between items-center gap-2",
+              div { class: "flex-grow",
+                p { class: "font-medium text-teal-200",
+                  "{profile.name}"
+                  if profile.built_in {
+                    span { class: "ml-2 text-xs text-gray-400", "Preset" }
+                  }
+                }
+                p { class: "text-gray-400 text-sm", "{profile.description}" }
+              }
+              button {
+                class: "text-gray-400 hover:text-teal-300 text-sm",
+                onclick: {
+                    let profile = profile.clone();
+                    move |_| {
+                        editing
+                            .set(
+                                Some(QualityProfile {
+                                    id: String::new(),
+                                    name: format!("{} (copy)", profile.name),
+                                    built_in: false,
+                                    ..profile.clone()
+                                }),
+                            )
+                    }
+                },
+                "Clone"
+              }
+              if !profile.built_in {
+                button {
+                  class: "text-gray-400 hover:text-teal-300 text-sm",
+                  onclick: {
+                      let profile = profile.clone();
+                      move |_| editing.set(Some(profile.clone()))
+                  },
+                  "Edit"
+                }
+                button {
+                  class: "text-gray-400 hover:text-red-400 text-sm",
+                  onclick: {
+                      let id = profile.id.clone();
+                      move |_| delete(id.clone())
+                  },
+                  "Remove"
+                }
+              }
+            }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,17 @@
+use dioxus::prelude::*;
+
+/// Placeholder shown while the code of a lazily loaded page is fetched.
+#[component]
+pub fn RouteSkeleton() -> Element {
+    rsx! {
+      div { class: "container mx-auto p-4 animate-pulse",
+        div { class: "h-8 w-48 bg-gray-700 rounded mb-8" }
+        div { class: "bg-gray-800 p-6 rounded-lg shadow-lg space-y-4",
+          div { class: "h-5 w-1/3 bg-gray-700 rounded" }
+          div { class: "h-4 w-full bg-gray-700 rounded" }
+          div { class: "h-4 w-5/6 bg-gray-700 rounded" }
+          div { class: "h-4 w-2/3 bg-gray-700 rounded" }
+        }
+      }
+    }
+}
@@ -0,0 +1,244 @@
+use dioxus::prelude::*;
+use shared::{
+    musicbrainz::EDITION_KEYWORDS,
+    slskd::{BitrateBonus, ScoringConfig, ScoringWeights, DEFAULT_SUFFIX_PENALTY},
+};
+
+use crate::{use_auth, Checkbox};
+
+const INPUT_CLASS: &str =
+    "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none";
+
+#[derive(Props, PartialEq, Clone)]
+struct WeightInputProps {
+    label: &'static str,
+    value: f64,
+    #[props(into)]
+    on_change: EventHandler<f64>,
+}
+
+#[component]
+fn WeightInput(props: WeightInputProps) -> Element {
+    rsx! {
+      div {
+        label { class: "block text-sm font-medium mb-1", {props.label} }
+        input {
+          class: INPUT_CLASS,
+          "type": "number",
+          min: "0",
+          step: "0.05",
+          value: "{props.value}",
+          onchange: move |e: FormEvent| {
+              if let Ok(value) = e.value().parse() {
+                  props.on_change.call(value);
+              }
+          },
+        }
+      }
+    }
+}
+
+/// The user's adjustments to how albums are ranked, on top of every quality
+/// profile: weights of the album score, match threshold and bitrate bonuses.
+#[component]
+pub fn ScoringSettings() -> Element {
+    let auth = use_auth();
+    let mut config = use_signal(ScoringConfig::default);
+    let mut error = use_signal(String::new);
+    let mut success_msg = use_signal(String::new);
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_scoring_config(token).await {
+                Ok(fetched) => config.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch scoring settings: {e}")),
+            }
+        }
+    });
+
+    let save = move |_| async move {
+        error.set(String::new());
+        success_msg.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::save_scoring_config(token, config()).await {
+            Ok(saved) => {
+                config.set(saved);
+                success_msg.set("Scoring settings saved".to_string());
+            }
+            Err(e) => error.set(e.to_string()),
+        }
+    };
+
+    let weights = config().weights;
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Album Scoring" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "How search results are ranked, whichever profile you search with. Favour the format to get lossless first, or the match to get the right album first."
+        }
+        div {
+          class: "flex items-center gap-2 cursor-pointer mb-4",
+          onclick: move |_| {
+              let custom = config.read().weights.is_some();
+              config.write().weights = (!custom).then(ScoringWeights::default);
+          },
+          Checkbox { is_selected: weights.is_some() }
+          label { class: "cursor-pointer text-sm", "Custom weights, instead of the profile's" }
+        }
+        if let Some(weights) = weights {
+          div { class: "grid grid-cols-2 md:grid-cols-4 gap-4 mb-4",
+            WeightInput {
+              label: "Match",
+              value: weights.match_score,
+              on_change: move |value| {
+                  if let Some(w) = config.write().weights.as_mut() {
+                      w.match_score = value;
+                  }
+              },
+            }
+            WeightInput {
+              label: "Completeness",
+              value: weights.completeness,
+              on_change: move |value| {
+                  if let Some(w) = config.write().weights.as_mut() {
+                      w.completeness = value;
+                  }
+              },
+            }
+            WeightInput {
+              label: "Format",
+              value: weights.format,
+              on_change: move |value| {
+                  if let Some(w) = config.write().weights.as_mut() {
+                      w.format = value;
+                  }
+              },
+            }
+            WeightInput {
+              label: "Availability",
+              value: weights.availability,
+              on_change: move |value| {
+                  if let Some(w) = config.write().weights.as_mut() {
+                      w.availability = value;
+                  }
+              },
+            }
+          }
+        }
+        div { class: "mb-4 md:w-1/2",
+          label { class: "block text-sm font-medium mb-1", "Minimum match score" }
+          input {
+            class: INPUT_CLASS,
+            "type": "number",
+            min: "0",
+            max: "1",
+            step: "0.05",
+            placeholder: "0.6",
+            value: config().min_match_score.map(|s| s.to_string()).unwrap_or_default(),
+            onchange: move |e: FormEvent| config.write().min_match_score = e.value().parse().ok(),
+          }
+        }
+        div { class: "mb-4",
+          label { class: "block text-sm font-medium mb-1", "Title suffixes to ignore" }
+          p { class: "text-gray-400 text-xs mb-2",
+            "A bracketed or dashed part at the end of a track or album title holding one of these is left out when matching files, as in 'Song (2011 Remaster)'. Always ignored: "
+            {EDITION_KEYWORDS.join(", ")}
+            "."
+          }
+          input {
+            class: INPUT_CLASS,
+            placeholder: "e.g. single version, mono",
+            value: config().title_suffixes.join(", "),
+            onchange: move |e: FormEvent| {
+                config.write().title_suffixes = e
+                    .value()
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+            },
+          }
+        }
+        div { class: "mb-4 md:w-1/2",
+          label { class: "block text-sm font-medium mb-1", "Suffix penalty" }
+          p { class: "text-gray-400 text-xs mb-2",
+            "Share of the match score lost when only the file's title or the searched one has such a suffix."
+          }
+          input {
+            class: INPUT_CLASS,
+            "type": "number",
+            min: "0",
+            max: "1",
+            step: "0.01",
+            placeholder: "{DEFAULT_SUFFIX_PENALTY}",
+            value: config().suffix_penalty.map(|s| s.to_string()).unwrap_or_default(),
+            onchange: move |e: FormEvent| config.write().suffix_penalty = e.value().parse().ok(),
+          }
+        }
+        h3 { class: "text-sm font-medium mb-1", "Bitrate bonuses" }
+        p { class: "text-gray-400 text-xs mb-2",
+          "Added to the score of files at or above the bitrate, the highest one reached counts. Negative bonuses penalise."
+        }
+        ul { class: "space-y-2 mb-2",
+          for (i , bonus) in config().bitrate_bonuses.into_iter().enumerate() {
+            li { key: "{i}", class: "flex items-center gap-2 text-sm",
+              input {
+                class: INPUT_CLASS,
+                "type": "number",
+                min: "1",
+                value: "{bonus.min_bitrate}",
+                onchange: move |e: FormEvent| {
+                    if let Ok(bitrate) = e.value().parse() {
+                        config.write().bitrate_bonuses[i].min_bitrate = bitrate;
+                    }
+                },
+              }
+              span { class: "text-gray-400", "kbps" }
+              input {
+                class: INPUT_CLASS,
+                "type": "number",
+                step: "0.05",
+                value: "{bonus.bonus}",
+                onchange: move |e: FormEvent| {
+                    if let Ok(value) = e.value().parse() {
+                        config.write().bitrate_bonuses[i].bonus = value;
+                    }
+                },
+              }
+              button {
+                class: "text-gray-400 hover:text-red-400",
+                onclick: move |_| {
+                    config.write().bitrate_bonuses.remove(i);
+                },
+                "Remove"
+              }
+            }
+          }
+        }
+        div { class: "flex gap-2",
+          button {
+            class: "bg-gray-600 hover:bg-gray-500 text-white py-2 px-4 rounded transition-colors",
+            onclick: move |_| {
+                let bonus = BitrateBonus { min_bitrate: 320, bonus: 0.1 };
+                config.write().bitrate_bonuses.push(bonus);
+            },
+            "Add bonus"
+          }
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+            onclick: save,
+            "Save"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+        if !success_msg().is_empty() {
+          p { class: "text-sm text-teal-400 mt-2", "{success_msg}" }
+        }
+      }
+    }
+}
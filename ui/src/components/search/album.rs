@@ -1,18 +1,21 @@
 use dioxus::prelude::*;
 use shared::musicbrainz::{Album, Track};
 
-use crate::CoverArt;
+use crate::{CoverArt, GenreChips};
 
 #[derive(Props, PartialEq, Clone)]
 pub struct Props {
     pub album: Album,
     pub on_click: EventHandler<String>,
+    /// Opens the discography of the album's artist.
+    pub on_artist_click: EventHandler<String>,
 }
 
 #[component]
 pub fn AlbumResult(props: Props) -> Element {
     let album_id = props.album.id.clone();
     let album = &props.album;
+    let artist = album.artist.clone();
 
     let cover_art_url = format!("https://coverartarchive.org/release/{}/front-250", album_id);
     let alt_text = format!("Album cover for {}", album.title);
@@ -26,10 +29,21 @@ pub fn AlbumResult(props: Props) -> Element {
 
         div { class: "flex-grow flex flex-col justify-center",
           h5 { class: "text-lg font-bold text-indigo-300", "{album.title}" }
-          p { class: "text-md text-gray-300", "{album.artist}" }
+          p { class: "text-md text-gray-300",
+            "{album.artist}"
+            button {
+              class: "ml-2 text-xs text-teal-300 hover:text-teal-200",
+              onclick: move |e| {
+                  e.stop_propagation();
+                  props.on_artist_click.call(artist.clone());
+              },
+              "Discography"
+            }
+          }
           if let Some(release_date) = &album.release_date {
             p { class: "text-sm text-gray-400 mt-1", "{release_date}" }
           }
+          GenreChips { genres: album.genres.clone() }
         }
       }
     }
@@ -0,0 +1,35 @@
+use dioxus::prelude::*;
+use shared::musicbrainz::Artist;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub artist: Artist,
+    /// Opens the artist's discography.
+    pub on_click: EventHandler<Artist>,
+}
+
+#[component]
+pub fn ArtistResult(props: Props) -> Element {
+    let artist = props.artist.clone();
+    let details = [Some(artist.disambiguation.clone()), artist.country.clone()]
+        .into_iter()
+        .flatten()
+        .filter(|detail| !detail.is_empty())
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    rsx! {
+      div {
+        onclick: move |_| props.on_click.call(props.artist.clone()),
+        class: "bg-gray-700 p-4 rounded-lg shadow-md hover:bg-gray-600 transition-colors duration-200 cursor-pointer flex justify-between items-center",
+
+        div {
+          h5 { class: "text-lg font-bold text-teal-300", "{artist.name}" }
+          if !details.is_empty() {
+            p { class: "text-sm text-gray-400", "{details}" }
+          }
+        }
+        span { class: "text-xs text-teal-300 pl-4 whitespace-nowrap", "Discography" }
+      }
+    }
+}
@@ -0,0 +1,176 @@
+use api::AvailabilityReport;
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+
+use crate::use_auth;
+
+/// Probe budgets offered, in searches.
+const BUDGETS: [usize; 4] = [5, 10, 20, 30];
+
+/// Formats found in the report, the most shared first.
+fn report_formats(report: &AvailabilityReport) -> Vec<String> {
+    let mut totals: Vec<(String, usize)> = Vec::new();
+    for formats in report.releases.iter().filter_map(|r| r.formats.as_ref()) {
+        for (format, users) in formats {
+            match totals.iter_mut().find(|(f, _)| f == format) {
+                Some((_, total)) => *total += users,
+                None => totals.push((format.clone(), *users)),
+            }
+        }
+    }
+    totals.sort_by(|a, b| b.1.cmp(&a.1));
+    totals.into_iter().map(|(format, _)| format).collect()
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub artist: String,
+}
+
+/// Which of the artist's albums Soulseek users share, in which formats, from
+/// a few short searches, to plan downloads before spending real searches.
+#[component]
+pub fn AvailabilityReportView(props: Props) -> Element {
+    let auth = use_auth();
+    let mut report = use_signal::<Option<AvailabilityReport>>(|| None);
+    let mut budget = use_signal(|| 10usize);
+    let mut error = use_signal(String::new);
+
+    // Follows the report while it runs, picking up one started earlier.
+    let artist = props.artist.clone();
+    use_future(move || {
+        let artist = artist.clone();
+        async move {
+            loop {
+                if let Some(token) = auth.token() {
+                    if let Ok(fetched) = api::get_availability_report(token).await {
+                        report.set(fetched.filter(|r| r.artist.eq_ignore_ascii_case(&artist)));
+                    }
+                }
+                TimeoutFuture::new(3_000).await;
+            }
+        }
+    });
+
+    let artist = props.artist.clone();
+    let start = move |_| {
+        let artist = artist.clone();
+        async move {
+            error.set(String::new());
+            let Some(token) = auth.token() else {
+                return;
+            };
+            match api::start_availability_report(token, artist, budget()).await {
+                Ok(started) => report.set(Some(started)),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let cancel = move |_| async move {
+        if let Some(token) = auth.token() {
+            match api::cancel_availability_report(token).await {
+                Ok(()) => report.set(None),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let running = report().is_some_and(|r| !r.done);
+
+    rsx! {
+      div { class: "border-t border-gray-700 pt-4",
+        h4 { class: "font-semibold text-indigo-300 mb-1", "Availability report" }
+        p { class: "text-sm text-gray-400 mb-3",
+          "Runs a short search per album to see which can be found, and in which formats. Each counts against the search rate limit, albums checked in the last hours are reused for free."
+        }
+        div { class: "flex items-center gap-2 mb-3",
+          select {
+            class: "p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            disabled: running,
+            onchange: move |e| budget.set(e.value().parse().unwrap_or(10)),
+            for b in BUDGETS {
+              option { value: "{b}", selected: budget() == b, "Up to {b} searches" }
+            }
+          }
+          if running {
+            button {
+              class: "text-sm text-gray-400 hover:text-red-400",
+              onclick: cancel,
+              "Stop"
+            }
+          } else {
+            button {
+              class: "bg-indigo-600 hover:bg-indigo-700 text-white text-sm font-bold py-2 px-4 rounded transition-colors disabled:bg-gray-600",
+              disabled: auth.is_demo(),
+              onclick: start,
+              "Check availability"
+            }
+          }
+        }
+        if let Some(report) = report() {
+          {
+              let formats = report_formats(&report);
+              rsx! {
+                p { class: "text-xs text-gray-400 mb-2",
+                  "{report.probes_used} of {report.budget} searches used"
+                  if !report.done {
+                    ", checking..."
+                  }
+                }
+                if let Some(error) = &report.error {
+                  p { class: "text-sm text-red-400 mb-2", "{error}" }
+                }
+                table { class: "w-full text-sm",
+                  thead {
+                    tr { class: "text-left text-gray-400",
+                      th { class: "font-normal pb-1", "Album" }
+                      for format in formats.iter() {
+                        th { class: "font-normal pb-1 text-right", "{format}" }
+                      }
+                    }
+                  }
+                  tbody {
+                    for release in report.releases.iter() {
+                      tr { key: "{release.album.id}", class: "border-t border-gray-700",
+                        td { class: "py-1",
+                          "{release.album.title}"
+                          if let Some(date) = &release.album.release_date {
+                            span { class: "text-gray-400 text-xs ml-2", "{date}" }
+                          }
+                        }
+                        match &release.formats {
+                            Some(found) if found.is_empty() => rsx! {
+                              td { class: "py-1 text-right text-red-400", colspan: "{formats.len().max(1)}", "Not found" }
+                            },
+                            Some(found) => rsx! {
+                              for format in formats.iter() {
+                                td { class: "py-1 text-right",
+                                  title: "Users sharing it as {format}",
+                                  {found.get(format).map(|n| n.to_string()).unwrap_or_else(|| "-".to_string())}
+                                }
+                              }
+                            },
+                            None => rsx! {
+                              td { class: "py-1 text-right text-gray-500", colspan: "{formats.len().max(1)}",
+                                if report.done {
+                                  "Not checked"
+                                } else {
+                                  "..."
+                                }
+                              }
+                            },
+                        }
+                      }
+                    }
+                  }
+                }
+              }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
@@ -0,0 +1,101 @@
+use dioxus::prelude::*;
+use shared::slskd::SharedDirectory;
+
+use crate::use_auth;
+
+#[derive(Props, PartialEq, Clone)]
+struct DirectoryNodeProps {
+    directory: SharedDirectory,
+}
+
+/// A folder of the browsed shares, collapsed until clicked.
+#[component]
+fn DirectoryNode(props: DirectoryNodeProps) -> Element {
+    let mut expanded = use_signal(|| false);
+    let directory = props.directory.clone();
+    let total = directory.total_files();
+
+    rsx! {
+      li {
+        div {
+          class: "flex items-center gap-2 p-1 rounded hover:bg-gray-700 cursor-pointer",
+          onclick: move |_| expanded.toggle(),
+          span { class: "text-gray-400 w-3", if expanded() { "▾" } else { "▸" } }
+          span { class: "flex-grow truncate", "{directory.name}" }
+          span { class: "text-xs text-gray-400", "{total} files" }
+        }
+        if expanded() {
+          ul { class: "ml-4 border-l border-gray-700 pl-2",
+            for child in directory.children.iter().cloned() {
+              DirectoryNode { key: "{child.path}", directory: child }
+            }
+            for file in directory.files.iter() {
+              li {
+                key: "{file.filename}",
+                class: "flex justify-between gap-2 p-1 text-sm text-gray-300",
+                span { class: "truncate",
+                  {file.filename.rsplit('\\').next().unwrap_or_default()}
+                }
+                span { class: "text-xs text-gray-500 whitespace-nowrap",
+                  if let Some(bitrate) = file.bitrate {
+                    "{bitrate} kbps · "
+                  }
+                  "{file.size / (1024 * 1024)} MB"
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub username: String,
+}
+
+/// Everything a Soulseek user shares, as a folder tree. The best source for
+/// one album often has the rest of the discography too.
+#[component]
+pub fn UserShares(props: Props) -> Element {
+    let auth = use_auth();
+    let mut directories = use_signal::<Option<Vec<SharedDirectory>>>(|| None);
+    let mut error = use_signal(String::new);
+
+    let username = props.username.clone();
+    use_future(move || {
+        let username = username.clone();
+        async move {
+            let Some(token) = auth.token() else {
+                return;
+            };
+            match api::browse_user_shares(token, username).await {
+                Ok(fetched) => directories.set(Some(fetched)),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    });
+
+    rsx! {
+      div { class: "text-white",
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400", "{error}" }
+        } else if let Some(directories) = directories() {
+          if directories.is_empty() {
+            p { class: "text-gray-400", "{props.username} shares nothing." }
+          }
+          ul { class: "space-y-1",
+            for directory in directories {
+              DirectoryNode { key: "{directory.path}", directory }
+            }
+          }
+        } else {
+          div { class: "flex flex-col items-center py-6 gap-2",
+            div { class: "animate-spin rounded-full h-10 w-10 border-t-4 border-b-4 border-teal-500" }
+            p { class: "text-sm text-gray-400", "Fetching the file list, large shares take a while..." }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,101 @@
+use dioxus::prelude::*;
+use shared::slskd::AlbumResult;
+
+use super::download_results::format_eta;
+use crate::ScoreBar;
+
+/// Albums that can be pinned for comparison at once.
+pub const MAX_PINNED: usize = 3;
+
+fn completeness(album: &AlbumResult) -> String {
+    if album.expected_tracks > 0 {
+        format!("{} of {} tracks", album.track_count, album.expected_tracks)
+    } else {
+        format!("{} tracks", album.track_count)
+    }
+}
+
+fn reliability(album: &AlbumResult) -> String {
+    match album.uploader_reliability {
+        Some(share) => format!("{:.0}% of files arrived", share * 100.0),
+        None => "Never downloaded from".to_string(),
+    }
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub albums: Vec<AlbumResult>,
+    #[props(into)]
+    pub on_unpin: EventHandler<AlbumResult>,
+    #[props(into)]
+    pub on_select_all: EventHandler<AlbumResult>,
+}
+
+/// The pinned albums side by side, to make the final pick without scrolling
+/// back and forth between their cards.
+#[component]
+pub fn ComparisonTray(props: Props) -> Element {
+    let rows: [(&str, fn(&AlbumResult) -> String); 5] = [
+        ("Size", |a| format!("{} MB", a.size_mb())),
+        ("Format", |a| match a.resolution() {
+            Some(resolution) => format!("{} {resolution}", a.dominant_quality),
+            None => a.dominant_quality.to_string(),
+        }),
+        ("Completeness", completeness),
+        ("ETA", |a| a.eta_seconds.map(format_eta).unwrap_or_default()),
+        ("Uploader", reliability),
+    ];
+
+    rsx! {
+        div { class: "sticky top-0 z-10 bg-gray-900 border border-teal-700 p-4 rounded-md mb-4 overflow-x-auto",
+            table { class: "w-full text-sm text-left",
+                thead {
+                    tr {
+                        th {}
+                        for album in props.albums.iter().cloned() {
+                            th { class: "align-top p-1 font-normal",
+                                p { class: "font-bold truncate", "{album.album_title}" }
+                                p { class: "text-xs text-gray-400 truncate", "{album.username}" }
+                                button {
+                                    class: "text-xs text-gray-400 hover:text-white",
+                                    onclick: move |_| props.on_unpin.call(album.clone()),
+                                    "Unpin"
+                                }
+                            }
+                        }
+                    }
+                }
+                tbody {
+                    tr {
+                        td { class: "p-1 text-gray-400", "Score" }
+                        for album in props.albums.iter() {
+                            td { class: "p-1",
+                                ScoreBar { score: album.score }
+                            }
+                        }
+                    }
+                    for (label , value) in rows {
+                        tr {
+                            td { class: "p-1 text-gray-400", "{label}" }
+                            for album in props.albums.iter() {
+                                td { class: "p-1", {value(album)} }
+                            }
+                        }
+                    }
+                    tr {
+                        td {}
+                        for album in props.albums.iter().cloned() {
+                            td { class: "p-1",
+                                button {
+                                    class: "bg-teal-600 hover:bg-teal-700 text-white font-semibold py-1 px-3 rounded-md text-xs transition-colors duration-300",
+                                    onclick: move |_| props.on_select_all.call(album.clone()),
+                                    "Select All"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
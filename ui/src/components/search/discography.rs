@@ -0,0 +1,293 @@
+use api::{
+    db::{AlbumRequest, AlbumRequestState, FollowedArtist},
+    get_album_requests,
+};
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use shared::musicbrainz::{Album, Artist};
+use std::collections::{HashMap, HashSet};
+
+use super::availability_report::AvailabilityReportView;
+use crate::{use_auth, Button, Checkbox};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub artist: String,
+    /// MusicBrainz id of the artist, to list their albums by id rather than
+    /// by name when they were picked from an artist search.
+    pub artist_id: Option<String>,
+    /// Quality profile the queued searches run with.
+    pub profile_id: String,
+}
+
+fn state_badge(request: &AlbumRequest) -> (&'static str, &'static str, String) {
+    let (class, label) = match request.state {
+        AlbumRequestState::Pending => ("text-gray-400", "Pending"),
+        AlbumRequestState::Searching => ("text-yellow-400", "Searching"),
+        AlbumRequestState::Done => ("text-green-400", "Queued for download"),
+        AlbumRequestState::Failed => ("text-red-400", "Failed"),
+    };
+    (class, label, request.error.clone().unwrap_or_default())
+}
+
+/// An artist's studio albums, to queue several of them at once. The albums are
+/// then searched one after the other on the server, and their progress shows
+/// next to each of them.
+#[component]
+pub fn Discography(props: Props) -> Element {
+    let auth = use_auth();
+    let mut albums = use_signal(Vec::<Album>::new);
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut requests = use_signal(HashMap::<String, AlbumRequest>::new);
+    let mut folders = use_signal(Vec::new);
+    let mut selected_folder = use_signal(String::new);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(String::new);
+
+    let artist = props.artist.clone();
+    let artist_id = props.artist_id.clone();
+    use_future(move || {
+        let artist = artist.clone();
+        let artist_id = artist_id.clone();
+        async move {
+            let fetched = match artist_id {
+                Some(id) => api::get_artist_releases(id).await,
+                None => api::get_artist_albums(artist).await,
+            };
+            match fetched {
+                Ok(fetched) => albums.set(fetched),
+                Err(e) => error.set(e.to_string()),
+            }
+            loading.set(false);
+        }
+    });
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            if let Ok(user_folders) = api::get_user_folders(token).await {
+                if let Some(first) = user_folders.first() {
+                    selected_folder.set(first.path.clone());
+                }
+                folders.set(user_folders);
+            }
+        }
+    });
+
+    // Latest request of each album, refreshed every few seconds.
+    let refresh = move || async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        if let Ok(fetched) = get_album_requests(token).await {
+            let mut latest = HashMap::new();
+            // Latest first, so the first request seen for an album wins.
+            for request in fetched {
+                latest.entry(request.album_id.clone()).or_insert(request);
+            }
+            requests.set(latest);
+        }
+    };
+
+    use_future(move || async move {
+        loop {
+            refresh().await;
+            TimeoutFuture::new(3_000).await;
+        }
+    });
+
+    let profile_id = props.profile_id.clone();
+    let queue = move |_| {
+        let profile_id = profile_id.clone();
+        async move {
+            error.set(String::new());
+            let Some(token) = auth.token() else {
+                return;
+            };
+            let chosen: Vec<Album> = albums
+                .read()
+                .iter()
+                .filter(|a| selected.read().contains(&a.id))
+                .cloned()
+                .collect();
+            match api::queue_albums(token, chosen, selected_folder(), Some(profile_id)).await {
+                Ok(_) => {
+                    selected.write().clear();
+                    refresh().await;
+                }
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let cancel = move |id: String| async move {
+        if let Some(token) = auth.token() {
+            match api::cancel_album_request(token, id).await {
+                Ok(()) => refresh().await,
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    // The follow of this artist, when the user follows them.
+    let mut followed = use_signal::<Option<FollowedArtist>>(|| None);
+    let mut auto_download = use_signal(|| false);
+
+    let artist_id = props.artist_id.clone();
+    use_future(move || {
+        let artist_id = artist_id.clone();
+        async move {
+            let (Some(artist_id), Some(token)) = (artist_id, auth.token()) else {
+                return;
+            };
+            if let Ok(follows) = api::get_followed_artists(token).await {
+                followed.set(follows.into_iter().find(|f| f.artist_id == artist_id));
+            }
+        }
+    });
+
+    let artist = props.artist_id.clone().map(|id| Artist {
+        id,
+        name: props.artist.clone(),
+        disambiguation: String::new(),
+        country: None,
+    });
+    let profile_id = props.profile_id.clone();
+    let follow = move |_| {
+        let (artist, profile_id) = (artist.clone(), profile_id.clone());
+        async move {
+            error.set(String::new());
+            let (Some(artist), Some(token)) = (artist, auth.token()) else {
+                return;
+            };
+            let folder = Some(selected_folder()).filter(|f| !f.is_empty());
+            match api::follow_artist(token, artist, auto_download(), folder, Some(profile_id)).await
+            {
+                Ok(follow) => followed.set(Some(follow)),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let unfollow = move |id: String| async move {
+        if let Some(token) = auth.token() {
+            match api::unfollow_artist(token, id).await {
+                Ok(()) => followed.set(None),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let count = selected.read().len();
+
+    rsx! {
+      div { class: "space-y-4",
+        if props.artist_id.is_some() {
+          div { class: "flex items-center gap-3 text-sm",
+            if let Some(follow) = followed() {
+              span { class: "text-teal-300",
+                if follow.auto_download {
+                  "Following, new albums are downloaded"
+                } else {
+                  "Following, new albums go to your wanted list"
+                }
+              }
+              button {
+                class: "text-gray-400 hover:text-red-400",
+                onclick: {
+                    let id = follow.id.clone();
+                    move |_| unfollow(id.clone())
+                },
+                "Unfollow"
+              }
+            } else {
+              Button {
+                disabled: auth.is_demo() || (auto_download() && selected_folder.read().is_empty()),
+                onclick: follow,
+                "Follow"
+              }
+              div {
+                class: "flex items-center gap-2 cursor-pointer",
+                title: "Queue new albums for download into the folder below once they are released",
+                onclick: move |_| auto_download.toggle(),
+                Checkbox { is_selected: auto_download() }
+                label { class: "text-gray-400 cursor-pointer", "Download new albums" }
+              }
+            }
+          }
+        }
+        if loading() {
+          div { class: "flex justify-center py-6",
+            div { class: "animate-spin rounded-full h-10 w-10 border-t-4 border-b-4 border-teal-500" }
+          }
+        } else if albums.read().is_empty() && error().is_empty() {
+          p { class: "text-gray-400", "No studio albums found for {props.artist}." }
+        }
+        ul { class: "space-y-1",
+          for album in albums.read().iter().cloned() {
+            li {
+              key: "{album.id}",
+              class: "flex items-center gap-2 p-1 rounded-md hover:bg-gray-700 cursor-pointer",
+              onclick: {
+                  let id = album.id.clone();
+                  move |_| {
+                      let mut selected = selected.write();
+                      if !selected.remove(&id) {
+                          selected.insert(id.clone());
+                      }
+                  }
+              },
+              Checkbox { is_selected: selected.read().contains(&album.id) }
+              span { class: "flex-grow",
+                "{album.title}"
+                if let Some(date) = &album.release_date {
+                  span { class: "text-gray-400 text-sm ml-2", "{date}" }
+                }
+              }
+              if let Some((class, label, title)) = requests.read().get(&album.id).map(state_badge) {
+                span { class: "text-xs {class}", title: "{title}", "{label}" }
+              }
+              if let Some(request) = requests
+                  .read()
+                  .get(&album.id)
+                  .filter(|r| r.state == AlbumRequestState::Pending)
+                  .cloned()
+              {
+                button {
+                  class: "text-xs text-gray-400 hover:text-red-400",
+                  onclick: move |e| {
+                      e.stop_propagation();
+                      cancel(request.id.clone())
+                  },
+                  "Cancel"
+                }
+              }
+            }
+          }
+        }
+        if !albums.read().is_empty() {
+          div { class: "flex gap-2 items-center",
+            select {
+              class: "flex-grow p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{selected_folder}",
+              onchange: move |e| selected_folder.set(e.value()),
+              for folder in folders.read().iter() {
+                option { value: "{folder.path}", "{folder.name}" }
+              }
+            }
+            Button {
+              disabled: selected.read().is_empty() || selected_folder.read().is_empty()
+                  || auth.is_demo(),
+              onclick: queue,
+              "Queue {count} albums"
+            }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400", "{error}" }
+        }
+        if !albums.read().is_empty() {
+          AvailabilityReportView { artist: props.artist.clone() }
+        }
+      }
+    }
+}
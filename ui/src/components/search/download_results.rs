@@ -1,28 +1,81 @@
 use dioxus::logger::tracing::info;
 use dioxus::prelude::*;
-use shared::slskd::{AlbumResult, TrackResult};
+use shared::slskd::{AlbumLayout, AlbumResult, TrackResult};
 use std::collections::HashSet;
 
-use crate::{use_auth, Checkbox};
+use api::DownloadOptions;
+
+use super::browse::UserShares;
+use super::comparison::{ComparisonTray, MAX_PINNED};
+use super::track_details::TrackDetails;
+use crate::{use_auth, Checkbox, Modal, ScoreBar};
+
+/// Sources kept per track to retry with when a download fails.
+const MAX_ALTERNATES: usize = 3;
+
+/// How download options are ordered.
+#[derive(Clone, Copy, PartialEq)]
+enum SortOrder {
+    BestScore,
+    FastestFirst,
+}
+
+/// Formats an ETA in seconds as a rough duration, e.g. "~3 min" or "~2 h 10 min".
+pub(super) fn format_eta(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).ceil() as u64;
+    match minutes {
+        0..=1 => "~1 min".to_string(),
+        2..=59 => format!("~{minutes} min"),
+        _ if minutes % 60 == 0 => format!("~{} h", minutes / 60),
+        _ => format!("~{} h {} min", minutes / 60, minutes % 60),
+    }
+}
 
 #[derive(Props, PartialEq, Clone)]
 pub struct Props {
     pub results: Vec<AlbumResult>,
+    /// Preselect the tracks of the best scoring album.
+    #[props(default)]
+    pub auto_pick: bool,
     #[props(into)]
-    pub on_download: EventHandler<(Vec<TrackResult>, String)>,
+    pub on_download: EventHandler<(Vec<TrackResult>, String, DownloadOptions)>,
 }
 
 #[derive(Props, Clone, PartialEq)]
 struct AlbumResultItemProps {
     album: AlbumResult,
     selected_tracks: Signal<HashSet<String>>,
+    pinned: bool,
+    /// Another album can be pinned, there are fewer than [`MAX_PINNED`].
+    can_pin: bool,
+    on_pin_toggle: EventHandler<AlbumResult>,
     on_album_select_all: EventHandler<AlbumResult>,
     on_track_toggle: EventHandler<String>,
+    on_browse: EventHandler<String>,
+    on_block: EventHandler<String>,
 }
 
 #[component]
 fn AlbumResultItem(props: AlbumResultItemProps) -> Element {
     let album = props.album.clone();
+    let username = album.username.clone();
+    let blocked_username = album.username.clone();
+    let pinned_album = album.clone();
+    let mut expanded = use_signal::<Option<String>>(|| None);
+    let companion_names = album
+        .companions
+        .iter()
+        .map(|f| f.filename.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let companions_label = [
+        (!album.artwork().is_empty()).then_some("artwork"),
+        album.has_rip_log().then_some("rip log"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" and ");
 
     rsx! {
         div { key: "{album.album_path}", class: "bg-gray-700 p-4 rounded-md",
@@ -30,25 +83,127 @@ fn AlbumResultItem(props: AlbumResultItemProps) -> Element {
                 div { class: "flex-grow",
                     h4 { class: "text-md font-bold", "{album.album_title}" }
                     p { class: "text-sm text-gray-400",
-                        "{album.artist.clone().unwrap_or_default()} - Quality: {album.dominant_quality}, Score: {album.score:.2}"
+                        "{album.artist.clone().unwrap_or_default()} - Quality: {album.dominant_quality}"
+                        if let Some(resolution) = album.resolution() {
+                            span { title: "Bit depth and sample rate reported by the uploader",
+                                " {resolution}"
+                            }
+                        }
+                        if let Some(eta) = album.eta_seconds {
+                            span {
+                                title: "Estimated from the uploader's speed, queue and past downloads",
+                                " - ETA {format_eta(eta)}"
+                            }
+                        }
+                    }
+                    if !album.companions.is_empty() {
+                        p { class: "text-xs text-gray-400", title: "{companion_names}",
+                            "With {companions_label}"
+                        }
+                    }
+                    if album.layout == AlbumLayout::CueImage {
+                        p {
+                            class: "text-xs text-indigo-300",
+                            title: "The whole album in one file with a cue sheet, split into tracks before the import",
+                            "Single file + cue"
+                        }
+                    }
+                    if album.ambiguous_matches {
+                        p {
+                            class: "text-xs text-yellow-400",
+                            title: "Several tracks had the same file as their best match, each got a different one or none",
+                            "Similar track titles, check the picks"
+                        }
+                    }
+                    if !album.missing_tracks.is_empty() {
+                        p {
+                            class: "text-xs text-yellow-400",
+                            title: "Requested from other uploaders once this album's files are in, when any has them",
+                            {format!(
+                                "Missing {} of {}: {}",
+                                album.missing_tracks.len(),
+                                album.expected_tracks,
+                                album.missing_tracks.join(", "),
+                            )}
+                        }
+                    }
+                    ScoreBar { score: album.score, label: "Score" }
+                    button {
+                        class: "text-xs text-teal-300 hover:text-teal-200",
+                        title: "See everything this uploader shares",
+                        onclick: move |_| props.on_browse.call(username.clone()),
+                        "Browse {album.username}'s shares"
+                    }
+                    button {
+                        class: "text-xs text-gray-400 hover:text-red-400 ml-3",
+                        title: "Leave this uploader's files out of your searches, undone in Settings",
+                        onclick: move |_| props.on_block.call(blocked_username.clone()),
+                        "Never use this uploader"
                     }
                 }
-                button {
-                    class: "bg-teal-600 hover:bg-teal-700 text-white font-semibold py-1 px-3 rounded-md text-sm transition-colors duration-300",
-                    onclick: move |_| props.on_album_select_all.call(album.clone()),
-                    "Select All"
+                div { class: "flex gap-2",
+                    button {
+                        class: "bg-gray-600 hover:bg-gray-500 text-white py-1 px-3 rounded-md text-sm transition-colors duration-300 disabled:opacity-50 disabled:cursor-not-allowed",
+                        title: "Compare up to {MAX_PINNED} albums side by side",
+                        disabled: !props.pinned && !props.can_pin,
+                        onclick: move |_| props.on_pin_toggle.call(pinned_album.clone()),
+                        if props.pinned {
+                            "Unpin"
+                        } else {
+                            "Pin"
+                        }
+                    }
+                    button {
+                        class: "bg-teal-600 hover:bg-teal-700 text-white font-semibold py-1 px-3 rounded-md text-sm transition-colors duration-300",
+                        onclick: move |_| props.on_album_select_all.call(album.clone()),
+                        "Select All"
+                    }
                 }
             }
             ul { class: "space-y-1",
-                for TrackResult { base , title , .. } in props.album.tracks {
+                for track in props.album.tracks {
                     li {
-                        key: "{base.filename}",
+                        key: "{track.base.filename}",
                         class: "flex items-center gap-2 p-1 rounded-md hover:bg-gray-600 cursor-pointer",
-                        onclick: move |_| props.on_track_toggle.call(base.filename.clone()),
+                        onclick: {
+                            let filename = track.base.filename.clone();
+                            move |_| props.on_track_toggle.call(filename.clone())
+                        },
 
-                        Checkbox { is_selected: props.selected_tracks.read().contains(&base.filename) }
+                        Checkbox { is_selected: props.selected_tracks.read().contains(&track.base.filename) }
 
-                        label { class: "cursor-pointer", "{title}" }
+                        label { class: "cursor-pointer flex-grow",
+                            if let Some(disc) = track.disc_number {
+                                span { class: "text-xs text-gray-400 mr-2", "CD{disc}" }
+                            }
+                            if let Some(number) = track.track_number {
+                                span { class: "text-xs text-gray-400 mr-2", "{number:02}." }
+                            }
+                            "{track.title}"
+                        }
+                        if !track.base.alternate_sources.is_empty() {
+                            span {
+                                class: "text-xs text-gray-400",
+                                title: "Identical copies shared by other users",
+                                "+{track.base.alternate_sources.len()} sources"
+                            }
+                        }
+                        button {
+                            class: "text-xs text-gray-400 hover:text-white",
+                            title: "Path, size, uploader and match scores of this file",
+                            onclick: {
+                                let filename = track.base.filename.clone();
+                                move |e: MouseEvent| {
+                                    e.stop_propagation();
+                                    let open = expanded().as_ref() == Some(&filename);
+                                    expanded.set((!open).then(|| filename.clone()));
+                                }
+                            },
+                            "Details"
+                        }
+                    }
+                    if expanded().as_ref() == Some(&track.base.filename) {
+                        TrackDetails { track: track.clone() }
                     }
                 }
             }
@@ -59,10 +214,51 @@ fn AlbumResultItem(props: AlbumResultItemProps) -> Element {
 /// Main component responsible for displaying all download options.
 #[component]
 pub fn DownloadResults(props: Props) -> Element {
-    let mut selected_tracks = use_signal(HashSet::<String>::new);
-    let results = props.results.clone();
+    let best_pick: HashSet<String> = match props.results.first() {
+        Some(best) if props.auto_pick => best
+            .tracks
+            .iter()
+            .map(|t| t.base.filename.clone())
+            .collect(),
+        _ => HashSet::new(),
+    };
+    let mut selected_tracks = use_signal(move || best_pick);
+    let mut sort_order = use_signal(|| SortOrder::BestScore);
+    let mut browsing = use_signal::<Option<String>>(|| None);
+    // Uploaders blocked from these results, hidden without searching again.
+    let mut blocked = use_signal(HashSet::<String>::new);
+    // Uploader and path of the albums in the comparison tray, in pin order.
+    let mut pinned = use_signal(Vec::<(String, String)>::new);
+    let mut results: Vec<AlbumResult> = props
+        .results
+        .iter()
+        .filter(|a| !blocked.read().contains(&a.username))
+        .cloned()
+        .collect();
+    if sort_order() == SortOrder::FastestFirst {
+        // Stable, so albums with the same ETA keep their score order.
+        results.sort_by(|a, b| {
+            let a = a.eta_seconds.unwrap_or(f64::INFINITY);
+            let b = b.eta_seconds.unwrap_or(f64::INFINITY);
+            a.total_cmp(&b)
+        });
+    }
+    let pinned_albums: Vec<AlbumResult> = pinned
+        .read()
+        .iter()
+        .filter_map(|(username, path)| {
+            results
+                .iter()
+                .find(|a| &a.username == username && &a.album_path == path)
+                .cloned()
+        })
+        .collect();
     let mut folders = use_signal(std::vec::Vec::new);
     let mut selected_folder = use_signal(|| "".to_string());
+    let mut review_before_import = use_signal(|| false);
+    let mut include_artwork = use_signal(|| false);
+    let mut note = use_signal(String::new);
+    let mut labels = use_signal(String::new);
     let auth = use_auth();
 
     use_future(move || async move {
@@ -96,6 +292,16 @@ pub fn DownloadResults(props: Props) -> Element {
         }
     };
 
+    let handle_pin_toggle = move |album: AlbumResult| {
+        let key = (album.username, album.album_path);
+        let mut pinned = pinned.write();
+        if let Some(i) = pinned.iter().position(|k| *k == key) {
+            pinned.remove(i);
+        } else if pinned.len() < MAX_PINNED {
+            pinned.push(key);
+        }
+    };
+
     let handle_track_toggle = move |filename: String| {
         info!("Toggle track selection: {}", filename);
         let mut selected = selected_tracks.write();
@@ -106,21 +312,97 @@ pub fn DownloadResults(props: Props) -> Element {
         }
     };
 
+    // Takes the uploader's files, to unselect them.
+    let handle_block = move |username: String, filenames: HashSet<String>| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::block_uploader(token, username.clone()).await {
+            Ok(_) => {
+                selected_tracks
+                    .write()
+                    .retain(|filename| !filenames.contains(filename));
+                pinned
+                    .write()
+                    .retain(|(pinned_user, _)| *pinned_user != username);
+                blocked.write().insert(username);
+            }
+            Err(e) => info!("Failed to block {}: {}", username, e),
+        }
+    };
+
     let handle_download = move |_| {
         let selected_filenames = selected_tracks.read();
-        let tracks_to_download: Vec<TrackResult> = props
+        let available: Vec<AlbumResult> = props
             .results
+            .iter()
+            .filter(|a| !blocked.read().contains(&a.username))
+            .cloned()
+            .collect();
+        let tracks_to_download: Vec<TrackResult> = available
             .iter()
             .flat_map(|album_result| album_result.tracks.iter())
             .filter(|track| selected_filenames.contains(&track.base.filename))
             .cloned()
             .collect();
+        let artwork: Vec<TrackResult> = if include_artwork() {
+            available
+                .iter()
+                .filter(|album| {
+                    album
+                        .tracks
+                        .iter()
+                        .any(|track| selected_filenames.contains(&track.base.filename))
+                })
+                .flat_map(|album| album.artwork())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // Tracks the picked albums lack, unless another pick covers them.
+        let gap_fills = available
+            .iter()
+            .filter(|album| {
+                album
+                    .tracks
+                    .iter()
+                    .any(|track| selected_filenames.contains(&track.base.filename))
+            })
+            .flat_map(|album| album.gap_sources(&available, MAX_ALTERNATES))
+            .filter(|gap| {
+                !tracks_to_download
+                    .iter()
+                    .any(|track| track.title.eq_ignore_ascii_case(&gap.title))
+            })
+            .collect();
+        let options = DownloadOptions {
+            review_before_import: review_before_import(),
+            note: Some(note()),
+            labels: labels().split(',').map(str::to_string).collect(),
+            alternates: AlbumResult::alternate_sources(
+                &tracks_to_download,
+                &available,
+                MAX_ALTERNATES,
+            ),
+            gap_fills,
+            artwork,
+            ..Default::default()
+        };
         props
             .on_download
-            .call((tracks_to_download, selected_folder()));
+            .call((tracks_to_download, selected_folder(), options));
     };
 
     rsx! {
+        if let Some(username) = browsing() {
+            Modal {
+                on_close: move |_| browsing.set(None),
+                header: rsx! {
+                    h3 { class: "text-xl font-bold text-teal-400", "{username}" }
+                },
+                UserShares { username }
+            }
+        }
         div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans relative",
             h3 { class: "text-2xl font-bold mb-6 text-center text-teal-400", "Download Options" }
             // if !folders.read().is_empty() {
@@ -136,21 +418,103 @@ pub fn DownloadResults(props: Props) -> Element {
                 }
             }
             // }
+            div {
+                class: "flex items-center gap-2 mb-4 cursor-pointer",
+                onclick: move |_| review_before_import.toggle(),
+                Checkbox { is_selected: review_before_import() }
+                label { class: "text-sm cursor-pointer", "Review files before import" }
+            }
+            div {
+                class: "flex items-center gap-2 mb-4 cursor-pointer",
+                title: "Cover scans shared next to the picked tracks, embedded into them unless the album has a custom cover",
+                onclick: move |_| include_artwork.toggle(),
+                Checkbox { is_selected: include_artwork() }
+                label { class: "text-sm cursor-pointer", "Download the uploader's artwork" }
+            }
+            div { class: "grid grid-cols-1 md:grid-cols-2 gap-4 mb-4",
+                div {
+                    label { class: "block text-sm font-medium mb-1", "Note" }
+                    input {
+                        class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value()),
+                        placeholder: "Optional",
+                        "type": "text",
+                    }
+                }
+                div {
+                    label { class: "block text-sm font-medium mb-1", "Labels" }
+                    input {
+                        class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                        value: "{labels}",
+                        oninput: move |e| labels.set(e.value()),
+                        placeholder: "vinyl rip, for mum",
+                        "type": "text",
+                    }
+                }
+            }
+            div { class: "mb-4",
+                label { class: "block text-sm font-medium mb-1", "Sort by" }
+                select {
+                    class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                    onchange: move |e| {
+                        sort_order
+                            .set(
+                                match e.value().as_str() {
+                                    "fastest" => SortOrder::FastestFirst,
+                                    _ => SortOrder::BestScore,
+                                },
+                            )
+                    },
+                    option { value: "score", selected: sort_order() == SortOrder::BestScore, "Best score" }
+                    option {
+                        value: "fastest",
+                        selected: sort_order() == SortOrder::FastestFirst,
+                        "Fastest first"
+                    }
+                }
+            }
 
+            if !pinned_albums.is_empty() {
+                ComparisonTray {
+                    albums: pinned_albums.clone(),
+                    on_unpin: handle_pin_toggle,
+                    on_select_all: handle_album_select_all,
+                }
+            }
             div { class: "space-y-4 mb-20",
-                for album in results {
+                for album in results.iter().cloned() {
                     AlbumResultItem {
+                        on_block: {
+                            let filenames: HashSet<String> = results
+                                .iter()
+                                .filter(|a| a.username == album.username)
+                                .flat_map(|a| a.tracks.iter().map(|t| t.base.filename.clone()))
+                                .collect();
+                            move |username| {
+                                spawn(handle_block(username, filenames.clone()));
+                            }
+                        },
+                        pinned: pinned_albums
+                            .iter()
+                            .any(|a| a.username == album.username && a.album_path == album.album_path),
+                        can_pin: pinned_albums.len() < MAX_PINNED,
                         album,
                         selected_tracks,
+                        on_pin_toggle: handle_pin_toggle,
                         on_album_select_all: handle_album_select_all,
                         on_track_toggle: handle_track_toggle,
+                        on_browse: move |username| browsing.set(Some(username)),
                     }
                 }
             }
-            div { class: "fixed bottom-8 right-8",
+            div {
+                class: "fixed bottom-8 right-8",
+                title: if auth.is_demo() { "Downloads are disabled in the demo, results are simulated" },
                 button {
                     class: "bg-teal-600 hover:bg-teal-700 text-white font-bold p-4 rounded-full shadow-lg transition-transform hover:scale-105 disabled:bg-gray-600 disabled:cursor-not-allowed flex items-center justify-center",
-                    disabled: selected_tracks.read().is_empty() || selected_folder.read().is_empty(),
+                    disabled: auth.is_demo() || selected_tracks.read().is_empty()
+                        || selected_folder.read().is_empty(),
                     onclick: handle_download,
                     svg {
                         class: "w-6 h-6",
@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+use shared::profiles::QualityProfile;
+use shared::slskd::{EmptySearchReason, SearchDiagnosis, SearchSuggestion};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub diagnosis: SearchDiagnosis,
+    /// What was searched, e.g. "Artist Album".
+    pub query: String,
+    pub profiles: Vec<QualityProfile>,
+    /// Profile the search ran with.
+    pub profile_id: String,
+    /// Searches `query` again as a raw search.
+    #[props(into)]
+    pub on_raw_search: EventHandler<()>,
+    /// Searches again with another profile, by id.
+    #[props(into)]
+    pub on_retry: EventHandler<String>,
+    #[props(into)]
+    pub on_back: EventHandler<()>,
+}
+
+fn reason_label(reason: &EmptySearchReason) -> String {
+    match reason {
+        EmptySearchReason::NoResponses => {
+            "Nobody shares audio files matching the search.".to_string()
+        }
+        EmptySearchReason::NoMatch { files } => {
+            format!("{files} files did not match the artist, album or track titles closely enough.")
+        }
+        EmptySearchReason::FormatExcluded { format, files } => {
+            format!("{files} matching {format} files were left out by the quality profile.")
+        }
+        EmptySearchReason::BitrateTooLow { format, files } => {
+            format!("{files} matching {format} files are below your minimum bitrate for {format}.")
+        }
+        EmptySearchReason::UploaderTooSlow { files } => {
+            format!("{files} matching files are shared by users without a free slot or below your minimum speed.")
+        }
+        EmptySearchReason::Incomplete { found, required } => {
+            format!("The most complete album had {found} of the {required} tracks required.")
+        }
+    }
+}
+
+/// Profiles other than `current` that would have kept something.
+fn profiles_for(
+    suggestion: &SearchSuggestion,
+    profiles: &[QualityProfile],
+    current: &str,
+) -> Vec<QualityProfile> {
+    profiles
+        .iter()
+        .filter(|p| p.id != current)
+        .filter(|p| match suggestion {
+            SearchSuggestion::AllowFormats(formats) => {
+                p.formats.is_empty() || formats.iter().any(|f| p.formats.contains(f))
+            }
+            SearchSuggestion::AcceptPartial { found, expected } => {
+                p.filters(Default::default()).required_tracks(*expected) <= *found
+            }
+            _ => false,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Why an album search found nothing, and what to try instead.
+#[component]
+pub fn EmptySearch(props: Props) -> Element {
+    let diagnosis = &props.diagnosis;
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans",
+        div { class: "flex items-center justify-between mb-2",
+          h3 { class: "text-2xl font-bold text-teal-400", "Nothing found" }
+          button {
+            class: "text-sm text-gray-400 hover:text-white",
+            onclick: move |_| props.on_back.call(()),
+            "Back to search"
+          }
+        }
+        p { class: "text-sm text-gray-400 mb-4",
+          "{diagnosis.audio_files} audio files from {diagnosis.users} users answered the search for \"{props.query}\"."
+        }
+        ul { class: "list-disc list-inside space-y-1 mb-6 text-gray-300",
+          for reason in diagnosis.reasons.iter() {
+            li { {reason_label(reason)} }
+          }
+        }
+        if !diagnosis.suggestions.is_empty() {
+          h4 { class: "text-lg font-semibold mb-2 text-indigo-300", "Try" }
+        }
+        ul { class: "space-y-3",
+          for suggestion in diagnosis.suggestions.iter() {
+            li { class: "bg-gray-700 p-3 rounded-md",
+              match suggestion {
+                  SearchSuggestion::RawSearch => rsx! {
+                    button {
+                      class: "text-teal-300 hover:text-teal-200",
+                      onclick: move |_| props.on_raw_search.call(()),
+                      "A raw search for \"{props.query}\", without matching files to the album"
+                    }
+                  },
+                  SearchSuggestion::AllowFormats(formats) => rsx! {
+                    p { {format!("A profile allowing {}", formats.join(", "))} }
+                  },
+                  SearchSuggestion::LowerBitrateCutoffs(formats) => rsx! {
+                    p { {format!("Lowering your minimum bitrate for {} in Settings", formats.join(", "))} }
+                  },
+                  SearchSuggestion::AcceptPartial { found, expected } => rsx! {
+                    p { "A profile accepting albums with {found} of the {expected} tracks" }
+                  },
+              }
+              div { class: "flex flex-wrap gap-2 mt-2",
+                for profile in profiles_for(suggestion, &props.profiles, &props.profile_id) {
+                  button {
+                    class: "bg-teal-600 hover:bg-teal-700 text-white text-sm py-1 px-3 rounded-md",
+                    title: "{profile.description}",
+                    onclick: {
+                        let id = profile.id.clone();
+                        move |_| props.on_retry.call(id.clone())
+                    },
+                    "Search again with {profile.name}"
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
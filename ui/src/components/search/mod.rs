@@ -1,75 +1,416 @@
 pub mod album;
+pub mod artist;
 pub mod track;
 
+use api::{SearchStatus, SearchTicket};
 use dioxus::logger::tracing::info;
 use dioxus::prelude::*;
-use shared::download::DownloadQuery;
-use shared::musicbrainz::{AlbumWithTracks, SearchResult};
-use shared::slskd::{AlbumResult as SlskdAlbumResult, TrackResult as SlskdTrackResult};
+use gloo_timers::future::TimeoutFuture;
+use shared::download::{DownloadQuery, TrackDownloadQuery};
+use shared::musicbrainz::{AlbumWithTracks, Artist, SearchPage, SearchResult, Track};
+use shared::profiles::{QualityProfile, DEFAULT_PROFILE_ID};
+use shared::slskd::{
+    AlbumResult as SlskdAlbumResult, SearchDiagnosis, SearchQueuePosition, SearchRefinements,
+    SearchResult as SlskdSearchResult, TrackResult as SlskdTrackResult,
+};
+use std::collections::{HashMap, HashSet};
 
+use artist::ArtistResult;
 use track::TrackResult;
 
 use crate::search::album::AlbumResult;
-use crate::{Album, AlbumHeader, Button, Modal};
+use crate::{use_auth, Album, AlbumHeader, Button, ButtonVariant, Checkbox, EditionPicker, Modal};
 
+/// A fresh id for a Soulseek search, so it can be cancelled while it runs.
+fn new_search_id() -> Option<String> {
+    web_sys::window()?.crypto().ok().map(|c| c.random_uuid())
+}
+
+/// Polls a background search until it ends, keeping `queued` up to date while
+/// it waits for its turn and `partial` while it runs. Returns its `Done`,
+/// `NothingFound` or `Files` status, `None` when it failed or when `running_search` no longer
+/// holds `search_id` because it was cancelled.
+async fn follow_search(
+    token: String,
+    ticket: Result<SearchTicket, ServerFnError>,
+    search_id: Option<String>,
+    running_search: Signal<Option<String>>,
+    mut queued: Signal<Option<String>>,
+    mut partial: Signal<Option<Vec<SlskdAlbumResult>>>,
+) -> Option<SearchStatus> {
+    let ticket = ticket.ok()?;
+    let mut status = ticket.status;
+    loop {
+        match status {
+            SearchStatus::Done(_) | SearchStatus::NothingFound(_) | SearchStatus::Files(_) => {
+                queued.set(None);
+                partial.set(None);
+                return Some(status);
+            }
+            SearchStatus::Failed(e) => {
+                info!("Search failed: {}", e);
+                queued.set(None);
+                partial.set(None);
+                return None;
+            }
+            SearchStatus::WaitingForOwnSearches(position) => queued.set(Some(format!(
+                "Waiting for your other searches to end: #{position} in line..."
+            ))),
+            SearchStatus::Queued(place) => queued.set(Some(queue_label(&place))),
+            SearchStatus::Running => queued.set(None),
+            SearchStatus::Partial(albums) => {
+                queued.set(None);
+                partial.set(Some(albums));
+            }
+        }
+        TimeoutFuture::new(2_000).await;
+        if *running_search.peek() != search_id {
+            queued.set(None);
+            partial.set(None);
+            return None;
+        }
+        status = api::search_status(token.clone(), ticket.search_id.clone())
+            .await
+            .ok()?;
+    }
+}
+
+/// An album or track search, kept to run it again.
+#[derive(Clone, PartialEq)]
+enum LastSearch {
+    Album(DownloadQuery),
+    Track(Track),
+}
+
+impl LastSearch {
+    /// What was searched, as it would be typed.
+    fn query(&self) -> String {
+        match self {
+            LastSearch::Album(query) => query
+                .query
+                .clone()
+                .unwrap_or_else(|| format!("{} {}", query.album.artist, query.album.title)),
+            LastSearch::Track(track) => format!("{} {}", track.artist, track.title),
+        }
+    }
+}
+
+/// Which MusicBrainz search the results come from.
+#[derive(Clone, Copy, PartialEq)]
+enum SearchKind {
+    Track,
+    Album,
+    Artist,
+}
+
+/// Runs the MusicBrainz search of `kind` for `query`.
+async fn musicbrainz_search(
+    kind: SearchKind,
+    query: api::SearchQuery,
+) -> Result<SearchPage, ServerFnError> {
+    match kind {
+        SearchKind::Track => api::search_track(query).await,
+        SearchKind::Album => api::search_album(query).await,
+        SearchKind::Artist => api::search_artist(query).await,
+    }
+}
+
+/// The page after `query`, unless MusicBrainz has no more results. A page
+/// can be empty with more to come, when none of its results were kept.
+fn next_page(
+    kind: SearchKind,
+    mut query: api::SearchQuery,
+    page: &SearchPage,
+) -> Option<(SearchKind, api::SearchQuery)> {
+    query.offset += query.limit.unwrap_or(api::SEARCH_PAGE_SIZE) as u16;
+    (u32::from(query.offset) < page.count).then_some((kind, query))
+}
+
+/// Genres of the results, the most common first.
+fn result_genres(results: &[SearchResult]) -> Vec<String> {
+    let mut counts: HashMap<String, (String, usize)> = HashMap::new();
+    for genre in results.iter().flat_map(|r| r.genres()) {
+        counts
+            .entry(genre.to_lowercase())
+            .or_insert_with(|| (genre.clone(), 0))
+            .1 += 1;
+    }
+    let mut genres: Vec<(String, usize)> = counts.into_values().collect();
+    genres.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    genres.into_iter().map(|(genre, _)| genre).collect()
+}
+
+/// Whether `result` is tagged with `genre`, or no genre is picked.
+fn has_genre(result: &SearchResult, genre: Option<&str>) -> bool {
+    genre.is_none_or(|genre| {
+        result
+            .genres()
+            .iter()
+            .any(|g| g.eq_ignore_ascii_case(genre))
+    })
+}
+
+fn queue_label(place: &SearchQueuePosition) -> String {
+    let eta = if place.eta_seconds >= 90 {
+        format!("{} min", (place.eta_seconds + 30) / 60)
+    } else {
+        format!("{}s", place.eta_seconds)
+    };
+    format!(
+        "Waiting for the Soulseek search limit: #{} in line, about {eta} to go...",
+        place.position
+    )
+}
+
+mod availability_report;
+mod browse;
+mod comparison;
+mod discography;
 mod download_results;
+mod empty_search;
+mod partial_results;
+mod raw_results;
+mod refinements;
+mod similar_artists;
+mod track_details;
+use discography::Discography;
 use download_results::DownloadResults;
+use empty_search::EmptySearch;
+use partial_results::PartialResults;
+use raw_results::RawResults;
+use refinements::Refinements;
+use similar_artists::SimilarArtists;
 
 #[component]
 pub fn Search() -> Element {
     let mut response = use_signal::<Option<Vec<SearchResult>>>(|| None);
+    // The search the results come from, at its next page, while MusicBrainz
+    // may have more.
+    let mut more = use_signal::<Option<(SearchKind, api::SearchQuery)>>(|| None);
+    let mut loading_more = use_signal(|| false);
+    // Genre the results are narrowed to.
+    let mut genre_filter = use_signal::<Option<String>>(|| None);
     let mut search = use_signal(String::new);
     let mut artist = use_signal::<Option<String>>(|| None);
     let mut loading = use_signal(|| false);
     let mut viewing_album = use_signal::<Option<AlbumWithTracks>>(|| None);
+    // Whether the open album is an edition the user picked, or the one their
+    // edition preference led to, rather than the release that was found.
+    let mut edition_picked = use_signal(|| false);
+    // Name of the artist whose discography is open, and their MBID when known.
+    let mut viewing_artist = use_signal::<Option<(String, Option<String>)>>(|| None);
     let mut download_options = use_signal::<Option<Vec<SlskdAlbumResult>>>(|| None);
+    let mut download_album_id = use_signal::<Option<String>>(|| None);
+    let mut download_recording_id = use_signal::<Option<String>>(|| None);
+    let mut profiles = use_signal(Vec::<QualityProfile>::new);
+    let mut profile_id = use_signal(|| DEFAULT_PROFILE_ID.to_string());
+    let mut refinements = use_signal(SearchRefinements::default);
+    // Id of the Soulseek search being waited on, cleared when it is cancelled.
+    let mut running_search = use_signal::<Option<String>>(|| None);
+    // Place of that search in line, while it waits for the rate limit.
+    let queued = use_signal::<Option<String>>(|| None);
+    // Albums ranked so far by that search, shown until it ends.
+    let partial = use_signal::<Option<Vec<SlskdAlbumResult>>>(|| None);
+    // Sends the query to Soulseek as typed, skipping MusicBrainz.
+    let mut raw_mode = use_signal(|| false);
+    let mut raw_results = use_signal::<Option<Vec<SlskdSearchResult>>>(|| None);
+    // Why the last album or track search found nothing, and that search.
+    let mut nothing_found = use_signal::<Option<(SearchDiagnosis, LastSearch)>>(|| None);
+    let auth = use_auth();
 
-    let download = move |query: DownloadQuery| async move {
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            if let Ok(fetched) = api::get_quality_profiles(token).await {
+                profiles.set(fetched);
+            }
+        }
+    });
+
+    let auto_pick = use_memo(move || {
+        profiles
+            .read()
+            .iter()
+            .any(|p| p.id == profile_id() && p.auto_pick)
+    });
+
+    let download = move |mut query: DownloadQuery| async move {
+        query.profile_id = Some(profile_id());
+        query.refinements = refinements();
+        query.search_id = new_search_id();
+        query.edition_picked = edition_picked();
+        let Some(token) = auth.token() else {
+            return;
+        };
         loading.set(true);
         viewing_album.set(None);
-        if let Ok(results) = api::search_downloads(query).await {
-            download_options.set(Some(results));
+        download_album_id.set(Some(query.album.id.clone()));
+        download_recording_id.set(None);
+        running_search.set(query.search_id.clone());
+        let search_id = query.search_id.clone();
+        let last_search = LastSearch::Album(query.clone());
+        let ticket = api::search_downloads(token.clone(), query).await;
+        let results = follow_search(
+            token,
+            ticket,
+            search_id.clone(),
+            running_search,
+            queued,
+            partial,
+        )
+        .await;
+        // Results of a cancelled search are dropped.
+        if running_search() == search_id {
+            match results {
+                Some(SearchStatus::Done(results)) => download_options.set(Some(results)),
+                Some(SearchStatus::NothingFound(diagnosis)) => {
+                    nothing_found.set(Some((diagnosis, last_search)))
+                }
+                _ => {}
+            }
+            running_search.set(None);
+            loading.set(false);
+        }
+    };
+
+    let download_single = move |track: Track| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        loading.set(true);
+        download_album_id.set(None);
+        download_recording_id.set(Some(track.id.clone()));
+        let search_id = new_search_id();
+        running_search.set(search_id.clone());
+        let last_search = LastSearch::Track(track.clone());
+        let query = TrackDownloadQuery {
+            track,
+            profile_id: Some(profile_id()),
+            search_id: search_id.clone(),
+            refinements: refinements(),
+        };
+        let ticket = api::search_single_downloads(token.clone(), query).await;
+        let results = follow_search(
+            token,
+            ticket,
+            search_id.clone(),
+            running_search,
+            queued,
+            partial,
+        )
+        .await;
+        if running_search() == search_id {
+            match results {
+                Some(SearchStatus::Done(results)) => download_options.set(Some(results)),
+                Some(SearchStatus::NothingFound(diagnosis)) => {
+                    nothing_found.set(Some((diagnosis, last_search)))
+                }
+                _ => {}
+            }
+            running_search.set(None);
+            loading.set(false);
+        }
+    };
+
+    let search_raw = move || async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        loading.set(true);
+        download_album_id.set(None);
+        download_recording_id.set(None);
+        let search_id = new_search_id();
+        running_search.set(search_id.clone());
+        let ticket = api::search_raw(token.clone(), search(), search_id.clone()).await;
+        let results = follow_search(
+            token,
+            ticket,
+            search_id.clone(),
+            running_search,
+            queued,
+            partial,
+        )
+        .await;
+        if running_search() == search_id {
+            if let Some(SearchStatus::Files(files)) = results {
+                raw_results.set(Some(files));
+            }
+            running_search.set(None);
+            loading.set(false);
         }
+    };
+
+    let cancel_search = move |_| async move {
+        let Some(search_id) = running_search() else {
+            return;
+        };
+        running_search.set(None);
         loading.set(false);
+        if let Some(token) = auth.token() {
+            if let Err(e) = api::cancel_search(token, search_id).await {
+                info!("Failed to cancel search: {}", e);
+            }
+        }
     };
 
-    let download_tracks = move |(tracks, folder): (Vec<SlskdTrackResult>, String)| async move {
+    let download_tracks = move |(tracks, folder, mut options): (
+        Vec<SlskdTrackResult>,
+        String,
+        api::DownloadOptions,
+    )| async move {
         loading.set(true);
         download_options.set(None);
-        if let Ok(_res) = api::download(tracks, folder).await {
+        raw_results.set(None);
+        let Some(token) = auth.token() else {
+            loading.set(false);
+            return;
+        };
+        options.album_id = download_album_id();
+        options.recording_id = download_recording_id();
+        if let Ok(_res) = api::download(token, tracks, folder, options).await {
             // TODO: Show download progress
             info!("Downloads started");
         }
         loading.set(false);
     };
 
-    let search_track = move || async move {
+    let search_musicbrainz = move |kind: SearchKind| async move {
         loading.set(true);
-        if let Ok(data) = api::search_track(api::SearchQuery {
+        more.set(None);
+        genre_filter.set(None);
+        let query = api::SearchQuery {
             artist: artist(),
             query: search(),
-        })
-        .await
-        {
-            response.set(Some(data));
+            offset: 0,
+            limit: None,
+        };
+        if let Ok(page) = musicbrainz_search(kind, query.clone()).await {
+            more.set(next_page(kind, query, &page));
+            response.set(Some(page.results));
         }
         loading.set(false);
     };
 
-    let search_album = move || async move {
-        loading.set(true);
-        if let Ok(data) = api::search_album(api::SearchQuery {
-            artist: artist(),
-            query: search(),
-        })
-        .await
-        {
-            response.set(Some(data));
+    // Appends the next page of results, leaving out those already shown.
+    let load_more = move |_| async move {
+        let Some((kind, query)) = more() else {
+            return;
+        };
+        loading_more.set(true);
+        if let Ok(page) = musicbrainz_search(kind, query.clone()).await {
+            more.set(next_page(kind, query, &page));
+            let mut response = response.write();
+            let items = response.get_or_insert_with(Vec::new);
+            let shown: HashSet<String> = items.iter().map(|item| item.id().to_string()).collect();
+            items.extend(
+                page.results
+                    .into_iter()
+                    .filter(|item| !shown.contains(item.id())),
+            );
         }
-        loading.set(false);
+        loading_more.set(false);
     };
 
-    let view_full_album = move |album_id: String| async move {
+    let open_album = move |album_id: String| async move {
         loading.set(true);
         if let Ok(album_data) = api::find_album(album_id.clone()).await {
             viewing_album.set(Some(album_data));
@@ -79,10 +420,67 @@ pub fn Search() -> Element {
         loading.set(false);
     };
 
+    // Opens the edition of the album the user prefers.
+    let view_full_album = move |album_id: String| async move {
+        let preferred = match auth.token() {
+            Some(token) => api::preferred_edition(token, album_id.clone())
+                .await
+                .unwrap_or(album_id.clone()),
+            None => album_id.clone(),
+        };
+        edition_picked.set(preferred != album_id);
+        open_album(preferred).await;
+    };
+
+    if let Some(files) = raw_results.read().clone() {
+        return rsx! {
+          RawResults {
+            files,
+            on_download: move |data| {
+                spawn(download_tracks(data));
+            },
+            on_back: move |_| raw_results.set(None),
+          }
+        };
+    }
+
+    if let Some((diagnosis, last_search)) = nothing_found.read().clone() {
+        return rsx! {
+          EmptySearch {
+            diagnosis,
+            query: last_search.query(),
+            profiles: profiles(),
+            profile_id: profile_id(),
+            on_raw_search: {
+                let query = last_search.query();
+                move |_| {
+                    nothing_found.set(None);
+                    raw_mode.set(true);
+                    search.set(query.clone());
+                    spawn(search_raw());
+                }
+            },
+            on_retry: {
+                let last_search = last_search.clone();
+                move |id: String| {
+                    nothing_found.set(None);
+                    profile_id.set(id);
+                    match last_search.clone() {
+                        LastSearch::Album(query) => spawn(download(query)),
+                        LastSearch::Track(track) => spawn(download_single(track)),
+                    };
+                }
+            },
+            on_back: move |_| nothing_found.set(None),
+          }
+        };
+    }
+
     if let Some(results) = download_options.read().clone() {
         return rsx! {
           DownloadResults {
             results,
+            auto_pick: auto_pick(),
             on_download: move |data| {
                 spawn(download_tracks(data));
             },
@@ -90,88 +488,249 @@ pub fn Search() -> Element {
         };
     }
 
+    let genres = response
+        .read()
+        .as_deref()
+        .map(result_genres)
+        .unwrap_or_default();
+
     rsx! {
       if let Some(data) = viewing_album.read().clone() {
         Modal {
           on_close: move |_| viewing_album.set(None),
           header: rsx! {
-            AlbumHeader { album: data.album.clone() }
+            // Keyed by release, to start over when another edition is picked.
+            AlbumHeader { key: "{data.album.id}", album: data.album.clone() }
+            EditionPicker {
+              release_id: data.album.id.clone(),
+              on_pick: move |id| {
+                  edition_picked.set(true);
+                  spawn(open_album(id));
+              },
+            }
           },
           Album {
-            data,
+            key: "{data.album.id}",
+            data: data.clone(),
             on_select: move |data: DownloadQuery| {
                 spawn(download(data));
             },
           }
+          if let Some(artist_id) = data.album.artist_id.clone() {
+            SimilarArtists {
+              key: "{artist_id}",
+              artist_id,
+              on_select: move |artist: Artist| {
+                  viewing_album.set(None);
+                  viewing_artist.set(Some((artist.name, Some(artist.id))));
+              },
+            }
+          }
+        }
+      }
+
+      if let Some((artist, artist_id)) = viewing_artist() {
+        Modal {
+          on_close: move |_| viewing_artist.set(None),
+          header: rsx! {
+            h3 { class: "text-xl font-bold text-teal-400", "{artist}" }
+          },
+          Discography {
+            key: "{artist}",
+            artist: artist.clone(),
+            artist_id: artist_id.clone(),
+            profile_id: profile_id(),
+          }
+          if let Some(artist_id) = artist_id {
+            SimilarArtists {
+              key: "{artist_id}",
+              artist_id,
+              on_select: move |artist: Artist| viewing_artist.set(Some((artist.name, Some(artist.id)))),
+            }
+          }
         }
       }
 
       div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans",
 
         h4 { class: "text-2xl font-bold mb-6 text-center text-teal-400",
-          "Search a track / album"
+          "Search a track / album / artist"
         }
 
         div { class: "flex flex-col sm:flex-row gap-4 mb-4",
 
           input {
             class: "flex-grow bg-gray-700 text-white placeholder-gray-400 px-4 py-2 rounded-md border border-gray-600 focus:outline-none focus:ring-2 focus:ring-teal-500 transition-shadow",
-            placeholder: "Search an album or track...",
+            placeholder: "Search an album, track or artist...",
             oninput: move |event| search.set(event.value()),
           }
-          input {
-            class: "flex-grow bg-gray-700 text-white placeholder-gray-400 px-4 py-2 rounded-md border border-gray-600 focus:outline-none focus:ring-2 focus:ring-teal-500 transition-shadow",
-            placeholder: "Artist (optional)",
-            oninput: move |event| {
-                let input = event.value();
-                if input.is_empty() {
-                    artist.set(None);
-                } else {
-                    artist.set(Some(input));
-                }
-            },
+          if !raw_mode() {
+            input {
+              class: "flex-grow bg-gray-700 text-white placeholder-gray-400 px-4 py-2 rounded-md border border-gray-600 focus:outline-none focus:ring-2 focus:ring-teal-500 transition-shadow",
+              placeholder: "Artist (optional)",
+              oninput: move |event| {
+                  let input = event.value();
+                  if input.is_empty() {
+                      artist.set(None);
+                  } else {
+                      artist.set(Some(input));
+                  }
+              },
+            }
+          }
+        }
+        div { class: "flex justify-end items-center gap-2 mb-4 text-sm",
+          div {
+            class: "flex items-center gap-2 mr-auto cursor-pointer",
+            title: "Send the search to Soulseek as typed and list every file found, for releases MusicBrainz does not know about",
+            onclick: move |_| raw_mode.toggle(),
+            Checkbox { is_selected: raw_mode() }
+            label { class: "text-gray-400 cursor-pointer", "Raw Soulseek search" }
+          }
+          label { class: "text-gray-400", "Profile" }
+          select {
+            class: "p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            onchange: move |e| profile_id.set(e.value()),
+            for profile in profiles.read().iter() {
+              option {
+                value: "{profile.id}",
+                title: "{profile.description}",
+                selected: profile.id == profile_id(),
+                "{profile.name}"
+              }
+            }
+          }
+        }
+        if !raw_mode() {
+          Refinements {
+            refinements: refinements(),
+            on_change: move |picked| refinements.set(picked),
           }
         }
         div { class: "flex justify-center gap-4 mb-8",
 
-          Button {
-            disabled: loading() || search.read().is_empty(),
-            onclick: move |_| search_track(),
+          if raw_mode() {
+            Button {
+              disabled: loading() || search.read().is_empty(),
+              onclick: move |_| search_raw(),
 
-            {"Search a Track"}
-          }
-          Button {
-            disabled: loading() || search.read().is_empty(),
-            onclick: move |_| search_album(),
+              {"Search Soulseek"}
+            }
+          } else {
+            Button {
+              disabled: loading() || search.read().is_empty(),
+              onclick: move |_| search_musicbrainz(SearchKind::Track),
 
-            {"Search an Album"}
+              {"Search a Track"}
+            }
+            Button {
+              disabled: loading() || search.read().is_empty(),
+              onclick: move |_| search_musicbrainz(SearchKind::Album),
+
+              {"Search an Album"}
+            }
+            Button {
+              disabled: loading() || search.read().is_empty(),
+              onclick: move |_| search_musicbrainz(SearchKind::Artist),
+
+              {"Search an Artist"}
+            }
           }
         }
 
         if loading() {
-          div { class: "flex justify-center items-center py-10",
+          div { class: "flex flex-col justify-center items-center py-10 gap-4",
             div { class: "animate-spin rounded-full h-16 w-16 border-t-4 border-b-4 border-teal-500" }
+            if let Some(label) = queued() {
+              p { class: "text-sm text-gray-400", "{label}" }
+            } else if running_search().is_some() {
+              p { class: "text-sm text-gray-400", "Searching Soulseek, this takes up to 45 seconds..." }
+            }
+            if let Some(albums) = partial() {
+              PartialResults { albums }
+            }
+            if running_search().is_some() {
+              button {
+                class: "text-sm text-gray-300 hover:text-red-400 border border-gray-600 rounded px-3 py-1",
+                onclick: cancel_search,
+                "Cancel search"
+              }
+            }
           }
         } else {
           match *response.read() {
               Some(ref items) if !items.is_empty() => rsx! {
                 h5 { class: "text-xl font-semibold mb-4 border-b border-gray-600 pb-2", "Results" }
+                if !genres.is_empty() {
+                  div { class: "flex items-center gap-2 mb-4 text-sm",
+                    label { class: "text-gray-400", "Genre" }
+                    select {
+                      class: "p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                      onchange: move |e| genre_filter.set(Some(e.value()).filter(|g| !g.is_empty())),
+                      option { value: "", selected: genre_filter().is_none(), "All genres" }
+                      for genre in genres.iter() {
+                        option {
+                          value: "{genre}",
+                          selected: genre_filter().as_deref() == Some(genre.as_str()),
+                          "{genre}"
+                        }
+                      }
+                    }
+                  }
+                }
                 ul { class: "list-none p-0 space-y-4",
-                  for item in items.iter() {
+                  for item in items.iter().filter(|item| has_genre(item, genre_filter().as_deref())) {
                     match item {
                         SearchResult::Track(ref track) => rsx! {
                           li { key: "{track.id}",
-                            TrackResult { on_album_click: move |id| view_full_album(id), track: track.clone() }
+                            TrackResult {
+                              on_album_click: move |id| view_full_album(id),
+                              on_download: move |track| {
+                                  spawn(download_single(track));
+                              },
+                              track: track.clone(),
+                            }
                           }
                         },
                         SearchResult::Album(album) => rsx! {
                           li { key: "{album.id}",
-                            AlbumResult { on_click: move |id| view_full_album(id), album: album.clone() }
+                            AlbumResult {
+                              on_click: move |id| view_full_album(id),
+                              on_artist_click: {
+                                  let artist_id = album.artist_id.clone();
+                                  move |artist| viewing_artist.set(Some((artist, artist_id.clone())))
+                              },
+                              album: album.clone(),
+                            }
+                          }
+                        },
+                        SearchResult::Artist(artist) => rsx! {
+                          li { key: "{artist.id}",
+                            ArtistResult {
+                              on_click: move |artist: Artist| {
+                                  viewing_artist.set(Some((artist.name, Some(artist.id))))
+                              },
+                              artist: artist.clone(),
+                            }
                           }
                         },
                     }
                   }
                 }
+                if more.read().is_some() {
+                  div { class: "flex justify-center mt-6",
+                    Button {
+                      variant: ButtonVariant::Secondary,
+                      disabled: loading_more(),
+                      onclick: load_more,
+                      if loading_more() {
+                        "Loading..."
+                      } else {
+                        "Load more"
+                      }
+                    }
+                  }
+                }
               },
               _ => rsx! {
                 div { class: "text-center text-gray-500 py-10", "Search for something to see results here." }
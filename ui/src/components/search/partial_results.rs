@@ -0,0 +1,39 @@
+use dioxus::prelude::*;
+use shared::slskd::AlbumResult;
+
+use crate::ScoreBar;
+
+/// Candidates shown at most while a search runs.
+const MAX_SHOWN: usize = 5;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    /// Albums ranked so far, best first.
+    pub albums: Vec<AlbumResult>,
+}
+
+/// The best albums a running search found so far, to see how it is going.
+/// They are picked from once the search is over.
+#[component]
+pub fn PartialResults(props: Props) -> Element {
+    rsx! {
+      div { class: "w-full max-w-xl",
+        p { class: "text-sm text-gray-400 mb-2", "{props.albums.len()} candidates so far" }
+        ul { class: "space-y-2",
+          for album in props.albums.iter().take(MAX_SHOWN) {
+            li {
+              key: "{album.username}{album.album_path}",
+              class: "bg-gray-800 p-3 rounded-md flex items-center justify-between gap-4",
+              div { class: "min-w-0",
+                p { class: "text-sm font-bold truncate", "{album.album_title}" }
+                p { class: "text-xs text-gray-400 truncate",
+                  "{album.username} - {album.tracks.len()} tracks - {album.dominant_quality}"
+                }
+              }
+              ScoreBar { score: album.score }
+            }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+use shared::slskd::{SearchResult, TrackResult};
+use std::collections::HashSet;
+
+use api::DownloadOptions;
+
+use crate::{use_auth, Button, Checkbox};
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub files: Vec<SearchResult>,
+    #[props(into)]
+    pub on_download: EventHandler<(Vec<TrackResult>, String, DownloadOptions)>,
+    #[props(into)]
+    pub on_back: EventHandler<()>,
+}
+
+/// Key of a file in the selection: the same path can be shared by several users.
+fn file_key(file: &SearchResult) -> String {
+    format!("{}\u{0}{}", file.username, file.filename)
+}
+
+/// Every file a raw search found, as a flat list to pick from. Nothing is
+/// matched against MusicBrainz, so files are shown by path.
+#[component]
+pub fn RawResults(props: Props) -> Element {
+    let auth = use_auth();
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut folders = use_signal(Vec::new);
+    let mut selected_folder = use_signal(String::new);
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            if let Ok(user_folders) = api::get_user_folders(token).await {
+                if let Some(first) = user_folders.first() {
+                    selected_folder.set(first.path.clone());
+                }
+                folders.set(user_folders);
+            }
+        }
+    });
+
+    let files = props.files.clone();
+    let download = move |_| {
+        let chosen: Vec<TrackResult> = files
+            .iter()
+            .filter(|f| selected.read().contains(&file_key(f)))
+            .cloned()
+            .map(TrackResult::from_file)
+            .collect();
+        props
+            .on_download
+            .call((chosen, selected_folder(), DownloadOptions::default()));
+    };
+
+    let count = selected.read().len();
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-3xl mx-auto my-10 font-sans",
+        div { class: "flex items-center justify-between mb-6",
+          h3 { class: "text-2xl font-bold text-teal-400", "{props.files.len()} files found" }
+          button {
+            class: "text-sm text-gray-400 hover:text-white",
+            onclick: move |_| props.on_back.call(()),
+            "Back to search"
+          }
+        }
+        if props.files.is_empty() {
+          p { class: "text-gray-400", "Nobody shares audio files matching this search." }
+        }
+        ul { class: "space-y-1 mb-6",
+          for file in props.files.iter().cloned() {
+            li {
+              key: "{file_key(&file)}",
+              class: "flex items-center gap-2 p-1 rounded-md hover:bg-gray-700 cursor-pointer",
+              onclick: {
+                  let key = file_key(&file);
+                  move |_| {
+                      let mut selected = selected.write();
+                      if !selected.remove(&key) {
+                          selected.insert(key.clone());
+                      }
+                  }
+              },
+              Checkbox { is_selected: selected.read().contains(&file_key(&file)) }
+              div { class: "flex-grow min-w-0",
+                p { class: "text-sm truncate", title: "{file.filename}",
+                  {file.filename.rsplit(['\\', '/']).next().unwrap_or_default()}
+                }
+                p { class: "text-xs text-gray-400 truncate", "{file.filename}" }
+              }
+              span { class: "text-xs text-gray-400 whitespace-nowrap",
+                "{file.username} · "
+                if let Some(bitrate) = file.bitrate {
+                  "{bitrate} kbps · "
+                }
+                "{file.size / (1024 * 1024)} MB"
+                if !file.has_free_upload_slot {
+                  " · queued"
+                }
+              }
+            }
+          }
+        }
+        div { class: "flex gap-2 items-center",
+          select {
+            class: "flex-grow p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            value: "{selected_folder}",
+            onchange: move |e| selected_folder.set(e.value()),
+            for folder in folders.read().iter() {
+              option { value: "{folder.path}", "{folder.name}" }
+            }
+          }
+          Button {
+            disabled: selected.read().is_empty() || selected_folder.read().is_empty() || auth.is_demo(),
+            onclick: download,
+            "Download {count} files"
+          }
+        }
+      }
+    }
+}
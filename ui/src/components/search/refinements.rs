@@ -0,0 +1,123 @@
+use dioxus::prelude::*;
+use shared::slskd::SearchRefinements;
+
+use crate::Checkbox;
+
+/// Formats offered to narrow a search down to.
+const FORMATS: [&str; 5] = ["flac", "mp3", "m4a", "ogg", "wav"];
+
+/// Minimum bitrates offered, in kbps.
+const BITRATES: [i32; 4] = [128, 192, 256, 320];
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub refinements: SearchRefinements,
+    #[props(into)]
+    pub on_change: EventHandler<SearchRefinements>,
+}
+
+/// Filters for the next album or track search, on top of the quality
+/// profile, e.g. to leave out long queues and low bitrates.
+#[component]
+pub fn Refinements(props: Props) -> Element {
+    let refinements = props.refinements.clone();
+
+    rsx! {
+      div { class: "flex flex-wrap items-center gap-x-4 gap-y-2 mb-4 text-sm text-gray-400",
+        div {
+          class: "flex items-center gap-2 cursor-pointer",
+          title: "Leave out users whose files would wait in their upload queue",
+          onclick: {
+              let mut refinements = refinements.clone();
+              move |_| {
+                  refinements.free_upload_slot_only = !refinements.free_upload_slot_only;
+                  props.on_change.call(refinements.clone());
+              }
+          },
+          Checkbox { is_selected: refinements.free_upload_slot_only }
+          label { class: "cursor-pointer", "Free slot only" }
+        }
+        div { class: "flex items-center gap-2",
+          label { "Min speed" }
+          input {
+            class: "w-20 p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            "type": "number",
+            min: "0",
+            placeholder: "KB/s",
+            value: refinements.min_upload_speed.map(|s| s.to_string()).unwrap_or_default(),
+            oninput: {
+                let mut refinements = refinements.clone();
+                move |e: FormEvent| {
+                    refinements.min_upload_speed = e.value().parse().ok().filter(|s| *s > 0);
+                    props.on_change.call(refinements.clone());
+                }
+            },
+          }
+          span { "KB/s" }
+        }
+        div { class: "flex items-center gap-2",
+          label { "Min bitrate" }
+          select {
+            class: "p-1 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+            onchange: {
+                let mut refinements = refinements.clone();
+                move |e: FormEvent| {
+                    refinements.min_bitrate = e.value().parse().ok();
+                    props.on_change.call(refinements.clone());
+                }
+            },
+            option { value: "", selected: refinements.min_bitrate.is_none(), "Any" }
+            for bitrate in BITRATES {
+              option {
+                value: "{bitrate}",
+                selected: refinements.min_bitrate == Some(bitrate),
+                "{bitrate} kbps"
+              }
+            }
+          }
+        }
+        div {
+          class: "flex items-center gap-2",
+          title: "Formats to keep, instead of the profile's. None picked keeps the profile's formats.",
+          for format in FORMATS {
+            div {
+              class: "flex items-center gap-1 cursor-pointer",
+              onclick: {
+                  let mut refinements = refinements.clone();
+                  move |_| {
+                      if let Some(i) = refinements.formats.iter().position(|f| f == format) {
+                          refinements.formats.remove(i);
+                      } else {
+                          refinements.formats.push(format.to_string());
+                      }
+                      props.on_change.call(refinements.clone());
+                  }
+              },
+              Checkbox { is_selected: refinements.formats.iter().any(|f| f == format) }
+              label { class: "cursor-pointer", "{format}" }
+            }
+          }
+        }
+        div {
+          class: "flex items-center gap-2 cursor-pointer",
+          title: "Leave out files naming another edition of the album, e.g. a remaster when the original is searched for",
+          onclick: {
+              let mut refinements = refinements.clone();
+              move |_| {
+                  refinements.exclude_other_editions = !refinements.exclude_other_editions;
+                  props.on_change.call(refinements.clone());
+              }
+          },
+          Checkbox { is_selected: refinements.exclude_other_editions }
+          label { class: "cursor-pointer", "This edition only" }
+        }
+        if !refinements.is_empty() {
+          button {
+            class: "text-gray-400 hover:text-white underline",
+            onclick: move |_| props.on_change.call(SearchRefinements::default()),
+            "Clear filters"
+          }
+        }
+      }
+    }
+}
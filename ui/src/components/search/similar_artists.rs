@@ -0,0 +1,72 @@
+use dioxus::prelude::*;
+use shared::musicbrainz::Artist;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    /// MusicBrainz id of the artist to suggest others for.
+    pub artist_id: String,
+    /// Opens the discography of a suggested artist.
+    pub on_select: EventHandler<Artist>,
+}
+
+/// Artists related to one through MusicBrainz, to go on from their albums to
+/// someone else's. They are only looked up once asked for, as that takes a
+/// few MusicBrainz requests.
+#[component]
+pub fn SimilarArtists(props: Props) -> Element {
+    let mut requested = use_signal(|| false);
+    let artist_id = props.artist_id.clone();
+    let similar = use_resource(move || {
+        let artist_id = artist_id.clone();
+        async move {
+            if !requested() {
+                return None;
+            }
+            Some(
+                api::get_similar_artists(artist_id)
+                    .await
+                    .unwrap_or_default(),
+            )
+        }
+    });
+
+    let artists = similar.read().clone().flatten();
+
+    rsx! {
+      div { class: "p-4 border-t border-gray-700",
+        if !requested() {
+          button {
+            class: "text-sm text-teal-400 hover:text-teal-300",
+            onclick: move |_| requested.set(true),
+            "You might also like..."
+          }
+        } else {
+          h4 { class: "text-sm font-semibold text-gray-300 mb-2", "You might also like" }
+          match artists {
+            None => rsx! {
+              p { class: "text-sm text-gray-400", "Loading..." }
+            },
+            Some(artists) if artists.is_empty() => rsx! {
+              p { class: "text-sm text-gray-400", "MusicBrainz knows of no related artists." }
+            },
+            Some(artists) => rsx! {
+              div { class: "flex flex-wrap gap-2",
+                for artist in artists {
+                  button {
+                    key: "{artist.id}",
+                    class: "text-sm bg-gray-700 hover:bg-gray-600 text-teal-300 rounded-full px-3 py-1",
+                    title: "{artist.disambiguation}",
+                    onclick: {
+                        let artist = artist.clone();
+                        move |_| props.on_select.call(artist.clone())
+                    },
+                    "{artist.name}"
+                  }
+                }
+              }
+            },
+          }
+        }
+      }
+    }
+}
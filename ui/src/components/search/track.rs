@@ -1,10 +1,14 @@
 use dioxus::prelude::*;
 use shared::musicbrainz::Track;
 
+use crate::GenreChips;
+
 #[derive(Props, PartialEq, Clone)]
 pub struct Props {
     pub track: Track,
     pub on_album_click: EventHandler<String>,
+    /// Downloads the track on its own, as a single.
+    pub on_download: EventHandler<Track>,
 }
 
 #[component]
@@ -32,11 +36,19 @@ pub fn TrackResult(props: Props) -> Element {
                   }
               }
             }
+            GenreChips { genres: track.genres.clone() }
           }
 
-          if let Some(duration) = &track.duration {
-            p { class: "text-sm font-mono text-gray-400 whitespace-nowrap pl-4",
-              "{duration}"
+          div { class: "flex items-center gap-4 pl-4",
+            if let Some(duration) = &track.duration {
+              p { class: "text-sm font-mono text-gray-400 whitespace-nowrap",
+                "{duration}"
+              }
+            }
+            button {
+              class: "text-sm text-teal-400 hover:text-teal-300",
+              onclick: move |_| props.on_download.call(props.track.clone()),
+              "Download"
             }
           }
         }
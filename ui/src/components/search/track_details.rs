@@ -0,0 +1,147 @@
+use dioxus::prelude::*;
+use shared::slskd::TrackResult;
+
+use crate::ScoreBar;
+
+fn format_duration(seconds: i32) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn format_size(bytes: i64) -> String {
+    format!("{bytes} bytes ({:.1} MB)", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Copies the file and how it was matched, as JSON, to paste into a report
+/// about a bad ranking.
+fn copy_report(track: &TrackResult) -> Result<(), String> {
+    let eval = document::eval(
+        r#"
+        const track = await dioxus.recv();
+        await navigator.clipboard.writeText(JSON.stringify(track, null, 2));
+        "#,
+    );
+    eval.send(track).map_err(|e| e.to_string())
+}
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    pub track: TrackResult,
+}
+
+/// Everything known about one download option: where the file is, what the
+/// uploader reported, and how it was matched to the track.
+#[component]
+pub fn TrackDetails(props: Props) -> Element {
+    let track = props.track;
+    let mut copied = use_signal(|| None::<Result<(), String>>);
+    let base = &track.base;
+    let not_reported = "Not reported".to_string();
+    let free_slot = if base.has_free_upload_slot {
+        "Yes"
+    } else {
+        "No"
+    };
+    let rows = [
+        ("Path", base.filename.clone()),
+        ("Size", format_size(base.size)),
+        (
+            "Bitrate",
+            base.bitrate
+                .map(|b| format!("{b} kbps"))
+                .unwrap_or_else(|| not_reported.clone()),
+        ),
+        (
+            "Resolution",
+            base.resolution().unwrap_or_else(|| not_reported.clone()),
+        ),
+        (
+            "Measured bitrate",
+            match (base.measured_kbps(), base.suspected_transcode()) {
+                (Some(kbps), true) => format!("{kbps:.0} kbps, likely a transcode"),
+                (Some(kbps), false) => format!("{kbps:.0} kbps"),
+                (None, _) => "Unknown".to_string(),
+            },
+        ),
+        (
+            "Duration",
+            base.duration
+                .map(format_duration)
+                .unwrap_or_else(|| not_reported.clone()),
+        ),
+        ("Uploader", base.username.clone()),
+        ("Free slot", free_slot.to_string()),
+        (
+            "Upload speed",
+            format!("{:.0} KB/s", base.upload_speed as f64 / 1024.0),
+        ),
+        ("Queue length", base.queue_length.to_string()),
+    ];
+
+    rsx! {
+      div { class: "ml-7 mb-2 p-3 rounded bg-gray-800 text-xs text-gray-300 space-y-3",
+        dl { class: "grid grid-cols-[max-content_1fr] gap-x-4 gap-y-1",
+          for (label , value) in rows {
+            dt { class: "text-gray-400", {label} }
+            dd { class: "break-all", {value} }
+          }
+        }
+        if let Some(matched) = &track.match_details {
+          div {
+            p { class: "text-gray-400 mb-1",
+              "Matched as {matched.guessed_artist} - {matched.guessed_album} - {matched.matched_track}"
+            }
+            div { class: "flex flex-wrap gap-x-4 gap-y-1",
+              ScoreBar { score: matched.artist_score, label: "Artist" }
+              ScoreBar { score: matched.album_score, label: "Album" }
+              ScoreBar { score: matched.track_score, label: "Track" }
+              if let Some(duration_score) = matched.duration_score {
+                ScoreBar { score: duration_score, label: "Length" }
+              }
+              ScoreBar { score: matched.total_score, label: "Total" }
+            }
+            if !matched.stripped_suffixes.is_empty() {
+              p { class: "text-gray-400 mt-1",
+                {format!("Compared without: {}", matched.stripped_suffixes.join(", "))}
+              }
+            }
+            div { class: "flex items-center gap-3 mt-2",
+              button {
+                class: "text-teal-300 hover:text-teal-200",
+                title: "The file, its uploader and every score above, as JSON",
+                onclick: {
+                    let track = track.clone();
+                    move |e: MouseEvent| {
+                        e.stop_propagation();
+                        copied.set(Some(copy_report(&track)));
+                    }
+                },
+                "Copy match report"
+              }
+              match copied() {
+                  Some(Ok(())) => rsx! {
+                    span { class: "text-gray-400", "Copied, paste it into your issue" }
+                  },
+                  Some(Err(e)) => rsx! {
+                    span { class: "text-red-400", "Could not copy: {e}" }
+                  },
+                  None => rsx! {},
+              }
+            }
+          }
+        }
+        if !base.alternate_sources.is_empty() {
+          div {
+            p { class: "text-gray-400 mb-1", "Identical copies" }
+            ul { class: "space-y-1",
+              for source in base.alternate_sources.iter() {
+                li { key: "{source.username}{source.filename}", class: "break-all",
+                  span { class: "text-teal-200", "{source.username}" }
+                  " {source.filename} ({source.upload_speed / 1024} KB/s, {source.queue_length} queued)"
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
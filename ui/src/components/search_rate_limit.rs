@@ -0,0 +1,127 @@
+use dioxus::prelude::*;
+use shared::slskd::{SearchRateLimit, SearchRateUsage};
+
+use crate::use_auth;
+
+fn usage_line(usage: &SearchRateUsage) -> String {
+    let mut line = format!(
+        "{} of {} searches used in the last {}s",
+        usage.used, usage.limit.max_searches, usage.limit.window_seconds
+    );
+    if usage.waiting > 0 {
+        line.push_str(&format!(", {} waiting", usage.waiting));
+    }
+    if let Some(seconds) = usage.next_slot_seconds {
+        if usage.used >= usage.limit.max_searches {
+            line.push_str(&format!(", next slot in {seconds}s"));
+        }
+    }
+    line
+}
+
+/// Admin form for how many Soulseek searches may start per window, with how
+/// much of the current window is used.
+#[component]
+pub fn SearchRateLimitSettings() -> Element {
+    let auth = use_auth();
+    let mut usage = use_signal::<Option<SearchRateUsage>>(|| None);
+    let mut max_searches = use_signal(String::new);
+    let mut window_seconds = use_signal(String::new);
+    let mut error = use_signal(String::new);
+    let mut success_msg = use_signal(String::new);
+
+    let fetch = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_search_rate_usage(token).await {
+                Ok(fetched) => usage.set(Some(fetched)),
+                Err(e) => error.set(format!("Failed to read the search rate limit: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch().await;
+        if let Some(current) = usage() {
+            max_searches.set(current.limit.max_searches.to_string());
+            window_seconds.set(current.limit.window_seconds.to_string());
+        }
+    });
+
+    let save = move |_| async move {
+        error.set(String::new());
+        success_msg.set(String::new());
+        let (Ok(max_searches), Ok(window_seconds)) = (
+            max_searches().trim().parse::<usize>(),
+            window_seconds().trim().parse::<i64>(),
+        ) else {
+            error.set("Searches and window must be numbers".to_string());
+            return;
+        };
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let limit = SearchRateLimit {
+            max_searches,
+            window_seconds,
+        };
+        match api::set_search_rate_limit(token, limit).await {
+            Ok(saved) => {
+                usage.set(Some(saved));
+                success_msg.set("Search rate limit saved".to_string());
+            }
+            Err(e) => error.set(e.to_string()),
+        }
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mt-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Search Rate Limit" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "The Soulseek server bans clients that search too often. Searches over the limit wait for a slot instead. Lower it if slskd gets disconnected after bursts of searches."
+        }
+        if let Some(current) = usage() {
+          div { class: "flex items-center gap-4 mb-4 text-sm text-gray-300",
+            span { {usage_line(&current)} }
+            button {
+              class: "text-gray-400 hover:text-white",
+              onclick: move |_| async move { fetch().await },
+              "Refresh"
+            }
+          }
+        }
+        div { class: "grid grid-cols-1 md:grid-cols-2 gap-4 mb-4",
+          div {
+            label { class: "block text-sm font-medium mb-1", "Searches per window" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{max_searches}",
+              oninput: move |e| max_searches.set(e.value()),
+              "type": "number",
+              min: "1",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Window (seconds)" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{window_seconds}",
+              oninput: move |e| window_seconds.set(e.value()),
+              "type": "number",
+              min: "10",
+            }
+          }
+        }
+        button {
+          class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+          onclick: save,
+          "Save"
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+        if !success_msg().is_empty() {
+          p { class: "text-sm text-teal-400 mt-2", "{success_msg}" }
+        }
+      }
+    }
+}
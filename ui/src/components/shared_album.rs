@@ -0,0 +1,108 @@
+use api::SharedAlbum;
+use dioxus::prelude::*;
+
+use crate::Button;
+
+#[derive(Props, PartialEq, Clone)]
+pub struct Props {
+    share_id: String,
+}
+
+/// Public page of a share link: the track list, with streaming and a zip
+/// download. Asks for the password first if the link has one.
+#[component]
+pub fn SharedAlbumPage(props: Props) -> Element {
+    let mut album = use_signal::<Option<SharedAlbum>>(|| None);
+    let mut password = use_signal(String::new);
+    let mut needs_password = use_signal(|| false);
+    let mut error = use_signal(String::new);
+
+    let share_id = props.share_id.clone();
+    let load = move || {
+        let share_id = share_id.clone();
+        async move {
+            error.set(String::new());
+            let password = Some(password()).filter(|p| !p.is_empty());
+            match api::get_shared_album(share_id, password).await {
+                Ok(fetched) => {
+                    needs_password.set(false);
+                    album.set(Some(fetched));
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    if message.contains("Password required") {
+                        needs_password.set(true);
+                    } else {
+                        error.set(message);
+                    }
+                }
+            }
+        }
+    };
+
+    let initial = load.clone();
+    use_future(move || {
+        let load = initial.clone();
+        async move { load().await }
+    });
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-2xl mx-auto my-10 font-sans",
+        if let Some(album) = album() {
+          div { class: "flex justify-between items-start mb-6",
+            div {
+              h3 { class: "text-2xl font-bold text-teal-400", "{album.album}" }
+              p { class: "text-gray-300", "{album.artist}" }
+            }
+            a {
+              class: "bg-teal-500 hover:bg-teal-600 text-white font-bold py-2 px-4 rounded-md",
+              href: "{album.zip_url}",
+              "Download zip"
+            }
+          }
+          ul { class: "space-y-3",
+            for track in album.tracks.iter() {
+              li { key: "{track.url}", class: "bg-gray-700 p-3 rounded-md",
+                p { class: "text-sm mb-2",
+                  if let Some(number) = track.track {
+                    span { class: "text-gray-400 mr-2", "{number}." }
+                  }
+                  "{track.title}"
+                  if track.artist != album.artist {
+                    span { class: "text-gray-400", " — {track.artist}" }
+                  }
+                }
+                audio {
+                  class: "w-full h-8",
+                  src: "{track.url}",
+                  controls: true,
+                  preload: "none",
+                }
+              }
+            }
+          }
+          p { class: "text-xs text-gray-500 mt-6", "This link expires {album.expires_at}." }
+        } else if needs_password() {
+          p { class: "text-gray-300 mb-4", "This album is password protected." }
+          div { class: "flex gap-2",
+            input {
+              class: "flex-grow p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{password}",
+              oninput: move |e| password.set(e.value()),
+              "type": "password",
+            }
+            Button {
+              onclick: {
+                  let load = load.clone();
+                  move |_| load()
+              },
+              "Open"
+            }
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-red-400 mt-4", "{error}" }
+        }
+      }
+    }
+}
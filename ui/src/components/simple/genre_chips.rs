@@ -0,0 +1,24 @@
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct Props {
+    genres: Vec<String>,
+}
+
+/// MusicBrainz genres and tags of a track or album, one chip each.
+#[component]
+pub fn GenreChips(props: Props) -> Element {
+    rsx! {
+      if !props.genres.is_empty() {
+        div { class: "flex flex-wrap gap-1 mt-1",
+          for genre in props.genres.iter() {
+            span {
+              key: "{genre}",
+              class: "text-xs bg-gray-800 text-gray-300 border border-gray-600 rounded-full px-2",
+              "{genre}"
+            }
+          }
+        }
+      }
+    }
+}
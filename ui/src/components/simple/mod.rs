@@ -1,5 +1,9 @@
 mod button;
 mod checkbox;
+mod genre_chips;
+mod score_bar;
 
 pub use button::*;
 pub use checkbox::*;
+pub use genre_chips::*;
+pub use score_bar::*;
@@ -0,0 +1,51 @@
+use dioxus::prelude::*;
+
+/// Score bounds between the red, yellow and teal bands of a [`ScoreBar`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct ScoreThresholds {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Default for ScoreThresholds {
+    fn default() -> Self {
+        Self {
+            low: 0.5,
+            high: 0.8,
+        }
+    }
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct Props {
+    /// Score between 0 and 1.
+    score: f64,
+    #[props(optional, default)]
+    thresholds: ScoreThresholds,
+    #[props(optional, into)]
+    label: Option<String>,
+}
+
+#[component]
+pub fn ScoreBar(props: Props) -> Element {
+    let score = props.score.clamp(0.0, 1.0);
+    let color = if score < props.thresholds.low {
+        "bg-red-500"
+    } else if score < props.thresholds.high {
+        "bg-yellow-500"
+    } else {
+        "bg-teal-500"
+    };
+    let width = format!("{:.0}%", score * 100.0);
+
+    rsx! {
+      div { class: "flex items-center gap-2", title: "{score:.2}",
+        if let Some(label) = &props.label {
+          span { class: "text-xs text-gray-400", "{label}" }
+        }
+        div { class: "w-20 h-2 rounded bg-gray-600 overflow-hidden",
+          div { class: "h-full {color}", style: "width: {width}" }
+        }
+      }
+    }
+}
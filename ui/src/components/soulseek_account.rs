@@ -0,0 +1,154 @@
+use dioxus::prelude::*;
+use shared::slskd::{ServerState, SoulseekAccount};
+
+use crate::use_auth;
+
+fn state_line(state: &ServerState) -> (&'static str, String) {
+    if state.is_logged_in {
+        match &state.username {
+            Some(username) => ("text-green-400", format!("✓ Logged in as {username}")),
+            None => ("text-green-400", format!("✓ Logged in ({})", state.state)),
+        }
+    } else if state.is_connected {
+        (
+            "text-yellow-400",
+            format!("Connected but not logged in ({})", state.state),
+        )
+    } else {
+        ("text-red-400", format!("✗ Not connected ({})", state.state))
+    }
+}
+
+/// Admin form for the Soulseek account slskd logs in with, and its listen port.
+#[component]
+pub fn SoulseekAccountSettings() -> Element {
+    let auth = use_auth();
+    let mut username = use_signal(String::new);
+    let mut password = use_signal(String::new);
+    let mut listen_port = use_signal(String::new);
+    let mut state = use_signal::<Option<ServerState>>(|| None);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(String::new);
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_soulseek_status(token).await {
+                Ok(status) => {
+                    username.set(status.account.username);
+                    listen_port.set(
+                        status
+                            .account
+                            .listen_port
+                            .map(|p| p.to_string())
+                            .unwrap_or_default(),
+                    );
+                    state.set(Some(status.state));
+                }
+                Err(e) => error.set(format!("Failed to read the slskd configuration: {e}")),
+            }
+        }
+    });
+
+    let save = move |_| async move {
+        error.set(String::new());
+        let listen_port = match listen_port().trim() {
+            "" => None,
+            port => match port.parse::<u16>() {
+                Ok(port) => Some(port),
+                Err(_) => {
+                    error.set("Listen port must be a number between 1 and 65535".to_string());
+                    return;
+                }
+            },
+        };
+        let Some(token) = auth.token() else {
+            return;
+        };
+        let account = SoulseekAccount {
+            username: username(),
+            listen_port,
+        };
+        busy.set(true);
+        match api::update_soulseek_account(token, account, Some(password())).await {
+            Ok(new_state) => {
+                password.set(String::new());
+                state.set(Some(new_state));
+            }
+            Err(e) => error.set(e.to_string()),
+        }
+        busy.set(false);
+    };
+
+    let test = move |_| async move {
+        error.set(String::new());
+        let Some(token) = auth.token() else {
+            return;
+        };
+        busy.set(true);
+        match api::test_soulseek_connection(token).await {
+            Ok(new_state) => state.set(Some(new_state)),
+            Err(e) => error.set(e.to_string()),
+        }
+        busy.set(false);
+    };
+
+    rsx! {
+      div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mt-8",
+        h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Soulseek Account" }
+        p { class: "text-gray-400 text-sm mb-4",
+          "The account slskd uses on the Soulseek network. Saving writes it to the slskd configuration, which needs remote_configuration: true, and reconnects."
+        }
+        if let Some((class, line)) = state().as_ref().map(state_line) {
+          p { class: "text-sm mb-4 {class}", "{line}" }
+        }
+        div { class: "grid grid-cols-1 md:grid-cols-3 gap-4 mb-4",
+          div {
+            label { class: "block text-sm font-medium mb-1", "Username" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{username}",
+              oninput: move |e| username.set(e.value()),
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Password" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{password}",
+              oninput: move |e| password.set(e.value()),
+              placeholder: "Unchanged",
+              "type": "password",
+            }
+          }
+          div {
+            label { class: "block text-sm font-medium mb-1", "Listen Port" }
+            input {
+              class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+              value: "{listen_port}",
+              oninput: move |e| listen_port.set(e.value()),
+              placeholder: "50300",
+              "type": "number",
+            }
+          }
+        }
+        div { class: "flex gap-2",
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors disabled:bg-gray-600",
+            disabled: busy(),
+            onclick: save,
+            "Save and Reconnect"
+          }
+          button {
+            class: "bg-indigo-600 hover:bg-indigo-700 text-white font-bold py-2 px-4 rounded transition-colors disabled:bg-gray-600",
+            disabled: busy(),
+            onclick: test,
+            "Test Connection"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
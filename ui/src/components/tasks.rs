@@ -0,0 +1,194 @@
+use api::{db::TaskRun, TaskOverview};
+use dioxus::prelude::*;
+
+use crate::{use_auth, Checkbox};
+
+#[derive(Props, PartialEq, Clone)]
+struct TaskRowProps {
+    overview: TaskOverview,
+    on_change: EventHandler,
+}
+
+/// One scheduled task, with its schedule editable in place.
+#[component]
+fn TaskRow(props: TaskRowProps) -> Element {
+    let auth = use_auth();
+    let task = props.overview.task.clone();
+    let upcoming = props.overview.upcoming.join(", ");
+    let mut cron = use_signal(|| task.cron.clone());
+    let mut jitter = use_signal(|| task.jitter_seconds.to_string());
+    let mut enabled = use_signal(|| task.enabled);
+    let mut error = use_signal(String::new);
+
+    let name = task.name.clone();
+    let save = move |_| {
+        let name = name.clone();
+        async move {
+            error.set(String::new());
+            let Ok(jitter_seconds) = jitter().trim().parse::<i64>() else {
+                error.set("Jitter must be a number of seconds".to_string());
+                return;
+            };
+            let Some(token) = auth.token() else {
+                return;
+            };
+            match api::update_scheduled_task(token, name, cron(), jitter_seconds, enabled()).await {
+                Ok(()) => props.on_change.call(()),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    let name = task.name.clone();
+    let run_now = move |_| {
+        let name = name.clone();
+        async move {
+            error.set(String::new());
+            let Some(token) = auth.token() else {
+                return;
+            };
+            match api::run_task_now(token, name).await {
+                Ok(()) => props.on_change.call(()),
+                Err(e) => error.set(e.to_string()),
+            }
+        }
+    };
+
+    rsx! {
+      li { class: "bg-gray-700 p-4 rounded-md",
+        div { class: "flex justify-between items-center mb-1",
+          h4 { class: "font-semibold text-teal-200", "{task.name}" }
+          if props.overview.running {
+            span { class: "text-xs font-semibold px-2 py-1 rounded bg-blue-600", "Running" }
+          }
+        }
+        p { class: "text-sm text-gray-400 mb-3", "{props.overview.description}" }
+        div { class: "grid grid-cols-1 md:grid-cols-3 gap-2 mb-3 items-end",
+          div {
+            label { class: "block text-xs text-gray-400 mb-1", "Cron (UTC)" }
+            input {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none font-mono text-sm",
+              value: "{cron}",
+              oninput: move |e| cron.set(e.value()),
+              "type": "text",
+            }
+          }
+          div {
+            label { class: "block text-xs text-gray-400 mb-1", "Jitter (seconds)" }
+            input {
+              class: "w-full p-2 rounded bg-gray-800 border border-gray-600 focus:border-teal-500 focus:outline-none text-sm",
+              value: "{jitter}",
+              oninput: move |e| jitter.set(e.value()),
+              "type": "number",
+            }
+          }
+          div {
+            class: "flex items-center gap-2 cursor-pointer pb-2",
+            onclick: move |_| enabled.toggle(),
+            Checkbox { is_selected: enabled() }
+            label { class: "text-sm cursor-pointer", "Enabled" }
+          }
+        }
+        div { class: "text-xs text-gray-400 mb-3",
+          if let Some(next) = &task.next_run_at {
+            p { "Next run: {next}" }
+          }
+          if !upcoming.is_empty() {
+            p { "Upcoming: {upcoming}" }
+          }
+        }
+        div { class: "flex gap-2",
+          button {
+            class: "bg-teal-600 hover:bg-teal-700 text-white text-sm font-semibold py-1 px-3 rounded",
+            onclick: save,
+            "Save"
+          }
+          button {
+            class: "bg-gray-600 hover:bg-gray-500 text-white text-sm font-semibold py-1 px-3 rounded disabled:opacity-50",
+            disabled: props.overview.running,
+            onclick: run_now,
+            "Run now"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-sm text-red-400 mt-2", "{error}" }
+        }
+      }
+    }
+}
+
+/// Admin view of background tasks: their schedules and recent runs.
+#[component]
+pub fn ScheduledTasks() -> Element {
+    let auth = use_auth();
+    let mut tasks = use_signal(Vec::<TaskOverview>::new);
+    let mut runs = use_signal(Vec::<TaskRun>::new);
+    let mut error = use_signal(String::new);
+
+    let fetch = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_scheduled_tasks(token.clone()).await {
+                Ok(fetched) => tasks.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch tasks: {e}")),
+            }
+            if let Ok(fetched) = api::get_task_runs(token).await {
+                runs.set(fetched);
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch().await;
+    });
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-3xl mx-auto my-10 font-sans",
+        div { class: "flex justify-between items-center mb-6",
+          h3 { class: "text-2xl font-bold text-teal-400", "Scheduled Tasks" }
+          button {
+            class: "text-sm text-gray-300 hover:text-white",
+            onclick: move |_| async move { fetch().await },
+            "Refresh"
+          }
+        }
+        if !error().is_empty() {
+          p { class: "text-red-400 mb-4", "{error}" }
+        }
+        ul { class: "space-y-4 mb-8",
+          for overview in tasks.read().iter().cloned() {
+            TaskRow {
+              key: "{overview.task.name}",
+              overview,
+              on_change: move |_| async move { fetch().await },
+            }
+          }
+        }
+        h4 { class: "text-lg font-semibold text-indigo-300 mb-2", "Recent Runs" }
+        if runs.read().is_empty() {
+          p { class: "text-sm text-gray-400", "No runs yet." }
+        }
+        ul { class: "text-sm space-y-1",
+          for run in runs.read().iter() {
+            li { key: "{run.id}", class: "flex gap-3",
+              span { class: "text-gray-400 whitespace-nowrap", "{run.started_at}" }
+              span { class: "font-medium whitespace-nowrap", "{run.task}" }
+              match run.success {
+                  None => rsx! {
+                    span { class: "text-blue-400", "Running" }
+                  },
+                  Some(true) => rsx! {
+                    span { class: "text-teal-400", "OK" }
+                  },
+                  Some(false) => rsx! {
+                    span { class: "text-red-400", "Failed" }
+                  },
+              }
+              if let Some(message) = &run.message {
+                span { class: "text-gray-300 truncate", title: "{message}", "{message}" }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use shared::slskd::FileEntry;
+
+use crate::use_auth;
+
+/// How often uploads are refreshed, in milliseconds.
+const POLL_INTERVAL: u32 = 5_000;
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn format_speed(bytes_per_sec: f64) -> String {
+    if bytes_per_sec >= 1024.0 * 1024.0 {
+        format!("{:.1} MB/s", bytes_per_sec / (1024.0 * 1024.0))
+    } else {
+        format!("{:.0} KB/s", bytes_per_sec / 1024.0)
+    }
+}
+
+/// Uploads grouped by the user downloading them, users in order of arrival.
+fn by_user(uploads: &[FileEntry]) -> Vec<(String, Vec<FileEntry>)> {
+    let mut users: Vec<(String, Vec<FileEntry>)> = Vec::new();
+    for upload in uploads {
+        match users.iter_mut().find(|(user, _)| *user == upload.username) {
+            Some((_, files)) => files.push(upload.clone()),
+            None => users.push((upload.username.clone(), vec![upload.clone()])),
+        }
+    }
+    users
+}
+
+/// Admin view of what other Soulseek users download from the shares.
+#[component]
+pub fn Uploads() -> Element {
+    let auth = use_auth();
+    let mut uploads = use_signal(Vec::<FileEntry>::new);
+    let mut error = use_signal(String::new);
+
+    let fetch = move || async move {
+        if let Some(token) = auth.token() {
+            match api::get_uploads(token).await {
+                Ok(fetched) => {
+                    error.set(String::new());
+                    uploads.set(fetched);
+                }
+                Err(e) => error.set(format!("Failed to fetch uploads: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        loop {
+            fetch().await;
+            TimeoutFuture::new(POLL_INTERVAL).await;
+        }
+    });
+
+    let cancel = move |username: String, id: String, remove: bool| async move {
+        let Some(token) = auth.token() else {
+            return;
+        };
+        match api::cancel_upload(token, username, id, remove).await {
+            Ok(()) => fetch().await,
+            Err(e) => error.set(format!("Failed to cancel upload: {e}")),
+        }
+    };
+
+    let users = by_user(&uploads.read());
+    let active = uploads.read().iter().filter(|u| !u.is_finished()).count();
+
+    rsx! {
+      div { class: "bg-gray-800 text-white p-6 sm:p-8 rounded-lg shadow-xl max-w-4xl mx-auto my-10 font-sans",
+        div { class: "flex justify-between items-center mb-2",
+          h3 { class: "text-2xl font-bold text-teal-400", "Uploads" }
+          span { class: "text-sm text-gray-400", "{active} active" }
+        }
+        p { class: "text-sm text-gray-400 mb-6",
+          "Files other Soulseek users are downloading from your shares."
+        }
+        if !error().is_empty() {
+          p { class: "text-red-400 mb-4", "{error}" }
+        }
+        if users.is_empty() {
+          p { class: "text-sm text-gray-400", "Nobody is downloading from you right now." }
+        }
+        for (username , files) in users {
+          div { key: "{username}", class: "mb-6",
+            h4 { class: "font-semibold text-indigo-300 mb-2", "{username}" }
+            ul { class: "space-y-2",
+              for upload in files {
+                li {
+                  key: "{upload.id}",
+                  class: "bg-gray-700 p-3 rounded flex items-center gap-4 text-sm",
+                  div { class: "flex-grow min-w-0",
+                    p { class: "truncate", title: "{upload.filename}", "{file_name(&upload.filename)}" }
+                    div { class: "w-full bg-gray-600 rounded-full h-1.5 mt-1",
+                      div {
+                        class: "bg-teal-500 h-1.5 rounded-full",
+                        style: "width: {upload.percent_complete}%",
+                      }
+                    }
+                  }
+                  span { class: "text-gray-400 whitespace-nowrap", "{upload.state_description}" }
+                  if upload.is_in_progress() {
+                    span { class: "text-gray-400 whitespace-nowrap", {format_speed(upload.average_speed)} }
+                  }
+                  button {
+                    class: "text-gray-400 hover:text-red-400 whitespace-nowrap",
+                    onclick: {
+                        let username = upload.username.clone();
+                        let id = upload.id.clone();
+                        let finished = upload.is_finished();
+                        move |_| cancel(username.clone(), id.clone(), finished)
+                    },
+                    if upload.is_finished() {
+                      "Remove"
+                    } else {
+                      "Cancel"
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+
+use crate::{use_auth, Modal};
+
+/// What changed since the user's last visit, shown once after an upgrade
+/// until dismissed.
+#[component]
+pub fn WhatsNew() -> Element {
+    let auth = use_auth();
+    let mut entries = use_signal(Vec::new);
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            if let Ok(fetched) = api::whats_new(token).await {
+                entries.set(fetched);
+            }
+        }
+    });
+
+    let dismiss = move |_| {
+        entries.set(Vec::new());
+        if let Some(token) = auth.token() {
+            spawn(async move {
+                let _ = api::dismiss_whats_new(token).await;
+            });
+        }
+    };
+
+    if entries.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+      Modal {
+        on_close: dismiss,
+        header: rsx! {
+          h3 { class: "text-xl font-bold text-teal-400", "What's new" }
+        },
+        div { class: "space-y-4 mt-4",
+          for entry in entries() {
+            div { key: "{entry.version}",
+              h4 { class: "font-semibold text-indigo-300 mb-1", "Version {entry.version}" }
+              ul { class: "list-disc list-inside space-y-1 text-sm text-gray-300",
+                for (i , highlight) in entry.highlights.iter().enumerate() {
+                  li { key: "{i}", "{highlight}" }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+}
@@ -6,5 +6,11 @@ pub use navbar::Navbar;
 mod auth;
 pub use auth::*;
 
+mod branding;
+pub use branding::*;
+
+mod browse;
+pub use browse::*;
+
 mod components;
 pub use components::*;
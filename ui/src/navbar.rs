@@ -1,15 +1,22 @@
 use dioxus::prelude::*;
 
+use crate::use_branding;
+
 #[component]
 pub fn Navbar(children: Element) -> Element {
+    let branding = use_branding();
+
     rsx! {
         nav { class: "bg-gray-900 border-b border-gray-800 sticky top-0 z-50",
             div { class: "container mx-auto px-4",
                 div { class: "flex items-center justify-between h-16",
                     div { class: "flex items-center gap-8",
-                        div { class: "flex-shrink-0",
-                            span { class: "text-teal-400 font-bold text-xl tracking-wider",
-                                "SOULBEET"
+                        div { class: "flex-shrink-0 flex items-center gap-2",
+                            if let Some(logo_url) = branding.logo_url() {
+                                img { class: "h-8 w-8 object-contain", src: "{logo_url}", alt: "" }
+                            }
+                            span { class: "text-teal-400 font-bold text-xl tracking-wider uppercase",
+                                "{branding.name()}"
                             }
                         }
                         div { class: "flex items-baseline space-x-4", {children} }
@@ -0,0 +1,32 @@
+//! Cache headers of the web app. Files bundled through `asset!` are named
+//! after a hash of their content, so browsers may keep them for good, while
+//! the pages must be checked on every load for a new release to reach them.
+
+use dioxus::fullstack::axum::{
+    extract::Request,
+    http::{header, HeaderValue, Method},
+    middleware::Next,
+    response::Response,
+};
+
+/// For the hashed assets, the wasm chunks of lazy routes included.
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// For everything else: cached, but revalidated before use.
+const REVALIDATE: &str = "no-cache";
+
+pub async fn cache_control(request: Request, next: Next) -> Response {
+    let cacheable = request.method() == Method::GET;
+    let hashed = request.uri().path().starts_with("/assets/");
+    let mut response = next.run(request).await;
+
+    if cacheable
+        && response.status().is_success()
+        && !response.headers().contains_key(header::CACHE_CONTROL)
+    {
+        let value = if hashed { IMMUTABLE } else { REVALIDATE };
+        response
+            .headers_mut()
+            .insert(header::CACHE_CONTROL, HeaderValue::from_static(value));
+    }
+    response
+}
@@ -1,10 +1,15 @@
 use auth::{use_auth, AuthProvider};
 use dioxus::prelude::*;
 
-use ui::Navbar;
-use views::{Home, Login, Settings};
+use ui::{
+    use_browse_mode, BrandingProvider, BrowseModeProvider, ConnectionStatus, Navbar, RouteSkeleton,
+    WhatsNew,
+};
+use views::{Downloads, Home, Library, Login, MatchDebug, Settings, Share, Tasks, Uploads};
 
 mod auth;
+#[cfg(feature = "server")]
+mod cache;
 mod views;
 
 #[derive(Debug, Clone, Routable, PartialEq)]
@@ -13,18 +18,42 @@ pub enum Route {
     #[route("/login")]
     Login {},
 
+    // Public, reached through share links by people without an account.
+    #[route("/share/:share_id")]
+    Share { share_id: String },
+
     #[layout(AuthGuard)]
         #[layout(WebNavbar)]
             #[route("/")]
             Home {},
+            #[route("/downloads")]
+            Downloads {},
+            #[route("/library")]
+            Library {},
             #[route("/settings")]
             Settings {},
+            #[route("/tasks")]
+            Tasks {},
+            #[route("/uploads")]
+            Uploads {},
+            // Not linked from the navbar, shared with users reporting missing matches.
+            #[route("/debug/match")]
+            MatchDebug {},
 }
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
 const MAIN_CSS: Asset = asset!("/assets/tailwind.css");
 
 fn main() {
+    #[cfg(feature = "server")]
+    dioxus::serve(|| async move {
+        api::startup().await;
+        let cache = dioxus::fullstack::axum::middleware::from_fn(cache::cache_control);
+        let browse = dioxus::fullstack::axum::middleware::from_fn(api::browse_guard);
+        Ok(dioxus::server::router(App).layer(cache).layer(browse))
+    });
+
+    #[cfg(not(feature = "server"))]
     dioxus::launch(App);
 }
 
@@ -34,7 +63,16 @@ fn App() -> Element {
         document::Link { rel: "icon", href: FAVICON }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
 
-        AuthProvider { Router::<Route> {} }
+        BrandingProvider {
+            BrowseModeProvider {
+                AuthProvider {
+                    // Every page but the home and login ones is loaded on first visit.
+                    SuspenseBoundary { fallback: |_| rsx! { RouteSkeleton {} },
+                        Router::<Route> {}
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -66,6 +104,7 @@ fn AuthGuard() -> Element {
 #[component]
 fn WebNavbar() -> Element {
     let mut auth = use_auth();
+    let browse = use_browse_mode();
     let nav = use_navigator();
 
     let logout = move |_| {
@@ -75,8 +114,30 @@ fn WebNavbar() -> Element {
 
     rsx! {
         Navbar {
-            Link { to: Route::Home {}, "Home" }
-            Link { to: Route::Settings {}, "Settings" }
+            if browse.enabled() {
+                // Read-only instance: no search, settings or Soulseek status.
+                Link { to: Route::Library {}, "Library" }
+                Link { to: Route::Downloads {}, "History" }
+            } else {
+                Link { to: Route::Home {}, "Home" }
+                Link { to: Route::Library {}, "Library" }
+                Link { to: Route::Downloads {}, "Downloads" }
+                Link { to: Route::Settings {}, "Settings" }
+                if auth.is_admin() {
+                    Link { to: Route::Tasks {}, "Tasks" }
+                    Link { to: Route::Uploads {}, "Uploads" }
+                }
+                if !auth.is_demo() {
+                    ConnectionStatus {}
+                }
+            }
+            if auth.is_demo() {
+                span {
+                    class: "text-xs font-semibold text-indigo-300 border border-indigo-400 rounded px-2 py-1",
+                    title: "Read-only demo: searches are simulated and downloads are disabled",
+                    "DEMO"
+                }
+            }
             button {
                 class: "text-gray-300 hover:text-white px-3 py-2 rounded-md text-sm font-medium",
                 onclick: logout,
@@ -84,6 +145,10 @@ fn WebNavbar() -> Element {
             }
         }
 
-        Outlet::<Route> {}
+        WhatsNew {}
+
+        SuspenseBoundary { fallback: |_| rsx! { RouteSkeleton {} },
+            Outlet::<Route> {}
+        }
     }
 }
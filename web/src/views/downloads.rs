@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component(lazy)]
+pub fn Downloads() -> Element {
+    rsx! {
+        ui::Downloads {}
+    }
+}
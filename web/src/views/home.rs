@@ -1,9 +1,17 @@
 use dioxus::prelude::*;
-use ui::Search;
+use ui::{use_browse_mode, FollowedArtists, Search};
 
 #[component]
 pub fn Home() -> Element {
+    // Browse-only instances have nothing to search, they open on the library.
+    if use_browse_mode().enabled() {
+        return rsx! {
+            ui::Library {}
+        };
+    }
+
     rsx! {
         Search {}
+        FollowedArtists {}
     }
 }
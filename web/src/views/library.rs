@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component(lazy)]
+pub fn Library() -> Element {
+    rsx! {
+        ui::Library {}
+    }
+}
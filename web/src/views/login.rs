@@ -1,4 +1,4 @@
-use api::login;
+use api::{demo_enabled, demo_login, finish_passkey_login, login, start_passkey_login};
 use dioxus::prelude::*;
 
 use crate::Route;
@@ -10,6 +10,8 @@ pub fn Login() -> Element {
     let mut error = use_signal(|| "".to_string());
     let navigator = use_navigator();
     let mut auth = crate::auth::use_auth();
+    let branding = ui::use_branding();
+    let demo = use_resource(|| async { demo_enabled().await.unwrap_or(false) });
 
     let handle_login = move |_| async move {
         match login(username(), password()).await {
@@ -23,11 +25,53 @@ pub fn Login() -> Element {
         }
     };
 
+    let handle_passkey = move |_| async move {
+        error.set("".to_string());
+        if username().trim().is_empty() {
+            error.set("Enter your username to sign in with a passkey".to_string());
+            return;
+        }
+        let result = async {
+            let challenge = start_passkey_login(username())
+                .await
+                .map_err(|e| e.to_string())?;
+            let credential = ui::get_passkey(&challenge.options).await?;
+            finish_passkey_login(challenge.challenge_id, credential)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        .await;
+        match result {
+            Ok(response) => {
+                auth.login(response);
+                navigator.push(Route::Home {});
+            }
+            Err(e) => {
+                error.set(e);
+            }
+        }
+    };
+
+    let handle_demo = move |_| async move {
+        match demo_login().await {
+            Ok(response) => {
+                auth.login(response);
+                navigator.push(Route::Home {});
+            }
+            Err(e) => {
+                error.set(e.to_string());
+            }
+        }
+    };
+
     rsx! {
       div { class: "flex flex-col items-center justify-center min-h-screen bg-gray-900 text-white",
         div { class: "p-8 bg-gray-800 rounded-lg shadow-xl w-full max-w-md",
+          if let Some(logo_url) = branding.logo_url() {
+            img { class: "h-16 mx-auto mb-4 object-contain", src: "{logo_url}", alt: "" }
+          }
           h1 { class: "text-2xl font-bold mb-6 text-center text-teal-400",
-            "Soulbeet Login"
+            "{branding.name()} Login"
           }
           div { class: "mb-4",
             label { class: "block text-sm font-medium mb-1", "Username" }
@@ -57,6 +101,23 @@ pub fn Login() -> Element {
             onclick: handle_login,
             "Login"
           }
+
+          button {
+            class: "w-full mt-3 bg-gray-700 hover:bg-gray-600 text-white font-bold py-2 px-4 rounded transition-colors",
+            onclick: handle_passkey,
+            "Sign in with a passkey"
+          }
+
+          if demo() == Some(true) {
+            button {
+              class: "w-full mt-3 bg-indigo-600 hover:bg-indigo-700 text-white font-bold py-2 px-4 rounded transition-colors",
+              onclick: handle_demo,
+              "Try the demo"
+            }
+            p { class: "mt-2 text-xs text-gray-400 text-center",
+              "Search and browse freely. Results are simulated and nothing is downloaded."
+            }
+          }
         }
       }
     }
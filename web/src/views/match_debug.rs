@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component(lazy)]
+pub fn MatchDebug() -> Element {
+    rsx! {
+        ui::MatchDebugger {}
+    }
+}
@@ -1,7 +1,19 @@
+mod downloads;
 mod home;
+mod library;
 mod login;
+mod match_debug;
 mod settings;
+mod share;
+mod tasks;
+mod uploads;
 
+pub use downloads::Downloads;
 pub use home::Home;
+pub use library::Library;
 pub use login::Login;
+pub use match_debug::MatchDebug;
 pub use settings::Settings;
+pub use share::Share;
+pub use tasks::Tasks;
+pub use uploads::Uploads;
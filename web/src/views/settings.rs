@@ -1,15 +1,33 @@
-use api::{create_user_folder, get_user_folders, register};
+use api::{
+    create_user_folder, db::LyricsMode, delete_bitrate_cutoff, delete_genre_mapping,
+    get_all_folders, get_bitrate_cutoffs, get_genre_mappings, get_library_sharing,
+    get_user_folders, register, set_bitrate_cutoff, set_folder_lyrics, set_genre_mapping,
+    set_library_sharing, validate_config, ConfigReport,
+};
 use dioxus::prelude::*;
 
-#[component]
+#[component(lazy)]
 pub fn Settings() -> Element {
     let mut folder_name = use_signal(|| "".to_string());
     let mut folder_path = use_signal(|| "".to_string());
     let mut folders = use_signal(Vec::new);
+    let mut all_folders = use_signal(Vec::new);
+    let mut editing_permissions = use_signal::<Option<String>>(|| None);
 
     let mut new_username = use_signal(|| "".to_string());
     let mut new_password = use_signal(|| "".to_string());
 
+    let mut config_report = use_signal::<Option<ConfigReport>>(|| None);
+    let mut share_library = use_signal(|| false);
+
+    let mut genre_mappings = use_signal(Vec::new);
+    let mut genre_source = use_signal(|| "".to_string());
+    let mut genre_target = use_signal(|| "".to_string());
+
+    let mut bitrate_cutoffs = use_signal(Vec::new);
+    let mut cutoff_format = use_signal(|| "".to_string());
+    let mut cutoff_bitrate = use_signal(|| "".to_string());
+
     let mut error = use_signal(|| "".to_string());
     let mut success_msg = use_signal(|| "".to_string());
     let auth = crate::auth::use_auth();
@@ -27,6 +45,138 @@ pub fn Settings() -> Element {
         fetch_folders().await;
     });
 
+    let fetch_all_folders = move || async move {
+        if !auth.is_admin() {
+            return;
+        }
+        if let Some(token) = auth.token() {
+            match get_all_folders(token).await {
+                Ok(fetched) => all_folders.set(fetched),
+                Err(e) => error.set(format!("Failed to fetch folders: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_all_folders().await;
+    });
+
+    use_future(move || async move {
+        if let Some(token) = auth.token() {
+            if let Ok(enabled) = get_library_sharing(token).await {
+                share_library.set(enabled);
+            }
+        }
+    });
+
+    let handle_toggle_sharing = move |_| async move {
+        error.set("".to_string());
+        success_msg.set("".to_string());
+
+        if let Some(token) = auth.token() {
+            let enabled = !share_library();
+            match set_library_sharing(token, enabled).await {
+                Ok(_) => {
+                    share_library.set(enabled);
+                    if enabled {
+                        success_msg.set("Your folders are now shared through slskd".to_string());
+                    }
+                }
+                Err(e) => error.set(format!("Failed to update sharing: {e}")),
+            }
+        }
+    };
+
+    let fetch_genre_mappings = move || async move {
+        if let Some(token) = auth.token() {
+            match get_genre_mappings(token).await {
+                Ok(mappings) => genre_mappings.set(mappings),
+                Err(e) => error.set(format!("Failed to fetch genre mappings: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_genre_mappings().await;
+    });
+
+    let handle_add_genre_mapping = move |_| async move {
+        error.set("".to_string());
+        success_msg.set("".to_string());
+
+        if genre_source().is_empty() || genre_target().is_empty() {
+            error.set("Tag and Genre are required".to_string());
+            return;
+        }
+
+        if let Some(token) = auth.token() {
+            match set_genre_mapping(token, genre_source(), genre_target()).await {
+                Ok(_) => {
+                    genre_source.set("".to_string());
+                    genre_target.set("".to_string());
+                    fetch_genre_mappings().await;
+                }
+                Err(e) => error.set(format!("Failed to save genre mapping: {e}")),
+            }
+        }
+    };
+
+    let handle_delete_genre_mapping = move |source: String| async move {
+        if let Some(token) = auth.token() {
+            match delete_genre_mapping(token, source).await {
+                Ok(_) => fetch_genre_mappings().await,
+                Err(e) => error.set(format!("Failed to delete genre mapping: {e}")),
+            }
+        }
+    };
+
+    let fetch_bitrate_cutoffs = move || async move {
+        if let Some(token) = auth.token() {
+            match get_bitrate_cutoffs(token).await {
+                Ok(cutoffs) => bitrate_cutoffs.set(cutoffs),
+                Err(e) => error.set(format!("Failed to fetch bitrate cutoffs: {e}")),
+            }
+        }
+    };
+
+    use_future(move || async move {
+        fetch_bitrate_cutoffs().await;
+    });
+
+    let handle_add_bitrate_cutoff = move |_| async move {
+        error.set("".to_string());
+        success_msg.set("".to_string());
+
+        let Ok(min_bitrate) = cutoff_bitrate().trim().parse::<i32>() else {
+            error.set("Minimum bitrate must be a number in kbps".to_string());
+            return;
+        };
+        if cutoff_format().is_empty() {
+            error.set("Format is required".to_string());
+            return;
+        }
+
+        if let Some(token) = auth.token() {
+            match set_bitrate_cutoff(token, cutoff_format(), min_bitrate).await {
+                Ok(_) => {
+                    cutoff_format.set("".to_string());
+                    cutoff_bitrate.set("".to_string());
+                    fetch_bitrate_cutoffs().await;
+                }
+                Err(e) => error.set(format!("Failed to save bitrate cutoff: {e}")),
+            }
+        }
+    };
+
+    let handle_delete_bitrate_cutoff = move |format: String| async move {
+        if let Some(token) = auth.token() {
+            match delete_bitrate_cutoff(token, format).await {
+                Ok(_) => fetch_bitrate_cutoffs().await,
+                Err(e) => error.set(format!("Failed to delete bitrate cutoff: {e}")),
+            }
+        }
+    };
+
     let handle_add_folder = move |_| async move {
         error.set("".to_string());
         success_msg.set("".to_string());
@@ -43,6 +193,7 @@ pub fn Settings() -> Element {
                     folder_name.set("".to_string());
                     folder_path.set("".to_string());
                     fetch_folders().await;
+                    fetch_all_folders().await;
                 }
                 Err(e) => error.set(format!("Failed to add folder: {e}")),
             }
@@ -51,6 +202,23 @@ pub fn Settings() -> Element {
         }
     };
 
+    let handle_set_lyrics = move |folder_id: String, value: String| async move {
+        error.set("".to_string());
+        success_msg.set("".to_string());
+
+        let mode = match value.as_str() {
+            "sidecar" => LyricsMode::Sidecar,
+            "tags" => LyricsMode::Tags,
+            _ => LyricsMode::Off,
+        };
+        if let Some(token) = auth.token() {
+            match set_folder_lyrics(token, folder_id, mode).await {
+                Ok(_) => fetch_folders().await,
+                Err(e) => error.set(format!("Failed to update lyrics setting: {e}")),
+            }
+        }
+    };
+
     let handle_create_user = move |_| async move {
         error.set("".to_string());
         success_msg.set("".to_string());
@@ -70,6 +238,16 @@ pub fn Settings() -> Element {
         }
     };
 
+    let handle_validate_config = move |_| async move {
+        error.set("".to_string());
+        if let Some(token) = auth.token() {
+            match validate_config(token).await {
+                Ok(report) => config_report.set(Some(report)),
+                Err(e) => error.set(format!("Failed to validate configuration: {e}")),
+            }
+        }
+    };
+
     rsx! {
         div { class: "container mx-auto p-4 bg-gray-900 min-h-screen text-white",
             h1 { class: "text-3xl font-bold mb-8 text-teal-400", "Settings" }
@@ -112,20 +290,196 @@ pub fn Settings() -> Element {
             // Existing Folders List
             div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
                 h2 { class: "text-xl font-semibold mb-4 text-indigo-300", "Your Folders" }
+                label { class: "flex items-center gap-2 mb-4 text-sm text-gray-300 cursor-pointer",
+                    input {
+                        "type": "checkbox",
+                        checked: share_library(),
+                        onchange: handle_toggle_sharing,
+                    }
+                    "Share my folders on Soulseek through slskd, rescanned after each import"
+                }
                 if folders.read().is_empty() {
                     p { class: "text-gray-400", "No folders added yet." }
                 } else {
                     ul { class: "space-y-2",
                         for folder in folders.read().iter() {
+                            li { key: "{folder.id}", class: "bg-gray-700 p-3 rounded",
+                                div { class: "flex justify-between items-center gap-4",
+                                    span { class: "font-medium text-teal-200", "{folder.name}" }
+                                    span { class: "text-gray-400 text-sm flex-grow", "{folder.path}" }
+                                    select {
+                                        class: "p-1 text-sm rounded bg-gray-600 border border-gray-500 focus:border-teal-500 focus:outline-none",
+                                        title: "Lyrics fetched from LRCLIB for imported tracks",
+                                        onchange: {
+                                            let folder_id = folder.id.clone();
+                                            move |e: FormEvent| handle_set_lyrics(folder_id.clone(), e.value())
+                                        },
+                                        option { value: "off", selected: folder.lyrics == LyricsMode::Off, "No lyrics" }
+                                        option { value: "sidecar", selected: folder.lyrics == LyricsMode::Sidecar, "Lyrics as .lrc files" }
+                                        option { value: "tags", selected: folder.lyrics == LyricsMode::Tags, "Lyrics in tags" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            // Folder Permissions Section
+            if auth.is_admin() {
+                div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+                    h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Folder Permissions" }
+                    p { class: "text-gray-400 text-sm mb-4",
+                        "Modes and owner of the files imported into each user's folders, for media servers running as another user than Soulbeet."
+                    }
+                    ul { class: "space-y-2",
+                        for entry in all_folders.read().iter() {
+                            li { key: "{entry.folder.id}", class: "bg-gray-700 p-3 rounded",
+                                div { class: "flex justify-between items-center gap-4",
+                                    span { class: "font-medium text-teal-200", "{entry.username}" }
+                                    span { class: "text-gray-400 text-sm flex-grow", "{entry.folder.path}" }
+                                    button {
+                                        class: "text-gray-400 hover:text-white text-sm",
+                                        onclick: {
+                                            let folder_id = entry.folder.id.clone();
+                                            move |_| {
+                                                let open = editing_permissions().as_ref() == Some(&folder_id);
+                                                editing_permissions.set((!open).then(|| folder_id.clone()));
+                                            }
+                                        },
+                                        "Permissions"
+                                    }
+                                }
+                                if editing_permissions().as_ref() == Some(&entry.folder.id) {
+                                    ui::FolderPermissionsForm {
+                                        folder_id: entry.folder.id.clone(),
+                                        permissions: entry.folder.permissions.clone(),
+                                        on_saved: move |_| async move {
+                                            editing_permissions.set(None);
+                                            success_msg.set("Folder permissions saved".to_string());
+                                            fetch_all_folders().await;
+                                        },
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Genre Mapping Section
+            div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+                h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Genre Mapping" }
+                p { class: "text-gray-400 text-sm mb-4",
+                    "Genres are written at import from MusicBrainz and Last.fm tags. Without mappings the most popular tags are used as they are; once you add mappings, only mapped tags are kept."
+                }
+                if !genre_mappings.read().is_empty() {
+                    ul { class: "space-y-2 mb-4",
+                        for mapping in genre_mappings.read().iter().cloned() {
                             li { class: "bg-gray-700 p-3 rounded flex justify-between items-center",
-                                span { class: "font-medium text-teal-200", "{folder.name}" }
-                                span { class: "text-gray-400 text-sm", "{folder.path}" }
+                                span { class: "text-gray-300", "{mapping.source} → " }
+                                span { class: "font-medium text-teal-200 flex-grow ml-1", "{mapping.target}" }
+                                button {
+                                    class: "text-gray-400 hover:text-red-400 text-sm",
+                                    onclick: move |_| handle_delete_genre_mapping(mapping.source.clone()),
+                                    "Remove"
+                                }
                             }
                         }
                     }
                 }
+                div { class: "grid grid-cols-1 md:grid-cols-2 gap-4 mb-4",
+                    div {
+                        label { class: "block text-sm font-medium mb-1", "Tag" }
+                        input {
+                            class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                            value: "{genre_source}",
+                            oninput: move |e| genre_source.set(e.value()),
+                            placeholder: "alternative rock",
+                            "type": "text",
+                        }
+                    }
+                    div {
+                        label { class: "block text-sm font-medium mb-1", "Genre" }
+                        input {
+                            class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                            value: "{genre_target}",
+                            oninput: move |e| genre_target.set(e.value()),
+                            placeholder: "Rock",
+                            "type": "text",
+                        }
+                    }
+                }
+                button {
+                    class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+                    onclick: handle_add_genre_mapping,
+                    "Add Mapping"
+                }
+            }
+
+            // Bitrate Cutoffs Section
+            div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mb-8",
+                h2 { class: "text-xl font-semibold mb-2 text-indigo-300", "Minimum Bitrates" }
+                p { class: "text-gray-400 text-sm mb-4",
+                    "Files of a format below its minimum bitrate are left out of search results. Files whose bitrate the uploader does not report are kept."
+                }
+                if !bitrate_cutoffs.read().is_empty() {
+                    ul { class: "space-y-2 mb-4",
+                        for cutoff in bitrate_cutoffs.read().iter().cloned() {
+                            li { class: "bg-gray-700 p-3 rounded flex justify-between items-center",
+                                span { class: "text-gray-300 uppercase", "{cutoff.format}" }
+                                span { class: "font-medium text-teal-200 flex-grow ml-2", "≥ {cutoff.min_bitrate} kbps" }
+                                button {
+                                    class: "text-gray-400 hover:text-red-400 text-sm",
+                                    onclick: move |_| handle_delete_bitrate_cutoff(cutoff.format.clone()),
+                                    "Remove"
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "grid grid-cols-1 md:grid-cols-2 gap-4 mb-4",
+                    div {
+                        label { class: "block text-sm font-medium mb-1", "Format" }
+                        input {
+                            class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                            value: "{cutoff_format}",
+                            oninput: move |e| cutoff_format.set(e.value()),
+                            placeholder: "mp3",
+                            "type": "text",
+                        }
+                    }
+                    div {
+                        label { class: "block text-sm font-medium mb-1", "Minimum bitrate (kbps)" }
+                        input {
+                            class: "w-full p-2 rounded bg-gray-700 border border-gray-600 focus:border-teal-500 focus:outline-none",
+                            value: "{cutoff_bitrate}",
+                            oninput: move |e| cutoff_bitrate.set(e.value()),
+                            placeholder: "256",
+                            "type": "number",
+                        }
+                    }
+                }
+                button {
+                    class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+                    onclick: handle_add_bitrate_cutoff,
+                    "Add Cutoff"
+                }
             }
 
+            ui::PasskeySettings {}
+
+            ui::NotificationSettings {}
+
+            ui::BlockedUploaders {}
+
+            ui::QualityProfiles {}
+
+            ui::ScoringSettings {}
+
+            ui::EditionPreferenceSettings {}
+
+            ui::DataExport {}
+
             // User Creation Section
             div { class: "bg-gray-800 p-6 rounded-lg shadow-lg",
                 h2 { class: "text-xl font-semibold mb-4 text-indigo-300", "Create New User" }
@@ -157,6 +511,36 @@ pub fn Settings() -> Element {
                     "Create User"
                 }
             }
+            // Configuration Check Section
+            if auth.is_admin() {
+                div { class: "bg-gray-800 p-6 rounded-lg shadow-lg mt-8",
+                    h2 { class: "text-xl font-semibold mb-4 text-indigo-300", "Configuration" }
+                    if let Some(report) = config_report() {
+                        ul { class: "space-y-2 mb-4",
+                            for check in report.checks {
+                                li { class: "bg-gray-700 p-3 rounded",
+                                    span { class: if check.ok { "font-medium text-green-400" } else { "font-medium text-red-400" },
+                                        if check.ok {
+                                            "✓ {check.name}"
+                                        } else {
+                                            "✗ {check.name}"
+                                        }
+                                    }
+                                    p { class: "text-gray-300 text-sm", "{check.message}" }
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "bg-teal-600 hover:bg-teal-700 text-white font-bold py-2 px-4 rounded transition-colors",
+                        onclick: handle_validate_config,
+                        "Validate Configuration"
+                    }
+                }
+                ui::SoulseekAccountSettings {}
+                ui::SearchRateLimitSettings {}
+                ui::BrandingSettings {}
+            }
             // Global Messages
             if !error().is_empty() {
                 div { class: "mt-4 p-4 bg-red-900/50 border border-red-500 rounded text-red-200",
@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component(lazy)]
+pub fn Share(share_id: String) -> Element {
+    rsx! {
+        ui::SharedAlbumPage { share_id }
+    }
+}
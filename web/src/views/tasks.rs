@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component(lazy)]
+pub fn Tasks() -> Element {
+    rsx! {
+        ui::ScheduledTasks {}
+    }
+}
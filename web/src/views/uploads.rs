@@ -0,0 +1,8 @@
+use dioxus::prelude::*;
+
+#[component(lazy)]
+pub fn Uploads() -> Element {
+    rsx! {
+        ui::Uploads {}
+    }
+}